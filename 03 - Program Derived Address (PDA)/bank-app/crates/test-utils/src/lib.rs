@@ -0,0 +1,199 @@
+// shared by the tests that run bank_app.so in solana-program-test. The program
+// is loaded from target/deploy, so the tests only run after an `anchor build`:
+// `cargo test -p test-utils -- --ignored`. They live here rather than in the
+// program crate so its own `cargo test` needs neither the .so nor program-test
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, AccountDeserialize,
+    InstructionData, ToAccountMetas,
+};
+use bank_app::{
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, REWARD_CLAIMS_SEED, USER_RESERVE_SEED},
+    error::BankAppError,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &bank_app::ID).0
+}
+
+pub fn reserve_address(user: &Pubkey) -> Pubkey {
+    pda(&[USER_RESERVE_SEED, user.as_ref()])
+}
+
+pub async fn start() -> ProgramTestContext {
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        std::env::set_var(
+            "SBF_OUT_DIR",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+        );
+    }
+    let mut program = ProgramTest::new("bank_app", bank_app::ID, None);
+    program.prefer_bpf(true);
+    program.start_with_context().await
+}
+
+// the payer pays the fee, `signers` sign next to it. A fresh blockhash each time,
+// or a second identical deposit would be the same transaction
+pub async fn send(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+pub fn assert_bank_error(result: Result<(), BanksClientError>, expected: BankAppError) {
+    let code = u32::from(expected);
+    match result.expect_err("the transaction succeeded").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code, "expected {expected}");
+        }
+        err => panic!("expected {expected}, got {err:?}"),
+    }
+}
+
+// a new wallet with `lamports` from the payer
+pub async fn user(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let user = Keypair::new();
+    let ix = system_instruction::transfer(&context.payer.pubkey(), &user.pubkey(), lamports);
+    send(context, ix, &[]).await.unwrap();
+    user
+}
+
+pub async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+pub async fn account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("the account doesn't exist");
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub fn initialize(authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Initialize {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Initialize {}.data(),
+    }
+}
+
+pub fn deposit(user: Pubkey, deposit_amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Deposit {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            user_reserve: reserve_address(&user),
+            user,
+            system_program: system_program::ID,
+            whitelist: None,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Deposit { deposit_amount }.data(),
+    }
+}
+
+pub fn withdraw(user: Pubkey, withdraw_amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Withdraw {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            user_reserve: reserve_address(&user),
+            user,
+            destination: user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Withdraw { withdraw_amount }.data(),
+    }
+}
+
+pub fn pause(authority: Pubkey, is_paused: bool) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Pause {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            authority,
+        }
+        .to_account_metas(None),
+        data: if is_paused {
+            bank_app::instruction::Pause {}.data()
+        } else {
+            bank_app::instruction::Unpause {}.data()
+        },
+    }
+}
+
+pub fn set_rewards_root(authority: Pubkey, rewards_root: [u8; 32], rewards: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::SetRewardsRoot {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::SetRewardsRoot {
+            rewards_root,
+            rewards,
+        }
+        .data(),
+    }
+}
+
+pub fn claim_rewards(user: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::ClaimRewards {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            reward_claims: pda(&[REWARD_CLAIMS_SEED, user.as_ref()]),
+            user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::ClaimRewards { amount, proof }.data(),
+    }
+}
+
+// an initialized bank, the payer is its authority
+pub async fn bank() -> ProgramTestContext {
+    let mut context = start().await;
+    let authority = context.payer.pubkey();
+    send(&mut context, initialize(authority), &[])
+        .await
+        .unwrap();
+    context
+}
@@ -0,0 +1,218 @@
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hashv, system_program};
+use bank_app::{
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, REWARD_CLAIMS_SEED},
+    error::BankAppError,
+    merkle::reward_leaf,
+    state::{BankInfo, RewardClaims, UserReserve},
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+use test_utils::{
+    account, assert_bank_error, bank, claim_rewards, deposit, lamports, pause, pda,
+    reserve_address, send, set_rewards_root, user, withdraw,
+};
+
+// the whole bank in solana-program-test, no validator and no Node. Each test
+// starts a fresh bank from target/deploy/bank_app.so
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn the_bank_lives_at_its_pdas() {
+    let mut context = bank().await;
+
+    // the same seeds the TS tests use, as plain strings
+    let (bank_info, _) = Pubkey::find_program_address(&[b"BANK_INFO_SEED"], &bank_app::ID);
+    let (bank_vault, vault_bump) =
+        Pubkey::find_program_address(&[b"BANK_VAULT_SEED"], &bank_app::ID);
+    assert_eq!(bank_info, pda(&[BANK_INFO_SEED]));
+    assert_eq!(bank_vault, pda(&[BANK_VAULT_SEED]));
+
+    let info: BankInfo = account(&mut context, bank_info).await;
+    assert_eq!(info.authority, context.payer.pubkey());
+    assert!(!info.is_paused);
+    // the vault's bump is kept to sign for it in withdraw
+    assert_eq!(info.bump, vault_bump);
+
+    let vault = context
+        .banks_client
+        .get_account(bank_vault)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vault.owner, system_program::ID);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_deposit_is_withdrawn_through_the_vault() {
+    let mut context = bank().await;
+    let user = user(&mut context, 2 * LAMPORTS_PER_SOL).await;
+    let vault = pda(&[BANK_VAULT_SEED]);
+    let vault_before = lamports(&mut context, vault).await;
+
+    send(
+        &mut context,
+        deposit(user.pubkey(), LAMPORTS_PER_SOL),
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let reserve: UserReserve = account(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.deposited_amount, LAMPORTS_PER_SOL);
+    assert_eq!(
+        lamports(&mut context, vault).await,
+        vault_before + LAMPORTS_PER_SOL
+    );
+
+    let wallet_before = lamports(&mut context, user.pubkey()).await;
+    send(
+        &mut context,
+        withdraw(user.pubkey(), LAMPORTS_PER_SOL / 4),
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let reserve: UserReserve = account(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.deposited_amount, 3 * LAMPORTS_PER_SOL / 4);
+    // the payer paid the fee, the user gets the whole amount
+    assert_eq!(
+        lamports(&mut context, user.pubkey()).await,
+        wallet_before + LAMPORTS_PER_SOL / 4
+    );
+    assert_eq!(
+        lamports(&mut context, vault).await,
+        vault_before + 3 * LAMPORTS_PER_SOL / 4
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn more_than_the_reserve_holds_is_not_withdrawn() {
+    let mut context = bank().await;
+    let user = user(&mut context, LAMPORTS_PER_SOL).await;
+    send(&mut context, deposit(user.pubkey(), 1_000_000), &[&user])
+        .await
+        .unwrap();
+
+    assert_bank_error(
+        send(&mut context, withdraw(user.pubkey(), 1_000_001), &[&user]).await,
+        BankAppError::InsufficientFunds,
+    );
+    let reserve: UserReserve = account(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.deposited_amount, 1_000_000);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_paused_bank_takes_and_pays_nothing() {
+    let mut context = bank().await;
+    let authority = context.payer.pubkey();
+    let user = user(&mut context, LAMPORTS_PER_SOL).await;
+    send(&mut context, deposit(user.pubkey(), 1_000_000), &[&user])
+        .await
+        .unwrap();
+
+    send(&mut context, pause(authority, true), &[])
+        .await
+        .unwrap();
+    assert_bank_error(
+        send(&mut context, deposit(user.pubkey(), 1_000_000), &[&user]).await,
+        BankAppError::BankAppPaused,
+    );
+    assert_bank_error(
+        send(&mut context, withdraw(user.pubkey(), 1_000_000), &[&user]).await,
+        BankAppError::BankAppPaused,
+    );
+
+    send(&mut context, pause(authority, false), &[])
+        .await
+        .unwrap();
+    send(&mut context, withdraw(user.pubkey(), 1_000_000), &[&user])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn only_the_authority_pauses() {
+    let mut context = bank().await;
+    let stranger = user(&mut context, LAMPORTS_PER_SOL).await;
+
+    assert_bank_error(
+        send(&mut context, pause(stranger.pubkey(), true), &[&stranger]).await,
+        BankAppError::Unauthorized,
+    );
+    let info: BankInfo = account(&mut context, pda(&[BANK_INFO_SEED])).await;
+    assert!(!info.is_paused);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn rewards_are_claimed_once_with_a_proof() {
+    let mut context = bank().await;
+    let authority = context.payer.pubkey();
+    let alice = user(&mut context, LAMPORTS_PER_SOL).await;
+    let bob = user(&mut context, LAMPORTS_PER_SOL).await;
+
+    assert_bank_error(
+        send(
+            &mut context,
+            claim_rewards(alice.pubkey(), 1_000, vec![]),
+            &[&alice],
+        )
+        .await,
+        BankAppError::RewardsRootNotSet,
+    );
+
+    // a two leaf tree, each leaf is the other's proof
+    let alice_leaf = reward_leaf(&alice.pubkey(), 1_000);
+    let bob_leaf = reward_leaf(&bob.pubkey(), 2_000);
+    let (low, high) = if alice_leaf <= bob_leaf {
+        (alice_leaf, bob_leaf)
+    } else {
+        (bob_leaf, alice_leaf)
+    };
+    let root = hashv(&[&low, &high]).to_bytes();
+    send(&mut context, set_rewards_root(authority, root, 3_000), &[])
+        .await
+        .unwrap();
+
+    assert_bank_error(
+        send(
+            &mut context,
+            claim_rewards(bob.pubkey(), 2_001, vec![alice_leaf]),
+            &[&bob],
+        )
+        .await,
+        BankAppError::InvalidMerkleProof,
+    );
+
+    let vault = pda(&[BANK_VAULT_SEED]);
+    let vault_before = lamports(&mut context, vault).await;
+    send(
+        &mut context,
+        claim_rewards(alice.pubkey(), 1_000, vec![bob_leaf]),
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    assert_eq!(lamports(&mut context, vault).await, vault_before - 1_000);
+    let info: BankInfo = account(&mut context, pda(&[BANK_INFO_SEED])).await;
+    assert_eq!(info.rewards_epoch, 1);
+    assert_eq!(info.rewards_remaining, 2_000);
+    let claims: RewardClaims = account(
+        &mut context,
+        pda(&[REWARD_CLAIMS_SEED, alice.pubkey().as_ref()]),
+    )
+    .await;
+    assert!(claims.is_claimed(1));
+
+    assert_bank_error(
+        send(
+            &mut context,
+            claim_rewards(alice.pubkey(), 1_000, vec![bob_leaf]),
+            &[&alice],
+        )
+        .await,
+        BankAppError::RewardAlreadyClaimed,
+    );
+}
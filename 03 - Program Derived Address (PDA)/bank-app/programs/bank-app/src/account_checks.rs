@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::BankAppError;
+
+// defense in depth for handlers that take accounts Anchor doesn't check, like
+// remaining accounts. Called at the top, before anything moves
+
+// no account may be passed twice, across the named and the remaining accounts
+pub fn require_unique(accounts: &[AccountInfo]) -> Result<()> {
+    let mut keys: Vec<&Pubkey> = accounts.iter().map(|account| account.key).collect();
+    keys.sort();
+    if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(BankAppError::DuplicateAccount.into());
+    }
+    Ok(())
+}
+
+// every account must be owned by one of `owners`
+pub fn require_owned_by(accounts: &[AccountInfo], owners: &[Pubkey]) -> Result<()> {
+    if accounts
+        .iter()
+        .any(|account| !owners.contains(account.owner))
+    {
+        return Err(BankAppError::UnexpectedAccountOwner.into());
+    }
+    Ok(())
+}
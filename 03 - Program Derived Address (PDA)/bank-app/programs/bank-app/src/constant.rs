@@ -4,3 +4,12 @@ use anchor_lang::prelude::*;
 pub const BANK_INFO_SEED: &[u8] = b"BANK_INFO_SEED";
 pub const BANK_VAULT_SEED: &[u8] = b"BANK_VAULT_SEED";
 pub const USER_RESERVE_SEED: &[u8] = b"USER_RESERVE_SEED";
+pub const WHITELIST_SEED: &[u8] = b"WHITELIST_SEED";
+pub const REWARD_CLAIMS_SEED: &[u8] = b"REWARD_CLAIMS_SEED";
+
+// how long a change of a bound withdrawal destination waits before it can be
+// applied, so a stolen key can't redirect withdrawals right away
+pub const DESTINATION_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+// one bit per epoch in RewardClaims.claimed
+pub const MAX_REWARD_EPOCHS: u32 = 32 * 8;
@@ -4,4 +4,38 @@ use anchor_lang::prelude::*;
 pub enum BankAppError {
     #[msg("The bank app is currently paused.")]
     BankAppPaused,
+    #[msg("The withdraw amount is more than the user deposited.")]
+    InsufficientFunds,
+    #[msg("Only the bank authority can do this.")]
+    Unauthorized,
+    #[msg("The user reserve still holds a deposit, withdraw it before closing.")]
+    ReserveNotEmpty,
+    #[msg("Only the nominated authority can accept the transfer.")]
+    NotPendingAuthority,
+    #[msg("There must be one recipient account for each amount.")]
+    BatchLengthMismatch,
+    #[msg("The amounts add up to more than a u64 can hold.")]
+    MathOverflow,
+    #[msg("The same account was passed more than once.")]
+    DuplicateAccount,
+    #[msg("An account is owned by a program the instruction doesn't expect.")]
+    UnexpectedAccountOwner,
+    #[msg("Withdrawals must go to the reserve's bound withdrawal destination.")]
+    WrongWithdrawalDestination,
+    #[msg("The withdrawal destination change is still timelocked.")]
+    DestinationTimelocked,
+    #[msg("The bank only takes deposits from whitelisted users.")]
+    NotWhitelisted,
+    #[msg("The bank isn't in whitelist mode, enable it first.")]
+    WhitelistDisabled,
+    #[msg("No rewards root has been set yet.")]
+    RewardsRootNotSet,
+    #[msg("The proof doesn't lead to the rewards root.")]
+    InvalidMerkleProof,
+    #[msg("The user already claimed this epoch's rewards.")]
+    RewardAlreadyClaimed,
+    #[msg("The claim is more than the rewards left in the vault.")]
+    RewardsExhausted,
+    #[msg("Every rewards epoch the claim bitmap can track has been used.")]
+    RewardEpochsExhausted,
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::BANK_INFO_SEED, error::BankAppError, state::BankInfo};
+
+// step two: the nominated key proves it can sign, then it becomes the authority
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.pending_authority @ BankAppError::NotPendingAuthority)]
+    pub new_authority: Signer<'info>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+    pub fn process(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.authority = bank_info.pending_authority;
+        bank_info.pending_authority = Pubkey::default();
+
+        msg!("authority accepted: {}", bank_info.authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{BANK_INFO_SEED, WHITELIST_SEED},
+    error::BankAppError,
+    state::{BankInfo, Whitelist},
+};
+
+// only in whitelist mode, so the list can't quietly build up while nobody checks it
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddToWhitelist<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump,
+        constraint = bank_info.require_whitelist @ BankAppError::WhitelistDisabled,
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init,
+        seeds = [WHITELIST_SEED, user.as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Whitelist>(),
+    )]
+    pub whitelist: Box<Account<'info, Whitelist>>,
+
+    #[account(mut, address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToWhitelist<'info> {
+    pub fn process(ctx: Context<AddToWhitelist>, user: Pubkey) -> Result<()> {
+        let whitelist = &mut ctx.accounts.whitelist;
+        whitelist.user = user;
+        whitelist.added_at = Clock::get()?.unix_timestamp;
+
+        msg!("{} whitelisted", user);
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    account_checks::{require_owned_by, require_unique},
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    error::BankAppError,
+    state::{BankInfo, UserReserve},
+    transfer_helper::{batch_total, sol_transfer_from_pda_batch},
+};
+
+// the same accounts as Withdraw. The recipients aren't known at compile time, so
+// they come in as remaining accounts, one per amount and in the same order
+#[derive(Accounts)]
+pub struct BatchWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BatchWithdraw<'info> {
+    // the remaining accounts and the named ones have to share 'info to be passed
+    // to the same transfer, hence the explicit lifetimes
+    pub fn process(
+        ctx: Context<'_, '_, '_, 'info, BatchWithdraw<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        if ctx.accounts.bank_info.is_paused {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+
+        // a recipient passed twice, or one that is also a named account like the
+        // vault, would be paid from the reserve without the lamports leaving the
+        // bank. Recipients are wallets, so the system program owns them
+        let mut accounts = ctx.accounts.to_account_infos();
+        accounts.extend_from_slice(ctx.remaining_accounts);
+        require_unique(&accounts)?;
+        require_owned_by(ctx.remaining_accounts, &[system_program::ID])?;
+
+        // a bound reserve pays out to its destination only
+        let user_reserve = &ctx.accounts.user_reserve;
+        if user_reserve.withdrawal_destination != Pubkey::default()
+            && ctx
+                .remaining_accounts
+                .iter()
+                .any(|recipient| recipient.key() != user_reserve.withdrawal_destination)
+        {
+            return Err(BankAppError::WrongWithdrawalDestination.into());
+        }
+
+        // the whole batch is checked against the deposit before anything moves
+        let total = batch_total(&amounts)?;
+        if total > ctx.accounts.user_reserve.deposited_amount {
+            return Err(BankAppError::InsufficientFunds.into());
+        }
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        // a recipient that isn't writable fails the transfer
+        sol_transfer_from_pda_batch(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.remaining_accounts,
+            &ctx.accounts.system_program,
+            pda_seeds,
+            &amounts,
+        )?;
+
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(total)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        msg!("withdrawn {} to {} recipients", total, amounts.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, REWARD_CLAIMS_SEED},
+    error::BankAppError,
+    merkle::{reward_leaf, verify_proof},
+    state::{BankInfo, RewardClaims},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// pays the user's leaf of the current rewards root. `proof` holds the sibling
+// hashes from the leaf up to the root
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [REWARD_CLAIMS_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<RewardClaims>(),
+    )]
+    pub reward_claims: Box<Account<'info, RewardClaims>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    pub fn process(ctx: Context<ClaimRewards>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let bank_info = &ctx.accounts.bank_info;
+        if bank_info.is_paused {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+        if bank_info.rewards_epoch == 0 {
+            return Err(BankAppError::RewardsRootNotSet.into());
+        }
+
+        let epoch = bank_info.rewards_epoch;
+        if ctx.accounts.reward_claims.is_claimed(epoch) {
+            return Err(BankAppError::RewardAlreadyClaimed.into());
+        }
+
+        let leaf = reward_leaf(ctx.accounts.user.key, amount);
+        if !verify_proof(&proof, bank_info.rewards_root, leaf) {
+            return Err(BankAppError::InvalidMerkleProof.into());
+        }
+        if amount > bank_info.rewards_remaining {
+            return Err(BankAppError::RewardsExhausted.into());
+        }
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            amount,
+        )?;
+
+        let reward_claims = &mut ctx.accounts.reward_claims;
+        reward_claims.user = ctx.accounts.user.key();
+        reward_claims.set_claimed(epoch);
+        ctx.accounts.bank_info.rewards_remaining = ctx
+            .accounts
+            .bank_info
+            .rewards_remaining
+            .checked_sub(amount)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        msg!(
+            "{} claimed {} for epoch {}",
+            ctx.accounts.user.key(),
+            amount,
+            epoch
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::USER_RESERVE_SEED, error::BankAppError, state::UserReserve};
+
+// once everything is withdrawn the reserve only holds rent. `close = user` zeroes
+// the account and sends its lamports back to the user who paid them at deposit
+#[derive(Accounts)]
+pub struct CloseUserReserve<'info> {
+    #[account(
+        mut,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        constraint = user_reserve.deposited_amount == 0 @ BankAppError::ReserveNotEmpty,
+        close = user
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+impl<'info> CloseUserReserve<'info> {
+    pub fn process(_ctx: Context<CloseUserReserve>) -> Result<()> {
+        msg!("user reserve closed, rent refunded");
+        Ok(())
+    }
+}
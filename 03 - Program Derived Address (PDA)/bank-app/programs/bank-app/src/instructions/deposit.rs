@@ -1,9 +1,9 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED, WHITELIST_SEED},
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    state::{BankInfo, UserReserve, Whitelist},
     transfer_helper::sol_transfer_from_user,
 };
 
@@ -36,6 +36,13 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // only needed while the bank requires a whitelist
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub whitelist: Option<Box<Account<'info, Whitelist>>>,
 }
 
 impl<'info> Deposit<'info> {
@@ -43,6 +50,9 @@ impl<'info> Deposit<'info> {
         if ctx.accounts.bank_info.is_paused {
             return Err(BankAppError::BankAppPaused.into());
         }
+        if ctx.accounts.bank_info.require_whitelist && ctx.accounts.whitelist.is_none() {
+            return Err(BankAppError::NotWhitelisted.into());
+        }
 
         let user_reserve = &mut ctx.accounts.user_reserve;
 
@@ -53,7 +63,10 @@ impl<'info> Deposit<'info> {
             deposit_amount,
         )?;
 
-        user_reserve.deposited_amount += deposit_amount;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::MathOverflow)?;
 
         Ok(())
     }
@@ -39,6 +39,11 @@ impl<'info> Initialize<'info> {
         bank_info.authority = ctx.accounts.authority.key();
         bank_info.is_paused = false;
         bank_info.bump = ctx.bumps.bank_vault;
+        bank_info.pending_authority = Pubkey::default();
+        bank_info.require_whitelist = false;
+        bank_info.rewards_root = [0u8; 32];
+        bank_info.rewards_epoch = 0;
+        bank_info.rewards_remaining = 0;
 
         msg!("bank app initialized!");
         Ok(())
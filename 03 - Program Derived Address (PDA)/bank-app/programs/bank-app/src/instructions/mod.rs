@@ -1,9 +1,29 @@
+pub mod accept_authority;
+pub mod add_to_whitelist;
+pub mod batch_withdraw;
+pub mod claim_rewards;
+pub mod close_user_reserve;
 pub mod deposit;
 pub mod initialize;
-// pub mod pause;
-// pub mod withdraw;
+pub mod nominate_authority;
+pub mod pause;
+pub mod remove_from_whitelist;
+pub mod set_rewards_root;
+pub mod set_whitelist_mode;
+pub mod set_withdrawal_destination;
+pub mod withdraw;
 
+pub use accept_authority::*;
+pub use add_to_whitelist::*;
+pub use batch_withdraw::*;
+pub use claim_rewards::*;
+pub use close_user_reserve::*;
 pub use deposit::*;
 pub use initialize::*;
-// pub use pause::*;
-// pub use withdraw::*;
+pub use nominate_authority::*;
+pub use pause::*;
+pub use remove_from_whitelist::*;
+pub use set_rewards_root::*;
+pub use set_whitelist_mode::*;
+pub use set_withdrawal_destination::*;
+pub use withdraw::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::BANK_INFO_SEED, error::BankAppError, state::BankInfo};
+
+// step one of handing the bank to another key. Nothing changes until that key
+// signs accept_authority, so a typo can't lock the bank out. Nominating again
+// replaces the pending key, Pubkey::default() cancels
+#[derive(Accounts)]
+pub struct NominateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> NominateAuthority<'info> {
+    pub fn process(ctx: Context<NominateAuthority>, new_authority: Pubkey) -> Result<()> {
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.pending_authority = new_authority;
+
+        msg!("authority nominated: {}", new_authority);
+        Ok(())
+    }
+}
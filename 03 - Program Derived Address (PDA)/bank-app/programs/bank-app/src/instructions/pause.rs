@@ -1 +1,28 @@
-//Your code here
+use anchor_lang::prelude::*;
+
+use crate::{constant::BANK_INFO_SEED, error::BankAppError, state::BankInfo};
+
+// the emergency stop: while paused, deposit and withdraw are rejected.
+// Only the authority saved in BankInfo at initialize can flip it
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> Pause<'info> {
+    pub fn process(ctx: Context<Pause>, is_paused: bool) -> Result<()> {
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.is_paused = is_paused;
+
+        msg!("bank app paused: {}", is_paused);
+        Ok(())
+    }
+}
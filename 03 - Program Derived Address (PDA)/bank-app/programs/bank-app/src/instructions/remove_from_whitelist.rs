@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{BANK_INFO_SEED, WHITELIST_SEED},
+    error::BankAppError,
+    state::{BankInfo, Whitelist},
+};
+
+// works in either mode, so the entries can be cleaned up after it's turned off.
+// The rent goes back to the authority, who paid it
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveFromWhitelist<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        seeds = [WHITELIST_SEED, user.as_ref()],
+        bump,
+        close = authority
+    )]
+    pub whitelist: Box<Account<'info, Whitelist>>,
+
+    #[account(mut, address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> RemoveFromWhitelist<'info> {
+    pub fn process(_ctx: Context<RemoveFromWhitelist>, user: Pubkey) -> Result<()> {
+        msg!("{} removed from the whitelist", user);
+        Ok(())
+    }
+}
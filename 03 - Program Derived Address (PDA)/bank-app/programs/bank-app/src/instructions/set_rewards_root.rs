@@ -0,0 +1,66 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, MAX_REWARD_EPOCHS},
+    error::BankAppError,
+    state::BankInfo,
+    transfer_helper::sol_transfer_from_user,
+};
+
+// starts a new rewards epoch. The authority pays `rewards` into the vault, on top
+// of whatever the last epoch left unclaimed. Claims against the old root stop, so
+// anything still owed from it belongs in the new tree
+#[derive(Accounts)]
+pub struct SetRewardsRoot<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetRewardsRoot<'info> {
+    pub fn process(
+        ctx: Context<SetRewardsRoot>,
+        rewards_root: [u8; 32],
+        rewards: u64,
+    ) -> Result<()> {
+        if ctx.accounts.bank_info.rewards_epoch >= MAX_REWARD_EPOCHS {
+            return Err(BankAppError::RewardEpochsExhausted.into());
+        }
+
+        if rewards > 0 {
+            sol_transfer_from_user(
+                &ctx.accounts.authority,
+                ctx.accounts.bank_vault.to_account_info(),
+                &ctx.accounts.system_program,
+                rewards,
+            )?;
+        }
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.rewards_root = rewards_root;
+        bank_info.rewards_epoch += 1;
+        bank_info.rewards_remaining = bank_info
+            .rewards_remaining
+            .checked_add(rewards)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        msg!("rewards epoch {} set", bank_info.rewards_epoch);
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::BANK_INFO_SEED, error::BankAppError, state::BankInfo};
+
+// permissioned mode: while it's on, only users with a Whitelist entry can deposit.
+// Withdrawals stay open, a user removed from the list can still take their funds out
+#[derive(Accounts)]
+pub struct SetWhitelistMode<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority @ BankAppError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetWhitelistMode<'info> {
+    pub fn process(ctx: Context<SetWhitelistMode>, require_whitelist: bool) -> Result<()> {
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.require_whitelist = require_whitelist;
+
+        msg!("bank app requires whitelist: {}", require_whitelist);
+        Ok(())
+    }
+}
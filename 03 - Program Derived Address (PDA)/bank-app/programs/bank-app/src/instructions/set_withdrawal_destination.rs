@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{DESTINATION_TIMELOCK_SECONDS, USER_RESERVE_SEED},
+    error::BankAppError,
+    state::UserReserve,
+};
+
+// binds every withdrawal of the reserve to one address, e.g. a cold wallet. The
+// first binding is immediate. A change has to be requested, then applied with a
+// second call once DESTINATION_TIMELOCK_SECONDS have passed. Pubkey::default()
+// unbinds, under the same timelock
+#[derive(Accounts)]
+pub struct SetWithdrawalDestination<'info> {
+    #[account(
+        mut,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> SetWithdrawalDestination<'info> {
+    pub fn process(ctx: Context<SetWithdrawalDestination>, destination: Pubkey) -> Result<()> {
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        let now = Clock::get()?.unix_timestamp;
+
+        // nothing bound yet, nothing to protect
+        if user_reserve.withdrawal_destination == Pubkey::default() {
+            user_reserve.withdrawal_destination = destination;
+            msg!("withdrawal destination bound: {}", destination);
+            return Ok(());
+        }
+
+        // asking for the current destination cancels a pending change
+        if destination == user_reserve.withdrawal_destination {
+            user_reserve.pending_destination = Pubkey::default();
+            user_reserve.destination_unlock_at = 0;
+            msg!("withdrawal destination change cancelled");
+            return Ok(());
+        }
+
+        // a new request, or one for another address, starts the timelock over
+        if user_reserve.destination_unlock_at == 0
+            || user_reserve.pending_destination != destination
+        {
+            user_reserve.pending_destination = destination;
+            user_reserve.destination_unlock_at = now + DESTINATION_TIMELOCK_SECONDS;
+            msg!(
+                "withdrawal destination change to {} unlocks at {}",
+                destination,
+                user_reserve.destination_unlock_at
+            );
+            return Ok(());
+        }
+
+        if now < user_reserve.destination_unlock_at {
+            return Err(BankAppError::DestinationTimelocked.into());
+        }
+
+        user_reserve.withdrawal_destination = destination;
+        user_reserve.pending_destination = Pubkey::default();
+        user_reserve.destination_unlock_at = 0;
+
+        msg!("withdrawal destination changed: {}", destination);
+        Ok(())
+    }
+}
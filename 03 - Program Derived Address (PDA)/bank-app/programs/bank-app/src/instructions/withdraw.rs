@@ -4,6 +4,7 @@ use crate::{
     constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
     error::BankAppError,
     state::{BankInfo, UserReserve},
+    transfer_helper::sol_transfer_from_pda,
 };
 
 #[derive(Accounts)]
@@ -33,6 +34,14 @@ pub struct Withdraw<'info> {
 
     #[account(mut)]
     pub user: Signer<'info>,
+
+    ///CHECK: receives the lamports. The user's own wallet, or the reserve's bound
+    /// withdrawal destination once there is one
+    #[account(
+        mut,
+        address = user_reserve.payout_address(user.key) @ BankAppError::WrongWithdrawalDestination
+    )]
+    pub destination: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -42,8 +51,25 @@ impl<'info> Withdraw<'info> {
             return Err(BankAppError::BankAppPaused.into());
         }
 
+        if withdraw_amount > ctx.accounts.user_reserve.deposited_amount {
+            return Err(BankAppError::InsufficientFunds.into());
+        }
+
         let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
-        // Your code here
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::MathOverflow)?;
 
         Ok(())
     }
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
+pub mod account_checks;
 pub mod constant;
 pub mod error;
 pub mod instructions;
+pub mod merkle;
 pub mod state;
 pub mod transfer_helper;
 
@@ -21,4 +23,77 @@ pub mod bank_app {
     pub fn deposit(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
         return Deposit::process(ctx, deposit_amount);
     }
+
+    pub fn withdraw(ctx: Context<Withdraw>, withdraw_amount: u64) -> Result<()> {
+        return Withdraw::process(ctx, withdraw_amount);
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        return Pause::process(ctx, true);
+    }
+
+    pub fn unpause(ctx: Context<Pause>) -> Result<()> {
+        return Pause::process(ctx, false);
+    }
+
+    pub fn close_user_reserve(ctx: Context<CloseUserReserve>) -> Result<()> {
+        return CloseUserReserve::process(ctx);
+    }
+
+    pub fn nominate_authority(
+        ctx: Context<NominateAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        return NominateAuthority::process(ctx, new_authority);
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        return AcceptAuthority::process(ctx);
+    }
+
+    pub fn batch_withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchWithdraw<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        return BatchWithdraw::process(ctx, amounts);
+    }
+
+    pub fn set_withdrawal_destination(
+        ctx: Context<SetWithdrawalDestination>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        return SetWithdrawalDestination::process(ctx, destination);
+    }
+
+    pub fn enable_whitelist(ctx: Context<SetWhitelistMode>) -> Result<()> {
+        return SetWhitelistMode::process(ctx, true);
+    }
+
+    pub fn disable_whitelist(ctx: Context<SetWhitelistMode>) -> Result<()> {
+        return SetWhitelistMode::process(ctx, false);
+    }
+
+    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, user: Pubkey) -> Result<()> {
+        return AddToWhitelist::process(ctx, user);
+    }
+
+    pub fn remove_from_whitelist(ctx: Context<RemoveFromWhitelist>, user: Pubkey) -> Result<()> {
+        return RemoveFromWhitelist::process(ctx, user);
+    }
+
+    pub fn set_rewards_root(
+        ctx: Context<SetRewardsRoot>,
+        rewards_root: [u8; 32],
+        rewards: u64,
+    ) -> Result<()> {
+        return SetRewardsRoot::process(ctx, rewards_root, rewards);
+    }
+
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        return ClaimRewards::process(ctx, amount, proof);
+    }
 }
@@ -0,0 +1,20 @@
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hashv};
+
+// leaf = sha256(user || amount). 40 bytes, so a leaf can never pass for the
+// 64 bytes of two hashed nodes
+pub fn reward_leaf(user: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[user.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+// parents hash the sorted pair, so proofs don't need left/right flags
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
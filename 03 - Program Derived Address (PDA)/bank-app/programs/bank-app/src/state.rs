@@ -6,10 +6,66 @@ pub struct BankInfo {
     pub authority: Pubkey,
     pub is_paused: bool,
     pub bump: u8,
+    // nominated by the authority, becomes it once it accepts. Pubkey::default()
+    // when no transfer is pending
+    pub pending_authority: Pubkey,
+    // permissioned mode: deposits need the user's Whitelist entry
+    pub require_whitelist: bool,
+    // the current rewards distribution, a Merkle root of (user, amount) leaves.
+    // rewards_epoch counts the roots set so far, 0 means none yet
+    pub rewards_root: [u8; 32],
+    pub rewards_epoch: u32,
+    // funded by set_rewards_root and not claimed yet, deposits are never paid as rewards
+    pub rewards_remaining: u64,
+}
+
+// the user may deposit while the bank requires a whitelist. The entry existing
+// is the approval, the authority creates and closes it
+#[account]
+#[derive(Default)]
+pub struct Whitelist {
+    pub user: Pubkey,
+    pub added_at: i64,
+}
+
+// bit `epoch - 1` is set once the user claimed that epoch's rewards
+#[account]
+#[derive(Default)]
+pub struct RewardClaims {
+    pub user: Pubkey,
+    pub claimed: [u8; 32],
+}
+
+impl RewardClaims {
+    pub fn is_claimed(&self, epoch: u32) -> bool {
+        let index = (epoch - 1) as usize;
+        self.claimed[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, epoch: u32) {
+        let index = (epoch - 1) as usize;
+        self.claimed[index / 8] |= 1 << (index % 8);
+    }
 }
 
 #[account]
 #[derive(Default)]
 pub struct UserReserve {
     pub deposited_amount: u64,
+    // where every withdrawal must go, Pubkey::default() is the user's own wallet
+    pub withdrawal_destination: Pubkey,
+    // the requested change, applied by calling set_withdrawal_destination again
+    // once destination_unlock_at has passed. 0 when no change is pending
+    pub pending_destination: Pubkey,
+    pub destination_unlock_at: i64,
+}
+
+impl UserReserve {
+    pub fn payout_address(&self, user: &Pubkey) -> Pubkey {
+        if self.withdrawal_destination == Pubkey::default() {
+            *user
+        } else {
+            self.withdrawal_destination
+        }
+    }
 }
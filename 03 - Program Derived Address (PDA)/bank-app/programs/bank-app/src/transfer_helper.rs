@@ -6,6 +6,8 @@ use anchor_lang::{
     },
 };
 
+use crate::error::BankAppError;
+
 //  transfer SOL from user
 pub fn sol_transfer_from_user<'info>(
     signer: &Signer<'info>,
@@ -33,6 +35,45 @@ pub fn sol_transfer_from_pda<'info>(
     signers_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
-    //Your code here
+    let ix = transfer(source.key, destination.key, amount);
+    invoke_signed(
+        &ix,
+        &[source, destination, system_program.to_account_info()],
+        signers_seeds,
+    )?;
+    Ok(())
+}
+
+// what a batch pays out in total. Checked, so amounts that wrap around can't
+// sneak under the user's deposit
+pub fn batch_total(amounts: &[u64]) -> Result<u64> {
+    amounts.iter().try_fold(0u64, |total, amount| {
+        total
+            .checked_add(*amount)
+            .ok_or(BankAppError::MathOverflow.into())
+    })
+}
+
+// transfer sol from PDA to each recipient, amounts[i] goes to recipients[i]
+pub fn sol_transfer_from_pda_batch<'info>(
+    source: AccountInfo<'info>,
+    recipients: &[AccountInfo<'info>],
+    system_program: &Program<'info, System>,
+    signers_seeds: &[&[&[u8]]],
+    amounts: &[u64],
+) -> Result<()> {
+    if recipients.len() != amounts.len() {
+        return Err(BankAppError::BatchLengthMismatch.into());
+    }
+
+    for (recipient, amount) in recipients.iter().zip(amounts) {
+        sol_transfer_from_pda(
+            source.clone(),
+            recipient.clone(),
+            system_program,
+            signers_seeds,
+            *amount,
+        )?;
+    }
     Ok(())
 }
@@ -1,8 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { BankApp } from "../target/types/bank_app";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
 import { BN } from "bn.js";
+import { createHash } from "crypto";
 
 describe("bank-app", () => {
   // Configure the client to use the local cluster.
@@ -27,6 +28,20 @@ describe("bank-app", () => {
       ],
       program.programId
     )[0],
+    whitelist: (pubkey: PublicKey) => PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("WHITELIST_SEED"),
+        pubkey.toBuffer()
+      ],
+      program.programId
+    )[0],
+    rewardClaims: (pubkey: PublicKey) => PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("REWARD_CLAIMS_SEED"),
+        pubkey.toBuffer()
+      ],
+      program.programId
+    )[0],
   }
 
   it("Is initialized!", async () => {
@@ -59,4 +74,485 @@ describe("bank-app", () => {
     const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
     console.log("User reserve: ", userReserve.depositedAmount.toString())
   });
+
+  it("Is withdrawn!", async () => {
+    const userReserveBefore = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+
+    const tx = await program.methods.withdraw(new BN(500_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        destination: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Withdraw signature: ", tx);
+
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    console.log("User reserve: ", userReserve.depositedAmount.toString())
+    if (!userReserve.depositedAmount.eq(userReserveBefore.depositedAmount.sub(new BN(500_000)))) {
+      throw new Error("User reserve was not reduced by the withdraw amount")
+    }
+
+    try {
+      await program.methods.withdraw(userReserve.depositedAmount.add(new BN(1)))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          user: provider.publicKey,
+          destination: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      throw new Error("Withdrawing more than the deposited amount should fail")
+    } catch (e) {
+      if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== "InsufficientFunds") {
+        throw e
+      }
+      console.log("Over-withdraw rejected: ", e.error.errorMessage)
+    }
+  });
+
+  it("Is paused and unpaused!", async () => {
+    const setPaused = (isPaused: boolean, authority: PublicKey) =>
+      (isPaused ? program.methods.pause() : program.methods.unpause())
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          authority,
+        })
+
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    // only the authority saved at initialize can pause
+    const stranger = Keypair.generate()
+    await expectError(setPaused(true, stranger.publicKey).signers([stranger]).rpc(), "Unauthorized")
+
+    const pauseTx = await setPaused(true, provider.publicKey).rpc();
+    console.log("Pause signature: ", pauseTx);
+
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+      user: provider.publicKey,
+      destination: provider.publicKey,
+      systemProgram: SystemProgram.programId
+    }
+    await expectError(program.methods.deposit(new BN(1_000)).accounts(accounts).rpc(), "BankAppPaused")
+    await expectError(program.methods.withdraw(new BN(1_000)).accounts(accounts).rpc(), "BankAppPaused")
+
+    const unpauseTx = await setPaused(false, provider.publicKey).rpc();
+    console.log("Unpause signature: ", unpauseTx);
+
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    if (bankInfo.isPaused) {
+      throw new Error("Bank should be unpaused")
+    }
+    await program.methods.deposit(new BN(1_000)).accounts(accounts).rpc()
+  });
+
+  it("Is user reserve closed!", async () => {
+    // a fresh user, so the reserve can be emptied without touching the provider's
+    const user = Keypair.generate()
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: user.publicKey,
+      lamports: 100_000_000,
+    })))
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(user.publicKey)
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve,
+      user: user.publicKey,
+      destination: user.publicKey,
+      systemProgram: SystemProgram.programId
+    }
+    const closeUserReserve = () => program.methods.closeUserReserve()
+      .accounts({
+        userReserve,
+        user: user.publicKey,
+      }).signers([user]).rpc()
+
+    await program.methods.deposit(new BN(1_000_000)).accounts(accounts).signers([user]).rpc()
+    try {
+      await closeUserReserve()
+      throw new Error("Closing a reserve with a deposit should fail")
+    } catch (e) {
+      if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== "ReserveNotEmpty") {
+        throw e
+      }
+    }
+
+    await program.methods.withdraw(new BN(1_000_000)).accounts(accounts).signers([user]).rpc()
+    const rent = (await provider.connection.getAccountInfo(userReserve)).lamports
+    const balanceBefore = await provider.connection.getBalance(user.publicKey)
+    const tx = await closeUserReserve();
+    console.log("Close user reserve signature: ", tx);
+
+    // the provider pays the fee, so the user gets exactly the rent back
+    if (await provider.connection.getAccountInfo(userReserve) != null) {
+      throw new Error("User reserve should be closed")
+    }
+    if (await provider.connection.getBalance(user.publicKey) != balanceBefore + rent) {
+      throw new Error("The rent was not refunded to the user")
+    }
+  });
+
+  it("Is authority transferred in two steps!", async () => {
+    const newAuthority = Keypair.generate()
+    const nominate = (authority: Keypair | null, nominee: PublicKey) => program.methods.nominateAuthority(nominee)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: authority?.publicKey ?? provider.publicKey,
+      }).signers(authority == null ? [] : [authority]).rpc()
+    const accept = (nominee: Keypair | null) => program.methods.acceptAuthority()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        newAuthority: nominee?.publicKey ?? provider.publicKey,
+      }).signers(nominee == null ? [] : [nominee]).rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    const nominateTx = await nominate(null, newAuthority.publicKey)
+    console.log("Nominate authority signature: ", nominateTx);
+
+    // nominating changes nothing yet, and only the nominee can accept
+    let bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    if (!bankInfo.authority.equals(provider.publicKey) || !bankInfo.pendingAuthority.equals(newAuthority.publicKey)) {
+      throw new Error("The nomination should only set pending_authority")
+    }
+    await expectError(accept(Keypair.generate()), "NotPendingAuthority")
+
+    const acceptTx = await accept(newAuthority)
+    console.log("Accept authority signature: ", acceptTx);
+
+    bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    if (!bankInfo.authority.equals(newAuthority.publicKey) || !bankInfo.pendingAuthority.equals(PublicKey.default)) {
+      throw new Error("The nominee should be the authority now")
+    }
+    await expectError(nominate(null, provider.publicKey), "Unauthorized")
+
+    // hand it back, so the other tests and later runs still have the provider as authority
+    await nominate(newAuthority, provider.publicKey)
+    await accept(null)
+  });
+
+  it("Is withdrawn to several recipients!", async () => {
+    const recipients = [Keypair.generate().publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey]
+    const amounts = [1_000_000, 2_000_000, 3_000_000]
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+      user: provider.publicKey,
+      systemProgram: SystemProgram.programId
+    }
+    // one writable remaining account per amount, in the same order
+    const batchWithdraw = (amounts: number[], recipients: PublicKey[]) => program.methods
+      .batchWithdraw(amounts.map((amount) => new BN(amount)))
+      .accounts(accounts)
+      .remainingAccounts(recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    await program.methods.deposit(new BN(6_000_000)).accounts(accounts).rpc()
+    const userReserveBefore = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+
+    await expectError(batchWithdraw(amounts, recipients.slice(1)), "BatchLengthMismatch")
+    await expectError(batchWithdraw([userReserveBefore.depositedAmount.toNumber(), 1], recipients.slice(1)), "InsufficientFunds")
+
+    const tx = await batchWithdraw(amounts, recipients)
+    console.log("Batch withdraw signature: ", tx);
+
+    // the system program creates each recipient with exactly its amount
+    for (const [index, recipient] of recipients.entries()) {
+      if (await provider.connection.getBalance(recipient) != amounts[index]) {
+        throw new Error("A recipient didn't get its amount")
+      }
+    }
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    if (!userReserve.depositedAmount.eq(userReserveBefore.depositedAmount.sub(new BN(6_000_000)))) {
+      throw new Error("User reserve was not reduced by the batch total")
+    }
+  });
+
+  it("Are duplicate and foreign accounts rejected!", async () => {
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+      user: provider.publicKey,
+      systemProgram: SystemProgram.programId
+    }
+    const batchWithdraw = (recipients: PublicKey[]) => program.methods
+      .batchWithdraw(recipients.map(() => new BN(1_000_000)))
+      .accounts(accounts)
+      .remainingAccounts(recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+    await program.methods.deposit(new BN(2_000_000)).accounts(accounts).rpc()
+
+    // the same recipient twice, or the vault paying itself
+    const recipient = Keypair.generate().publicKey
+    await expectError(batchWithdraw([recipient, recipient]), "DuplicateAccount")
+    await expectError(batchWithdraw([BANK_APP_ACCOUNTS.bankVault]), "DuplicateAccount")
+
+    // an account some program owns, here this one, isn't a wallet
+    const foreign = Keypair.generate()
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.createAccount({
+      fromPubkey: provider.publicKey,
+      newAccountPubkey: foreign.publicKey,
+      space: 0,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(0),
+      programId: program.programId,
+    })), [foreign])
+    await expectError(batchWithdraw([recipient, foreign.publicKey]), "UnexpectedAccountOwner")
+
+    // nothing moved, and a clean batch still goes through
+    if (await provider.connection.getBalance(recipient) != 0) {
+      throw new Error("A rejected batch paid out")
+    }
+    await batchWithdraw([recipient])
+  });
+
+  it("Are withdrawals bound to a destination!", async () => {
+    // a fresh user, so the binding doesn't stick to the provider's reserve
+    const user = Keypair.generate()
+    const coldWallet = Keypair.generate().publicKey
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: user.publicKey,
+      lamports: 100_000_000,
+    })))
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(user.publicKey)
+    const withdraw = (destination: PublicKey) => program.methods.withdraw(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: user.publicKey,
+        destination,
+        systemProgram: SystemProgram.programId
+      }).signers([user]).rpc()
+    const setDestination = (destination: PublicKey) => program.methods.setWithdrawalDestination(destination)
+      .accounts({
+        userReserve,
+        user: user.publicKey,
+      }).signers([user]).rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    await program.methods.deposit(new BN(5_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId
+      }).signers([user]).rpc()
+
+    // unbound, the user's own wallet is the only destination
+    await expectError(withdraw(coldWallet), "WrongWithdrawalDestination")
+
+    // the first binding is immediate, then the user's wallet is refused
+    const tx = await setDestination(coldWallet)
+    console.log("Set withdrawal destination signature: ", tx);
+    await expectError(withdraw(user.publicKey), "WrongWithdrawalDestination")
+    await withdraw(coldWallet)
+    if (await provider.connection.getBalance(coldWallet) != 1_000_000) {
+      throw new Error("The cold wallet didn't receive the withdrawal")
+    }
+
+    // a change is only requested, and can't be applied before the timelock
+    const other = Keypair.generate().publicKey
+    await setDestination(other)
+    let reserve = await program.account.userReserve.fetch(userReserve)
+    if (!reserve.withdrawalDestination.equals(coldWallet) || !reserve.pendingDestination.equals(other)) {
+      throw new Error("The change should only be pending")
+    }
+    await expectError(setDestination(other), "DestinationTimelocked")
+    await expectError(withdraw(other), "WrongWithdrawalDestination")
+
+    // asking for the current destination cancels the change
+    await setDestination(coldWallet)
+    reserve = await program.account.userReserve.fetch(userReserve)
+    if (!reserve.pendingDestination.equals(PublicKey.default) || !reserve.destinationUnlockAt.isZero()) {
+      throw new Error("The pending change should be cancelled")
+    }
+  });
+  it("Is deposit gated by the whitelist!", async () => {
+    const user = Keypair.generate()
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: user.publicKey,
+      lamports: 100_000_000,
+    })))
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(user.publicKey)
+    const whitelist = BANK_APP_ACCOUNTS.whitelist(user.publicKey)
+    const deposit = (withWhitelist: boolean) => program.methods.deposit(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        whitelist: withWhitelist ? whitelist : null,
+      }).signers([user]).rpc()
+    const setMode = (requireWhitelist: boolean, authority: PublicKey) =>
+      (requireWhitelist ? program.methods.enableWhitelist() : program.methods.disableWhitelist())
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          authority,
+        })
+    const addToWhitelist = () => program.methods.addToWhitelist(user.publicKey)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        whitelist,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    // open mode: anyone deposits, and there's no list to add to
+    await deposit(false)
+    await expectError(addToWhitelist(), "WhitelistDisabled")
+
+    const stranger = Keypair.generate()
+    await expectError(setMode(true, stranger.publicKey).signers([stranger]).rpc(), "Unauthorized")
+    const enableTx = await setMode(true, provider.publicKey).rpc();
+    console.log("Enable whitelist signature: ", enableTx);
+
+    try {
+      await expectError(deposit(false), "NotWhitelisted")
+
+      const addTx = await addToWhitelist();
+      console.log("Add to whitelist signature: ", addTx);
+      await deposit(true)
+
+      const removeTx = await program.methods.removeFromWhitelist(user.publicKey)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          whitelist,
+          authority: provider.publicKey,
+        }).rpc();
+      console.log("Remove from whitelist signature: ", removeTx);
+      await expectError(deposit(false), "NotWhitelisted")
+
+      // a removed user can still take their funds out
+      await program.methods.withdraw(new BN(1_000_000))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve,
+          user: user.publicKey,
+          destination: user.publicKey,
+          systemProgram: SystemProgram.programId
+        }).signers([user]).rpc()
+    } finally {
+      // the tests after this one deposit without a whitelist
+      await setMode(false, provider.publicKey).rpc()
+    }
+
+    const reserve = await program.account.userReserve.fetch(userReserve)
+    if (!reserve.depositedAmount.eqn(1_000_000)) {
+      throw new Error("Two deposits were allowed and one withdrawn, 1_000_000 should be left")
+    }
+  });
+
+  it("Are rewards claimed with a Merkle proof!", async () => {
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest()
+    // the same hashing as merkle.rs: sha256(user || amount), parents hash the sorted pair
+    const leaf = (user: PublicKey, amount: number) => sha256(user.toBuffer(), new BN(amount).toArrayLike(Buffer, "le", 8))
+    const other = Keypair.generate().publicKey
+    const ownLeaf = leaf(provider.publicKey, 50_000)
+    const otherLeaf = leaf(other, 70_000)
+    const root = sha256(...[ownLeaf, otherLeaf].sort(Buffer.compare))
+
+    const setTx = await program.methods.setRewardsRoot([...root], new BN(120_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Set rewards root signature: ", setTx);
+
+    const claim = (amount: number) => program.methods.claimRewards(new BN(amount), [[...otherLeaf]])
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        rewardClaims: BANK_APP_ACCOUNTS.rewardClaims(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const expectError = async (tx: Promise<string>, code: string) => {
+      try {
+        await tx
+        throw new Error(`expected ${code}`)
+      } catch (e) {
+        if (!(e instanceof anchor.AnchorError) || e.error.errorCode.code !== code) {
+          throw e
+        }
+      }
+    }
+
+    await expectError(claim(60_000), "InvalidMerkleProof")
+    const claimTx = await claim(50_000);
+    console.log("Claim rewards signature: ", claimTx);
+    await expectError(claim(50_000), "RewardAlreadyClaimed")
+  });
 });
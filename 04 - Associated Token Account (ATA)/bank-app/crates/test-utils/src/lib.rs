@@ -0,0 +1,370 @@
+// shared by the tests that run bank_app.so in solana-program-test. The program
+// is loaded from target/deploy, so the tests only run after an `anchor build`:
+// `cargo test -p test-utils -- --ignored`. They live here rather than in the
+// program crate so its own `cargo test` needs neither the .so nor program-test
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, program_pack::Pack},
+    system_program, AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::{self, spl_token},
+};
+use bank_app::{
+    constant::{
+        BANK_INFO_SEED, BANK_VAULT_SEED, FEE_TREASURY_SEED, METRICS_SEED, REFERRAL_SEED,
+        SUPPORTED_MINT_SEED, USER_RESERVE_SEED,
+    },
+    error::BankAppError,
+};
+use bytemuck::Pod;
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, InstructionError},
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &bank_app::ID).0
+}
+
+pub fn reserve_address(user: &Pubkey) -> Pubkey {
+    pda(&[USER_RESERVE_SEED, user.as_ref()])
+}
+
+pub async fn start() -> ProgramTestContext {
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        std::env::set_var(
+            "SBF_OUT_DIR",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+        );
+    }
+    let mut program = ProgramTest::new("bank_app", bank_app::ID, None);
+    program.prefer_bpf(true);
+    program.start_with_context().await
+}
+
+// an initialized bank, the payer is its authority
+pub async fn bank() -> ProgramTestContext {
+    let mut context = start().await;
+    let authority = context.payer.pubkey();
+    send(&mut context, initialize(authority), &[])
+        .await
+        .unwrap();
+    context
+}
+
+// the payer pays the fee, `signers` sign next to it. A fresh blockhash each time,
+// or a second identical deposit would be the same transaction
+pub async fn send(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+pub fn assert_bank_error(result: Result<(), BanksClientError>, expected: BankAppError) {
+    assert_custom_error(result, u32::from(expected));
+}
+
+// for Anchor's own errors, `ErrorCode::X as u32`
+pub fn assert_custom_error(result: Result<(), BanksClientError>, expected: u32) {
+    match result.expect_err("the transaction succeeded").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, expected);
+        }
+        err => panic!("expected custom error {expected}, got {err:?}"),
+    }
+}
+
+// a new wallet with `lamports` from the payer
+pub async fn user(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let user = Keypair::new();
+    let ix = system_instruction::transfer(&context.payer.pubkey(), &user.pubkey(), lamports);
+    send(context, ix, &[]).await.unwrap();
+    user
+}
+
+pub async fn raw_account(context: &mut ProgramTestContext, address: Pubkey) -> Account {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("the account doesn't exist")
+}
+
+pub async fn account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = raw_account(context, address).await;
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+// a zero-copy account, read past its discriminator
+pub async fn load<T: Pod>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = raw_account(context, address).await;
+    bytemuck::pod_read_unaligned(&account.data[8..8 + std::mem::size_of::<T>()])
+}
+
+// writes a zero-copy account back, for states no instruction leads to yet
+pub async fn store<T: Pod>(context: &mut ProgramTestContext, address: Pubkey, value: &T) {
+    let mut account = raw_account(context, address).await;
+    account.data[8..8 + std::mem::size_of::<T>()].copy_from_slice(bytemuck::bytes_of(value));
+    context.set_account(&address, &account.into());
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, ata: Pubkey) -> u64 {
+    account::<token::TokenAccount>(context, ata).await.amount
+}
+
+// a classic SPL mint with 6 decimals, the payer is its mint authority
+pub async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    send(
+        context,
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &token::ID,
+        ),
+        &[&mint],
+    )
+    .await
+    .unwrap();
+    let ix = spl_token::instruction::initialize_mint2(&token::ID, &mint.pubkey(), &payer, None, 6)
+        .unwrap();
+    send(context, ix, &[]).await.unwrap();
+    mint.pubkey()
+}
+
+// creates the wallet's ATA and mints `amount` into it
+pub async fn fund_ata(
+    context: &mut ProgramTestContext,
+    mint: Pubkey,
+    wallet: Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let ata = get_associated_token_address(&wallet, &mint);
+    let create = Instruction {
+        program_id: associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new(ata, false),
+            AccountMeta::new_readonly(wallet, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        // AssociatedTokenAccountInstruction::Create
+        data: vec![0],
+    };
+    send(context, create, &[]).await.unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = spl_token::instruction::mint_to(&token::ID, &mint, &ata, &payer, &[], amount).unwrap();
+    send(context, ix, &[]).await.unwrap();
+    ata
+}
+
+pub fn initialize(authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Initialize {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            fee_treasury: pda(&[FEE_TREASURY_SEED]),
+            metrics: pda(&[METRICS_SEED]),
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Initialize {}.data(),
+    }
+}
+
+pub fn deposit(user: Pubkey, deposit_amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Deposit {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            user_reserve: reserve_address(&user),
+            metrics: pda(&[METRICS_SEED]),
+            referral: None,
+            user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Deposit {
+            deposit_amount,
+            referrer: None,
+        }
+        .data(),
+    }
+}
+
+// the user withdraws lamports to themselves, no delegate
+pub fn withdraw(user: Pubkey, withdraw_amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::Withdraw {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            fee_treasury: pda(&[FEE_TREASURY_SEED]),
+            user_reserve: reserve_address(&user),
+            metrics: pda(&[METRICS_SEED]),
+            delegation: None,
+            user,
+            signer: user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::Withdraw { withdraw_amount }.data(),
+    }
+}
+
+pub fn add_supported_mint(authority: Pubkey, mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::AddSupportedMint {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            token_mint: mint,
+            supported_mint: pda(&[SUPPORTED_MINT_SEED, mint.as_ref()]),
+            bank_ata: get_associated_token_address(&pda(&[BANK_VAULT_SEED]), &mint),
+            fee_treasury: pda(&[FEE_TREASURY_SEED]),
+            fee_ata: get_associated_token_address(&pda(&[FEE_TREASURY_SEED]), &mint),
+            authority,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::AddSupportedMint {}.data(),
+    }
+}
+
+pub fn deposit_token(user: Pubkey, mint: Pubkey, deposit_amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::DepositToken {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            token_mint: mint,
+            supported_mint: pda(&[SUPPORTED_MINT_SEED, mint.as_ref()]),
+            price_feed: None,
+            user_ata: get_associated_token_address(&user, &mint),
+            bank_ata: get_associated_token_address(&pda(&[BANK_VAULT_SEED]), &mint),
+            user_reserve: reserve_address(&user),
+            metrics: pda(&[METRICS_SEED]),
+            referral: None,
+            user,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::DepositToken {
+            deposit_amount,
+            referrer: None,
+        }
+        .data(),
+    }
+}
+
+// the user withdraws to their own ATA, no delegate and no referral
+pub fn withdraw_token(user: Pubkey, mint: Pubkey, withdraw_amount: u64) -> Instruction {
+    let referral = pda(&[REFERRAL_SEED, user.as_ref()]);
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::WithdrawToken {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            bank_vault: pda(&[BANK_VAULT_SEED]),
+            token_mint: mint,
+            supported_mint: pda(&[SUPPORTED_MINT_SEED, mint.as_ref()]),
+            price_feed: None,
+            recipient_ata: get_associated_token_address(&user, &mint),
+            bank_ata: get_associated_token_address(&pda(&[BANK_VAULT_SEED]), &mint),
+            fee_treasury: pda(&[FEE_TREASURY_SEED]),
+            fee_ata: get_associated_token_address(&pda(&[FEE_TREASURY_SEED]), &mint),
+            referral,
+            referral_ata: None,
+            user_reserve: reserve_address(&user),
+            metrics: pda(&[METRICS_SEED]),
+            delegation: None,
+            user,
+            signer: user,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::WithdrawToken { withdraw_amount }.data(),
+    }
+}
+
+pub fn update_limits(
+    authority: Pubkey,
+    max_deposit_per_user: u64,
+    max_total_deposits: u64,
+) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::UpdateLimits {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            authority,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::UpdateLimits {
+            max_deposit_per_user,
+            max_total_deposits,
+        }
+        .data(),
+    }
+}
+
+pub fn set_fee(authority: Pubkey, fee_bps: u16) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::SetFee {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            authority,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::SetFee { fee_bps }.data(),
+    }
+}
+
+pub fn collect_fees(authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app::ID,
+        accounts: bank_app::accounts::CollectFees {
+            bank_info: pda(&[BANK_INFO_SEED]),
+            fee_treasury: pda(&[FEE_TREASURY_SEED]),
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: bank_app::instruction::CollectFees {}.data(),
+    }
+}
@@ -0,0 +1,275 @@
+use anchor_lang::{error::ErrorCode, prelude::Pubkey, system_program};
+use anchor_spl::associated_token::get_associated_token_address;
+use bank_app::{
+    constant::{
+        BANK_INFO_SEED, BANK_INFO_VERSION, BANK_VAULT_SEED, FEE_TREASURY_SEED, METRICS_SEED,
+        SUPPORTED_MINT_SEED,
+    },
+    error::BankAppError,
+    state::{BankInfo, SupportedMint, UserReserve},
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+use test_utils::{
+    account, add_supported_mint, assert_bank_error, assert_custom_error, bank, create_mint,
+    deposit, deposit_token, fund_ata, load, pda, raw_account, reserve_address, send, store,
+    token_balance, update_limits, user, withdraw_token,
+};
+
+// `pause` is yours to write. Once it is, add it to these flows next to the
+// paused deposit. The SOL `withdraw` and its fee are in withdrawal_fee.rs
+
+// a bank with a supported mint and a user holding `amount` of it
+async fn token_bank(amount: u64) -> (ProgramTestContext, Pubkey, Keypair) {
+    let mut context = bank().await;
+    let authority = context.payer.pubkey();
+    let mint = create_mint(&mut context).await;
+    send(&mut context, add_supported_mint(authority, mint), &[])
+        .await
+        .unwrap();
+
+    let user = user(&mut context, LAMPORTS_PER_SOL).await;
+    fund_ata(&mut context, mint, user.pubkey(), amount).await;
+    (context, mint, user)
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn the_bank_lives_at_its_pdas() {
+    let (mut context, mint, _) = token_bank(0).await;
+
+    // the same seeds the TS tests use, as plain strings
+    let (bank_info, _) = Pubkey::find_program_address(&[b"BANK_INFO_SEED"], &bank_app::ID);
+    let (bank_vault, vault_bump) =
+        Pubkey::find_program_address(&[b"BANK_VAULT_SEED"], &bank_app::ID);
+    let (fee_treasury, treasury_bump) =
+        Pubkey::find_program_address(&[b"FEE_TREASURY_SEED"], &bank_app::ID);
+    assert_eq!(bank_info, pda(&[BANK_INFO_SEED]));
+    assert_eq!(bank_vault, pda(&[BANK_VAULT_SEED]));
+    assert_eq!(fee_treasury, pda(&[FEE_TREASURY_SEED]));
+
+    let info: BankInfo = load(&mut context, bank_info).await;
+    assert_eq!(info.authority, context.payer.pubkey());
+    assert_eq!(info.version, BANK_INFO_VERSION);
+    assert!(!info.is_paused());
+    // kept to sign for the vault and the treasury
+    assert_eq!(info.bump, vault_bump);
+    assert_eq!(info.fee_treasury_bump, treasury_bump);
+    for address in [bank_vault, fee_treasury] {
+        let account = raw_account(&mut context, address).await;
+        assert_eq!(account.owner, system_program::ID);
+    }
+    assert_eq!(
+        raw_account(&mut context, pda(&[METRICS_SEED])).await.owner,
+        bank_app::ID
+    );
+
+    // one SupportedMint per mint, and the mint's ATAs belong to the vault and the treasury
+    let supported: SupportedMint =
+        account(&mut context, pda(&[SUPPORTED_MINT_SEED, mint.as_ref()])).await;
+    assert_eq!(supported.mint, mint);
+    assert_eq!(
+        token_balance(
+            &mut context,
+            get_associated_token_address(&bank_vault, &mint)
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        token_balance(
+            &mut context,
+            get_associated_token_address(&fee_treasury, &mint)
+        )
+        .await,
+        0
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_token_deposit_is_withdrawn_through_the_bank_ata() {
+    let (mut context, mint, user) = token_bank(1_000).await;
+    let user_ata = get_associated_token_address(&user.pubkey(), &mint);
+    let bank_ata = get_associated_token_address(&pda(&[BANK_VAULT_SEED]), &mint);
+
+    send(
+        &mut context,
+        deposit_token(user.pubkey(), mint, 600),
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let reserve: UserReserve = load(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.token_balance(&mint), 600);
+    assert_eq!(token_balance(&mut context, bank_ata).await, 600);
+    assert_eq!(token_balance(&mut context, user_ata).await, 400);
+
+    send(
+        &mut context,
+        withdraw_token(user.pubkey(), mint, 250),
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let reserve: UserReserve = load(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.token_balance(&mint), 350);
+    // no fee was set, the whole amount comes back
+    assert_eq!(token_balance(&mut context, bank_ata).await, 350);
+    assert_eq!(token_balance(&mut context, user_ata).await, 650);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_sol_deposit_is_kept_in_the_vault() {
+    let mut context = bank().await;
+    let user = user(&mut context, 2 * LAMPORTS_PER_SOL).await;
+    let vault = pda(&[BANK_VAULT_SEED]);
+    let vault_before = raw_account(&mut context, vault).await.lamports;
+
+    send(
+        &mut context,
+        deposit(user.pubkey(), LAMPORTS_PER_SOL),
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let reserve: UserReserve = load(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.deposited_amount, LAMPORTS_PER_SOL);
+    assert_eq!(
+        raw_account(&mut context, vault).await.lamports,
+        vault_before + LAMPORTS_PER_SOL
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn more_than_the_reserve_holds_is_not_withdrawn() {
+    let (mut context, mint, user) = token_bank(1_000).await;
+    send(
+        &mut context,
+        deposit_token(user.pubkey(), mint, 600),
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    assert_bank_error(
+        send(
+            &mut context,
+            withdraw_token(user.pubkey(), mint, 601),
+            &[&user],
+        )
+        .await,
+        BankAppError::InsufficientFunds,
+    );
+    let reserve: UserReserve = load(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.token_balance(&mint), 600);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_deposit_over_the_cap_is_rejected() {
+    let (mut context, mint, user) = token_bank(1_000).await;
+    let authority = context.payer.pubkey();
+    send(&mut context, update_limits(authority, 500, 0), &[])
+        .await
+        .unwrap();
+
+    assert_bank_error(
+        send(
+            &mut context,
+            deposit_token(user.pubkey(), mint, 501),
+            &[&user],
+        )
+        .await,
+        BankAppError::UserDepositCapExceeded,
+    );
+    send(
+        &mut context,
+        deposit_token(user.pubkey(), mint, 500),
+        &[&user],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_paused_bank_takes_and_pays_nothing() {
+    let (mut context, mint, user) = token_bank(1_000).await;
+    send(
+        &mut context,
+        deposit_token(user.pubkey(), mint, 600),
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // there's no pause instruction until you write it, so the flag is set directly
+    let bank_info = pda(&[BANK_INFO_SEED]);
+    let mut info: BankInfo = load(&mut context, bank_info).await;
+    info.paused = 1;
+    store(&mut context, bank_info, &info).await;
+
+    assert_bank_error(
+        send(&mut context, deposit(user.pubkey(), 1_000_000), &[&user]).await,
+        BankAppError::BankAppPaused,
+    );
+    assert_bank_error(
+        send(
+            &mut context,
+            deposit_token(user.pubkey(), mint, 100),
+            &[&user],
+        )
+        .await,
+        BankAppError::BankAppPaused,
+    );
+    assert_bank_error(
+        send(
+            &mut context,
+            withdraw_token(user.pubkey(), mint, 100),
+            &[&user],
+        )
+        .await,
+        BankAppError::BankAppPaused,
+    );
+
+    info.paused = 0;
+    store(&mut context, bank_info, &info).await;
+    send(
+        &mut context,
+        withdraw_token(user.pubkey(), mint, 100),
+        &[&user],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn only_the_authority_adds_a_mint() {
+    let mut context = bank().await;
+    let mint = create_mint(&mut context).await;
+    let stranger = user(&mut context, LAMPORTS_PER_SOL).await;
+
+    // `address = bank_info.load()?.authority` has no custom error, so it's Anchor's
+    assert_custom_error(
+        send(
+            &mut context,
+            add_supported_mint(stranger.pubkey(), mint),
+            &[&stranger],
+        )
+        .await,
+        ErrorCode::ConstraintAddress as u32,
+    );
+    assert!(context
+        .banks_client
+        .get_account(pda(&[SUPPORTED_MINT_SEED, mint.as_ref()]))
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,66 @@
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use test_utils::{deposit, initialize, set_fee, start, update_limits, withdraw};
+
+// ceilings with some headroom, not measurements. A change that pushes an
+// instruction past its budget should be looked at before the budget is raised
+const INITIALIZE_BUDGET: u64 = 60_000;
+const FIRST_DEPOSIT_BUDGET: u64 = 40_000;
+const DEPOSIT_BUDGET: u64 = 25_000;
+const SET_FEE_BUDGET: u64 = 10_000;
+const WITHDRAW_BUDGET: u64 = 30_000;
+const UPDATE_LIMITS_BUDGET: u64 = 10_000;
+
+// simulated first for the units, then sent so the next instruction sees its state
+async fn units_consumed(context: &mut ProgramTestContext, ix: Instruction) -> u64 {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx.clone())
+        .await
+        .unwrap();
+    simulation.result.unwrap().unwrap();
+    let units = simulation.simulation_details.unwrap().units_consumed;
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+    units
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn instructions_stay_within_their_compute_budget() {
+    let mut context = start().await;
+    let authority = context.payer.pubkey();
+
+    let units = units_consumed(&mut context, initialize(authority)).await;
+    assert!(units <= INITIALIZE_BUDGET, "initialize used {units} CUs");
+
+    // the first deposit also creates the user reserve
+    let units = units_consumed(&mut context, deposit(authority, 1_000_000)).await;
+    assert!(
+        units <= FIRST_DEPOSIT_BUDGET,
+        "first deposit used {units} CUs"
+    );
+    let units = units_consumed(&mut context, deposit(authority, 2_000_000)).await;
+    assert!(units <= DEPOSIT_BUDGET, "deposit used {units} CUs");
+
+    let units = units_consumed(&mut context, set_fee(authority, 50)).await;
+    assert!(units <= SET_FEE_BUDGET, "set_fee used {units} CUs");
+
+    // with the fee set, two transfers out of the vault
+    let units = units_consumed(&mut context, withdraw(authority, 1_000_000)).await;
+    assert!(units <= WITHDRAW_BUDGET, "withdraw used {units} CUs");
+
+    let update_limits = update_limits(authority, 10_000_000, 100_000_000);
+    let units = units_consumed(&mut context, update_limits).await;
+    assert!(
+        units <= UPDATE_LIMITS_BUDGET,
+        "update_limits used {units} CUs"
+    );
+}
@@ -0,0 +1,121 @@
+use bank_app::{
+    constant::{BANK_VAULT_SEED, FEE_TREASURY_SEED, MAX_FEE_BPS},
+    error::BankAppError,
+    state::UserReserve,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer};
+use test_utils::{
+    assert_bank_error, bank, collect_fees, deposit, load, pda, raw_account, reserve_address, send,
+    set_fee, user, withdraw,
+};
+
+// program-test charges the default 5000 lamports per signature, and the payer
+// signs alone
+const TX_FEE: u64 = 5_000;
+
+async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    raw_account(context, address).await.lamports
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn a_withdrawal_pays_its_fee_into_the_treasury() {
+    let mut context = bank().await;
+    let authority = context.payer.pubkey();
+    let user = user(&mut context, LAMPORTS_PER_SOL).await;
+    let vault = pda(&[BANK_VAULT_SEED]);
+    let treasury = pda(&[FEE_TREASURY_SEED]);
+
+    send(&mut context, set_fee(authority, 50), &[])
+        .await
+        .unwrap();
+    send(&mut context, deposit(user.pubkey(), 100_000_000), &[&user])
+        .await
+        .unwrap();
+
+    let user_before = lamports(&mut context, user.pubkey()).await;
+    let vault_before = lamports(&mut context, vault).await;
+    let treasury_before = lamports(&mut context, treasury).await;
+
+    // 0.5% of 10_000_000, the payer pays the transaction fee
+    send(&mut context, withdraw(user.pubkey(), 10_000_000), &[&user])
+        .await
+        .unwrap();
+    assert_eq!(
+        lamports(&mut context, user.pubkey()).await,
+        user_before + 9_950_000
+    );
+    assert_eq!(
+        lamports(&mut context, vault).await,
+        vault_before - 10_000_000
+    );
+    assert_eq!(
+        lamports(&mut context, treasury).await,
+        treasury_before + 50_000
+    );
+    let reserve: UserReserve = load(&mut context, reserve_address(&user.pubkey())).await;
+    assert_eq!(reserve.deposited_amount, 90_000_000);
+
+    // too small to owe a whole lamport, the user gets all of it
+    send(&mut context, withdraw(user.pubkey(), 199), &[&user])
+        .await
+        .unwrap();
+    assert_eq!(
+        lamports(&mut context, treasury).await,
+        treasury_before + 50_000
+    );
+
+    // the reserve is debited the full amount, not what the user received
+    assert_bank_error(
+        send(&mut context, withdraw(user.pubkey(), 90_000_000), &[&user]).await,
+        BankAppError::InsufficientFunds,
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+async fn collected_fees_go_to_the_authority() {
+    let mut context = bank().await;
+    let authority = context.payer.pubkey();
+    let user = user(&mut context, LAMPORTS_PER_SOL).await;
+    let treasury = pda(&[FEE_TREASURY_SEED]);
+    let treasury_rent = lamports(&mut context, treasury).await;
+
+    send(&mut context, set_fee(authority, MAX_FEE_BPS), &[])
+        .await
+        .unwrap();
+    send(&mut context, deposit(user.pubkey(), 100_000_000), &[&user])
+        .await
+        .unwrap();
+    send(&mut context, withdraw(user.pubkey(), 30_000_000), &[&user])
+        .await
+        .unwrap();
+    send(&mut context, withdraw(user.pubkey(), 20_000_001), &[&user])
+        .await
+        .unwrap();
+
+    // 10% of each withdrawal, the odd lamport rounds down
+    let fees = 3_000_000 + 2_000_000;
+    assert_eq!(lamports(&mut context, treasury).await, treasury_rent + fees);
+
+    let authority_before = lamports(&mut context, authority).await;
+    send(&mut context, collect_fees(authority), &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        lamports(&mut context, authority).await,
+        authority_before + fees - TX_FEE
+    );
+    // the treasury keeps its rent
+    assert_eq!(lamports(&mut context, treasury).await, treasury_rent);
+
+    // nothing left, a second sweep only costs the transaction
+    send(&mut context, collect_fees(authority), &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        lamports(&mut context, authority).await,
+        authority_before + fees - 2 * TX_FEE
+    );
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// with `anchor build -- --features debug-cu` every handler logs the compute
+// units left when it starts and when it's done, the difference is what it cost.
+// Without the feature this compiles to nothing
+#[cfg(feature = "debug-cu")]
+pub fn log_compute_units(step: &str) {
+    msg!(step);
+    anchor_lang::solana_program::log::sol_log_compute_units();
+}
+
+#[cfg(not(feature = "debug-cu"))]
+#[inline(always)]
+pub fn log_compute_units(_step: &str) {}
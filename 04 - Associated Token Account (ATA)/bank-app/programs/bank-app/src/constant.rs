@@ -4,3 +4,32 @@ use anchor_lang::prelude::*;
 pub const BANK_INFO_SEED: &[u8] = b"BANK_INFO_SEED";
 pub const BANK_VAULT_SEED: &[u8] = b"BANK_VAULT_SEED";
 pub const USER_RESERVE_SEED: &[u8] = b"USER_RESERVE_SEED";
+pub const SUPPORTED_MINT_SEED: &[u8] = b"SUPPORTED_MINT_SEED";
+pub const FEE_TREASURY_SEED: &[u8] = b"FEE_TREASURY_SEED";
+pub const DELEGATION_SEED: &[u8] = b"DELEGATION_SEED";
+pub const REFERRAL_SEED: &[u8] = b"REFERRAL_SEED";
+pub const METRICS_SEED: &[u8] = b"METRICS_SEED";
+
+// 10%, the most a withdrawal can be charged
+pub const MAX_FEE_BPS: u16 = 1_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// how many mints one UserReserve holds at once, a slot is freed when it's emptied
+pub const MAX_TOKEN_BALANCES: usize = 8;
+
+// one counter per Metric, with spare ones so a new instruction doesn't need a
+// migration
+pub const METRIC_COUNT: usize = 8;
+
+// a feed more than this many slots behind the clock is stale, about 10 seconds
+pub const MAX_ORACLE_STALENESS_SLOTS: u64 = 25;
+// the spread between the feed's oracles, as its standard deviation, may be at
+// most 1% of the price
+pub const MAX_ORACLE_CONFIDENCE_BPS: u64 = 100;
+// reserves are valued in USD with 6 decimals, like USDC
+pub const USD_DECIMALS: u8 = 6;
+
+// bumped whenever a layout changes, migrate_bank_info / migrate_user_reserve
+// bring an older account up to it. Version 0 is the layout from before versioning
+pub const BANK_INFO_VERSION: u8 = 2;
+pub const USER_RESERVE_VERSION: u8 = 2;
@@ -4,4 +4,46 @@ use anchor_lang::prelude::*;
 pub enum BankAppError {
     #[msg("The bank app is currently paused.")]
     BankAppPaused,
+    #[msg("The withdraw amount is more than the user deposited.")]
+    InsufficientFunds,
+    #[msg("The fee is more than MAX_FEE_BPS.")]
+    InvalidFee,
+    #[msg("The fee calculation overflowed.")]
+    MathOverflow,
+    #[msg("The deposit would take the user's reserve above max_deposit_per_user.")]
+    UserDepositCapExceeded,
+    #[msg("The deposit would take the bank's holdings above max_total_deposits.")]
+    GlobalDepositCapExceeded,
+    #[msg("The token account does not hold wrapped SOL.")]
+    NotWrappedSol,
+    #[msg("The user reserve already holds MAX_TOKEN_BALANCES other mints.")]
+    TooManyTokenBalances,
+    #[msg("The account is from another layout version, migrate it first.")]
+    VersionMismatch,
+    #[msg("The account is already at the current layout version.")]
+    AlreadyMigrated,
+    #[msg("The signer is neither the user nor their approved delegate.")]
+    NotDelegate,
+    #[msg("The withdrawal is more than the delegate's remaining allowance.")]
+    DelegateAllowanceExceeded,
+    #[msg("The delegation is for another asset.")]
+    DelegatedAssetMismatch,
+    #[msg("The ATAs to create come as (mint, ata, token program) triples.")]
+    InvalidAtaAccounts,
+    #[msg("A user can't be their own referrer.")]
+    SelfReferral,
+    #[msg("A referrer can only be set with the user's first deposit.")]
+    ReferrerAfterFirstDeposit,
+    #[msg("The referrer and the referral accounts don't match.")]
+    InvalidReferral,
+    #[msg("The referral share is more than the whole fee.")]
+    InvalidReferralShare,
+    #[msg("The price feed is missing or isn't the one set for the mint.")]
+    InvalidPriceFeed,
+    #[msg("The oracle price is not valid.")]
+    InvalidOraclePrice,
+    #[msg("The oracle price is too old.")]
+    StaleOraclePrice,
+    #[msg("The oracles disagree too much on the price.")]
+    OracleConfidenceTooWide,
 }
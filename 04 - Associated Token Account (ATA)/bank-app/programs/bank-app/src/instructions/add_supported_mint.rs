@@ -0,0 +1,91 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, FEE_TREASURY_SEED, SUPPORTED_MINT_SEED},
+    state::{BankInfo, SupportedMint},
+};
+
+#[derive(Accounts)]
+pub struct AddSupportedMint<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [SUPPORTED_MINT_SEED, token_mint.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<SupportedMint>(),
+    )]
+    pub supported_mint: Box<Account<'info, SupportedMint>>,
+
+    // the mint's vault, every user's deposits of this mint sit here
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = bank_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(
+        seeds = [FEE_TREASURY_SEED],
+        bump = bank_info.load()?.fee_treasury_bump,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    // withdrawal fees in this mint collect here until collect_token_fees
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub fee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddSupportedMint<'info> {
+    pub fn process(ctx: Context<AddSupportedMint>) -> Result<()> {
+        log_compute_units("add_supported_mint: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        let supported_mint = &mut ctx.accounts.supported_mint;
+
+        supported_mint.mint = ctx.accounts.token_mint.key();
+        supported_mint.token_program = ctx.accounts.token_program.key();
+        supported_mint.bump = ctx.bumps.supported_mint;
+
+        msg!("supported mint added: {}", supported_mint.mint);
+        log_compute_units("add_supported_mint: end");
+        Ok(())
+    }
+}
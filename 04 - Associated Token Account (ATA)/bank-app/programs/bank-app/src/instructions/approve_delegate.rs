@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, constant::DELEGATION_SEED, state::Delegation};
+
+// `mint` is Pubkey::default() to delegate lamports. A user has one delegation,
+// approving again replaces the delegate, the asset and the allowance
+#[derive(Accounts)]
+pub struct ApproveDelegate<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [DELEGATION_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<Delegation>(),
+    )]
+    pub delegation: Box<Account<'info, Delegation>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveDelegate<'info> {
+    pub fn process(
+        ctx: Context<ApproveDelegate>,
+        delegate: Pubkey,
+        mint: Pubkey,
+        allowance: u64,
+    ) -> Result<()> {
+        log_compute_units("approve_delegate: start");
+        let delegation = &mut ctx.accounts.delegation;
+
+        delegation.user = ctx.accounts.user.key();
+        delegation.delegate = delegate;
+        delegation.mint = mint;
+        delegation.allowance = allowance;
+        delegation.bump = ctx.bumps.delegation;
+
+        msg!("{} may withdraw {} of {}", delegate, allowance, mint);
+        log_compute_units("approve_delegate: end");
+        Ok(())
+    }
+}
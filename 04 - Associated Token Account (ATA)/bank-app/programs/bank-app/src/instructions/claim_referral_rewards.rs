@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, REFERRAL_SEED},
+    state::{BankInfo, ReferralAccount},
+    transfer_helper::token_transfer_from_pda,
+};
+
+// one referral and one mint per call, a referrer with several referred users
+// claims each of them
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(
+        seeds = [REFERRAL_SEED, referral.user.as_ref()],
+        bump,
+        has_one = referrer,
+    )]
+    pub referral: Box<Account<'info, ReferralAccount>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = referral,
+        associated_token::token_program = token_program,
+    )]
+    pub referral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = token_mint,
+        associated_token::authority = referrer,
+        associated_token::token_program = token_program,
+    )]
+    pub referrer_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimReferralRewards<'info> {
+    pub fn process(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        log_compute_units("claim_referral_rewards: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        let rewards = ctx.accounts.referral_ata.amount;
+        if rewards == 0 {
+            msg!("no referral rewards to claim");
+            return Ok(());
+        }
+
+        // the referral ATA is owned by the referral PDA, so the referral signs
+        let user = ctx.accounts.referral.user;
+        let pda_seeds: &[&[&[u8]]] = &[&[REFERRAL_SEED, user.as_ref(), &[ctx.bumps.referral]]];
+
+        token_transfer_from_pda(
+            ctx.accounts.referral_ata.to_account_info(),
+            ctx.accounts.referral.to_account_info(),
+            ctx.accounts.referrer_ata.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            pda_seeds,
+            rewards,
+        )?;
+
+        msg!(
+            "claimed {} of {} in referral rewards",
+            rewards,
+            ctx.accounts.token_mint.key()
+        );
+        log_compute_units("claim_referral_rewards: end");
+        Ok(())
+    }
+}
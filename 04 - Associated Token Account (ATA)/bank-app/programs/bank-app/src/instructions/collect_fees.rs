@@ -0,0 +1,61 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, FEE_TREASURY_SEED},
+    state::BankInfo,
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// sweeps the SOL fees to the authority, collect_token_fees does the same per mint
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump = bank_info.load()?.fee_treasury_bump,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CollectFees<'info> {
+    pub fn process(ctx: Context<CollectFees>) -> Result<()> {
+        log_compute_units("collect_fees: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        // the treasury keeps its rent, everything above it is fees
+        let rent = Rent::get()?.minimum_balance(0);
+        let fees = ctx.accounts.fee_treasury.lamports().saturating_sub(rent);
+        if fees == 0 {
+            msg!("no fees to collect");
+            return Ok(());
+        }
+
+        let fee_treasury_bump = ctx.accounts.bank_info.load()?.fee_treasury_bump;
+        let pda_seeds: &[&[&[u8]]] = &[&[FEE_TREASURY_SEED, &[fee_treasury_bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.fee_treasury.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            fees,
+        )?;
+
+        msg!("collected {} lamports of fees", fees);
+        log_compute_units("collect_fees: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, FEE_TREASURY_SEED},
+    state::BankInfo,
+    transfer_helper::token_transfer_from_pda,
+};
+
+#[derive(Accounts)]
+pub struct CollectTokenFees<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        seeds = [FEE_TREASURY_SEED],
+        bump = bank_info.load()?.fee_treasury_bump,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub fee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CollectTokenFees<'info> {
+    pub fn process(ctx: Context<CollectTokenFees>) -> Result<()> {
+        log_compute_units("collect_token_fees: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        let fees = ctx.accounts.fee_ata.amount;
+        if fees == 0 {
+            msg!("no fees to collect");
+            return Ok(());
+        }
+
+        let fee_treasury_bump = ctx.accounts.bank_info.load()?.fee_treasury_bump;
+        let pda_seeds: &[&[&[u8]]] = &[&[FEE_TREASURY_SEED, &[fee_treasury_bump]]];
+
+        token_transfer_from_pda(
+            ctx.accounts.fee_ata.to_account_info(),
+            ctx.accounts.fee_treasury.to_account_info(),
+            ctx.accounts.authority_ata.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            pda_seeds,
+            fees,
+        )?;
+
+        msg!(
+            "collected {} of {} in fees",
+            fees,
+            ctx.accounts.token_mint.key()
+        );
+        log_compute_units("collect_token_fees: end");
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, METRICS_SEED, REFERRAL_SEED, USER_RESERVE_SEED},
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    state::{BankInfo, Metric, Metrics, ReferralAccount, UserReserve},
     transfer_helper::sol_transfer_from_user,
 };
 
@@ -13,7 +14,7 @@ pub struct Deposit<'info> {
         seeds = [BANK_INFO_SEED],
         bump
     )]
-    pub bank_info: Box<Account<'info, BankInfo>>,
+    pub bank_info: AccountLoader<'info, BankInfo>,
 
     ///CHECK:
     #[account(
@@ -31,7 +32,24 @@ pub struct Deposit<'info> {
         payer = user,
         space = 8 + std::mem::size_of::<UserReserve>(),
     )]
-    pub user_reserve: Box<Account<'info, UserReserve>>,
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    // only with a referrer, on the user's first deposit
+    #[account(
+        init,
+        seeds = [REFERRAL_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<ReferralAccount>(),
+    )]
+    pub referral: Option<Box<Account<'info, ReferralAccount>>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
@@ -39,12 +57,29 @@ pub struct Deposit<'info> {
 }
 
 impl<'info> Deposit<'info> {
-    pub fn process(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
-        if ctx.accounts.bank_info.is_paused {
+    pub fn process(
+        ctx: Context<Deposit>,
+        deposit_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        log_compute_units("deposit: start");
+        let bank_info = ctx.accounts.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
             return Err(BankAppError::BankAppPaused.into());
         }
 
-        let user_reserve = &mut ctx.accounts.user_reserve;
+        ReferralAccount::open(
+            ctx.accounts
+                .referral
+                .as_deref_mut()
+                .map(|referral| &mut **referral),
+            ctx.accounts.user.key,
+            referrer,
+            UserReserve::is_new(&ctx.accounts.user_reserve)?,
+        )?;
+
+        let mut user_reserve = UserReserve::load_or_init(&ctx.accounts.user_reserve)?;
 
         sol_transfer_from_user(
             &ctx.accounts.user,
@@ -53,8 +88,22 @@ impl<'info> Deposit<'info> {
             deposit_amount,
         )?;
 
-        user_reserve.deposited_amount += deposit_amount;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        bank_info.check_deposit_limits(
+            user_reserve.deposited_amount,
+            ctx.accounts.bank_vault.lamports(),
+        )?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::Deposit, deposit_amount);
 
+        log_compute_units("deposit: end");
         Ok(())
     }
 }
@@ -1,13 +1,14 @@
 use anchor_lang::{prelude::*, system_program};
-use anchor_spl::{
-    token::Token,
-    token_interface::{Mint, TokenAccount},
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{
+        BANK_INFO_SEED, BANK_VAULT_SEED, METRICS_SEED, REFERRAL_SEED, SUPPORTED_MINT_SEED,
+        USER_RESERVE_SEED,
+    },
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    state::{BankInfo, Metric, Metrics, ReferralAccount, SupportedMint, UserReserve},
     transfer_helper::token_transfer_from_user,
 };
 
@@ -17,7 +18,7 @@ pub struct DepositToken<'info> {
         seeds = [BANK_INFO_SEED],
         bump
     )]
-    pub bank_info: Box<Account<'info, BankInfo>>,
+    pub bank_info: AccountLoader<'info, BankInfo>,
 
     ///CHECK:
     #[account(
@@ -28,62 +29,128 @@ pub struct DepositToken<'info> {
     )]
     pub bank_vault: UncheckedAccount<'info>,
 
-    #[account(mut)]
+    #[account(mint::token_program = token_program)]
     pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    // fails with AccountNotInitialized until the authority adds the mint
+    #[account(
+        seeds = [SUPPORTED_MINT_SEED, token_mint.key().as_ref()],
+        bump = supported_mint.bump,
+    )]
+    pub supported_mint: Box<Account<'info, SupportedMint>>,
+
+    ///CHECK: the mint's switchboard feed, checked against supported_mint.price_feed
+    /// when it's read. Only for a priced mint
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
     #[account(
         mut,
         associated_token::mint = token_mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
     pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = token_mint,
-        associated_token::authority = bank_vault
+        associated_token::authority = bank_vault,
+        associated_token::token_program = token_program,
     )]
     pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
-        seeds = [
-            USER_RESERVE_SEED,
-            user.key().as_ref(),
-            token_mint.key().as_ref()
-        ],
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
         bump,
         payer = user,
         space = 8 + std::mem::size_of::<UserReserve>(),
     )]
-    pub user_reserve: Box<Account<'info, UserReserve>>,
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    // only with a referrer, on the user's first deposit
+    #[account(
+        init,
+        seeds = [REFERRAL_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<ReferralAccount>(),
+    )]
+    pub referral: Option<Box<Account<'info, ReferralAccount>>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> DepositToken<'info> {
-    pub fn process(ctx: Context<DepositToken>, deposit_amount: u64) -> Result<()> {
-        let bank_info = &mut ctx.accounts.bank_info;
-
-        if bank_info.is_paused {
+    pub fn process(
+        ctx: Context<DepositToken>,
+        deposit_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        log_compute_units("deposit_token: start");
+        let bank_info = ctx.accounts.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
             return Err(BankAppError::BankAppPaused.into());
         }
 
-        let user_reserve = &mut ctx.accounts.user_reserve;
+        ReferralAccount::open(
+            ctx.accounts
+                .referral
+                .as_deref_mut()
+                .map(|referral| &mut **referral),
+            ctx.accounts.user.key,
+            referrer,
+            UserReserve::is_new(&ctx.accounts.user_reserve)?,
+        )?;
 
+        let balance_before = ctx.accounts.bank_ata.amount;
         token_transfer_from_user(
             ctx.accounts.user_ata.to_account_info(),
             &ctx.accounts.user,
             ctx.accounts.bank_ata.to_account_info(),
+            &ctx.accounts.token_mint,
             &ctx.accounts.token_program,
             deposit_amount,
         )?;
 
-        user_reserve.deposited_amount += deposit_amount;
+        // a Token-2022 transfer fee is held back from what the bank ATA receives,
+        // so the reserve is credited with what arrived, not with deposit_amount
+        ctx.accounts.bank_ata.reload()?;
+        let received = ctx
+            .accounts
+            .bank_ata
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        let mut user_reserve = UserReserve::load_or_init(&ctx.accounts.user_reserve)?;
+        let deposited = user_reserve.credit_token(&ctx.accounts.token_mint.key(), received)?;
+        user_reserve.credit_usd(ctx.accounts.supported_mint.usd_value(
+            received,
+            ctx.accounts.token_mint.decimals,
+            ctx.accounts.price_feed.as_deref(),
+            &Clock::get()?,
+        )?)?;
+
+        bank_info.check_deposit_limits(deposited, ctx.accounts.bank_ata.amount)?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::DepositToken, received);
 
+        log_compute_units("deposit_token: end");
         Ok(())
     }
 }
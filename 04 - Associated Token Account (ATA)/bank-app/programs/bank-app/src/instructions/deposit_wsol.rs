@@ -0,0 +1,125 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token::spl_token::native_mint,
+    token_interface::{
+        close_account, sync_native, CloseAccount, SyncNative, TokenAccount, TokenInterface,
+    },
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, METRICS_SEED, USER_RESERVE_SEED},
+    error::BankAppError,
+    state::{BankInfo, Metric, Metrics, UserReserve},
+    transfer_helper::sol_transfer_from_user,
+};
+
+// wSOL is SOL held in a token account, so it's credited to the SOL reserve
+// instead of a token reserve of its own
+#[derive(Accounts)]
+pub struct DepositWsol<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    // any of the user's wSOL accounts, usually their ATA for the native mint
+    #[account(
+        mut,
+        constraint = user_wsol.mint == native_mint::ID @ BankAppError::NotWrappedSol,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_wsol: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositWsol<'info> {
+    // deposits the whole wSOL balance and closes the account, its rent goes back
+    // to the user
+    pub fn process(ctx: Context<DepositWsol>) -> Result<()> {
+        log_compute_units("deposit_wsol: start");
+        let bank_info = ctx.accounts.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+
+        // SOL sent straight to the account only counts as wSOL after a sync
+        sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.user_wsol.to_account_info(),
+            },
+        ))?;
+        ctx.accounts.user_wsol.reload()?;
+        let amount = ctx.accounts.user_wsol.amount;
+
+        // closing a native account pays out all of its lamports, wSOL and rent
+        close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.user_wsol.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+
+        sol_transfer_from_user(
+            &ctx.accounts.user,
+            ctx.accounts.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        let mut user_reserve = UserReserve::load_or_init(&ctx.accounts.user_reserve)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(amount)
+            .ok_or(BankAppError::MathOverflow)?;
+
+        bank_info.check_deposit_limits(
+            user_reserve.deposited_amount,
+            ctx.accounts.bank_vault.lamports(),
+        )?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::DepositWsol, amount);
+
+        msg!("deposited {} lamports of wSOL", amount);
+        log_compute_units("deposit_wsol: end");
+        Ok(())
+    }
+}
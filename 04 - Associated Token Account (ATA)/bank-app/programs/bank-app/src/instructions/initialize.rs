@@ -1,8 +1,11 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED},
-    state::BankInfo,
+    compute::log_compute_units,
+    constant::{
+        BANK_INFO_SEED, BANK_INFO_VERSION, BANK_VAULT_SEED, FEE_TREASURY_SEED, METRICS_SEED,
+    },
+    state::{BankInfo, Metrics},
 };
 
 #[derive(Accounts)]
@@ -14,7 +17,7 @@ pub struct Initialize<'info> {
         payer = authority,
         space = 8 + std::mem::size_of::<BankInfo>(),
     )]
-    pub bank_info: Box<Account<'info, BankInfo>>,
+    pub bank_info: AccountLoader<'info, BankInfo>,
 
     ///CHECK:
     #[account(
@@ -27,6 +30,26 @@ pub struct Initialize<'info> {
     )]
     pub bank_vault: UncheckedAccount<'info>,
 
+    ///CHECK: holds the SOL withdrawal fees, and owns an ATA per mint for token fees
+    #[account(
+        init,
+        seeds = [FEE_TREASURY_SEED],
+        bump,
+        payer = authority,
+        space = 0,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [METRICS_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Metrics>(),
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -34,13 +57,26 @@ pub struct Initialize<'info> {
 
 impl<'info> Initialize<'info> {
     pub fn process(ctx: Context<Initialize>) -> Result<()> {
-        let bank_info = &mut ctx.accounts.bank_info;
+        log_compute_units("initialize: start");
+        let mut bank_info = ctx.accounts.bank_info.load_init()?;
 
         bank_info.authority = ctx.accounts.authority.key();
-        bank_info.is_paused = false;
+        bank_info.paused = 0;
         bank_info.bump = ctx.bumps.bank_vault;
+        bank_info.fee_bps = 0;
+        bank_info.fee_treasury_bump = ctx.bumps.fee_treasury;
+        bank_info.max_deposit_per_user = 0;
+        bank_info.max_total_deposits = 0;
+        bank_info.referral_share_bps = 0;
+        bank_info.version = BANK_INFO_VERSION;
+
+        ctx.accounts
+            .metrics
+            .load_init()?
+            .reset(Clock::get()?.unix_timestamp);
 
         msg!("bank app initialized!");
+        log_compute_units("initialize: end");
         Ok(())
     }
 }
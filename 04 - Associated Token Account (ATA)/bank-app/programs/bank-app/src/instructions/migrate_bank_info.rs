@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BANK_INFO_VERSION},
+    error::BankAppError,
+    state::BankInfo,
+};
+
+#[derive(Accounts)]
+pub struct MigrateBankInfo<'info> {
+    // grown to the current layout before the handler runs, load would map past
+    // the end of a shorter account
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump,
+        realloc = 8 + std::mem::size_of::<BankInfo>(),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateBankInfo<'info> {
+    pub fn process(ctx: Context<MigrateBankInfo>) -> Result<()> {
+        log_compute_units("migrate_bank_info: start");
+        let mut bank_info = ctx.accounts.bank_info.load_mut()?;
+        if bank_info.version >= BANK_INFO_VERSION {
+            return Err(BankAppError::AlreadyMigrated.into());
+        }
+
+        // v1 only numbered the layout, version 0 took the byte from the padding
+        // and every other field is already there. v2 took the rest of the padding
+        // for referral_share_bps, it was zero so no share is paid until it's set
+        bank_info.version = BANK_INFO_VERSION;
+
+        msg!("bank info migrated to v{}", BANK_INFO_VERSION);
+        log_compute_units("migrate_bank_info: end");
+        Ok(())
+    }
+}
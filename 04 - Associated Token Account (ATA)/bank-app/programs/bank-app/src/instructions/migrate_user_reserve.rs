@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{USER_RESERVE_SEED, USER_RESERVE_VERSION},
+    error::BankAppError,
+    state::UserReserve,
+};
+
+#[derive(Accounts)]
+pub struct MigrateUserReserve<'info> {
+    // grown to the current layout before the handler runs, the new bytes come
+    // in zeroed and are backfilled below
+    #[account(
+        mut,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        realloc = 8 + std::mem::size_of::<UserReserve>(),
+        realloc::payer = user,
+        realloc::zero = false,
+    )]
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserReserve<'info> {
+    pub fn process(ctx: Context<MigrateUserReserve>) -> Result<()> {
+        log_compute_units("migrate_user_reserve: start");
+        let mut user_reserve = ctx.accounts.user_reserve.load_mut()?;
+        if user_reserve.version >= USER_RESERVE_VERSION {
+            return Err(BankAppError::AlreadyMigrated.into());
+        }
+
+        // v1 appended the version and its padding, the balances are unchanged.
+        // v2 appended usd_value, it starts at zero and only counts what moves
+        // from now on
+        user_reserve.version = USER_RESERVE_VERSION;
+
+        msg!("user reserve migrated to v{}", USER_RESERVE_VERSION);
+        log_compute_units("migrate_user_reserve: end");
+        Ok(())
+    }
+}
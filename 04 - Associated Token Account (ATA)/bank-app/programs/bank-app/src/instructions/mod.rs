@@ -1,13 +1,45 @@
+pub mod add_supported_mint;
+pub mod approve_delegate;
+pub mod claim_referral_rewards;
+pub mod collect_fees;
+pub mod collect_token_fees;
 pub mod deposit;
 pub mod deposit_token;
+pub mod deposit_wsol;
 pub mod initialize;
+pub mod migrate_bank_info;
+pub mod migrate_user_reserve;
+pub mod open_account;
 // pub mod pause;
-// pub mod withdraw;
-// pub mod withdraw_token;
+pub mod read_reserve;
+pub mod reset_metrics;
+pub mod revoke_delegate;
+pub mod set_fee;
+pub mod set_price_feed;
+pub mod set_referral_share;
+pub mod update_limits;
+pub mod withdraw;
+pub mod withdraw_token;
 
+pub use add_supported_mint::*;
+pub use approve_delegate::*;
+pub use claim_referral_rewards::*;
+pub use collect_fees::*;
+pub use collect_token_fees::*;
 pub use deposit::*;
 pub use deposit_token::*;
+pub use deposit_wsol::*;
 pub use initialize::*;
+pub use migrate_bank_info::*;
+pub use migrate_user_reserve::*;
+pub use open_account::*;
 // pub use pause::*;
-// pub use withdraw::*;
-// pub use withdraw_token::*;
+pub use read_reserve::*;
+pub use reset_metrics::*;
+pub use revoke_delegate::*;
+pub use set_fee::*;
+pub use set_price_feed::*;
+pub use set_referral_share::*;
+pub use update_limits::*;
+pub use withdraw::*;
+pub use withdraw_token::*;
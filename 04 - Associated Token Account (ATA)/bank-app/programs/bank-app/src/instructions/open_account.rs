@@ -0,0 +1,118 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::associated_token::{create_idempotent, AssociatedToken, Create};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        BANK_INFO_SEED, BANK_VAULT_SEED, METRICS_SEED, USER_RESERVE_SEED, USER_RESERVE_VERSION,
+    },
+    error::BankAppError,
+    state::{BankInfo, Metric, Metrics, UserReserve},
+    transfer_helper::sol_transfer_from_user,
+};
+
+// a composite context: its accounts and constraints are declared once and nested
+// in any instruction that takes the bank, clients pass them as `bank: { ... }`
+#[derive(Accounts)]
+pub struct BankAccounts<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+}
+
+// creates the reserve, the user's ATAs and makes the first deposit in one
+// transaction. The ATAs come as remaining accounts, (mint, ata, token program)
+// for each, and none is fine
+#[derive(Accounts)]
+pub struct OpenAccount<'info> {
+    pub bank: BankAccounts<'info>,
+
+    // init, not init_if_needed: an account is only opened once
+    #[account(
+        init,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenAccount<'info> {
+    pub fn process(ctx: Context<OpenAccount>, deposit_amount: u64) -> Result<()> {
+        log_compute_units("open_account: start");
+        let bank_info = ctx.accounts.bank.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+
+        let mut user_reserve = ctx.accounts.user_reserve.load_init()?;
+        user_reserve.version = USER_RESERVE_VERSION;
+
+        if ctx.remaining_accounts.len() % 3 != 0 {
+            return Err(BankAppError::InvalidAtaAccounts.into());
+        }
+        // idempotent, an ATA the user already has is left as it is. The ATA
+        // program checks the address and that the token program owns the mint
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: ctx.accounts.user.to_account_info(),
+                    associated_token: accounts[1].clone(),
+                    authority: ctx.accounts.user.to_account_info(),
+                    mint: accounts[0].clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: accounts[2].clone(),
+                },
+            ))?;
+        }
+
+        sol_transfer_from_user(
+            &ctx.accounts.user,
+            ctx.accounts.bank.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            deposit_amount,
+        )?;
+
+        user_reserve.deposited_amount = deposit_amount;
+
+        bank_info.check_deposit_limits(
+            user_reserve.deposited_amount,
+            ctx.accounts.bank.bank_vault.lamports(),
+        )?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::OpenAccount, deposit_amount);
+
+        msg!("account opened with {}", deposit_amount);
+        log_compute_units("open_account: end");
+        Ok(())
+    }
+}
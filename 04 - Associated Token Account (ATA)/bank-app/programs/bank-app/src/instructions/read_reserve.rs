@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{MAX_TOKEN_BALANCES, USER_RESERVE_SEED, USER_RESERVE_VERSION},
+    error::BankAppError,
+    state::UserReserve,
+};
+
+// read-only, the same reserve read the two ways so their compute units can be
+// compared. Neither is needed to use the bank
+
+// what UserReserve would be as a plain #[account]. The layout has no implicit
+// padding, so Borsh reads the same bytes the zero-copy struct maps
+#[derive(AnchorDeserialize)]
+struct BorshUserReserve {
+    deposited_amount: u64,
+    token_balances: [BorshTokenBalance; MAX_TOKEN_BALANCES],
+    version: u8,
+    _padding: [u8; 7],
+}
+
+#[derive(AnchorDeserialize)]
+struct BorshTokenBalance {
+    mint: Pubkey,
+    // copied like every other field, though nothing below reads it
+    _amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct ReadReserveBorsh<'info> {
+    ///CHECK: the discriminator is checked before it's deserialized
+    #[account(
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub user_reserve: UncheckedAccount<'info>,
+
+    ///CHECK: only used for the seeds
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReadReserveZeroCopy<'info> {
+    #[account(
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    ///CHECK: only used for the seeds
+    pub user: UncheckedAccount<'info>,
+}
+
+impl<'info> ReadReserveBorsh<'info> {
+    // what Account<UserReserve> would do, every field is copied out of the data
+    pub fn process(ctx: Context<ReadReserveBorsh>) -> Result<()> {
+        log_compute_units("read_reserve_borsh: start");
+        let data = ctx.accounts.user_reserve.try_borrow_data()?;
+        if !data.starts_with(UserReserve::DISCRIMINATOR) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        let reserve =
+            BorshUserReserve::deserialize(&mut &data[UserReserve::DISCRIMINATOR.len()..])?;
+        if reserve.version != USER_RESERVE_VERSION {
+            return Err(BankAppError::VersionMismatch.into());
+        }
+
+        let mints = reserve
+            .token_balances
+            .iter()
+            .filter(|balance| balance.mint != Pubkey::default())
+            .count();
+        msg!("{} lamports and {} mints", reserve.deposited_amount, mints);
+        log_compute_units("read_reserve_borsh: end");
+        Ok(())
+    }
+}
+
+impl<'info> ReadReserveZeroCopy<'info> {
+    // the data is borrowed in place, nothing is copied
+    pub fn process(ctx: Context<ReadReserveZeroCopy>) -> Result<()> {
+        log_compute_units("read_reserve_zero_copy: start");
+        UserReserve::check_len(&ctx.accounts.user_reserve.to_account_info())?;
+        let reserve = ctx.accounts.user_reserve.load()?;
+        reserve.check_version()?;
+
+        let mints = reserve
+            .token_balances
+            .iter()
+            .filter(|balance| balance.mint != Pubkey::default())
+            .count();
+        msg!("{} lamports and {} mints", reserve.deposited_amount, mints);
+        log_compute_units("read_reserve_zero_copy: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, METRICS_SEED},
+    state::{BankInfo, Metrics},
+};
+
+// also creates Metrics for a bank initialized before it existed
+#[derive(Accounts)]
+pub struct ResetMetrics<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(
+        init_if_needed,
+        seeds = [METRICS_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Metrics>(),
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResetMetrics<'info> {
+    pub fn process(ctx: Context<ResetMetrics>) -> Result<()> {
+        log_compute_units("reset_metrics: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        // like a new UserReserve, a new Metrics has no discriminator yet
+        let is_new = ctx.accounts.metrics.to_account_info().try_borrow_data()?[..8]
+            .iter()
+            .all(|byte| *byte == 0);
+        let mut metrics = if is_new {
+            ctx.accounts.metrics.load_init()?
+        } else {
+            ctx.accounts.metrics.load_mut()?
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        metrics.reset(now);
+
+        msg!("metrics reset at {}", now);
+        log_compute_units("reset_metrics: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, constant::DELEGATION_SEED, state::Delegation};
+
+// closing the delegation takes away whatever allowance is left and refunds its rent
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, user.key().as_ref()],
+        bump = delegation.bump,
+        close = user,
+    )]
+    pub delegation: Box<Account<'info, Delegation>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+impl<'info> RevokeDelegate<'info> {
+    pub fn process(ctx: Context<RevokeDelegate>) -> Result<()> {
+        log_compute_units("revoke_delegate: start");
+        msg!("{} revoked", ctx.accounts.delegation.delegate);
+        log_compute_units("revoke_delegate: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, MAX_FEE_BPS},
+    error::BankAppError,
+    state::BankInfo,
+};
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetFee<'info> {
+    pub fn process(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+        log_compute_units("set_fee: start");
+        if fee_bps > MAX_FEE_BPS {
+            return Err(BankAppError::InvalidFee.into());
+        }
+
+        let mut bank_info = ctx.accounts.bank_info.load_mut()?;
+        bank_info.check_version()?;
+        bank_info.fee_bps = fee_bps;
+
+        msg!("withdrawal fee set to {} bps", fee_bps);
+        log_compute_units("set_fee: end");
+        Ok(())
+    }
+}
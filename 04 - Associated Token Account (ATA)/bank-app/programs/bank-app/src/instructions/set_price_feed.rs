@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, SUPPORTED_MINT_SEED},
+    state::{BankInfo, SupportedMint},
+};
+
+// prices a supported mint with its own feed and staleness bound. A default
+// price_feed unprices it again
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    ///CHECK: a SupportedMint. A mint added before the feed fields is shorter than
+    /// the struct and can't be deserialized, so it's grown first and read below
+    #[account(
+        mut,
+        seeds = [SUPPORTED_MINT_SEED, token_mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+        realloc = 8 + std::mem::size_of::<SupportedMint>(),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub supported_mint: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPriceFeed<'info> {
+    pub fn process(
+        ctx: Context<SetPriceFeed>,
+        price_feed: Pubkey,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        log_compute_units("set_price_feed: start");
+        ctx.accounts.bank_info.load()?.check_version()?;
+
+        // the new bytes come in zeroed, an older mint reads as unpriced
+        let supported_mint_info = ctx.accounts.supported_mint.to_account_info();
+        let mut supported_mint =
+            SupportedMint::try_deserialize(&mut &supported_mint_info.try_borrow_data()?[..])?;
+        supported_mint.price_feed = price_feed;
+        supported_mint.max_staleness_slots = max_staleness_slots;
+        supported_mint.try_serialize(&mut &mut supported_mint_info.try_borrow_mut_data()?[..])?;
+
+        msg!(
+            "token {} priced by {}, {} slots",
+            supported_mint.mint,
+            price_feed,
+            max_staleness_slots
+        );
+        log_compute_units("set_price_feed: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BPS_DENOMINATOR},
+    error::BankAppError,
+    state::BankInfo,
+};
+
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetReferralShare<'info> {
+    // bps of the fee, not of the withdrawal. 10_000 gives referrers the whole fee
+    pub fn process(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+        log_compute_units("set_referral_share: start");
+        if referral_share_bps as u64 > BPS_DENOMINATOR {
+            return Err(BankAppError::InvalidReferralShare.into());
+        }
+
+        let mut bank_info = ctx.accounts.bank_info.load_mut()?;
+        bank_info.check_version()?;
+        bank_info.referral_share_bps = referral_share_bps;
+
+        msg!(
+            "referral share set to {} bps of the fee",
+            referral_share_bps
+        );
+        log_compute_units("set_referral_share: end");
+        Ok(())
+    }
+}
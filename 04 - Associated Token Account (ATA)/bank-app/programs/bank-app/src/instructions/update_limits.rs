@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, constant::BANK_INFO_SEED, state::BankInfo};
+
+#[derive(Accounts)]
+pub struct UpdateLimits<'info> {
+    #[account(
+        mut,
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    #[account(address = bank_info.load()?.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateLimits<'info> {
+    // lowering a cap below what is already deposited only blocks new deposits
+    pub fn process(
+        ctx: Context<UpdateLimits>,
+        max_deposit_per_user: u64,
+        max_total_deposits: u64,
+    ) -> Result<()> {
+        log_compute_units("update_limits: start");
+        let mut bank_info = ctx.accounts.bank_info.load_mut()?;
+        bank_info.check_version()?;
+        bank_info.max_deposit_per_user = max_deposit_per_user;
+        bank_info.max_total_deposits = max_total_deposits;
+
+        msg!(
+            "deposit limits set to {} per user, {} in total",
+            max_deposit_per_user,
+            max_total_deposits
+        );
+        log_compute_units("update_limits: end");
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{
+        BANK_INFO_SEED, BANK_VAULT_SEED, DELEGATION_SEED, FEE_TREASURY_SEED, METRICS_SEED,
+        USER_RESERVE_SEED,
+    },
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    state::{BankInfo, Delegation, Metric, Metrics, UserReserve},
+    transfer_helper::sol_transfer_from_pda_with_fee,
 };
 
 #[derive(Accounts)]
@@ -13,7 +18,7 @@ pub struct Withdraw<'info> {
         seeds = [BANK_INFO_SEED],
         bump
     )]
-    pub bank_info: Box<Account<'info, BankInfo>>,
+    pub bank_info: AccountLoader<'info, BankInfo>,
 
     ///CHECK:
     #[account(
@@ -24,27 +29,92 @@ pub struct Withdraw<'info> {
     )]
     pub bank_vault: UncheckedAccount<'info>,
 
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump = bank_info.load()?.fee_treasury_bump,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [USER_RESERVE_SEED, user.key().as_ref()],
         bump,
     )]
-    pub user_reserve: Box<Account<'info, UserReserve>>,
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
 
+    // only needed when a delegate signs
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, user.key().as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Option<Box<Account<'info, Delegation>>>,
+
+    ///CHECK: the reserve's owner, it signs itself or its delegate signs instead
+    pub user: UncheckedAccount<'info>,
+    // receives the lamports, the user or the delegate
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub signer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> Withdraw<'info> {
     pub fn process(ctx: Context<Withdraw>, withdraw_amount: u64) -> Result<()> {
-        if ctx.accounts.bank_info.is_paused {
+        log_compute_units("withdraw: start");
+        let bank_info = ctx.accounts.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
             return Err(BankAppError::BankAppPaused.into());
         }
 
-        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
-        // Your code here
+        // lamports are delegated with the default pubkey as the mint
+        Delegation::authorize_withdrawal(
+            ctx.accounts.user.key,
+            ctx.accounts.signer.key,
+            ctx.accounts
+                .delegation
+                .as_deref_mut()
+                .map(|delegation| &mut **delegation),
+            &Pubkey::default(),
+            withdraw_amount,
+        )?;
+
+        // the reserve is debited the full amount, the fee comes out of what the
+        // signer receives
+        let mut user_reserve = UserReserve::load_current(&ctx.accounts.user_reserve)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::InsufficientFunds)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+        let fee = sol_transfer_from_pda_with_fee(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.fee_treasury.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+            bank_info.fee_bps,
+        )?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::Withdraw, withdraw_amount);
 
+        msg!("withdrawn {}, fee {}", withdraw_amount, fee);
+        log_compute_units("withdraw: end");
         Ok(())
     }
 }
@@ -1 +1,207 @@
-//Your code here
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        BANK_INFO_SEED, BANK_VAULT_SEED, DELEGATION_SEED, FEE_TREASURY_SEED, METRICS_SEED,
+        REFERRAL_SEED, SUPPORTED_MINT_SEED, USER_RESERVE_SEED,
+    },
+    error::BankAppError,
+    state::{BankInfo, Delegation, Metric, Metrics, SupportedMint, UserReserve},
+    transfer_helper::token_transfer_from_pda_with_fee,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    #[account(
+        seeds = [BANK_INFO_SEED],
+        bump
+    )]
+    pub bank_info: AccountLoader<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds = [BANK_VAULT_SEED],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // fails with AccountNotInitialized until the authority adds the mint
+    #[account(
+        seeds = [SUPPORTED_MINT_SEED, token_mint.key().as_ref()],
+        bump = supported_mint.bump,
+    )]
+    pub supported_mint: Box<Account<'info, SupportedMint>>,
+
+    ///CHECK: the mint's switchboard feed, checked against supported_mint.price_feed
+    /// when it's read. Only for a priced mint
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    // the signer's ATA, the user's own or the delegate's. Created here if it was
+    // closed or never existed
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bank_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(
+        seeds = [FEE_TREASURY_SEED],
+        bump = bank_info.load()?.fee_treasury_bump,
+        owner = system_program::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub fee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    ///CHECK: the user's ReferralAccount, always passed so a referred user can't
+    /// leave it out. It only exists if they were referred, checked in process
+    #[account(
+        seeds = [REFERRAL_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub referral: UncheckedAccount<'info>,
+
+    // where the referral's share of the fee accrues, needed when the user was referred
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint,
+        associated_token::authority = referral,
+        associated_token::token_program = token_program,
+    )]
+    pub referral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_reserve: AccountLoader<'info, UserReserve>,
+
+    #[account(
+        mut,
+        seeds = [METRICS_SEED],
+        bump
+    )]
+    pub metrics: AccountLoader<'info, Metrics>,
+
+    // only needed when a delegate signs
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, user.key().as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Option<Box<Account<'info, Delegation>>>,
+
+    ///CHECK: the reserve's owner, it signs itself or its delegate signs instead
+    pub user: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawToken<'info> {
+    pub fn process(ctx: Context<WithdrawToken>, withdraw_amount: u64) -> Result<()> {
+        log_compute_units("withdraw_token: start");
+        let bank_info = ctx.accounts.bank_info.load()?;
+        bank_info.check_version()?;
+        if bank_info.is_paused() {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+
+        Delegation::authorize_withdrawal(
+            ctx.accounts.user.key,
+            ctx.accounts.signer.key,
+            ctx.accounts
+                .delegation
+                .as_deref_mut()
+                .map(|delegation| &mut **delegation),
+            &ctx.accounts.token_mint.key(),
+            withdraw_amount,
+        )?;
+
+        // the reserve is debited the full amount, the bank's fee and a transfer
+        // fee on the way out are taken from what the signer receives
+        let mut user_reserve = UserReserve::load_current(&ctx.accounts.user_reserve)?;
+        user_reserve.debit_token(&ctx.accounts.token_mint.key(), withdraw_amount)?;
+        user_reserve.debit_usd(ctx.accounts.supported_mint.usd_value(
+            withdraw_amount,
+            ctx.accounts.token_mint.decimals,
+            ctx.accounts.price_feed.as_deref(),
+            &Clock::get()?,
+        )?);
+
+        // the referral PDA is only owned by this program once it was created
+        let referral_ata = if ctx.accounts.referral.owner == &crate::ID {
+            let referral_ata = ctx
+                .accounts
+                .referral_ata
+                .as_ref()
+                .ok_or(BankAppError::InvalidReferral)?;
+            Some(referral_ata.to_account_info())
+        } else {
+            None
+        };
+
+        // the bank ATA is owned by the vault PDA, so the vault signs the transfer
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+
+        let (fee, referral_share) = token_transfer_from_pda_with_fee(
+            ctx.accounts.bank_ata.to_account_info(),
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.recipient_ata.to_account_info(),
+            ctx.accounts.fee_ata.to_account_info(),
+            referral_ata,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            pda_seeds,
+            withdraw_amount,
+            bank_info.fee_bps,
+            bank_info.referral_share_bps,
+        )?;
+
+        ctx.accounts
+            .metrics
+            .load_mut()?
+            .record(Metric::WithdrawToken, withdraw_amount);
+
+        msg!(
+            "withdrawn {}, fee {}, referral share {}",
+            withdraw_amount,
+            fee,
+            referral_share
+        );
+        log_compute_units("withdraw_token: end");
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
+pub mod compute;
 pub mod constant;
 pub mod error;
 pub mod instructions;
+pub mod oracle;
 pub mod state;
 pub mod transfer_helper;
 
@@ -18,11 +20,111 @@ pub mod bank_app {
         return Initialize::process(ctx);
     }
 
-    pub fn deposit(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
-        return Deposit::process(ctx, deposit_amount);
+    pub fn deposit(
+        ctx: Context<Deposit>,
+        deposit_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        return Deposit::process(ctx, deposit_amount, referrer);
     }
 
-    pub fn deposit_token(ctx: Context<DepositToken>, deposit_amount: u64) -> Result<()> {
-        return DepositToken::process(ctx, deposit_amount);
+    pub fn deposit_token(
+        ctx: Context<DepositToken>,
+        deposit_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        return DepositToken::process(ctx, deposit_amount, referrer);
+    }
+
+    pub fn deposit_wsol(ctx: Context<DepositWsol>) -> Result<()> {
+        return DepositWsol::process(ctx);
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, withdraw_amount: u64) -> Result<()> {
+        return Withdraw::process(ctx, withdraw_amount);
+    }
+
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, withdraw_amount: u64) -> Result<()> {
+        return WithdrawToken::process(ctx, withdraw_amount);
+    }
+
+    pub fn add_supported_mint(ctx: Context<AddSupportedMint>) -> Result<()> {
+        return AddSupportedMint::process(ctx);
+    }
+
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+        return SetFee::process(ctx, fee_bps);
+    }
+
+    pub fn update_limits(
+        ctx: Context<UpdateLimits>,
+        max_deposit_per_user: u64,
+        max_total_deposits: u64,
+    ) -> Result<()> {
+        return UpdateLimits::process(ctx, max_deposit_per_user, max_total_deposits);
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        return CollectFees::process(ctx);
+    }
+
+    pub fn collect_token_fees(ctx: Context<CollectTokenFees>) -> Result<()> {
+        return CollectTokenFees::process(ctx);
+    }
+
+    pub fn migrate_bank_info(ctx: Context<MigrateBankInfo>) -> Result<()> {
+        return MigrateBankInfo::process(ctx);
+    }
+
+    pub fn migrate_user_reserve(ctx: Context<MigrateUserReserve>) -> Result<()> {
+        return MigrateUserReserve::process(ctx);
+    }
+
+    pub fn read_reserve_borsh(ctx: Context<ReadReserveBorsh>) -> Result<()> {
+        return ReadReserveBorsh::process(ctx);
+    }
+
+    pub fn read_reserve_zero_copy(ctx: Context<ReadReserveZeroCopy>) -> Result<()> {
+        return ReadReserveZeroCopy::process(ctx);
+    }
+
+    pub fn approve_delegate(
+        ctx: Context<ApproveDelegate>,
+        delegate: Pubkey,
+        mint: Pubkey,
+        allowance: u64,
+    ) -> Result<()> {
+        return ApproveDelegate::process(ctx, delegate, mint, allowance);
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        return RevokeDelegate::process(ctx);
+    }
+
+    pub fn open_account(ctx: Context<OpenAccount>, deposit_amount: u64) -> Result<()> {
+        return OpenAccount::process(ctx, deposit_amount);
+    }
+
+    pub fn set_referral_share(
+        ctx: Context<SetReferralShare>,
+        referral_share_bps: u16,
+    ) -> Result<()> {
+        return SetReferralShare::process(ctx, referral_share_bps);
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        return ClaimReferralRewards::process(ctx);
+    }
+
+    pub fn reset_metrics(ctx: Context<ResetMetrics>) -> Result<()> {
+        return ResetMetrics::process(ctx);
+    }
+
+    pub fn set_price_feed(
+        ctx: Context<SetPriceFeed>,
+        price_feed: Pubkey,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        return SetPriceFeed::process(ctx, price_feed, max_staleness_slots);
     }
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::PullFeedAccountData;
+
+use crate::{
+    constant::{BPS_DENOMINATOR, MAX_ORACLE_CONFIDENCE_BPS, USD_DECIMALS},
+    error::BankAppError,
+};
+
+// switchboard feeds store the median as an i128 with 18 decimals, it's cut
+// down to 9 so amount * price fits in a u128
+const FEED_DECIMALS: u32 = 18;
+const PRICE_DECIMALS: u32 = 9;
+
+// the feed is owned by the switchboard program, it's read like any other account
+pub fn feed_price(feed: &AccountInfo, clock: &Clock, max_staleness_slots: u64) -> Result<u128> {
+    let data = feed.try_borrow_data()?;
+    let feed = PullFeedAccountData::parse(data).map_err(|_| BankAppError::InvalidOraclePrice)?;
+
+    check_price(
+        feed.result.value,
+        feed.result.std_dev,
+        clock.slot.saturating_sub(feed.result.slot),
+        max_staleness_slots,
+    )
+}
+
+// the checks on a feed's result, apart from the parsing so they can be tested
+// without a feed account. Returns the price with PRICE_DECIMALS
+pub fn check_price(
+    value: i128,
+    std_dev: i128,
+    age_slots: u64,
+    max_staleness_slots: u64,
+) -> Result<u128> {
+    if age_slots > max_staleness_slots {
+        return Err(BankAppError::StaleOraclePrice.into());
+    }
+    if value <= 0 || std_dev < 0 {
+        return Err(BankAppError::InvalidOraclePrice.into());
+    }
+
+    // a wide spread means the oracles don't agree on the price, any of them
+    // could be off
+    let spread = (std_dev as u128).checked_mul(BPS_DENOMINATOR as u128);
+    let max_spread = (value as u128).checked_mul(MAX_ORACLE_CONFIDENCE_BPS as u128);
+    let (Some(spread), Some(max_spread)) = (spread, max_spread) else {
+        return Err(BankAppError::InvalidOraclePrice.into());
+    };
+    if spread > max_spread {
+        return Err(BankAppError::OracleConfidenceTooWide.into());
+    }
+
+    Ok(value as u128 / 10u128.pow(FEED_DECIMALS - PRICE_DECIMALS))
+}
+
+// what `amount` of a mint with `decimals` is worth in USD with USD_DECIMALS,
+// rounded down
+pub fn usd_value(amount: u64, price: u128, decimals: u8) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(price)
+        .and_then(|value| value.checked_mul(10u128.pow(USD_DECIMALS as u32)))
+        .ok_or(BankAppError::InvalidOraclePrice)?;
+    let value = value / 10u128.pow(PRICE_DECIMALS + decimals as u32);
+
+    Ok(u64::try_from(value).map_err(|_| BankAppError::InvalidOraclePrice)?)
+}
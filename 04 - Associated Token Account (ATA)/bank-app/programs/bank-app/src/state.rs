@@ -1,15 +1,363 @@
+use std::cell::RefMut;
+
 use anchor_lang::prelude::*;
 
-#[account]
+use crate::{
+    constant::{
+        BANK_INFO_VERSION, MAX_ORACLE_STALENESS_SLOTS, MAX_TOKEN_BALANCES, METRIC_COUNT,
+        USER_RESERVE_VERSION,
+    },
+    error::BankAppError,
+    oracle::{feed_price, usd_value},
+};
+
+// zero-copy accounts are read in place with AccountLoader, nothing is deserialized.
+// The fields are Pod and ordered largest first, the explicit padding keeps the
+// layout the same as the IDL's. New fields are appended, or take padding bytes,
+// so an older account is a prefix of the new layout
+#[account(zero_copy)]
 #[derive(Default)]
 pub struct BankInfo {
     pub authority: Pubkey,
-    pub is_paused: bool,
+    // in base units of whatever is deposited, lamports or tokens, 0 is no cap.
+    // A user's cap is per asset, the total cap is what the vault holds of it
+    pub max_deposit_per_user: u64,
+    pub max_total_deposits: u64,
+    // charged on every withdrawal and kept in the fee treasury, 0 is no fee
+    pub fee_bps: u16,
+    // a bool isn't Pod, 0 is running, anything else paused
+    pub paused: u8,
     pub bump: u8,
+    pub fee_treasury_bump: u8,
+    pub version: u8,
+    // the part of every withdrawal fee paid to a referred user's referrer, in bps
+    // of the fee. Took the last padding bytes in v2
+    pub referral_share_bps: u16,
 }
 
-#[account]
+impl BankInfo {
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    pub fn check_version(&self) -> Result<()> {
+        if self.version != BANK_INFO_VERSION {
+            return Err(BankAppError::VersionMismatch.into());
+        }
+        Ok(())
+    }
+
+    // checked with the balances after the deposit, so a deposit that lands
+    // exactly on a cap is still accepted
+    pub fn check_deposit_limits(&self, user_deposited: u64, total_deposited: u64) -> Result<()> {
+        if self.max_deposit_per_user != 0 && user_deposited > self.max_deposit_per_user {
+            return Err(BankAppError::UserDepositCapExceeded.into());
+        }
+        if self.max_total_deposits != 0 && total_deposited > self.max_total_deposits {
+            return Err(BankAppError::GlobalDepositCapExceeded.into());
+        }
+        Ok(())
+    }
+}
+
+// one per user at [USER_RESERVE_SEED, user], SOL and every token in the same account
+#[account(zero_copy)]
 #[derive(Default)]
 pub struct UserReserve {
+    // lamports
     pub deposited_amount: u64,
+    pub token_balances: [TokenBalance; MAX_TOKEN_BALANCES],
+    pub version: u8,
+    pub _padding: [u8; 7],
+    // in USD_DECIMALS, appended in v2. What the priced tokens were worth when they
+    // came in, less what they were worth when they went out. Each move is valued
+    // at its own price, the reserve isn't marked to market
+    pub usd_value: u64,
+}
+
+// a slot is free while its mint is the default pubkey
+#[zero_copy]
+#[derive(Default)]
+pub struct TokenBalance {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl UserReserve {
+    // an older reserve can be shorter than the struct, and load would map past
+    // its end, so the length is checked first
+    pub fn check_len(reserve: &AccountInfo) -> Result<()> {
+        if reserve.data_len() != 8 + std::mem::size_of::<UserReserve>() {
+            return Err(BankAppError::VersionMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_version(&self) -> Result<()> {
+        if self.version != USER_RESERVE_VERSION {
+            return Err(BankAppError::VersionMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn load_current<'a>(
+        reserve: &'a AccountLoader<'_, UserReserve>,
+    ) -> Result<RefMut<'a, UserReserve>> {
+        Self::check_len(&reserve.to_account_info())?;
+        let loaded = reserve.load_mut()?;
+        loaded.check_version()?;
+        Ok(loaded)
+    }
+
+    // init_if_needed leaves a new reserve zeroed, its discriminator is only
+    // written when the instruction exits
+    pub fn is_new(reserve: &AccountLoader<'_, UserReserve>) -> Result<bool> {
+        Ok(reserve.to_account_info().try_borrow_data()?[..8]
+            .iter()
+            .all(|byte| *byte == 0))
+    }
+
+    // load_mut can't read a new reserve yet, it's initialized instead
+    pub fn load_or_init<'a>(
+        reserve: &'a AccountLoader<'_, UserReserve>,
+    ) -> Result<RefMut<'a, UserReserve>> {
+        if !Self::is_new(reserve)? {
+            return Self::load_current(reserve);
+        }
+
+        let mut loaded = reserve.load_init()?;
+        loaded.version = USER_RESERVE_VERSION;
+        Ok(loaded)
+    }
+
+    pub fn token_balance(&self, mint: &Pubkey) -> u64 {
+        self.token_balances
+            .iter()
+            .find(|balance| balance.mint == *mint)
+            .map_or(0, |balance| balance.amount)
+    }
+
+    // credits the mint's slot, or takes the first free one. Returns the new balance
+    pub fn credit_token(&mut self, mint: &Pubkey, amount: u64) -> Result<u64> {
+        let index = match self.token_balances.iter().position(|b| b.mint == *mint) {
+            Some(index) => index,
+            None => self
+                .token_balances
+                .iter()
+                .position(|b| b.mint == Pubkey::default())
+                .ok_or(BankAppError::TooManyTokenBalances)?,
+        };
+
+        let balance = &mut self.token_balances[index];
+        balance.mint = *mint;
+        balance.amount = balance
+            .amount
+            .checked_add(amount)
+            .ok_or(BankAppError::MathOverflow)?;
+        Ok(balance.amount)
+    }
+
+    // a slot emptied by the debit is freed for another mint
+    pub fn debit_token(&mut self, mint: &Pubkey, amount: u64) -> Result<()> {
+        let balance = self
+            .token_balances
+            .iter_mut()
+            .find(|balance| balance.mint == *mint)
+            .ok_or(BankAppError::InsufficientFunds)?;
+
+        balance.amount = balance
+            .amount
+            .checked_sub(amount)
+            .ok_or(BankAppError::InsufficientFunds)?;
+        if balance.amount == 0 {
+            *balance = TokenBalance::default();
+        }
+        Ok(())
+    }
+
+    pub fn credit_usd(&mut self, value: u64) -> Result<()> {
+        self.usd_value = self
+            .usd_value
+            .checked_add(value)
+            .ok_or(BankAppError::MathOverflow)?;
+        Ok(())
+    }
+
+    // saturates, tokens can go out worth more than they were when they came in
+    pub fn debit_usd(&mut self, value: u64) {
+        self.usd_value = self.usd_value.saturating_sub(value);
+    }
+}
+
+// one per mint the bank accepts, at [SUPPORTED_MINT_SEED, mint]. A new mint is
+// one more PDA, nothing has to grow or be scanned to find it
+#[account]
+#[derive(Default)]
+pub struct SupportedMint {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub bump: u8,
+    // the mint's switchboard feed, set by set_price_feed. Pubkey::default()
+    // leaves the mint unpriced
+    pub price_feed: Pubkey,
+    // 0 is MAX_ORACLE_STALENESS_SLOTS
+    pub max_staleness_slots: u64,
+}
+
+impl SupportedMint {
+    pub fn is_priced(&self) -> bool {
+        self.price_feed != Pubkey::default()
+    }
+
+    // an unpriced mint adds nothing to usd_value. A priced one can't be moved
+    // without its feed, or the reserve's value would drift from its balances
+    pub fn usd_value(
+        &self,
+        amount: u64,
+        decimals: u8,
+        feed: Option<&AccountInfo>,
+        clock: &Clock,
+    ) -> Result<u64> {
+        if !self.is_priced() {
+            return Ok(0);
+        }
+        let feed = feed.ok_or(BankAppError::InvalidPriceFeed)?;
+        if feed.key() != self.price_feed {
+            return Err(BankAppError::InvalidPriceFeed.into());
+        }
+
+        let max_staleness_slots = match self.max_staleness_slots {
+            0 => MAX_ORACLE_STALENESS_SLOTS,
+            slots => slots,
+        };
+        let price = feed_price(feed, clock, max_staleness_slots)?;
+        usd_value(amount, price, decimals)
+    }
+}
+
+// one per user at [DELEGATION_SEED, user], like an SPL token approve: the delegate
+// may withdraw up to allowance of one asset, approving again replaces it
+#[account]
+#[derive(Default)]
+pub struct Delegation {
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+    // Pubkey::default() is lamports
+    pub mint: Pubkey,
+    pub allowance: u64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    // the user withdraws freely, anyone else only as the user's delegate
+    pub fn authorize_withdrawal(
+        user: &Pubkey,
+        signer: &Pubkey,
+        delegation: Option<&mut Delegation>,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        if signer == user {
+            return Ok(());
+        }
+        delegation
+            .ok_or(BankAppError::NotDelegate)?
+            .spend(signer, mint, amount)
+    }
+
+    // the delegate's withdrawal comes out of the allowance, what's left of it stays
+    pub fn spend(&mut self, delegate: &Pubkey, mint: &Pubkey, amount: u64) -> Result<()> {
+        if self.delegate != *delegate {
+            return Err(BankAppError::NotDelegate.into());
+        }
+        if self.mint != *mint {
+            return Err(BankAppError::DelegatedAssetMismatch.into());
+        }
+        self.allowance = self
+            .allowance
+            .checked_sub(amount)
+            .ok_or(BankAppError::DelegateAllowanceExceeded)?;
+        Ok(())
+    }
+}
+
+// one per referred user at [REFERRAL_SEED, user], created with their first
+// deposit. It owns an ATA per mint where its share of the user's withdrawal fees
+// accrues until the referrer claims it
+#[account]
+#[derive(Default)]
+pub struct ReferralAccount {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+}
+
+impl ReferralAccount {
+    // the referrer and the account come together, and only with the user's first
+    // deposit. Nobody refers themselves
+    pub fn open(
+        referral: Option<&mut ReferralAccount>,
+        user: &Pubkey,
+        referrer: Option<Pubkey>,
+        is_first_deposit: bool,
+    ) -> Result<()> {
+        match (referral, referrer) {
+            (None, None) => Ok(()),
+            (Some(referral), Some(referrer)) => {
+                if referrer == *user {
+                    return Err(BankAppError::SelfReferral.into());
+                }
+                if !is_first_deposit {
+                    return Err(BankAppError::ReferrerAfterFirstDeposit.into());
+                }
+                referral.user = *user;
+                referral.referrer = referrer;
+                Ok(())
+            }
+            _ => Err(BankAppError::InvalidReferral.into()),
+        }
+    }
+}
+
+// at [METRICS_SEED], apart from BankInfo so that the instructions counting
+// themselves don't all have to write BankInfo
+#[account(zero_copy)]
+#[derive(Default)]
+pub struct Metrics {
+    // indexed by Metric
+    pub counters: [InstructionCounter; METRIC_COUNT],
+    // when the counting started, at initialize or the last reset_metrics
+    pub since: i64,
+}
+
+#[zero_copy]
+#[derive(Default)]
+pub struct InstructionCounter {
+    pub calls: u64,
+    // in base units of whatever moved, so a token instruction's total mixes mints
+    pub amount: u64,
+}
+
+// the counter's index in Metrics, new variants only ever go at the end
+#[derive(Clone, Copy)]
+pub enum Metric {
+    Deposit,
+    DepositToken,
+    DepositWsol,
+    Withdraw,
+    WithdrawToken,
+    OpenAccount,
+}
+
+impl Metrics {
+    // saturates instead of failing, a full counter must not block withdrawals
+    pub fn record(&mut self, metric: Metric, amount: u64) {
+        let counter = &mut self.counters[metric as usize];
+        counter.calls = counter.calls.saturating_add(1);
+        counter.amount = counter.amount.saturating_add(amount);
+    }
+
+    pub fn reset(&mut self, now: i64) {
+        self.counters = [InstructionCounter::default(); METRIC_COUNT];
+        self.since = now;
+    }
 }
@@ -5,7 +5,18 @@ use anchor_lang::{
         system_instruction::transfer,
     },
 };
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{self, Mint, TokenInterface};
+
+use crate::{constant::BPS_DENOMINATOR, error::BankAppError};
+
+// rounds down, a withdrawal too small to owe a whole unit is free
+pub fn withdrawal_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(BankAppError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(fee).map_err(|_| BankAppError::MathOverflow.into())
+}
 
 pub fn sol_transfer_from_user<'info>(
     signer: &Signer<'info>,
@@ -32,7 +43,12 @@ pub fn sol_transfer_from_pda<'info>(
     pda_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
-    //Your code here
+    let ix = transfer(source.key, destination.key, amount);
+    invoke_signed(
+        &ix,
+        &[source, destination, system_program.to_account_info()],
+        pda_seeds,
+    )?;
     Ok(())
 }
 
@@ -40,18 +56,22 @@ pub fn token_transfer_from_user<'info>(
     from: AccountInfo<'info>,
     authority: &Signer<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
 ) -> Result<()> {
+    // transfer_checked works with both token programs, Token-2022 rejects plain
+    // transfer for mints with a transfer fee
     let cpi_ctx: CpiContext<_> = CpiContext::new(
         token_program.to_account_info(),
-        token::Transfer {
+        token_interface::TransferChecked {
             from,
+            mint: mint.to_account_info(),
             authority: authority.to_account_info(),
             to,
         },
     );
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
 
     Ok(())
 }
@@ -60,10 +80,108 @@ pub fn token_transfer_from_pda<'info>(
     from: AccountInfo<'info>,
     authority: AccountInfo<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     pda_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
-    //Your code here
+    let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::TransferChecked {
+            from,
+            mint: mint.to_account_info(),
+            authority,
+            to,
+        },
+        pda_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
     Ok(())
 }
+
+// pays `amount` minus the fee to `destination` and the fee to the fee treasury,
+// returns the fee
+pub fn sol_transfer_from_pda_with_fee<'info>(
+    source: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    fee_treasury: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    pda_seeds: &[&[&[u8]]],
+    amount: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    let fee = withdrawal_fee(amount, fee_bps)?;
+    let net = amount.checked_sub(fee).ok_or(BankAppError::MathOverflow)?;
+
+    sol_transfer_from_pda(source.clone(), destination, system_program, pda_seeds, net)?;
+    if fee > 0 {
+        sol_transfer_from_pda(source, fee_treasury, system_program, pda_seeds, fee)?;
+    }
+    Ok(fee)
+}
+
+// the referrer's part of a fee, rounded down like the fee so the bank keeps
+// any remainder
+pub fn referral_share(fee: u64, referral_share_bps: u16) -> Result<u64> {
+    withdrawal_fee(fee, referral_share_bps)
+}
+
+// the token side of sol_transfer_from_pda_with_fee, the fee goes to the fee
+// treasury's ATA of the same mint. With a referral ATA, its share of the fee
+// goes there instead. Returns the fee and the referral's share of it
+#[allow(clippy::too_many_arguments)]
+pub fn token_transfer_from_pda_with_fee<'info>(
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    fee_ata: AccountInfo<'info>,
+    referral_ata: Option<AccountInfo<'info>>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    pda_seeds: &[&[&[u8]]],
+    amount: u64,
+    fee_bps: u16,
+    referral_share_bps: u16,
+) -> Result<(u64, u64)> {
+    let fee = withdrawal_fee(amount, fee_bps)?;
+    let net = amount.checked_sub(fee).ok_or(BankAppError::MathOverflow)?;
+    let share = match referral_ata {
+        Some(_) => referral_share(fee, referral_share_bps)?,
+        None => 0,
+    };
+    let treasury_fee = fee.checked_sub(share).ok_or(BankAppError::MathOverflow)?;
+
+    token_transfer_from_pda(
+        from.clone(),
+        authority.clone(),
+        to,
+        mint,
+        token_program,
+        pda_seeds,
+        net,
+    )?;
+    if treasury_fee > 0 {
+        token_transfer_from_pda(
+            from.clone(),
+            authority.clone(),
+            fee_ata,
+            mint,
+            token_program,
+            pda_seeds,
+            treasury_fee,
+        )?;
+    }
+    if let Some(referral_ata) = referral_ata.filter(|_| share > 0) {
+        token_transfer_from_pda(
+            from,
+            authority,
+            referral_ata,
+            mint,
+            token_program,
+            pda_seeds,
+            share,
+        )?;
+    }
+    Ok((fee, share))
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::Pubkey;
+use bank_app::{error::BankAppError, state::Delegation};
+
+fn delegation(mint: Pubkey, allowance: u64) -> Delegation {
+    Delegation {
+        user: Pubkey::new_unique(),
+        delegate: Pubkey::new_unique(),
+        mint,
+        allowance,
+        bump: 255,
+    }
+}
+
+#[test]
+fn the_user_needs_no_delegation() {
+    let user = Pubkey::new_unique();
+    Delegation::authorize_withdrawal(&user, &user, None, &Pubkey::new_unique(), u64::MAX).unwrap();
+}
+
+#[test]
+fn the_user_withdrawing_leaves_the_allowance_alone() {
+    let mint = Pubkey::new_unique();
+    let mut delegation = delegation(mint, 100);
+    let user = delegation.user;
+    Delegation::authorize_withdrawal(&user, &user, Some(&mut delegation), &mint, 60).unwrap();
+    assert_eq!(delegation.allowance, 100);
+}
+
+#[test]
+fn a_delegate_spends_the_allowance_down_to_zero() {
+    let mint = Pubkey::new_unique();
+    let mut delegation = delegation(mint, 100);
+    let (user, delegate) = (delegation.user, delegation.delegate);
+
+    Delegation::authorize_withdrawal(&user, &delegate, Some(&mut delegation), &mint, 60).unwrap();
+    assert_eq!(delegation.allowance, 40);
+    Delegation::authorize_withdrawal(&user, &delegate, Some(&mut delegation), &mint, 40).unwrap();
+    assert_eq!(delegation.allowance, 0);
+
+    assert_eq!(
+        Delegation::authorize_withdrawal(&user, &delegate, Some(&mut delegation), &mint, 1)
+            .unwrap_err(),
+        BankAppError::DelegateAllowanceExceeded.into()
+    );
+}
+
+#[test]
+fn more_than_the_allowance_is_rejected_and_nothing_is_spent() {
+    let mint = Pubkey::new_unique();
+    let mut delegation = delegation(mint, 100);
+    let (user, delegate) = (delegation.user, delegation.delegate);
+
+    assert_eq!(
+        Delegation::authorize_withdrawal(&user, &delegate, Some(&mut delegation), &mint, 101)
+            .unwrap_err(),
+        BankAppError::DelegateAllowanceExceeded.into()
+    );
+    assert_eq!(delegation.allowance, 100);
+}
+
+#[test]
+fn a_stranger_is_not_a_delegate() {
+    let mint = Pubkey::new_unique();
+    let mut delegation = delegation(mint, 100);
+    let user = delegation.user;
+    let stranger = Pubkey::new_unique();
+
+    assert_eq!(
+        Delegation::authorize_withdrawal(&user, &stranger, Some(&mut delegation), &mint, 1)
+            .unwrap_err(),
+        BankAppError::NotDelegate.into()
+    );
+    // without a delegation there's nobody else who may sign
+    assert_eq!(
+        Delegation::authorize_withdrawal(&user, &stranger, None, &mint, 1).unwrap_err(),
+        BankAppError::NotDelegate.into()
+    );
+}
+
+#[test]
+fn the_allowance_is_for_one_asset() {
+    let mut delegation = delegation(Pubkey::new_unique(), 100);
+    let (user, delegate) = (delegation.user, delegation.delegate);
+
+    assert_eq!(
+        Delegation::authorize_withdrawal(
+            &user,
+            &delegate,
+            Some(&mut delegation),
+            &Pubkey::new_unique(),
+            1
+        )
+        .unwrap_err(),
+        BankAppError::DelegatedAssetMismatch.into()
+    );
+    // lamports are their own asset too
+    assert_eq!(
+        Delegation::authorize_withdrawal(
+            &user,
+            &delegate,
+            Some(&mut delegation),
+            &Pubkey::default(),
+            1
+        )
+        .unwrap_err(),
+        BankAppError::DelegatedAssetMismatch.into()
+    );
+}
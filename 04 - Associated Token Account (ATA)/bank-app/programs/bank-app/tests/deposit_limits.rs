@@ -0,0 +1,53 @@
+use bank_app::{error::BankAppError, state::BankInfo};
+
+fn limits(max_deposit_per_user: u64, max_total_deposits: u64) -> BankInfo {
+    BankInfo {
+        max_deposit_per_user,
+        max_total_deposits,
+        ..BankInfo::default()
+    }
+}
+
+#[test]
+fn no_caps_accept_anything() {
+    assert!(limits(0, 0)
+        .check_deposit_limits(u64::MAX, u64::MAX)
+        .is_ok());
+}
+
+#[test]
+fn a_deposit_up_to_the_caps_is_accepted() {
+    assert!(limits(1_000, 5_000)
+        .check_deposit_limits(1_000, 5_000)
+        .is_ok());
+}
+
+#[test]
+fn the_user_cap_is_hit() {
+    assert_eq!(
+        limits(1_000, 0)
+            .check_deposit_limits(1_001, 1_001)
+            .unwrap_err(),
+        BankAppError::UserDepositCapExceeded.into()
+    );
+}
+
+#[test]
+fn the_global_cap_is_hit() {
+    assert_eq!(
+        limits(1_000, 5_000)
+            .check_deposit_limits(500, 5_001)
+            .unwrap_err(),
+        BankAppError::GlobalDepositCapExceeded.into()
+    );
+}
+
+#[test]
+fn the_user_cap_is_checked_first() {
+    assert_eq!(
+        limits(1_000, 5_000)
+            .check_deposit_limits(1_001, 5_001)
+            .unwrap_err(),
+        BankAppError::UserDepositCapExceeded.into()
+    );
+}
@@ -0,0 +1,39 @@
+use bank_app::state::{Metric, Metrics};
+
+#[test]
+fn each_instruction_counts_in_its_own_slot() {
+    let mut metrics = Metrics::default();
+    metrics.record(Metric::Deposit, 100);
+    metrics.record(Metric::Deposit, 50);
+    metrics.record(Metric::WithdrawToken, 7);
+
+    let deposit = metrics.counters[Metric::Deposit as usize];
+    assert_eq!((deposit.calls, deposit.amount), (2, 150));
+    let withdraw_token = metrics.counters[Metric::WithdrawToken as usize];
+    assert_eq!((withdraw_token.calls, withdraw_token.amount), (1, 7));
+    assert_eq!(metrics.counters[Metric::DepositToken as usize].calls, 0);
+}
+
+#[test]
+fn a_full_counter_saturates_instead_of_failing() {
+    let mut metrics = Metrics::default();
+    metrics.record(Metric::Deposit, u64::MAX);
+    metrics.record(Metric::Deposit, 1);
+
+    let deposit = metrics.counters[Metric::Deposit as usize];
+    assert_eq!((deposit.calls, deposit.amount), (2, u64::MAX));
+}
+
+#[test]
+fn a_reset_zeroes_every_counter() {
+    let mut metrics = Metrics::default();
+    metrics.record(Metric::Deposit, 100);
+    metrics.record(Metric::OpenAccount, 1);
+    metrics.reset(1_700_000_000);
+
+    assert!(metrics
+        .counters
+        .iter()
+        .all(|counter| counter.calls == 0 && counter.amount == 0));
+    assert_eq!(metrics.since, 1_700_000_000);
+}
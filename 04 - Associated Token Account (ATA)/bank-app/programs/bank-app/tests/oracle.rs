@@ -0,0 +1,105 @@
+use anchor_lang::prelude::{Clock, Pubkey};
+use bank_app::{
+    constant::MAX_ORACLE_STALENESS_SLOTS,
+    error::BankAppError,
+    oracle::{check_price, usd_value},
+    state::{SupportedMint, UserReserve},
+};
+
+// $150 with the feed's 18 decimals
+const FEED_PRICE: i128 = 150 * 10i128.pow(18);
+// $150 with 9, what check_price returns
+const PRICE: u128 = 150 * 10u128.pow(9);
+
+#[test]
+fn a_fresh_tight_price_is_scaled_down() {
+    assert_eq!(
+        check_price(
+            FEED_PRICE,
+            FEED_PRICE / 1_000,
+            0,
+            MAX_ORACLE_STALENESS_SLOTS
+        )
+        .unwrap(),
+        PRICE
+    );
+}
+
+#[test]
+fn a_price_past_the_staleness_bound_is_rejected() {
+    let stale = MAX_ORACLE_STALENESS_SLOTS + 1;
+    assert_eq!(
+        check_price(FEED_PRICE, 0, stale, MAX_ORACLE_STALENESS_SLOTS).unwrap_err(),
+        BankAppError::StaleOraclePrice.into()
+    );
+    check_price(FEED_PRICE, 0, stale - 1, MAX_ORACLE_STALENESS_SLOTS).unwrap();
+}
+
+#[test]
+fn a_spread_over_one_percent_is_rejected() {
+    check_price(FEED_PRICE, FEED_PRICE / 100, 0, MAX_ORACLE_STALENESS_SLOTS).unwrap();
+    assert_eq!(
+        check_price(
+            FEED_PRICE,
+            FEED_PRICE / 100 + 1,
+            0,
+            MAX_ORACLE_STALENESS_SLOTS
+        )
+        .unwrap_err(),
+        BankAppError::OracleConfidenceTooWide.into()
+    );
+}
+
+#[test]
+fn a_price_that_isnt_positive_is_rejected() {
+    for price in [0, -FEED_PRICE] {
+        assert_eq!(
+            check_price(price, 0, 0, MAX_ORACLE_STALENESS_SLOTS).unwrap_err(),
+            BankAppError::InvalidOraclePrice.into()
+        );
+    }
+}
+
+#[test]
+fn amounts_are_valued_with_the_mints_decimals() {
+    // 2.5 tokens with 9 decimals and with 6, both $375
+    assert_eq!(usd_value(2_500_000_000, PRICE, 9).unwrap(), 375_000_000);
+    assert_eq!(usd_value(2_500_000, PRICE, 6).unwrap(), 375_000_000);
+    // less than a micro-dollar rounds down to nothing
+    assert_eq!(usd_value(1, PRICE, 9).unwrap(), 0);
+}
+
+#[test]
+fn an_unpriced_mint_is_worth_nothing_and_needs_no_feed() {
+    let supported_mint = SupportedMint::default();
+    assert_eq!(
+        supported_mint
+            .usd_value(1_000_000, 6, None, &Clock::default())
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn a_priced_mint_needs_its_feed() {
+    let supported_mint = SupportedMint {
+        price_feed: Pubkey::new_unique(),
+        ..SupportedMint::default()
+    };
+    assert_eq!(
+        supported_mint
+            .usd_value(1_000_000, 6, None, &Clock::default())
+            .unwrap_err(),
+        BankAppError::InvalidPriceFeed.into()
+    );
+}
+
+#[test]
+fn a_withdrawal_worth_more_than_went_in_leaves_zero() {
+    let mut user_reserve = UserReserve::default();
+    user_reserve.credit_usd(100).unwrap();
+    user_reserve.debit_usd(60);
+    assert_eq!(user_reserve.usd_value, 40);
+    user_reserve.debit_usd(60);
+    assert_eq!(user_reserve.usd_value, 0);
+}
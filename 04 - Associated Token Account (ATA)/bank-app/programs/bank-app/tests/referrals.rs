@@ -0,0 +1,78 @@
+use anchor_lang::prelude::Pubkey;
+use bank_app::{
+    constant::BPS_DENOMINATOR,
+    error::BankAppError,
+    state::ReferralAccount,
+    transfer_helper::{referral_share, withdrawal_fee},
+};
+
+#[test]
+fn a_first_deposit_records_the_referrer() {
+    let mut referral = ReferralAccount::default();
+    let (user, referrer) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    ReferralAccount::open(Some(&mut referral), &user, Some(referrer), true).unwrap();
+    assert_eq!(referral.user, user);
+    assert_eq!(referral.referrer, referrer);
+}
+
+#[test]
+fn a_deposit_without_a_referrer_needs_no_account() {
+    let user = Pubkey::new_unique();
+    ReferralAccount::open(None, &user, None, true).unwrap();
+    ReferralAccount::open(None, &user, None, false).unwrap();
+}
+
+#[test]
+fn a_referrer_comes_too_late_after_the_first_deposit() {
+    let mut referral = ReferralAccount::default();
+    assert_eq!(
+        ReferralAccount::open(
+            Some(&mut referral),
+            &Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            false
+        )
+        .unwrap_err(),
+        BankAppError::ReferrerAfterFirstDeposit.into()
+    );
+}
+
+#[test]
+fn nobody_refers_themselves() {
+    let mut referral = ReferralAccount::default();
+    let user = Pubkey::new_unique();
+    assert_eq!(
+        ReferralAccount::open(Some(&mut referral), &user, Some(user), true).unwrap_err(),
+        BankAppError::SelfReferral.into()
+    );
+}
+
+#[test]
+fn the_referrer_and_the_account_come_together() {
+    let mut referral = ReferralAccount::default();
+    let user = Pubkey::new_unique();
+    assert_eq!(
+        ReferralAccount::open(None, &user, Some(Pubkey::new_unique()), true).unwrap_err(),
+        BankAppError::InvalidReferral.into()
+    );
+    assert_eq!(
+        ReferralAccount::open(Some(&mut referral), &user, None, true).unwrap_err(),
+        BankAppError::InvalidReferral.into()
+    );
+}
+
+#[test]
+fn the_share_is_taken_from_the_fee_and_rounds_down() {
+    // 0.5% of 10_000 is a fee of 50, 20% of it goes to the referrer
+    let fee = withdrawal_fee(10_000, 50).unwrap();
+    assert_eq!(referral_share(fee, 2_000).unwrap(), 10);
+    // 20% of 4 is 0.8, the bank keeps it all
+    assert_eq!(referral_share(4, 2_000).unwrap(), 0);
+}
+
+#[test]
+fn the_whole_fee_at_most() {
+    assert_eq!(referral_share(50, BPS_DENOMINATOR as u16).unwrap(), 50);
+    assert_eq!(referral_share(50, 0).unwrap(), 0);
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::Pubkey;
+use bank_app::{constant::MAX_TOKEN_BALANCES, error::BankAppError, state::UserReserve};
+
+#[test]
+fn a_mint_is_credited_in_one_slot() {
+    let mut reserve = UserReserve::default();
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(reserve.credit_token(&mint, 100).unwrap(), 100);
+    assert_eq!(reserve.credit_token(&mint, 50).unwrap(), 150);
+    assert_eq!(reserve.token_balance(&mint), 150);
+    assert_eq!(
+        reserve
+            .token_balances
+            .iter()
+            .filter(|balance| balance.mint == mint)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn a_mint_never_deposited_has_no_balance() {
+    let reserve = UserReserve::default();
+    assert_eq!(reserve.token_balance(&Pubkey::new_unique()), 0);
+}
+
+#[test]
+fn one_mint_too_many_is_rejected() {
+    let mut reserve = UserReserve::default();
+    for _ in 0..MAX_TOKEN_BALANCES {
+        reserve.credit_token(&Pubkey::new_unique(), 1).unwrap();
+    }
+
+    assert_eq!(
+        reserve.credit_token(&Pubkey::new_unique(), 1).unwrap_err(),
+        BankAppError::TooManyTokenBalances.into()
+    );
+}
+
+#[test]
+fn an_emptied_slot_is_reused() {
+    let mut reserve = UserReserve::default();
+    let mints: Vec<Pubkey> = (0..MAX_TOKEN_BALANCES)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    for mint in &mints {
+        reserve.credit_token(mint, 10).unwrap();
+    }
+
+    reserve.debit_token(&mints[3], 10).unwrap();
+    let other = Pubkey::new_unique();
+    assert_eq!(reserve.credit_token(&other, 5).unwrap(), 5);
+    assert_eq!(reserve.token_balances[3].mint, other);
+}
+
+#[test]
+fn a_debit_above_the_balance_is_rejected() {
+    let mut reserve = UserReserve::default();
+    let mint = Pubkey::new_unique();
+    reserve.credit_token(&mint, 10).unwrap();
+
+    assert_eq!(
+        reserve.debit_token(&mint, 11).unwrap_err(),
+        BankAppError::InsufficientFunds.into()
+    );
+    assert_eq!(
+        reserve.debit_token(&Pubkey::new_unique(), 1).unwrap_err(),
+        BankAppError::InsufficientFunds.into()
+    );
+    assert_eq!(reserve.token_balance(&mint), 10);
+}
+
+#[test]
+fn a_credit_past_u64_max_overflows() {
+    let mut reserve = UserReserve::default();
+    let mint = Pubkey::new_unique();
+    reserve.credit_token(&mint, u64::MAX).unwrap();
+
+    assert_eq!(
+        reserve.credit_token(&mint, 1).unwrap_err(),
+        BankAppError::MathOverflow.into()
+    );
+    assert_eq!(reserve.token_balance(&mint), u64::MAX);
+}
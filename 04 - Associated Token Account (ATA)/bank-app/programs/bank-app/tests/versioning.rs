@@ -0,0 +1,66 @@
+use bank_app::{
+    constant::{BANK_INFO_VERSION, USER_RESERVE_VERSION},
+    error::BankAppError,
+    state::{BankInfo, UserReserve},
+};
+
+#[test]
+fn an_unversioned_bank_info_is_rejected() {
+    assert_eq!(
+        BankInfo::default().check_version().unwrap_err(),
+        BankAppError::VersionMismatch.into()
+    );
+}
+
+#[test]
+fn a_current_bank_info_is_accepted() {
+    let bank_info = BankInfo {
+        version: BANK_INFO_VERSION,
+        ..BankInfo::default()
+    };
+    assert!(bank_info.check_version().is_ok());
+}
+
+#[test]
+fn a_v1_bank_info_is_rejected() {
+    let bank_info = BankInfo {
+        version: 1,
+        ..BankInfo::default()
+    };
+    assert_eq!(
+        bank_info.check_version().unwrap_err(),
+        BankAppError::VersionMismatch.into()
+    );
+}
+
+#[test]
+fn an_unversioned_user_reserve_is_rejected() {
+    assert_eq!(
+        UserReserve::default().check_version().unwrap_err(),
+        BankAppError::VersionMismatch.into()
+    );
+}
+
+#[test]
+fn a_newer_user_reserve_is_rejected() {
+    let user_reserve = UserReserve {
+        version: USER_RESERVE_VERSION + 1,
+        ..UserReserve::default()
+    };
+    assert_eq!(
+        user_reserve.check_version().unwrap_err(),
+        BankAppError::VersionMismatch.into()
+    );
+}
+
+#[test]
+fn a_v1_user_reserve_is_rejected() {
+    let user_reserve = UserReserve {
+        version: 1,
+        ..UserReserve::default()
+    };
+    assert_eq!(
+        user_reserve.check_version().unwrap_err(),
+        BankAppError::VersionMismatch.into()
+    );
+}
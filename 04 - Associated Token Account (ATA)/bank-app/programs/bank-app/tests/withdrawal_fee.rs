@@ -0,0 +1,26 @@
+use bank_app::{constant::MAX_FEE_BPS, error::BankAppError, transfer_helper::withdrawal_fee};
+
+// the flows that withdraw and collect fees run in crates/test-utils
+
+#[test]
+fn the_fee_rounds_down() {
+    // 0.5% of 199 is 0.995
+    assert_eq!(withdrawal_fee(199, 50).unwrap(), 0);
+    assert_eq!(withdrawal_fee(200, 50).unwrap(), 1);
+    assert_eq!(withdrawal_fee(399, 50).unwrap(), 1);
+    assert_eq!(withdrawal_fee(10_000, 50).unwrap(), 50);
+    assert_eq!(withdrawal_fee(1, MAX_FEE_BPS).unwrap(), 0);
+}
+
+#[test]
+fn an_oversized_fee_errors_instead_of_wrapping() {
+    // set_fee stops anything above MAX_FEE_BPS, the math doesn't rely on it
+    assert_eq!(
+        withdrawal_fee(u64::MAX, MAX_FEE_BPS).unwrap(),
+        u64::MAX / 10
+    );
+    assert_eq!(
+        withdrawal_fee(u64::MAX, u16::MAX).unwrap_err(),
+        BankAppError::MathOverflow.into()
+    );
+}
@@ -1,9 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { BankApp } from "../target/types/bank_app";
-import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { Keypair, PublicKey, SystemProgram, Transaction, TransactionInstruction } from "@solana/web3.js";
 import { BN } from "bn.js";
-import { createAssociatedTokenAccountInstruction, getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, createAssociatedTokenAccountIdempotentInstruction, createAssociatedTokenAccountInstruction, createInitializeMint2Instruction, createInitializeTransferFeeConfigInstruction, createMintToInstruction, ExtensionType, getAccount, getAssociatedTokenAddressSync, getMintLen, MINT_SIZE, NATIVE_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
 
 describe("bank-app", () => {
   // Configure the client to use the local cluster.
@@ -21,21 +22,56 @@ describe("bank-app", () => {
       [Buffer.from("BANK_VAULT_SEED")],
       program.programId
     )[0],
-    userReserve: (pubkey: PublicKey, tokenMint?: PublicKey) => {
-      let SEEDS = [
-        Buffer.from("USER_RESERVE_SEED"),
-        pubkey.toBuffer(),
-      ]
-
-      if (tokenMint != undefined) {
-        SEEDS.push(tokenMint.toBuffer())
-      }
+    feeTreasury: PublicKey.findProgramAddressSync(
+      [Buffer.from("FEE_TREASURY_SEED")],
+      program.programId
+    )[0],
+    metrics: PublicKey.findProgramAddressSync(
+      [Buffer.from("METRICS_SEED")],
+      program.programId
+    )[0],
+    // SOL and every token of the user, in one account
+    userReserve: (pubkey: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("USER_RESERVE_SEED"), pubkey.toBuffer()],
+      program.programId
+    )[0],
+    supportedMint: (tokenMint: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("SUPPORTED_MINT_SEED"), tokenMint.toBuffer()],
+      program.programId
+    )[0],
+    // only exists for a user who was referred
+    referral: (pubkey: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("REFERRAL_SEED"), pubkey.toBuffer()],
+      program.programId
+    )[0]
+  }
 
-      return PublicKey.findProgramAddressSync(
-        SEEDS,
-        program.programId
-      )[0]
+  // a token's slot in the user's reserve, 0 when the user holds none of it
+  const tokenBalance = async (tokenMint: PublicKey) => {
+    const reserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    return reserve.tokenBalances.find((balance) => balance.mint.equals(tokenMint))?.amount ?? new BN(0)
+  }
+
+  // registers the mint and creates its vault ATA, once per mint
+  const addSupportedMint = async (tokenMint: PublicKey, tokenProgram: PublicKey) => {
+    if (await provider.connection.getAccountInfo(BANK_APP_ACCOUNTS.supportedMint(tokenMint)) != null) {
+      return
     }
+    const tx = await program.methods.addSupportedMint()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true, tokenProgram),
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true, tokenProgram),
+        authority: provider.publicKey,
+        tokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Add supported mint signature: ", tx);
   }
 
   it("Is initialized!", async () => {
@@ -47,6 +83,8 @@ describe("bank-app", () => {
         .accounts({
           bankInfo: BANK_APP_ACCOUNTS.bankInfo,
           bankVault: BANK_APP_ACCOUNTS.bankVault,
+          feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+          metrics: BANK_APP_ACCOUNTS.metrics,
           authority: provider.publicKey,
           systemProgram: SystemProgram.programId
         }).rpc();
@@ -55,11 +93,13 @@ describe("bank-app", () => {
   });
 
   it("Is deposited!", async () => {
-    const tx = await program.methods.deposit(new BN(1_000_000))
+    const tx = await program.methods.deposit(new BN(1_000_000), null)
       .accounts({
         bankInfo: BANK_APP_ACCOUNTS.bankInfo,
         bankVault: BANK_APP_ACCOUNTS.bankVault,
         userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
         user: provider.publicKey,
         systemProgram: SystemProgram.programId
       }).rpc();
@@ -69,6 +109,15 @@ describe("bank-app", () => {
     console.log("User reserve: ", userReserve.depositedAmount.toString())
   });
 
+  it("Is supported mint added!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    await addSupportedMint(tokenMint, TOKEN_PROGRAM_ID)
+
+    const supportedMint = await program.account.supportedMint.fetch(BANK_APP_ACCOUNTS.supportedMint(tokenMint))
+    assert.equal(supportedMint.mint.toBase58(), tokenMint.toBase58())
+    assert.equal(supportedMint.tokenProgram.toBase58(), TOKEN_PROGRAM_ID.toBase58())
+  });
+
   it("Is deposited token!", async () => {
     let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
     let userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
@@ -84,21 +133,829 @@ describe("bank-app", () => {
       ))
     }
 
-    const tx = await program.methods.depositToken(new BN(1_000_000_000))
+    const tx = await program.methods.depositToken(new BN(1_000_000_000), null)
       .accounts({
         bankInfo: BANK_APP_ACCOUNTS.bankInfo,
         bankVault: BANK_APP_ACCOUNTS.bankVault,
         tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
         userAta,
         bankAta,
-        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
         user: provider.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId
       }).preInstructions(preInstructions).rpc();
     console.log("Deposit token signature: ", tx);
 
-    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint))
-    console.log("User reserve: ", userReserve.depositedAmount.toString())
+    console.log("Token balance: ", (await tokenBalance(tokenMint)).toString())
+  });
+
+  it("Is withdrawn token!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    let userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    let bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+    let userReserveAddress = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+
+    // the user ATA doesn't need to exist, withdraw_token creates it
+    const withdrawToken = (amount: BN) => program.methods.withdrawToken(amount)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        recipientAta: userAta,
+        bankAta,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true),
+        referral: BANK_APP_ACCOUNTS.referral(provider.publicKey),
+        referralAta: null,
+        userReserve: userReserveAddress,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        delegation: null,
+        user: provider.publicKey,
+        signer: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const deposited = await tokenBalance(tokenMint)
+    const userBalance = async () => new BN((await getAccount(provider.connection, userAta)).amount.toString())
+
+    // partial withdrawal
+    const balanceBefore = await userBalance()
+    const partial = deposited.div(new BN(2))
+    const tx = await withdrawToken(partial);
+    console.log("Withdraw token signature: ", tx);
+
+    let remaining = await tokenBalance(tokenMint)
+    console.log("Token balance: ", remaining.toString())
+    assert.equal(remaining.toString(), deposited.sub(partial).toString())
+    assert.equal((await userBalance()).sub(balanceBefore).toString(), partial.toString())
+
+    // more than what's left is rejected
+    try {
+      await withdrawToken(remaining.add(new BN(1)))
+      assert.fail("expected InsufficientFunds")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InsufficientFunds")
+    }
+
+    // full withdrawal of the rest
+    const fullTx = await withdrawToken(remaining);
+    console.log("Withdraw all token signature: ", fullTx);
+
+    assert.equal((await tokenBalance(tokenMint)).toNumber(), 0)
+    assert.equal((await userBalance()).sub(balanceBefore).toString(), deposited.toString())
+  });
+
+  it("Is deposited Token-2022 token with transfer fee!", async () => {
+    // a new Token-2022 mint charging 1% on every transfer, capped at 1 token
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const space = getMintLen([ExtensionType.TransferFeeConfig])
+    const userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey, false, TOKEN_2022_PROGRAM_ID)
+    const bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true, TOKEN_2022_PROGRAM_ID)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(tokenMint, provider.publicKey, provider.publicKey, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null, TOKEN_2022_PROGRAM_ID),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, tokenMint, TOKEN_2022_PROGRAM_ID),
+      createMintToInstruction(tokenMint, userAta, provider.publicKey, 1_000_000, [], TOKEN_2022_PROGRAM_ID),
+    ), [mint])
+    await addSupportedMint(tokenMint, TOKEN_2022_PROGRAM_ID)
+    const userReserveAddress = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+
+    const tx = await program.methods.depositToken(new BN(500_000), null)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        userAta,
+        bankAta,
+        userReserve: userReserveAddress,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Deposit token 2022 signature: ", tx);
+
+    // 1% of 500_000 stays withheld in the bank ATA, only the rest is credited
+    assert.equal((await tokenBalance(tokenMint)).toNumber(), 495_000)
+
+    // the fee is charged again on the way out, the user gets 1% less than withdrawn
+    const withdrawTx = await program.methods.withdrawToken(new BN(495_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        recipientAta: userAta,
+        bankAta,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true, TOKEN_2022_PROGRAM_ID),
+        referral: BANK_APP_ACCOUNTS.referral(provider.publicKey),
+        referralAta: null,
+        userReserve: userReserveAddress,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        delegation: null,
+        user: provider.publicKey,
+        signer: provider.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Withdraw token 2022 signature: ", withdrawTx);
+
+    assert.equal((await tokenBalance(tokenMint)).toNumber(), 0)
+    const user = await getAccount(provider.connection, userAta, undefined, TOKEN_2022_PROGRAM_ID)
+    assert.equal(user.amount.toString(), (500_000 + 495_000 - 4_950).toString())
+  });
+
+  it("Is withdrawal fee collected!", async () => {
+    // a fresh SPL mint, so the balances below only move by this test
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    const bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+    const feeAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, tokenMint),
+      createMintToInstruction(tokenMint, userAta, provider.publicKey, 1_000_000),
+    ), [mint])
+    await addSupportedMint(tokenMint, TOKEN_PROGRAM_ID)
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+
+    await program.methods.depositToken(new BN(1_000_000), null)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        userAta,
+        bankAta,
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+
+    const setFee = (feeBps: number) => program.methods.setFee(feeBps)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+    const withdrawToken = (amount: number) => program.methods.withdrawToken(new BN(amount))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        recipientAta: userAta,
+        bankAta,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta,
+        referral: BANK_APP_ACCOUNTS.referral(provider.publicKey),
+        referralAta: null,
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        delegation: null,
+        user: provider.publicKey,
+        signer: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const balance = async (ata: PublicKey) => Number((await getAccount(provider.connection, ata)).amount)
+
+    // more than MAX_FEE_BPS is rejected
+    try {
+      await setFee(1_001)
+      assert.fail("expected InvalidFee")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidFee")
+    }
+
+    try {
+      await setFee(50)
+
+      // 0.5% of 199 rounds down to nothing
+      await withdrawToken(199)
+      assert.equal(await balance(userAta), 199)
+      assert.equal(await balance(feeAta), 0)
+
+      // 0.5% of 10_000 is 50, the reserve is debited the full amount
+      await withdrawToken(10_000)
+      assert.equal(await balance(userAta), 199 + 9_950)
+      assert.equal(await balance(feeAta), 50)
+      assert.equal((await tokenBalance(tokenMint)).toNumber(), 1_000_000 - 199 - 10_000)
+    } finally {
+      // the other tests expect fee-free withdrawals
+      await setFee(0)
+    }
+
+    // the authority is the user here, so the fees come back to the same ATA
+    const collectTx = await program.methods.collectTokenFees()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        tokenMint,
+        feeAta,
+        authorityAta: userAta,
+        authority: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Collect token fees signature: ", collectTx);
+
+    assert.equal(await balance(feeAta), 0)
+    assert.equal(await balance(userAta), 199 + 9_950 + 50)
+
+    // empties the mint's slot, the reserve holds MAX_TOKEN_BALANCES mints at most
+    await withdrawToken(1_000_000 - 199 - 10_000)
+  });
+
+  it("Are deposit caps enforced!", async () => {
+    const updateLimits = (maxDepositPerUser: number, maxTotalDeposits: number) => program.methods
+      .updateLimits(new BN(maxDepositPerUser), new BN(maxTotalDeposits))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+    const deposit = (amount: number) => program.methods.deposit(new BN(amount), null)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const reserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    const deposited = reserve.depositedAmount.toNumber()
+    const vaultBalance = await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault)
+
+    try {
+      // room for 100 more lamports in this reserve
+      await updateLimits(deposited + 100, 0)
+      try {
+        await deposit(101)
+        assert.fail("expected UserDepositCapExceeded")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "UserDepositCapExceeded")
+      }
+      await deposit(100)
+
+      // room for 50 more lamports in the vault
+      await updateLimits(0, vaultBalance + 100 + 50)
+      try {
+        await deposit(51)
+        assert.fail("expected GlobalDepositCapExceeded")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "GlobalDepositCapExceeded")
+      }
+      await deposit(50)
+    } finally {
+      // the other tests deposit without caps
+      await updateLimits(0, 0)
+    }
+  });
+
+  it("Is deposited wSOL!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const userWsol = getAssociatedTokenAddressSync(NATIVE_MINT, provider.publicKey)
+    const reserveBefore = (await program.account.userReserve.fetch(userReserve)).depositedAmount.toNumber()
+
+    // plain SOL sent to the ATA, deposit_wsol syncs it before reading the amount
+    await provider.sendAndConfirm(new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userWsol, provider.publicKey, NATIVE_MINT),
+      SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: userWsol, lamports: 2_000_000 }),
+    ))
+
+    const tx = await program.methods.depositWsol()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userWsol,
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Deposit wSOL signature: ", tx);
+
+    // the same bucket as native deposits, and the wSOL account is gone
+    const reserve = await program.account.userReserve.fetch(userReserve)
+    assert.equal(reserve.depositedAmount.toNumber(), reserveBefore + 2_000_000)
+    assert.isNull(await provider.connection.getAccountInfo(userWsol))
+  });
+
+  it("Is reading a reserve cheaper with zero-copy!", async () => {
+    // the same reserve, copied out with Borsh or borrowed in place
+    const simulate = async (method: any) => {
+      const tx = await method.accounts({
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+      }).transaction()
+      tx.feePayer = provider.publicKey
+      tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash
+      return (await provider.connection.simulateTransaction(tx)).value
+    }
+
+    const borsh = await simulate(program.methods.readReserveBorsh())
+    const zeroCopy = await simulate(program.methods.readReserveZeroCopy())
+    assert.isNull(borsh.err)
+    assert.isNull(zeroCopy.err)
+    console.log("CU to read a reserve, Borsh vs zero-copy: ", borsh.unitsConsumed, zeroCopy.unitsConsumed)
+    assert.isBelow(zeroCopy.unitsConsumed, borsh.unitsConsumed)
+  });
+
+  it("Are accounts versioned!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    assert.equal((await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)).version, 2)
+    assert.equal((await program.account.userReserve.fetch(userReserve)).version, 2)
+
+    // both are current, so there's nothing to migrate
+    try {
+      await program.methods.migrateBankInfo()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          authority: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+      assert.fail("expected AlreadyMigrated")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "AlreadyMigrated")
+    }
+    try {
+      await program.methods.migrateUserReserve()
+        .accounts({
+          userReserve,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+      assert.fail("expected AlreadyMigrated")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "AlreadyMigrated")
+    }
+  });
+  it("Are withdrawals delegated!", async () => {
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const delegate = Keypair.generate()
+    const userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    const delegateAta = getAssociatedTokenAddressSync(tokenMint, delegate.publicKey)
+    const bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+    const delegation = PublicKey.findProgramAddressSync(
+      [Buffer.from("DELEGATION_SEED"), provider.publicKey.toBuffer()],
+      program.programId
+    )[0]
+    // the delegate pays for its own ATA
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, tokenMint),
+      createMintToInstruction(tokenMint, userAta, provider.publicKey, 1_000_000),
+      SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: delegate.publicKey, lamports: 10_000_000 }),
+    ), [mint])
+    await addSupportedMint(tokenMint, TOKEN_PROGRAM_ID)
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+
+    await program.methods.depositToken(new BN(1_000_000), null)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        userAta,
+        bankAta,
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+
+    const withdrawToken = (amount: number, signer: Keypair | null) => {
+      const accounts = {
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        recipientAta: signer ? delegateAta : userAta,
+        bankAta,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true),
+        referral: BANK_APP_ACCOUNTS.referral(provider.publicKey),
+        referralAta: null,
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        delegation: signer ? delegation : null,
+        user: provider.publicKey,
+        signer: signer ? signer.publicKey : provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }
+      const method = program.methods.withdrawToken(new BN(amount)).accounts(accounts)
+      return signer ? method.signers([signer]).rpc() : method.rpc()
+    }
+
+    // nobody but the user can withdraw before a delegate is approved
+    try {
+      await withdrawToken(1, delegate)
+      assert.fail("expected AccountNotInitialized")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "AccountNotInitialized")
+    }
+
+    const tx = await program.methods.approveDelegate(delegate.publicKey, tokenMint, new BN(300_000))
+      .accounts({
+        delegation,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    console.log("Approve delegate signature: ", tx)
+
+    // the delegate withdraws to its own ATA, out of the allowance
+    await withdrawToken(200_000, delegate)
+    assert.equal(Number((await getAccount(provider.connection, delegateAta)).amount), 200_000)
+    assert.equal((await tokenBalance(tokenMint)).toNumber(), 800_000)
+    assert.equal((await program.account.delegation.fetch(delegation)).allowance.toNumber(), 100_000)
+
+    try {
+      await withdrawToken(100_001, delegate)
+      assert.fail("expected DelegateAllowanceExceeded")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "DelegateAllowanceExceeded")
+    }
+
+    // the user's own withdrawals don't touch the allowance
+    await withdrawToken(100_000, null)
+    assert.equal((await program.account.delegation.fetch(delegation)).allowance.toNumber(), 100_000)
+
+    await program.methods.revokeDelegate()
+      .accounts({
+        delegation,
+        user: provider.publicKey,
+      }).rpc()
+    assert.isNull(await provider.connection.getAccountInfo(delegation))
+
+    try {
+      await withdrawToken(1, delegate)
+      assert.fail("expected AccountNotInitialized")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "AccountNotInitialized")
+    }
+
+    // empties the mint's slot, the reserve holds MAX_TOKEN_BALANCES mints at most
+    await withdrawToken(700_000, null)
+  });
+  it("Is an account opened in one transaction!", async () => {
+    // a new user with no reserve and no ATAs yet
+    const user = Keypair.generate()
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const userAta = getAssociatedTokenAddressSync(tokenMint, user.publicKey)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: user.publicKey, lamports: 1_000_000_000 }),
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null),
+    ), [mint])
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(user.publicKey)
+
+    const openAccount = () => program.methods.openAccount(new BN(100_000_000))
+      .accounts({
+        bank: {
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+        },
+        userReserve,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        user: user.publicKey,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      })
+      .remainingAccounts([
+        { pubkey: tokenMint, isSigner: false, isWritable: false },
+        { pubkey: userAta, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ])
+      .signers([user]).rpc()
+
+    const tx = await openAccount()
+    console.log("Open account signature: ", tx)
+
+    const reserve = await program.account.userReserve.fetch(userReserve)
+    assert.equal(reserve.depositedAmount.toNumber(), 100_000_000)
+    assert.equal(reserve.version, 2)
+    const ata = await getAccount(provider.connection, userAta)
+    assert.isTrue(ata.owner.equals(user.publicKey))
+
+    // the reserve is created with init, an account is only opened once
+    try {
+      await openAccount()
+      assert.fail("expected the reserve to exist already")
+    } catch (e) {
+      assert.include(e.toString(), "already in use")
+    }
+  });
+  it("Are referral rewards accrued and claimed!", async () => {
+    const referrer = Keypair.generate()
+    const user = Keypair.generate()
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const userAta = getAssociatedTokenAddressSync(tokenMint, user.publicKey)
+    const bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+    const feeAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.feeTreasury, true)
+    const referral = BANK_APP_ACCOUNTS.referral(user.publicKey)
+    const referralAta = getAssociatedTokenAddressSync(tokenMint, referral, true)
+    const referrerAta = getAssociatedTokenAddressSync(tokenMint, referrer.publicKey)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: user.publicKey, lamports: 100_000_000 }),
+      SystemProgram.transfer({ fromPubkey: provider.publicKey, toPubkey: referrer.publicKey, lamports: 100_000_000 }),
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, user.publicKey, tokenMint),
+      createMintToInstruction(tokenMint, userAta, provider.publicKey, 1_000_000),
+    ), [mint])
+    await addSupportedMint(tokenMint, TOKEN_PROGRAM_ID)
+    const balance = async (ata: PublicKey) => Number((await getAccount(provider.connection, ata)).amount)
+    const setFees = (feeBps: number, referralShareBps: number) => Promise.all([
+      program.methods.setFee(feeBps)
+        .accounts({ bankInfo: BANK_APP_ACCOUNTS.bankInfo, authority: provider.publicKey }).rpc(),
+      program.methods.setReferralShare(referralShareBps)
+        .accounts({ bankInfo: BANK_APP_ACCOUNTS.bankInfo, authority: provider.publicKey }).rpc(),
+    ])
+
+    // the referrer is named with the first deposit, and creates the referral
+    await program.methods.depositToken(new BN(1_000_000), referrer.publicKey)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        userAta,
+        bankAta,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(user.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).signers([user]).rpc();
+    const account = await program.account.referralAccount.fetch(referral)
+    assert.isTrue(account.user.equals(user.publicKey))
+    assert.isTrue(account.referrer.equals(referrer.publicKey))
+
+    // a user who already deposited can't pick a referrer any more
+    try {
+      await program.methods.deposit(new BN(1_000), referrer.publicKey)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          metrics: BANK_APP_ACCOUNTS.metrics,
+          referral: BANK_APP_ACCOUNTS.referral(provider.publicKey),
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+      assert.fail("expected ReferrerAfterFirstDeposit")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "ReferrerAfterFirstDeposit")
+    }
+
+    const withdrawToken = (amount: number, withReferralAta: boolean) => program.methods.withdrawToken(new BN(amount))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed: null,
+        recipientAta: userAta,
+        bankAta,
+        feeTreasury: BANK_APP_ACCOUNTS.feeTreasury,
+        feeAta,
+        referral,
+        referralAta: withReferralAta ? referralAta : null,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(user.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        delegation: null,
+        user: user.publicKey,
+        signer: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).signers([user]).rpc()
+
+    try {
+      // a 1% fee, half of it to the referrer
+      await setFees(100, 5_000)
+
+      // a referred user can't leave the referral's ATA out
+      try {
+        await withdrawToken(1_000_000, false)
+        assert.fail("expected InvalidReferral")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "InvalidReferral")
+      }
+
+      await withdrawToken(1_000_000, true)
+      assert.equal(await balance(userAta), 990_000)
+      assert.equal(await balance(feeAta), 5_000)
+      assert.equal(await balance(referralAta), 5_000)
+    } finally {
+      // the other tests withdraw without fees
+      await setFees(0, 0)
+    }
+
+    const tx = await program.methods.claimReferralRewards()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        referral,
+        tokenMint,
+        referralAta,
+        referrerAta,
+        referrer: referrer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).signers([referrer]).rpc()
+    console.log("Claim referral rewards signature: ", tx)
+
+    assert.equal(await balance(referrerAta), 5_000)
+    assert.equal(await balance(referralAta), 0)
+  });
+
+  it("Are instructions counted!", async () => {
+    // also creates the metrics of a bank initialized before they existed
+    const reset = () => program.methods.resetMetrics()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    await reset()
+    // the counters are in the same order as the Metric enum
+    const deposits = async () => (await program.account.metrics.fetch(BANK_APP_ACCOUNTS.metrics)).counters[0]
+
+    for (const amount of [1_000, 2_000]) {
+      await program.methods.deposit(new BN(amount), null)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          metrics: BANK_APP_ACCOUNTS.metrics,
+          referral: null,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+    }
+    assert.equal((await deposits()).calls.toNumber(), 2)
+    assert.equal((await deposits()).amount.toNumber(), 3_000)
+
+    // only the authority resets them
+    const stranger = Keypair.generate()
+    try {
+      await program.methods.resetMetrics()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          metrics: BANK_APP_ACCOUNTS.metrics,
+          authority: stranger.publicKey,
+          systemProgram: SystemProgram.programId
+        }).signers([stranger]).rpc()
+      assert.fail("expected ConstraintAddress")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "ConstraintAddress")
+    }
+
+    const tx = await reset()
+    console.log("Reset metrics signature: ", tx)
+    const metrics = await program.account.metrics.fetch(BANK_APP_ACCOUNTS.metrics)
+    assert.isTrue(metrics.counters.every((counter) => counter.calls.isZero() && counter.amount.isZero()))
+    assert.isAbove(metrics.since.toNumber(), 0)
+  });
+
+  it("Are reserves valued in USD!", async () => {
+    const mint = Keypair.generate()
+    const tokenMint = mint.publicKey
+    const userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint, 6, provider.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, tokenMint),
+      createMintToInstruction(tokenMint, userAta, provider.publicKey, 1_000_000),
+    ), [mint])
+    await addSupportedMint(tokenMint, TOKEN_PROGRAM_ID)
+
+    const setPriceFeed = (priceFeed: PublicKey) => program.methods.setPriceFeed(priceFeed, new BN(0))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const depositToken = (priceFeed: PublicKey | null) => program.methods.depositToken(new BN(1_000_000), null)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        supportedMint: BANK_APP_ACCOUNTS.supportedMint(tokenMint),
+        priceFeed,
+        userAta,
+        bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true),
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        metrics: BANK_APP_ACCOUNTS.metrics,
+        referral: null,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    // a priced mint can't be moved without its own feed. There's no live feed on
+    // the local validator, so only the checks before the read are exercised
+    const priceFeed = Keypair.generate().publicKey
+    await setPriceFeed(priceFeed)
+    const supportedMint = await program.account.supportedMint.fetch(BANK_APP_ACCOUNTS.supportedMint(tokenMint))
+    assert.isTrue(supportedMint.priceFeed.equals(priceFeed))
+    for (const feed of [null, Keypair.generate().publicKey]) {
+      try {
+        await depositToken(feed)
+        assert.fail("expected InvalidPriceFeed")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "InvalidPriceFeed")
+      }
+    }
+
+    // unpriced again, the deposit goes through and adds nothing to usd_value
+    await setPriceFeed(PublicKey.default)
+    const usdValueBefore = (await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))).usdValue
+    const tx = await depositToken(null)
+    console.log("Deposit unpriced token signature: ", tx)
+    const reserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    assert.isTrue(reserve.usdValue.eq(usdValueBefore))
+    assert.equal((await tokenBalance(tokenMint)).toNumber(), 1_000_000)
   });
 });
@@ -0,0 +1,83 @@
+import { LangErrorMessage } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+export type CpiErrorKind =
+  | "SystemCpiFailed"
+  | "TokenCpiFailed"
+  | "StakePoolCpiFailed"
+  | "SwapCpiFailed"
+  | "WormholeCpiFailed"
+  | "BubblegumCpiFailed"
+  | "UnknownCpiFailed"
+
+export type CpiError = {
+  kind: CpiErrorKind,
+  program: PublicKey,
+  code: number | null,
+  name: string | null,
+  // the raw failure, e.g. "custom program error: 0x1"
+  reason: string,
+}
+
+const SYSTEM_ERRORS = [
+  "AccountAlreadyInUse", "ResultWithNegativeLamports", "InvalidProgramId", "InvalidAccountDataLength",
+  "MaxSeedLengthExceeded", "AddressWithSeedMismatch", "NonceNoRecentBlockhashes", "NonceBlockhashNotExpired",
+  "NonceUnexpectedBlockhashValue",
+]
+
+const TOKEN_ERRORS = [
+  "NotRentExempt", "InsufficientFunds", "InvalidMint", "MintMismatch", "OwnerMismatch", "FixedSupply",
+  "AlreadyInUse", "InvalidNumberOfProvidedSigners", "InvalidNumberOfRequiredSigners", "UninitializedState",
+  "NativeNotSupported", "NonNativeHasBalance", "InvalidInstruction", "InvalidState", "Overflow",
+  "AuthorityTypeNotSupported", "MintCannotFreeze", "AccountFrozen", "MintDecimalsMismatch", "NonNativeNotSupported",
+]
+
+type KnownProgram = { kind: CpiErrorKind, name: (code: number) => string | undefined }
+
+// anchor programs share the framework error codes (2000..) plus their own from 6000
+const anchorErrorName = (code: number) => LangErrorMessage.get(code) ?? (code >= 6000 ? `Custom(${code})` : undefined)
+
+export const cpiPrograms = (stakingProgramId: PublicKey): Map<string, KnownProgram> => new Map([
+  [SystemProgram.programId.toBase58(), { kind: "SystemCpiFailed", name: (code) => SYSTEM_ERRORS[code] }],
+  [TOKEN_PROGRAM_ID.toBase58(), { kind: "TokenCpiFailed", name: (code) => TOKEN_ERRORS[code] }],
+  [stakingProgramId.toBase58(), { kind: "StakePoolCpiFailed", name: anchorErrorName }],
+  ["whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", { kind: "SwapCpiFailed", name: anchorErrorName }],
+  ["3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5", { kind: "WormholeCpiFailed", name: () => undefined }],
+  ["BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY", { kind: "BubblegumCpiFailed", name: anchorErrorName }],
+])
+
+const FAILED_LOG = /^Program (\w+) failed: (.*)$/
+const CUSTOM_ERROR = /custom program error: 0x([0-9a-f]+)/
+
+// a failing CPI aborts the whole transaction, the bank never gets the error back
+// to translate on-chain. The logs still show which inner program failed first
+export const translateCpiError = (
+  err: any,
+  bankProgramId: PublicKey,
+  programs: Map<string, KnownProgram>,
+): CpiError | null => {
+  const logs: string[] = err?.logs ?? err?.transactionLogs ?? []
+
+  for (const log of logs) {
+    const failed = log.match(FAILED_LOG)
+    if (failed == null || failed[1] == bankProgramId.toBase58()) {
+      continue
+    }
+
+    const program = new PublicKey(failed[1])
+    const known = programs.get(program.toBase58())
+    const custom = failed[2].match(CUSTOM_ERROR)
+    const code = custom == null ? null : parseInt(custom[1], 16)
+
+    return {
+      kind: known?.kind ?? "UnknownCpiFailed",
+      program,
+      code,
+      name: code == null ? null : known?.name(code) ?? null,
+      reason: failed[2],
+    }
+  }
+
+  return null
+}
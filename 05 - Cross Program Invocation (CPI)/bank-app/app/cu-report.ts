@@ -0,0 +1,125 @@
+import { AnchorProvider, Program } from "@coral-xyz/anchor";
+import { Connection, PublicKey, Transaction, TransactionSignature, VersionedTransaction } from "@solana/web3.js";
+import { mkdirSync, writeFileSync } from "fs";
+import { dirname } from "path";
+
+type Range = { min: number, max: number }
+
+type InstructionStats = {
+  calls: number,
+  // what the instruction itself consumed, CPIs included, other instructions not
+  computeUnits: Range,
+  // the whole signed transaction the instruction was sent in
+  transactionBytes: Range,
+}
+
+export type CuReport = {
+  [program: string]: {
+    instructions: { [instruction: string]: InstructionStats },
+    // allocated size of each account type, discriminator included
+    accounts: { [account: string]: number },
+  }
+}
+
+const widen = (range: Range | undefined, value: number): Range =>
+  range == undefined ? { min: value, max: value } : { min: Math.min(range.min, value), max: Math.max(range.max, value) }
+
+// top-level instructions of the transaction, in order, as (program id, data)
+const topLevelInstructions = (tx: Transaction | VersionedTransaction): [PublicKey, Buffer][] => {
+  if (tx instanceof Transaction) {
+    return tx.instructions.map((ix) => [ix.programId, ix.data])
+  }
+  const keys = tx.message.staticAccountKeys
+  return tx.message.compiledInstructions.map((ix) => [keys[ix.programIdIndex], Buffer.from(ix.data)])
+}
+
+// the units each top-level invocation of `programId` consumed, in order. Builtins
+// and precompiles log no "consumed" line, so only the program's own are counted
+const consumedByProgram = (logs: string[], programId: PublicKey): number[] => {
+  const stack: string[] = []
+  const consumed: number[] = []
+  for (const line of logs) {
+    const invoke = line.match(/^Program (\w+) invoke \[(\d+)\]$/)
+    if (invoke) {
+      stack.push(invoke[1])
+      continue
+    }
+    const units = line.match(/^Program (\w+) consumed (\d+) of \d+ compute units$/)
+    if (units && stack.length == 1 && units[1] == programId.toBase58()) {
+      consumed.push(Number(units[2]))
+      continue
+    }
+    if (/^Program \w+ (success|failed)/.test(line)) {
+      stack.pop()
+    }
+  }
+  return consumed
+}
+
+export class CuReporter {
+  private instructions = new Map<string, Map<string, InstructionStats>>()
+
+  constructor(private programs: Program[]) {}
+
+  async record(connection: Connection, tx: Transaction | VersionedTransaction, signature: TransactionSignature) {
+    const confirmed = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 })
+    const logs = confirmed?.meta?.logMessages ?? []
+    const transactionBytes = tx.serialize().length
+    const instructions = topLevelInstructions(tx)
+
+    for (const program of this.programs) {
+      const names = instructions
+        .filter(([programId]) => programId.equals(program.programId))
+        .map(([, data]) => program.coder.instruction.decode(data)?.name)
+      const consumed = consumedByProgram(logs, program.programId)
+
+      names.forEach((name, index) => {
+        if (name == undefined || consumed[index] == undefined) {
+          return
+        }
+        const stats = this.programStats(program).get(name)
+        this.programStats(program).set(name, {
+          calls: (stats?.calls ?? 0) + 1,
+          computeUnits: widen(stats?.computeUnits, consumed[index]),
+          transactionBytes: widen(stats?.transactionBytes, transactionBytes),
+        })
+      })
+    }
+  }
+
+  // keys sorted, no timestamps, so two runs of the same code give the same file
+  report(): CuReport {
+    const sorted = <T>(entries: [string, T][]) =>
+      Object.fromEntries(entries.sort(([a], [b]) => a.localeCompare(b)))
+
+    return sorted(this.programs.map((program) => [program.idl.metadata.name, {
+      instructions: sorted([...this.programStats(program).entries()]),
+      accounts: sorted(Object.entries(program.account).map(([name, client]) => [name, client.size])),
+    }]))
+  }
+
+  write(path: string) {
+    mkdirSync(dirname(path), { recursive: true })
+    writeFileSync(path, JSON.stringify(this.report(), null, 2) + "\n")
+  }
+
+  // every transaction a test sends, through .rpc() or sendAndConfirm, is recorded
+  // once it landed. Failed ones throw before that and are left out
+  install() {
+    const sendAndConfirm = AnchorProvider.prototype.sendAndConfirm
+    const reporter = this
+    AnchorProvider.prototype.sendAndConfirm = async function (tx, signers, opts) {
+      const signature = await sendAndConfirm.call(this, tx, signers, opts)
+      await reporter.record(this.connection, tx, signature)
+      return signature
+    }
+  }
+
+  private programStats(program: Program): Map<string, InstructionStats> {
+    const name = program.idl.metadata.name
+    if (!this.instructions.has(name)) {
+      this.instructions.set(name, new Map())
+    }
+    return this.instructions.get(name)
+  }
+}
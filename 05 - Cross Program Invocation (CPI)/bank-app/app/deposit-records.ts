@@ -0,0 +1,52 @@
+import { Program } from "@coral-xyz/anchor";
+import { ParsedInstruction, PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { createHash } from "crypto";
+import { BankApp } from "../target/types/bank_app";
+
+export type TaggedDeposit = {
+  address: PublicKey,
+  index: number,
+  amount: BN,
+  createdAt: number,
+  // null when the memo could not be found or does not match the stored hash
+  tag: string | null,
+}
+
+// DepositRecord: discriminator (8) then user
+const DEPOSIT_RECORD_USER_OFFSET = 8
+
+export const fetchTaggedDeposits = async (program: Program<BankApp>, user: PublicKey): Promise<TaggedDeposit[]> => {
+  const connection = program.provider.connection
+  const records = await program.account.depositRecord.all([
+    { memcmp: { offset: DEPOSIT_RECORD_USER_OFFSET, bytes: user.toBase58() } }
+  ])
+
+  const deposits = await Promise.all(records.map(async ({ publicKey, account }) => {
+    // the record is only written once, so the oldest signature touching it is the deposit
+    const signatures = await connection.getSignaturesForAddress(publicKey, {}, "confirmed")
+    const signature = signatures[signatures.length - 1]?.signature
+
+    let tag: string | null = null
+    if (signature != undefined) {
+      const tx = await connection.getParsedTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 })
+      const memos = (tx?.meta?.innerInstructions ?? [])
+        .flatMap((inner) => inner.instructions)
+        .filter((ix): ix is ParsedInstruction => "parsed" in ix && ix.program == "spl-memo")
+        .map((ix) => ix.parsed as string)
+
+      const expected = Buffer.from(account.tagHash)
+      tag = memos.find((memo) => createHash("sha256").update(memo).digest().equals(expected)) ?? null
+    }
+
+    return {
+      address: publicKey,
+      index: account.index.toNumber(),
+      amount: account.amount,
+      createdAt: account.createdAt.toNumber(),
+      tag,
+    }
+  }))
+
+  return deposits.sort((a, b) => a.index - b.index)
+}
@@ -0,0 +1,42 @@
+import { PublicKey } from "@solana/web3.js";
+import { existsSync, readFileSync } from "fs";
+import { join } from "path";
+
+// written by `cargo run -p bank-app-bootstrap`, see crates/bank-app-bootstrap/src/manifest.rs
+export const DEFAULT_MANIFEST_PATH = join(__dirname, "../target/localnet.json")
+
+export type LocalnetMint = { address: PublicKey, decimals: number, tokenProgram: PublicKey }
+
+export type LocalnetManifest = {
+  rpcUrl: string,
+  seed: string,
+  authority: PublicKey,
+  programs: { [name: string]: PublicKey },
+  accounts: { [name: string]: PublicKey },
+  mints: { [name: string]: LocalnetMint },
+  wallets: { [name: string]: PublicKey },
+}
+
+const keys = (entries: { [name: string]: string }) =>
+  Object.fromEntries(Object.entries(entries).map(([name, key]) => [name, new PublicKey(key)]))
+
+// null when bootstrap hasn't run, so tests can fall back to setting up their own
+export const loadLocalnet = (path: string = DEFAULT_MANIFEST_PATH): LocalnetManifest | null => {
+  if (!existsSync(path)) {
+    return null
+  }
+  const raw = JSON.parse(readFileSync(path, "utf8"))
+  return {
+    rpcUrl: raw.rpcUrl,
+    seed: raw.seed,
+    authority: new PublicKey(raw.authority),
+    programs: keys(raw.programs),
+    accounts: keys(raw.accounts),
+    mints: Object.fromEntries(Object.entries(raw.mints).map(([name, mint]: [string, any]) => [name, {
+      address: new PublicKey(mint.address),
+      decimals: mint.decimals,
+      tokenProgram: new PublicKey(mint.tokenProgram),
+    }])),
+    wallets: keys(raw.wallets),
+  }
+}
@@ -0,0 +1,16 @@
+import { Keypair } from "@solana/web3.js";
+import { createHash } from "crypto";
+
+// the same derivation as crates/bank-app-testing/src/test_ids.rs: the ed25519
+// secret of a named identity is sha256("<seed>:<name>")
+export const DEFAULT_TEST_SEED = "bank-app"
+
+export const testKeypair = (seed: string, name: string): Keypair =>
+  Keypair.fromSeed(createHash("sha256").update(`${seed}:${name}`).digest())
+
+export const testIds = (seed: string = DEFAULT_TEST_SEED) => ({
+  admin: testKeypair(seed, "admin"),
+  alice: testKeypair(seed, "alice"),
+  bob: testKeypair(seed, "bob"),
+  attacker: testKeypair(seed, "attacker"),
+})
@@ -0,0 +1,64 @@
+use std::{fmt, path::PathBuf};
+
+use anchor_client::{
+    solana_client::client_error::ClientError as RpcError, solana_sdk::pubkey::Pubkey,
+};
+
+#[derive(Debug)]
+pub enum BootstrapError {
+    Rpc(RpcError),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    // `anchor build` wasn't run, or ran in another workspace
+    MissingBuild(PathBuf),
+    ValidatorNotReady(String),
+    Command(String),
+    Keypair(PathBuf),
+    InvalidManifest(String),
+    // an account at a bootstrap address that isn't what bootstrap creates there
+    UnexpectedAccount(Pubkey),
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapError::Rpc(err) => write!(f, "rpc: {}", err),
+            BootstrapError::Io(err) => write!(f, "io: {}", err),
+            BootstrapError::Json(err) => write!(f, "json: {}", err),
+            BootstrapError::MissingBuild(path) => {
+                write!(f, "{} is missing, run `anchor build` first", path.display())
+            }
+            BootstrapError::ValidatorNotReady(url) => {
+                write!(f, "no validator answered at {} in time", url)
+            }
+            BootstrapError::Command(command) => write!(f, "`{}` failed", command),
+            BootstrapError::Keypair(path) => {
+                write!(f, "can't read a keypair from {}", path.display())
+            }
+            BootstrapError::InvalidManifest(reason) => write!(f, "invalid manifest: {}", reason),
+            BootstrapError::UnexpectedAccount(address) => {
+                write!(f, "{} exists but wasn't created by bootstrap", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BootstrapError {}
+
+impl From<RpcError> for BootstrapError {
+    fn from(err: RpcError) -> Self {
+        BootstrapError::Rpc(err)
+    }
+}
+
+impl From<std::io::Error> for BootstrapError {
+    fn from(err: std::io::Error) -> Self {
+        BootstrapError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for BootstrapError {
+    fn from(err: serde_json::Error) -> Self {
+        BootstrapError::Json(err)
+    }
+}
@@ -0,0 +1,12 @@
+// dev tool: one command from nothing to a localnet with the chapter programs,
+// an initialized bank, test mints and funded test wallets. Everything it set up
+// is written to a manifest that tests and tools read instead of hardcoding keys
+pub mod error;
+pub mod manifest;
+pub mod setup;
+pub mod validator;
+
+pub use error::BootstrapError;
+pub use manifest::{Manifest, MintEntry, DEFAULT_MANIFEST_PATH};
+pub use setup::{bootstrap, BootstrapConfig, TestMint, TEST_MINTS};
+pub use validator::{ensure_programs, is_running, start_validator, LocalProgram, PROGRAMS};
@@ -0,0 +1,106 @@
+// one command from nothing to a working localnet, run from the workspace root
+// after `anchor build`:
+//   cargo run -p bank-app-bootstrap
+//   cargo run -p bank-app-bootstrap -- --url http://127.0.0.1:8899 --no-start
+//   cargo run -p bank-app-bootstrap -- --seed my-seed --out target/my-localnet.json
+use std::{path::PathBuf, process::ExitCode};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file},
+};
+use bank_app_bootstrap::{
+    bootstrap, ensure_programs, is_running, start_validator, BootstrapConfig, BootstrapError,
+    DEFAULT_MANIFEST_PATH,
+};
+use bank_app_testing::test_ids::DEFAULT_TEST_SEED;
+
+const LOCALNET_URL: &str = "http://127.0.0.1:8899";
+
+struct Args {
+    url: String,
+    keypair: PathBuf,
+    seed: String,
+    out: PathBuf,
+    ledger: PathBuf,
+    start: bool,
+}
+
+fn main() -> ExitCode {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let mut args = Args {
+        url: LOCALNET_URL.to_string(),
+        keypair: PathBuf::from(home).join(".config/solana/id.json"),
+        seed: DEFAULT_TEST_SEED.to_string(),
+        out: PathBuf::from(DEFAULT_MANIFEST_PATH),
+        ledger: PathBuf::from("test-ledger"),
+        start: true,
+    };
+
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--url" => args.url = value(),
+            "--keypair" => args.keypair = PathBuf::from(value()),
+            "--seed" => args.seed = value(),
+            "--out" => args.out = PathBuf::from(value()),
+            "--ledger" => args.ledger = PathBuf::from(value()),
+            "--no-start" => args.start = false,
+            _ => {
+                eprintln!("usage: bootstrap [--url <rpc>] [--keypair <path>] [--seed <seed>] [--out <manifest>] [--ledger <dir>] [--no-start]");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), BootstrapError> {
+    let workspace = std::env::current_dir()?;
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed());
+
+    if is_running(&rpc) {
+        for name in ensure_programs(&rpc, &workspace, &args.keypair)? {
+            println!("deployed {name}");
+        }
+    } else if args.start {
+        let validator = start_validator(&rpc, &workspace, &args.ledger)?;
+        println!(
+            "started solana-test-validator (pid {}) with its ledger in {}",
+            validator.id(),
+            args.ledger.display()
+        );
+    } else {
+        return Err(BootstrapError::ValidatorNotReady(args.url.clone()));
+    }
+
+    let authority = read_keypair_file(&args.keypair)
+        .map_err(|_| BootstrapError::Keypair(args.keypair.clone()))?;
+    let manifest = bootstrap(
+        &rpc,
+        &BootstrapConfig {
+            rpc_url: args.url.clone(),
+            seed: args.seed.clone(),
+            authority,
+        },
+    )?;
+    manifest.write(&args.out)?;
+
+    println!("bank authority {}", manifest.authority);
+    for (name, wallet) in &manifest.wallets {
+        println!("wallet {name} {wallet}");
+    }
+    for (name, mint) in &manifest.mints {
+        println!("mint {name} {}", mint.address);
+    }
+    println!("manifest written to {}", args.out.display());
+    Ok(())
+}
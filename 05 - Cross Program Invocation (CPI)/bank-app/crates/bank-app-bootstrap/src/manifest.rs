@@ -0,0 +1,105 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+
+use crate::error::BootstrapError;
+
+// relative to the workspace root, next to the build it was made from
+pub const DEFAULT_MANIFEST_PATH: &str = "target/localnet.json";
+
+// what `bootstrap` set up, keys as base58 strings so app/localnet.ts can read it
+// without a Rust dependency. Maps are sorted, so reruns produce the same file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub rpc_url: String,
+    // the test_keypair seed the wallets and mints are derived from
+    pub seed: String,
+    #[serde(with = "base58")]
+    pub authority: Pubkey,
+    #[serde(with = "base58_map")]
+    pub programs: BTreeMap<String, Pubkey>,
+    // the bank's singleton PDAs
+    #[serde(with = "base58_map")]
+    pub accounts: BTreeMap<String, Pubkey>,
+    pub mints: BTreeMap<String, MintEntry>,
+    #[serde(with = "base58_map")]
+    pub wallets: BTreeMap<String, Pubkey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MintEntry {
+    #[serde(with = "base58")]
+    pub address: Pubkey,
+    pub decimals: u8,
+    #[serde(with = "base58")]
+    pub token_program: Pubkey,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, BootstrapError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), BootstrapError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    pub fn wallet(&self, name: &str) -> Result<Pubkey, BootstrapError> {
+        self.wallets
+            .get(name)
+            .copied()
+            .ok_or_else(|| BootstrapError::InvalidManifest(format!("no wallet {}", name)))
+    }
+
+    pub fn mint(&self, name: &str) -> Result<&MintEntry, BootstrapError> {
+        self.mints
+            .get(name)
+            .ok_or_else(|| BootstrapError::InvalidManifest(format!("no mint {}", name)))
+    }
+}
+
+mod base58 {
+    use std::str::FromStr;
+
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(key: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(key)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Pubkey::from_str(&key).map_err(D::Error::custom)
+    }
+}
+
+mod base58_map {
+    use std::{collections::BTreeMap, str::FromStr};
+
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        keys: &BTreeMap<String, Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(keys.iter().map(|(name, key)| (name, key.to_string())))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Pubkey>, D::Error> {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, key)| Ok((name, Pubkey::from_str(&key).map_err(D::Error::custom)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::Instruction,
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+};
+use bank_app_client::{
+    instructions,
+    types::{BankAccount, BankInfo, BankPda},
+};
+use bank_app_testing::{test_keypair, TestIds};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{
+    error::BootstrapError,
+    manifest::{Manifest, MintEntry},
+    validator::PROGRAMS,
+};
+
+// a wallet below half of this is topped up with an airdrop
+const WALLET_LAMPORTS: u64 = 100 * LAMPORTS_PER_SOL;
+// whole tokens of every test mint in each wallet's ATA
+const WALLET_TOKENS: u64 = 1_000_000;
+
+pub struct TestMint {
+    pub name: &'static str,
+    pub decimals: u8,
+}
+
+// SPL Token mints, the keypair of each is test_keypair(seed, "mint:<name>")
+pub const TEST_MINTS: &[TestMint] = &[
+    TestMint {
+        name: "usdc",
+        decimals: 6,
+    },
+    TestMint {
+        name: "bonk",
+        decimals: 5,
+    },
+];
+
+pub struct BootstrapConfig {
+    pub rpc_url: String,
+    pub seed: String,
+    // pays for everything, becomes the bank authority and the mint authority.
+    // The TS tests expect it to be their provider wallet
+    pub authority: Keypair,
+}
+
+// safe to run again: every step checks whether it already happened
+pub fn bootstrap(rpc: &RpcClient, config: &BootstrapConfig) -> Result<Manifest, BootstrapError> {
+    let authority = &config.authority;
+    fund(rpc, &authority.pubkey())?;
+
+    let bank_info_address = BankPda::BankInfo.find_address().0;
+    if rpc
+        .get_account_with_commitment(&bank_info_address, rpc.commitment())?
+        .value
+        .is_none()
+    {
+        send(
+            rpc,
+            authority,
+            &[],
+            &[instructions::initialize(&authority.pubkey())],
+        )?;
+    }
+    let bank_info = fetch_bank_info(rpc)?;
+    // a bank initialized by another key can't be given the test mints
+    if bank_info.authority != authority.pubkey() {
+        return Err(BootstrapError::UnexpectedAccount(bank_info_address));
+    }
+
+    let ids = TestIds::new(&config.seed);
+    let wallets = [
+        ("admin", &ids.admin),
+        ("alice", &ids.alice),
+        ("bob", &ids.bob),
+        ("attacker", &ids.attacker),
+    ];
+    for (_, wallet) in wallets {
+        fund(rpc, &wallet.pubkey())?;
+    }
+
+    let mut mints = BTreeMap::new();
+    for test_mint in TEST_MINTS {
+        let mint = test_keypair(&config.seed, &format!("mint:{}", test_mint.name));
+        create_mint(rpc, authority, &mint, test_mint.decimals)?;
+        if !bank_info
+            .token_configs
+            .iter()
+            .any(|token| token.mint == mint.pubkey())
+        {
+            send(
+                rpc,
+                authority,
+                &[],
+                &[instructions::add_supported_token(
+                    &authority.pubkey(),
+                    &mint.pubkey(),
+                )],
+            )?;
+        }
+        for (_, wallet) in wallets {
+            fund_tokens(
+                rpc,
+                authority,
+                &mint.pubkey(),
+                test_mint.decimals,
+                &wallet.pubkey(),
+            )?;
+        }
+
+        mints.insert(
+            test_mint.name.to_string(),
+            MintEntry {
+                address: mint.pubkey(),
+                decimals: test_mint.decimals,
+                token_program: spl_token::ID,
+            },
+        );
+    }
+
+    Ok(Manifest {
+        rpc_url: config.rpc_url.clone(),
+        seed: config.seed.clone(),
+        authority: authority.pubkey(),
+        programs: PROGRAMS
+            .iter()
+            .map(|program| (program.name.to_string(), program.id))
+            .collect(),
+        accounts: [
+            ("bankInfo", BankPda::BankInfo),
+            ("bankVault", BankPda::BankVault),
+            ("versionBeacon", BankPda::VersionBeacon),
+        ]
+        .into_iter()
+        .map(|(name, pda)| (name.to_string(), pda.find_address().0))
+        .collect(),
+        mints,
+        wallets: wallets
+            .iter()
+            .map(|(name, wallet)| (name.to_string(), wallet.pubkey()))
+            .collect(),
+    })
+}
+
+fn fetch_bank_info(rpc: &RpcClient) -> Result<BankInfo, BootstrapError> {
+    let address = BankPda::BankInfo.find_address().0;
+    let account = rpc.get_account(&address)?;
+    BankInfo::decode(&account.data).map_err(|_| BootstrapError::UnexpectedAccount(address))
+}
+
+fn fund(rpc: &RpcClient, wallet: &Pubkey) -> Result<(), BootstrapError> {
+    if rpc.get_balance(wallet)? >= WALLET_LAMPORTS / 2 {
+        return Ok(());
+    }
+    let signature = rpc.request_airdrop(wallet, WALLET_LAMPORTS)?;
+    rpc.poll_for_signature_with_commitment(&signature, rpc.commitment())?;
+    Ok(())
+}
+
+fn create_mint(
+    rpc: &RpcClient,
+    authority: &Keypair,
+    mint: &Keypair,
+    decimals: u8,
+) -> Result<(), BootstrapError> {
+    if let Some(account) = rpc
+        .get_account_with_commitment(&mint.pubkey(), rpc.commitment())?
+        .value
+    {
+        if account.owner != spl_token::ID {
+            return Err(BootstrapError::UnexpectedAccount(mint.pubkey()));
+        }
+        return Ok(());
+    }
+
+    let rent = rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)?;
+    send(
+        rpc,
+        authority,
+        &[mint],
+        &[
+            system_instruction::create_account(
+                &authority.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &authority.pubkey(),
+                None,
+                decimals,
+            )
+            .expect("valid mint instruction"),
+        ],
+    )?;
+    Ok(())
+}
+
+// only a wallet without an ATA gets tokens, so reruns don't mint more
+fn fund_tokens(
+    rpc: &RpcClient,
+    authority: &Keypair,
+    mint: &Pubkey,
+    decimals: u8,
+    wallet: &Pubkey,
+) -> Result<(), BootstrapError> {
+    let ata = get_associated_token_address(wallet, mint);
+    if rpc
+        .get_account_with_commitment(&ata, rpc.commitment())?
+        .value
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    send(
+        rpc,
+        authority,
+        &[],
+        &[
+            create_associated_token_account_idempotent(
+                &authority.pubkey(),
+                wallet,
+                mint,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint,
+                &ata,
+                &authority.pubkey(),
+                &[],
+                WALLET_TOKENS * 10u64.pow(decimals as u32),
+            )
+            .expect("valid mint_to instruction"),
+        ],
+    )?;
+    Ok(())
+}
+
+fn send(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    ixs: &[Instruction],
+) -> Result<Signature, BootstrapError> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer.pubkey()),
+        &all_signers,
+        rpc.get_latest_blockhash()?,
+    );
+    Ok(rpc.send_and_confirm_transaction(&tx)?)
+}
@@ -0,0 +1,147 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{pubkey, pubkey::Pubkey},
+};
+
+use crate::error::BootstrapError;
+
+const VALIDATOR_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct LocalProgram {
+    // the crate name, which is also the name of its .so and keypair in target/deploy
+    pub name: &'static str,
+    pub id: Pubkey,
+}
+
+// every program of the workspace, ids as in declare_id!
+pub const PROGRAMS: &[LocalProgram] = &[
+    LocalProgram {
+        name: "bank_app",
+        id: pubkey!("3q57ftWH75aKfxoNnV6Lu1n8LhV73xxKHAxPapL6Jvh7"),
+    },
+    LocalProgram {
+        name: "escrow_app",
+        id: pubkey!("E9w3kzAJW37EBDbYko4Tw6YckfcMzwPKgk9f3XDDKCRu"),
+    },
+    LocalProgram {
+        name: "faucet_app",
+        id: pubkey!("CVa29bwQjoiJXmMdyQTe4YrV5f55PexSTyviJj5UGGaW"),
+    },
+    LocalProgram {
+        name: "memory_lesson",
+        id: pubkey!("4w85YfHpSeiz4bCb2wHyfesydYT55LpkVqRh5YT2xn5a"),
+    },
+    LocalProgram {
+        name: "receipt_hook",
+        id: pubkey!("8GUNx5QmwW7qVrE3R34W7yzrv7UfNrPumcBahkWsxWiX"),
+    },
+    LocalProgram {
+        name: "staking_app",
+        id: pubkey!("EYdKY4wWuwNr7uVRQNBUEXeJyLCAatSELPck3quW7JvA"),
+    },
+];
+
+impl LocalProgram {
+    pub fn so_path(&self, workspace: &Path) -> PathBuf {
+        workspace.join(format!("target/deploy/{}.so", self.name))
+    }
+
+    pub fn keypair_path(&self, workspace: &Path) -> PathBuf {
+        workspace.join(format!("target/deploy/{}-keypair.json", self.name))
+    }
+}
+
+pub fn is_running(rpc: &RpcClient) -> bool {
+    rpc.get_health().is_ok()
+}
+
+// starts solana-test-validator with a fresh ledger and every program loaded at
+// genesis. The validator outlives bootstrap, stop it like any other process
+pub fn start_validator(
+    rpc: &RpcClient,
+    workspace: &Path,
+    ledger: &Path,
+) -> Result<Child, BootstrapError> {
+    let mut command = Command::new("solana-test-validator");
+    command
+        .arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger")
+        .arg(ledger);
+    for program in PROGRAMS {
+        let so_path = existing(program.so_path(workspace))?;
+        command
+            .arg("--bpf-program")
+            .arg(program.id.to_string())
+            .arg(so_path);
+    }
+
+    let child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|_| BootstrapError::Command("solana-test-validator".to_string()))?;
+
+    let started = Instant::now();
+    while !is_running(rpc) {
+        if started.elapsed() > VALIDATOR_STARTUP_TIMEOUT {
+            return Err(BootstrapError::ValidatorNotReady(rpc.url()));
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Ok(child)
+}
+
+// for a validator that was already running: deploys the programs it doesn't have
+// with the solana CLI. Programs that are there are left alone, even if they're older
+// than the local build, `anchor deploy` upgrades them
+pub fn ensure_programs(
+    rpc: &RpcClient,
+    workspace: &Path,
+    keypair: &Path,
+) -> Result<Vec<&'static str>, BootstrapError> {
+    let mut deployed = vec![];
+    for program in PROGRAMS {
+        let account = rpc
+            .get_account_with_commitment(&program.id, rpc.commitment())?
+            .value;
+        if account.is_some_and(|account| account.executable) {
+            continue;
+        }
+
+        let status = Command::new("solana")
+            .arg("program")
+            .arg("deploy")
+            .arg("--url")
+            .arg(rpc.url())
+            .arg("--keypair")
+            .arg(keypair)
+            .arg("--program-id")
+            .arg(existing(program.keypair_path(workspace))?)
+            .arg(existing(program.so_path(workspace))?)
+            .status()?;
+        if !status.success() {
+            return Err(BootstrapError::Command(format!(
+                "solana program deploy {}",
+                program.name
+            )));
+        }
+        deployed.push(program.name);
+    }
+    Ok(deployed)
+}
+
+fn existing(path: PathBuf) -> Result<PathBuf, BootstrapError> {
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(BootstrapError::MissingBuild(path))
+    }
+}
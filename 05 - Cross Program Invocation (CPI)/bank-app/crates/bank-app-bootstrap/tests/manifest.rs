@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use bank_app_bootstrap::{Manifest, MintEntry, PROGRAMS};
+
+fn manifest() -> Manifest {
+    Manifest {
+        rpc_url: "http://127.0.0.1:8899".to_string(),
+        seed: "bank-app".to_string(),
+        authority: Pubkey::new_unique(),
+        programs: PROGRAMS
+            .iter()
+            .map(|program| (program.name.to_string(), program.id))
+            .collect(),
+        accounts: BTreeMap::from([("bankInfo".to_string(), Pubkey::new_unique())]),
+        mints: BTreeMap::from([(
+            "usdc".to_string(),
+            MintEntry {
+                address: Pubkey::new_unique(),
+                decimals: 6,
+                token_program: spl_token::ID,
+            },
+        )]),
+        wallets: BTreeMap::from([("alice".to_string(), Pubkey::new_unique())]),
+    }
+}
+
+#[test]
+fn keys_are_written_as_base58() {
+    let manifest = manifest();
+    let json: serde_json::Value = serde_json::to_value(&manifest).unwrap();
+
+    assert_eq!(json["authority"], manifest.authority.to_string());
+    assert_eq!(json["rpcUrl"], "http://127.0.0.1:8899");
+    assert_eq!(
+        json["programs"]["bank_app"],
+        "3q57ftWH75aKfxoNnV6Lu1n8LhV73xxKHAxPapL6Jvh7"
+    );
+    assert_eq!(
+        json["mints"]["usdc"]["tokenProgram"],
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    );
+}
+
+#[test]
+fn a_written_manifest_loads_back() {
+    let manifest = manifest();
+    let path = std::env::temp_dir().join(format!("bootstrap-{}.json", Pubkey::new_unique()));
+
+    manifest.write(&path).unwrap();
+    assert_eq!(Manifest::load(&path).unwrap(), manifest);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn missing_names_are_errors() {
+    let manifest = manifest();
+    assert_eq!(manifest.wallet("alice").unwrap(), manifest.wallets["alice"]);
+    assert!(manifest.wallet("carol").is_err());
+    assert!(manifest.mint("bonk").is_err());
+}
+
+#[test]
+fn an_invalid_key_is_rejected() {
+    let mut json = serde_json::to_value(manifest()).unwrap();
+    json["wallets"]["alice"] = "not-a-key".into();
+    assert!(serde_json::from_value::<Manifest>(json).is_err());
+}
@@ -0,0 +1,14 @@
+// prints the BankAppError catalog, or looks up one code:
+//   cargo run -p bank-app-client --example error_catalog --no-default-features
+//   cargo run -p bank-app-client --example error_catalog --no-default-features -- 0x1b58
+use bank_app_client::catalog::{error_catalog_markdown, lookup_error};
+
+fn main() {
+    match std::env::args().nth(1) {
+        Some(code) => match lookup_error(&code) {
+            Some(entry) => println!("{} ({}): {}", entry.name, entry.code, entry.message),
+            None => println!("{code} is not a BankAppError code"),
+        },
+        None => print!("{}", error_catalog_markdown()),
+    }
+}
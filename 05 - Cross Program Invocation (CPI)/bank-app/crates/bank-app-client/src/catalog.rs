@@ -0,0 +1,45 @@
+use bank_app_types::BankAppError;
+
+// code -> name -> message for every BankAppError, for support tooling that only
+// sees a code in a log or a wallet's error popup
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorEntry {
+    pub code: u32,
+    pub name: &'static str,
+    pub message: &'static str,
+}
+
+impl From<BankAppError> for ErrorEntry {
+    fn from(error: BankAppError) -> Self {
+        ErrorEntry {
+            code: error.code(),
+            name: error.name(),
+            message: error.message(),
+        }
+    }
+}
+
+pub fn error_catalog() -> impl Iterator<Item = ErrorEntry> {
+    BankAppError::ALL.iter().copied().map(ErrorEntry::from)
+}
+
+// accepts the decimal code or the hex one from `custom program error: 0x1b58`
+pub fn lookup_error(code: &str) -> Option<ErrorEntry> {
+    let code = match code.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => code.parse().ok()?,
+    };
+    BankAppError::from_code(code).map(ErrorEntry::from)
+}
+
+// the catalog as a Markdown table, for a support wiki or the README
+pub fn error_catalog_markdown() -> String {
+    let mut table = String::from("| Code | Hex | Name | Message |\n|---|---|---|---|\n");
+    for entry in error_catalog() {
+        table.push_str(&format!(
+            "| {} | 0x{:x} | `{}` | {} |\n",
+            entry.code, entry.code, entry.name, entry.message
+        ));
+    }
+    table
+}
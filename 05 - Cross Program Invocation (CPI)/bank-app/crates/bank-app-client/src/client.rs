@@ -0,0 +1,187 @@
+use std::{ops::Deref, time::Duration};
+
+use anchor_client::{
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Signature, Signer},
+        transaction::Transaction,
+    },
+    Client, Cluster, Program,
+};
+use bank_app_types::{BankAccount, BankEvent, BankInfo, BankPda, UserReserve};
+
+use crate::{
+    confirm::confirm_transaction,
+    error::BankClientError,
+    instructions,
+    logs::{decode_transaction, DecodedLogs},
+    portfolio::{fetch_portfolio, Portfolio},
+    preflight::{preflight, PreflightReport},
+    tx_builder::TxBuilder,
+};
+
+const DEFAULT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct BankClient<C> {
+    program: Program<C>,
+    payer: C,
+    commitment: CommitmentConfig,
+    confirm_timeout: Duration,
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> BankClient<C> {
+    // `commitment` is used for reads, preflight and confirmation alike
+    pub fn new(
+        cluster: Cluster,
+        payer: C,
+        commitment: CommitmentConfig,
+    ) -> Result<Self, BankClientError> {
+        let client = Client::new_with_options(cluster, payer.clone(), commitment);
+        Ok(Self {
+            program: client.program(bank_app_types::ID)?,
+            payer,
+            commitment,
+            confirm_timeout: DEFAULT_CONFIRM_TIMEOUT,
+        })
+    }
+
+    pub fn with_confirm_timeout(mut self, confirm_timeout: Duration) -> Self {
+        self.confirm_timeout = confirm_timeout;
+        self
+    }
+
+    pub fn program(&self) -> &Program<C> {
+        &self.program
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        self.program.rpc()
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    pub async fn bank_info(&self) -> Result<BankInfo, BankClientError> {
+        self.fetch(&BankPda::BankInfo.find_address().0).await
+    }
+
+    pub async fn user_reserve(&self, user: &Pubkey) -> Result<UserReserve, BankClientError> {
+        self.fetch(&BankPda::UserReserve { user: *user }.find_address().0)
+            .await
+    }
+
+    // everything `wallet` holds in the bank, read with a few getMultipleAccounts calls
+    pub async fn portfolio(
+        &self,
+        wallet: &Pubkey,
+        campaign_ids: &[u64],
+    ) -> Result<Portfolio, BankClientError> {
+        fetch_portfolio(&self.rpc(), wallet, campaign_ids, self.commitment).await
+    }
+
+    pub async fn fetch<T: BankAccount>(&self, address: &Pubkey) -> Result<T, BankClientError> {
+        let account = self
+            .rpc()
+            .get_account_with_commitment(address, self.commitment)
+            .await?
+            .value
+            .ok_or(BankClientError::AccountNotFound(*address))?;
+        Ok(T::decode(&account.data)?)
+    }
+
+    pub async fn deposit(
+        &self,
+        amount: u64,
+        deadline: Option<i64>,
+    ) -> Result<Signature, BankClientError> {
+        self.send(&[instructions::deposit(&self.payer(), amount, deadline)])
+            .await
+    }
+
+    pub async fn deposit_once(
+        &self,
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: [u8; 16],
+    ) -> Result<Signature, BankClientError> {
+        self.send(&[instructions::deposit_once(
+            &self.payer(),
+            amount,
+            deadline,
+            op_id,
+        )])
+        .await
+    }
+
+    pub async fn withdraw(&self, amount: u64) -> Result<Signature, BankClientError> {
+        self.send(&[instructions::withdraw(&self.payer(), amount)])
+            .await
+    }
+
+    // signs with the payer, sends and waits for `self.commitment`
+    pub async fn send(&self, ixs: &[Instruction]) -> Result<Signature, BankClientError> {
+        let blockhash = self.rpc().get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.payer()),
+            &[&*self.payer],
+            blockhash,
+        );
+        self.send_transaction(&tx).await
+    }
+
+    // like `send`, with the compute unit limit and priority fee estimated
+    pub async fn send_estimated(&self, ixs: &[Instruction]) -> Result<Signature, BankClientError> {
+        let tx = self
+            .tx_builder()
+            .instructions(ixs.iter().cloned())
+            .build(&[&*self.payer])
+            .await?;
+        self.send_transaction(&tx).await
+    }
+
+    pub async fn preflight(&self, ixs: &[Instruction]) -> Result<PreflightReport, BankClientError> {
+        preflight(&self.rpc(), &self.payer(), ixs, self.commitment).await
+    }
+
+    pub fn tx_builder(&self) -> TxBuilder {
+        TxBuilder::new(self.rpc(), self.payer(), self.commitment)
+    }
+
+    pub async fn send_transaction(&self, tx: &Transaction) -> Result<Signature, BankClientError> {
+        let signature = self
+            .rpc()
+            .send_transaction_with_config(
+                tx,
+                RpcSendTransactionConfig {
+                    preflight_commitment: Some(self.commitment.commitment),
+                    ..RpcSendTransactionConfig::default()
+                },
+            )
+            .await?;
+        self.confirm(&signature).await?;
+
+        Ok(signature)
+    }
+
+    pub async fn decode_transaction<E: BankEvent>(
+        &self,
+        signature: &Signature,
+    ) -> Result<DecodedLogs<E>, BankClientError> {
+        decode_transaction(&self.rpc(), signature, self.commitment).await
+    }
+
+    pub async fn confirm(&self, signature: &Signature) -> Result<(), BankClientError> {
+        confirm_transaction(
+            &self.rpc(),
+            signature,
+            self.commitment,
+            self.confirm_timeout,
+        )
+        .await
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anchor_client::{
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signature::Signature},
+};
+use tokio::time::{sleep, Instant};
+
+use crate::error::BankClientError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// resolves once the transaction reached `commitment`. A transaction that landed
+// with an error fails right away instead of waiting out the timeout
+pub async fn confirm_transaction(
+    rpc: &RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+    timeout: Duration,
+) -> Result<(), BankClientError> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let statuses = rpc.get_signature_statuses(&[*signature]).await?.value;
+        if let Some(Some(status)) = statuses.into_iter().next() {
+            if let Some(err) = status.err {
+                return Err(BankClientError::TransactionFailed(*signature, err));
+            }
+            if status.satisfies_commitment(commitment) {
+                return Ok(());
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Err(BankClientError::ConfirmationTimeout(*signature));
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
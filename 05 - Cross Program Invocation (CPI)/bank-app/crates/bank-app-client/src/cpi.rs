@@ -0,0 +1,114 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+use crate::instructions::{
+    instruction_data, DepositArgs, DEPOSIT_DISCRIMINATOR, WITHDRAW_DISCRIMINATOR,
+};
+
+// the same shape as the `cpi` module Anchor generates for a program crate, for
+// programs that would rather not depend on bank-app itself. The bank checks every
+// seed, so callers only pass the accounts along. Its optional trailing accounts
+// (op_record, user_history, ...) go in `remaining_accounts`, in bank_app's order
+
+pub mod accounts {
+    use anchor_lang::prelude::*;
+
+    // bank_app::Deposit, the user's UserReserve is created on their first deposit
+    pub struct Deposit<'info> {
+        pub bank_info: AccountInfo<'info>,
+        pub bank_vault: AccountInfo<'info>,
+        pub user_reserve: AccountInfo<'info>,
+        pub user: AccountInfo<'info>,
+        pub system_program: AccountInfo<'info>,
+    }
+
+    impl ToAccountMetas for Deposit<'_> {
+        fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(*self.bank_info.key, false),
+                AccountMeta::new(*self.bank_vault.key, false),
+                AccountMeta::new(*self.user_reserve.key, false),
+                AccountMeta::new(*self.user.key, is_signer.unwrap_or(true)),
+                AccountMeta::new_readonly(*self.system_program.key, false),
+            ]
+        }
+    }
+
+    impl<'info> ToAccountInfos<'info> for Deposit<'info> {
+        fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+            vec![
+                self.bank_info.clone(),
+                self.bank_vault.clone(),
+                self.user_reserve.clone(),
+                self.user.clone(),
+                self.system_program.clone(),
+            ]
+        }
+    }
+
+    // bank_app::Withdraw, the lamports go back to `user`
+    pub struct Withdraw<'info> {
+        pub bank_info: AccountInfo<'info>,
+        pub bank_vault: AccountInfo<'info>,
+        pub user_reserve: AccountInfo<'info>,
+        pub withdrawal_window: AccountInfo<'info>,
+        pub user: AccountInfo<'info>,
+        pub system_program: AccountInfo<'info>,
+    }
+
+    impl ToAccountMetas for Withdraw<'_> {
+        fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(*self.bank_info.key, false),
+                AccountMeta::new(*self.bank_vault.key, false),
+                AccountMeta::new(*self.user_reserve.key, false),
+                AccountMeta::new(*self.withdrawal_window.key, false),
+                AccountMeta::new(*self.user.key, is_signer.unwrap_or(true)),
+                AccountMeta::new_readonly(*self.system_program.key, false),
+            ]
+        }
+    }
+
+    impl<'info> ToAccountInfos<'info> for Withdraw<'info> {
+        fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+            vec![
+                self.bank_info.clone(),
+                self.bank_vault.clone(),
+                self.user_reserve.clone(),
+                self.withdrawal_window.clone(),
+                self.user.clone(),
+                self.system_program.clone(),
+            ]
+        }
+    }
+}
+
+pub fn deposit<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Deposit<'info>>,
+    amount: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
+    let data = instruction_data(DEPOSIT_DISCRIMINATOR, &DepositArgs::V2 { amount, deadline });
+    invoke(ctx, data)
+}
+
+pub fn withdraw<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Withdraw<'info>>,
+    amount: u64,
+) -> Result<()> {
+    invoke(ctx, instruction_data(WITHDRAW_DISCRIMINATOR, &amount))
+}
+
+fn invoke<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let ix = Instruction {
+        program_id: ctx.program.key(),
+        accounts: ctx.to_account_metas(None),
+        data,
+    };
+    invoke_signed(&ix, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use anchor_client::{
+    solana_client::client_error::ClientError as RpcError,
+    solana_sdk::{
+        pubkey::Pubkey, signature::Signature, signer::SignerError, transaction::TransactionError,
+    },
+    ClientError,
+};
+use bank_app_types::DecodeError;
+
+#[derive(Debug)]
+pub enum BankClientError {
+    Anchor(ClientError),
+    Rpc(RpcError),
+    Decode(DecodeError),
+    AccountNotFound(Pubkey),
+    TransactionFailed(Signature, TransactionError),
+    ConfirmationTimeout(Signature),
+    SimulationFailed(TransactionError, Vec<String>),
+    InvalidNonceAccount(Pubkey),
+    Signing(SignerError),
+    RetriesExhausted(String),
+    TransactionNotFound(Signature),
+}
+
+impl fmt::Display for BankClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankClientError::Anchor(err) => write!(f, "anchor client: {}", err),
+            BankClientError::Rpc(err) => write!(f, "rpc: {}", err),
+            BankClientError::Decode(err) => write!(f, "decode: {}", err),
+            BankClientError::AccountNotFound(address) => write!(f, "account {} not found", address),
+            BankClientError::TransactionFailed(signature, err) => {
+                write!(f, "transaction {} failed: {}", signature, err)
+            }
+            BankClientError::ConfirmationTimeout(signature) => {
+                write!(f, "transaction {} was not confirmed in time", signature)
+            }
+            BankClientError::SimulationFailed(err, _) => write!(f, "simulation failed: {}", err),
+            BankClientError::InvalidNonceAccount(address) => {
+                write!(f, "{} is not an initialized nonce account", address)
+            }
+            BankClientError::Signing(err) => write!(f, "signing: {}", err),
+            BankClientError::RetriesExhausted(reason) => {
+                write!(f, "gave up after retrying, last error: {}", reason)
+            }
+            BankClientError::TransactionNotFound(signature) => {
+                write!(f, "transaction {} has no status meta", signature)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BankClientError {}
+
+impl From<ClientError> for BankClientError {
+    fn from(err: ClientError) -> Self {
+        BankClientError::Anchor(err)
+    }
+}
+
+impl From<RpcError> for BankClientError {
+    fn from(err: RpcError) -> Self {
+        BankClientError::Rpc(err)
+    }
+}
+
+impl From<DecodeError> for BankClientError {
+    fn from(err: DecodeError) -> Self {
+        BankClientError::Decode(err)
+    }
+}
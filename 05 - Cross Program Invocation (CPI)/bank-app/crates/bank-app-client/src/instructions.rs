@@ -0,0 +1,372 @@
+use bank_app_types::{BankPda, Pubkey};
+use borsh::BorshSerialize;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_sdk_ids::system_program;
+
+// plain instruction builders, they need no RPC so they can go into any transaction.
+// They don't depend on the program crate either (it doesn't build for wasm), so the
+// argument layouts are copied from lib.rs and each DISCRIMINATOR is
+// sha256("global:<instruction>")[..8], the same as #[program]
+
+pub(crate) const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub(crate) const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const ASSERT_VERSION_DISCRIMINATOR: [u8; 8] = [16, 245, 72, 185, 249, 131, 186, 90];
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const ADD_SUPPORTED_TOKEN_DISCRIMINATOR: [u8; 8] = [109, 142, 133, 205, 240, 28, 197, 245];
+const PAUSE_DISCRIMINATOR: [u8; 8] = [211, 22, 221, 251, 74, 121, 193, 47];
+const UNPAUSE_DISCRIMINATOR: [u8; 8] = [169, 144, 4, 38, 10, 141, 188, 255];
+const SET_GUARDIAN_DISCRIMINATOR: [u8; 8] = [147, 243, 50, 121, 154, 164, 50, 30];
+const GUARDIAN_SET_PAUSED_DISCRIMINATOR: [u8; 8] = [230, 250, 248, 69, 217, 119, 83, 227];
+const SET_APPROVERS_DISCRIMINATOR: [u8; 8] = [200, 243, 4, 34, 150, 154, 148, 93];
+const APPROVE_ACTION_DISCRIMINATOR: [u8; 8] = [200, 117, 44, 13, 133, 139, 131, 36];
+const CLOSE_BANK_DISCRIMINATOR: [u8; 8] = [5, 98, 148, 223, 249, 112, 102, 111];
+const WITHDRAW_TO_ESCROW_DISCRIMINATOR: [u8; 8] = [153, 37, 73, 170, 187, 182, 107, 233];
+
+// escrow_app's id and ESCROW_SEED, withdraw_to_escrow locks into it
+pub const ESCROW_APP_ID: Pubkey =
+    Pubkey::from_str_const("E9w3kzAJW37EBDbYko4Tw6YckfcMzwPKgk9f3XDDKCRu");
+const ESCROW_SEED: &[u8] = b"ESCROW";
+
+// bank_app::instructions::DepositArgs, V5 is the newest layout
+#[derive(BorshSerialize)]
+pub enum DepositArgs {
+    V1 {
+        amount: u64,
+    },
+    V2 {
+        amount: u64,
+        deadline: Option<i64>,
+    },
+    V3 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+    },
+    V4 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+        dry_run: bool,
+    },
+    V5 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+        dry_run: bool,
+        expected_version: Option<u32>,
+    },
+}
+
+// bank_app::instructions::WithdrawOptions
+#[derive(BorshSerialize)]
+pub struct WithdrawOptions {
+    pub dry_run: bool,
+    pub expected_version: Option<u32>,
+}
+
+// bank_app::state::ApprovalAction, in the same order
+#[derive(BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApprovalAction {
+    SetApprovers,
+    CoverShortfall,
+    CloseBank,
+    RescueTokens,
+}
+
+pub(crate) fn instruction_data(discriminator: [u8; 8], args: &impl BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)
+        .expect("writing to a Vec can't fail");
+    data
+}
+
+pub fn deposit(user: &Pubkey, amount: u64, deadline: Option<i64>) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::BankVault.find_address().0, false),
+            AccountMeta::new(BankPda::UserReserve { user: *user }.find_address().0, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(DEPOSIT_DISCRIMINATOR, &DepositArgs::V2 { amount, deadline }),
+    }
+}
+
+// a deposit that lands at most once per op_id, however many times it's resent.
+// Pick the op_id when the deposit is requested and reuse it for every retry
+pub fn deposit_once(
+    user: &Pubkey,
+    amount: u64,
+    deadline: Option<i64>,
+    op_id: [u8; 16],
+) -> Instruction {
+    let mut ix = deposit(user, amount, deadline);
+    ix.accounts.push(AccountMeta::new(
+        BankPda::OpRecord { user: *user, op_id }.find_address().0,
+        false,
+    ));
+    ix.data = instruction_data(
+        DEPOSIT_DISCRIMINATOR,
+        &DepositArgs::V3 {
+            amount,
+            deadline,
+            op_id: Some(op_id),
+        },
+    );
+    ix
+}
+
+pub fn withdraw(user: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::BankVault.find_address().0, false),
+            AccountMeta::new(BankPda::UserReserve { user: *user }.find_address().0, false),
+            AccountMeta::new(
+                BankPda::WithdrawalWindow { user: *user }.find_address().0,
+                false,
+            ),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(WITHDRAW_DISCRIMINATOR, &amount),
+    }
+}
+
+// dry runs fail with DryRunOk once every check passed, so they're only useful to
+// `preflight`. The outcome is the DepositDryRun or WithdrawDryRun event in its logs
+
+pub fn deposit_dry_run(user: &Pubkey, amount: u64) -> Instruction {
+    let mut ix = deposit(user, amount, None);
+    ix.data = instruction_data(
+        DEPOSIT_DISCRIMINATOR,
+        &DepositArgs::V4 {
+            amount,
+            deadline: None,
+            op_id: None,
+            dry_run: true,
+        },
+    );
+    ix
+}
+
+pub fn withdraw_dry_run(user: &Pubkey, amount: u64) -> Instruction {
+    let mut ix = withdraw(user, amount);
+    ix.data = instruction_data(
+        WITHDRAW_DISCRIMINATOR,
+        &(
+            amount,
+            WithdrawOptions {
+                dry_run: true,
+                expected_version: None,
+            },
+        ),
+    );
+    ix
+}
+
+// deposit and withdraw that fail with VersionMismatch, like assert_version
+// without the extra instruction
+
+pub fn deposit_expecting(user: &Pubkey, amount: u64, expected_version: u32) -> Instruction {
+    let mut ix = deposit(user, amount, None);
+    ix.data = instruction_data(
+        DEPOSIT_DISCRIMINATOR,
+        &DepositArgs::V5 {
+            amount,
+            deadline: None,
+            op_id: None,
+            dry_run: false,
+            expected_version: Some(expected_version),
+        },
+    );
+    ix
+}
+
+pub fn withdraw_expecting(user: &Pubkey, amount: u64, expected_version: u32) -> Instruction {
+    let mut ix = withdraw(user, amount);
+    ix.data = instruction_data(
+        WITHDRAW_DISCRIMINATOR,
+        &(
+            amount,
+            WithdrawOptions {
+                dry_run: false,
+                expected_version: Some(expected_version),
+            },
+        ),
+    );
+    ix
+}
+
+// put first in a transaction: it fails with VersionMismatch or MissingFeatures
+// unless the program is compatible with `expected_version` (major << 16 |
+// minor << 8 | patch) and has every bit of `required_features`
+pub fn assert_version(expected_version: u32, required_features: u64) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![],
+        data: instruction_data(
+            ASSERT_VERSION_DISCRIMINATOR,
+            &(expected_version, required_features),
+        ),
+    }
+}
+
+// `authority` pays for the bank's accounts and becomes BankInfo.authority
+pub fn initialize(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::BankVault.find_address().0, false),
+            AccountMeta::new(BankPda::VersionBeacon.find_address().0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: INITIALIZE_DISCRIMINATOR.to_vec(),
+    }
+}
+
+pub fn add_supported_token(authority: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: ADD_SUPPORTED_TOKEN_DISCRIMINATOR.to_vec(),
+    }
+}
+
+// `pause` and `unpause` take the same accounts, only the authority signs them
+pub fn set_paused(authority: &Pubkey, is_paused: bool) -> Instruction {
+    let discriminator = match is_paused {
+        true => PAUSE_DISCRIMINATOR,
+        false => UNPAUSE_DISCRIMINATOR,
+    };
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: discriminator.to_vec(),
+    }
+}
+
+pub fn set_guardian(authority: &Pubkey, guardian: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data(SET_GUARDIAN_DISCRIMINATOR, guardian),
+    }
+}
+
+// the guardian can only pause, unpausing is the authority's
+pub fn guardian_pause(guardian: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new_readonly(*guardian, true),
+        ],
+        data: GUARDIAN_SET_PAUSED_DISCRIMINATOR.to_vec(),
+    }
+}
+
+// the first call needs only the authority. Once approvers are set, changing them
+// needs their approvals of ApprovalAction::SetApprovers
+pub fn set_approvers(
+    authority: &Pubkey,
+    approvers: &[Pubkey],
+    threshold: u8,
+    expiry_slots: u64,
+) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::ApprovalSet.find_address().0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(
+            SET_APPROVERS_DISCRIMINATOR,
+            &(approvers.to_vec(), threshold, expiry_slots),
+        ),
+    }
+}
+
+pub fn approve_action(approver: &Pubkey, action: ApprovalAction) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::ApprovalSet.find_address().0, false),
+            AccountMeta::new_readonly(*approver, true),
+        ],
+        data: instruction_data(APPROVE_ACTION_DISCRIMINATOR, &action),
+    }
+}
+
+// fails with BankNotEmpty while anything is owed, and with ApprovalThresholdNotMet
+// without enough approvals of ApprovalAction::CloseBank
+pub fn close_bank(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::BankVault.find_address().0, false),
+            AccountMeta::new(BankPda::ApprovalSet.find_address().0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: CLOSE_BANK_DISCRIMINATOR.to_vec(),
+    }
+}
+
+// one escrow per depositor and recipient, until it's released
+pub fn escrow_address(depositor: &Pubkey, recipient: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ESCROW_SEED, depositor.as_ref(), recipient.as_ref()],
+        &ESCROW_APP_ID,
+    )
+    .0
+}
+
+// withdraws `amount` and locks it in escrow_app for `recipient` until
+// `unlock_at`, the user is the escrow's depositor
+pub fn withdraw_to_escrow(
+    user: &Pubkey,
+    amount: u64,
+    recipient: &Pubkey,
+    unlock_at: i64,
+) -> Instruction {
+    Instruction {
+        program_id: bank_app_types::ID,
+        accounts: vec![
+            AccountMeta::new(BankPda::BankInfo.find_address().0, false),
+            AccountMeta::new(BankPda::BankVault.find_address().0, false),
+            AccountMeta::new(BankPda::UserReserve { user: *user }.find_address().0, false),
+            AccountMeta::new(
+                BankPda::WithdrawalWindow { user: *user }.find_address().0,
+                false,
+            ),
+            AccountMeta::new(escrow_address(user, recipient), false),
+            AccountMeta::new_readonly(ESCROW_APP_ID, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(
+            WITHDRAW_TO_ESCROW_DISCRIMINATOR,
+            &(amount, *recipient, unlock_at),
+        ),
+    }
+}
@@ -0,0 +1,50 @@
+// async client for Rust services that use the bank. Reads go through
+// bank-app-types, writes are sent on the nonblocking RPC client and awaited
+// at the commitment the client was created with.
+// Without the `rpc` feature only the instruction builders and bank-app-types
+// are left, which also build for wasm32-unknown-unknown. The `cpi` feature adds
+// the typed CPI calls for onchain programs
+pub mod catalog;
+pub mod instructions;
+
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+#[cfg(feature = "rpc")]
+pub mod client;
+#[cfg(feature = "rpc")]
+pub mod confirm;
+#[cfg(feature = "rpc")]
+pub mod error;
+#[cfg(feature = "rpc")]
+pub mod logs;
+#[cfg(feature = "rpc")]
+pub mod portfolio;
+#[cfg(feature = "rpc")]
+pub mod preflight;
+#[cfg(feature = "rpc")]
+pub mod submit;
+#[cfg(feature = "rpc")]
+pub mod tx_builder;
+
+pub use bank_app_types as types;
+pub use catalog::{error_catalog, lookup_error, ErrorEntry};
+
+#[cfg(feature = "rpc")]
+pub use client::BankClient;
+#[cfg(feature = "rpc")]
+pub use confirm::confirm_transaction;
+#[cfg(feature = "rpc")]
+pub use error::BankClientError;
+#[cfg(feature = "rpc")]
+pub use logs::{decode_logs, decode_transaction, DecodedLogs, FailedInstruction};
+#[cfg(feature = "rpc")]
+pub use portfolio::{
+    fetch_portfolio, get_multiple_accounts_batched, Portfolio, Position, PositionKind,
+};
+#[cfg(feature = "rpc")]
+pub use preflight::{preflight, PreflightFailure, PreflightReport};
+#[cfg(feature = "rpc")]
+pub use submit::{LogHook, SubmitHook, Submitter};
+#[cfg(feature = "rpc")]
+pub use tx_builder::TxBuilder;
@@ -0,0 +1,75 @@
+use anchor_client::{
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig},
+    solana_sdk::{
+        commitment_config::CommitmentConfig, signature::Signature, transaction::TransactionError,
+    },
+};
+use bank_app_types::{decode_events, BankEvent};
+use solana_transaction_status_client_types::UiTransactionEncoding;
+
+use crate::{
+    error::BankClientError,
+    preflight::{decode_failure, PreflightFailure},
+};
+
+#[derive(Debug, Clone)]
+pub struct FailedInstruction {
+    // None when the transaction failed before running any instruction (fees, blockhash)
+    pub index: Option<u8>,
+    pub failure: PreflightFailure,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedLogs<E> {
+    pub failed: Option<FailedInstruction>,
+    // empty for a failed transaction, whatever it emitted was rolled back with it
+    pub events: Vec<E>,
+}
+
+// works the same on simulation logs and on the logs of a landed transaction
+pub fn decode_logs<E: BankEvent>(err: Option<TransactionError>, logs: &[String]) -> DecodedLogs<E> {
+    match err {
+        Some(err) => {
+            let index = match &err {
+                TransactionError::InstructionError(index, _) => Some(*index),
+                _ => None,
+            };
+            DecodedLogs {
+                failed: Some(FailedInstruction {
+                    index,
+                    failure: decode_failure(err, logs),
+                }),
+                events: vec![],
+            }
+        }
+        None => DecodedLogs {
+            failed: None,
+            events: decode_events(logs, &bank_app_types::ID),
+        },
+    }
+}
+
+pub async fn decode_transaction<E: BankEvent>(
+    rpc: &RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<DecodedLogs<E>, BankClientError> {
+    let tx = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(commitment),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+
+    let meta = tx
+        .transaction
+        .meta
+        .ok_or(BankClientError::TransactionNotFound(*signature))?;
+    let logs: Option<Vec<String>> = meta.log_messages.into();
+
+    Ok(decode_logs(meta.err, &logs.unwrap_or_default()))
+}
@@ -0,0 +1,197 @@
+use anchor_client::{
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey},
+};
+use bank_app_types::{
+    BankAccount, BankInfo, BankPda, CampaignMatch, DepositCounter, DepositRecord, Goal,
+    LuckyTicket, UserReserve,
+};
+
+use crate::error::BankClientError;
+
+// getMultipleAccounts takes at most 100 keys
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+// goals are numbered by the user, any of the 256 indices can be used
+const GOAL_INDICES: usize = u8::MAX as usize + 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionKind {
+    Reserve,
+    TokenReserve { mint: Pubkey },
+    Goal { index: u8, name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub address: Pubkey,
+    pub kind: PositionKind,
+    // in the mint's base units for a token reserve, lamports otherwise
+    pub principal: u64,
+    // as of the last accrual, goals earn none
+    pub accrued_interest: u64,
+    // a goal's lock, or when a reserve's pending withdrawal may be executed
+    pub locked_until: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Portfolio {
+    pub wallet: Pubkey,
+    pub positions: Vec<Position>,
+    pub deposit_records: Vec<DepositRecord>,
+    pub lucky_ticket: Option<LuckyTicket>,
+    // campaign id and the bonus it matched, already part of the reserve's principal
+    pub rewards: Vec<(u64, u64)>,
+}
+
+impl Portfolio {
+    // the reserve and the goals, token reserves are in other units
+    pub fn sol_principal(&self) -> u64 {
+        self.positions
+            .iter()
+            .filter(|position| !matches!(position.kind, PositionKind::TokenReserve { .. }))
+            .map(|position| position.principal)
+            .sum()
+    }
+
+    pub fn total_rewards(&self) -> u64 {
+        self.rewards.iter().map(|(_, matched)| matched).sum()
+    }
+}
+
+// like get_multiple_accounts, for any number of addresses, in their order
+pub async fn get_multiple_accounts_batched(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+    commitment: CommitmentConfig,
+) -> Result<Vec<Option<Account>>, BankClientError> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(
+            rpc.get_multiple_accounts_with_commitment(chunk, commitment)
+                .await?
+                .value,
+        );
+    }
+    Ok(accounts)
+}
+
+// every PDA a wallet can own is derived and read in two rounds: the first finds
+// the supported mints and the deposit count, the second reads what depends on them.
+// Campaign ids can't be derived, pass the ones the UI knows about. Joint reserves
+// need the other owner and are left out
+pub async fn fetch_portfolio(
+    rpc: &RpcClient,
+    wallet: &Pubkey,
+    campaign_ids: &[u64],
+    commitment: CommitmentConfig,
+) -> Result<Portfolio, BankClientError> {
+    let user = *wallet;
+    let mut first = vec![
+        BankPda::BankInfo,
+        BankPda::DepositCounter { user },
+        BankPda::UserReserve { user },
+        BankPda::LuckyTicket { user },
+    ];
+    first.extend((0..=u8::MAX).map(|index| BankPda::Goal { user, index }));
+    first.extend(campaign_ids.iter().map(|id| BankPda::CampaignMatch {
+        campaign: BankPda::Campaign { id: *id }.find_address().0,
+        user,
+    }));
+    let first = fetch_pdas(rpc, &first, commitment).await?;
+
+    let (bank_info, _) = &first[0];
+    let bank_info: BankInfo = decode(bank_info)?.ok_or(BankClientError::AccountNotFound(
+        BankPda::BankInfo.find_address().0,
+    ))?;
+    let (counter, _) = &first[1];
+    let deposit_count = decode::<DepositCounter>(counter)?.map_or(0, |counter| counter.count);
+
+    let mut second: Vec<BankPda> = bank_info
+        .token_configs
+        .iter()
+        .map(|config| BankPda::UserTokenReserve {
+            user,
+            mint: config.mint,
+        })
+        .collect();
+    second.extend((0..deposit_count).map(|index| BankPda::DepositRecord { user, index }));
+    let second = fetch_pdas(rpc, &second, commitment).await?;
+
+    let mut portfolio = Portfolio {
+        wallet: user,
+        ..Portfolio::default()
+    };
+
+    let (reserve, address) = &first[2];
+    if let Some(reserve) = decode::<UserReserve>(reserve)? {
+        portfolio
+            .positions
+            .push(reserve_position(*address, PositionKind::Reserve, &reserve));
+    }
+    for (config, (reserve, address)) in bank_info.token_configs.iter().zip(&second) {
+        if let Some(reserve) = decode::<UserReserve>(reserve)? {
+            let kind = PositionKind::TokenReserve { mint: config.mint };
+            portfolio
+                .positions
+                .push(reserve_position(*address, kind, &reserve));
+        }
+    }
+    for (goal, address) in &first[4..4 + GOAL_INDICES] {
+        if let Some(goal) = decode::<Goal>(goal)? {
+            portfolio.positions.push(Position {
+                address: *address,
+                kind: PositionKind::Goal {
+                    index: goal.index,
+                    name: goal.name,
+                },
+                principal: goal.amount,
+                accrued_interest: 0,
+                locked_until: goal.lock_until,
+            });
+        }
+    }
+
+    let (ticket, _) = &first[3];
+    portfolio.lucky_ticket = decode(ticket)?;
+    for (id, (campaign_match, _)) in campaign_ids.iter().zip(&first[4 + GOAL_INDICES..]) {
+        if let Some(campaign_match) = decode::<CampaignMatch>(campaign_match)? {
+            portfolio.rewards.push((*id, campaign_match.matched));
+        }
+    }
+    // closed records leave gaps in the numbering
+    for (record, _) in &second[bank_info.token_configs.len()..] {
+        portfolio
+            .deposit_records
+            .extend(decode::<DepositRecord>(record)?);
+    }
+
+    Ok(portfolio)
+}
+
+async fn fetch_pdas(
+    rpc: &RpcClient,
+    pdas: &[BankPda],
+    commitment: CommitmentConfig,
+) -> Result<Vec<(Option<Account>, Pubkey)>, BankClientError> {
+    let addresses: Vec<Pubkey> = pdas.iter().map(|pda| pda.find_address().0).collect();
+    let accounts = get_multiple_accounts_batched(rpc, &addresses, commitment).await?;
+    Ok(accounts.into_iter().zip(addresses).collect())
+}
+
+// a missing account is None, one of another type at a derived address is an error
+fn decode<T: BankAccount>(account: &Option<Account>) -> Result<Option<T>, BankClientError> {
+    match account {
+        Some(account) => Ok(Some(T::decode(&account.data)?)),
+        None => Ok(None),
+    }
+}
+
+fn reserve_position(address: Pubkey, kind: PositionKind, reserve: &UserReserve) -> Position {
+    Position {
+        address,
+        kind,
+        principal: reserve.deposited_amount,
+        accrued_interest: reserve.accrued_interest,
+        locked_until: (reserve.pending_withdrawal > 0).then_some(reserve.unlock_at),
+    }
+}
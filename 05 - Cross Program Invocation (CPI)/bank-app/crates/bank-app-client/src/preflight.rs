@@ -0,0 +1,149 @@
+use anchor_client::{
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        transaction::{Transaction, TransactionError},
+    },
+};
+use bank_app_types::BankAppError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::error::BankClientError;
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const ACCOUNT_NOT_INITIALIZED: u32 = 3012;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreflightFailure {
+    // a BankAppError, `account` is set when a constraint on that account failed
+    Bank {
+        error: BankAppError,
+        account: Option<String>,
+    },
+    // an `_ata` account the instruction expects was never created
+    MissingTokenAccount {
+        account: String,
+    },
+    InsufficientLamports {
+        have: u64,
+        need: u64,
+    },
+    InsufficientTokens,
+    InsufficientFundsForFee,
+    Other(TransactionError),
+}
+
+#[derive(Debug, Clone)]
+pub struct PreflightReport {
+    pub failure: Option<PreflightFailure>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub return_data: Option<Vec<u8>>,
+}
+
+impl PreflightReport {
+    pub fn is_ok(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+// simulates the instructions as `payer` would send them and says why they would
+// fail, before anything is signed. Only RPC errors are returned as Err, a failing
+// simulation is a report with `failure` set
+pub async fn preflight(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    ixs: &[Instruction],
+    commitment: CommitmentConfig,
+) -> Result<PreflightReport, BankClientError> {
+    let tx = Transaction::new_with_payer(ixs, Some(payer));
+    let result = rpc
+        .simulate_transaction_with_config(
+            &tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(commitment),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .await?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    let return_data = result
+        .return_data
+        .filter(|data| data.program_id == bank_app_types::ID.to_string())
+        .and_then(|data| STANDARD.decode(data.data.0).ok());
+
+    Ok(PreflightReport {
+        failure: result.err.map(|err| decode_failure(err, &logs)),
+        logs,
+        units_consumed: result.units_consumed,
+        return_data,
+    })
+}
+
+pub fn decode_failure(err: TransactionError, logs: &[String]) -> PreflightFailure {
+    let code = match &err {
+        TransactionError::InsufficientFundsForFee => {
+            return PreflightFailure::InsufficientFundsForFee
+        }
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => *code,
+        _ => return PreflightFailure::Other(err),
+    };
+
+    // the first "failed:" line is the innermost program, a CPI target's code
+    // reaches the bank unchanged and must not be read as a BankAppError
+    let failed_program = logs.iter().find_map(|log| {
+        log.strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(" failed: "))
+            .map(|(program, _)| program)
+    });
+
+    let bank_program = bank_app_types::ID.to_string();
+    match failed_program {
+        Some(TOKEN_PROGRAM) if code == 1 => PreflightFailure::InsufficientTokens,
+        Some(SYSTEM_PROGRAM) => logs
+            .iter()
+            .find_map(|log| insufficient_lamports(log))
+            .unwrap_or(PreflightFailure::Other(err)),
+        Some(program) if program == bank_program => {
+            let account = anchor_error_account(logs);
+            if code == ACCOUNT_NOT_INITIALIZED {
+                if let Some(ata) = account.as_ref().filter(|name| name.ends_with("_ata")) {
+                    return PreflightFailure::MissingTokenAccount {
+                        account: ata.clone(),
+                    };
+                }
+            }
+            match BankAppError::from_code(code) {
+                Some(error) => PreflightFailure::Bank { error, account },
+                None => PreflightFailure::Other(err),
+            }
+        }
+        _ => PreflightFailure::Other(err),
+    }
+}
+
+// "Transfer: insufficient lamports 10, need 20"
+fn insufficient_lamports(log: &str) -> Option<PreflightFailure> {
+    let (_, amounts) = log.split_once("Transfer: insufficient lamports ")?;
+    let (have, need) = amounts.split_once(", need ")?;
+    Some(PreflightFailure::InsufficientLamports {
+        have: have.trim().parse().ok()?,
+        need: need.trim().parse().ok()?,
+    })
+}
+
+// "AnchorError caused by account: user_reserve. Error Code: ..."
+fn anchor_error_account(logs: &[String]) -> Option<String> {
+    logs.iter().find_map(|log| {
+        let (_, rest) = log.split_once("AnchorError caused by account: ")?;
+        let (account, _) = rest.split_once('.')?;
+        Some(account.to_string())
+    })
+}
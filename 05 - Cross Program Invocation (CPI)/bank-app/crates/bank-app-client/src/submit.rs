@@ -0,0 +1,300 @@
+use std::{sync::Arc, time::Duration};
+
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError as RpcError, ClientErrorKind},
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::RpcSendTransactionConfig,
+        rpc_request::{RpcError as RpcRequestError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig, instruction::Instruction, pubkey::Pubkey,
+        signature::Signature, signer::signers::Signers, transaction::Transaction,
+        transaction::TransactionError,
+    },
+};
+use tokio::time::sleep;
+
+use crate::error::BankClientError;
+
+pub const DEFAULT_MAX_ATTEMPTS: usize = 5;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// called from the submission loop, every method defaults to doing nothing
+pub trait SubmitHook: Send + Sync {
+    fn on_send(&self, _endpoint: &str, _attempt: usize, _signature: &Signature) {}
+    fn on_retry(&self, _endpoint: &str, _attempt: usize, _reason: &str) {}
+    fn on_confirmed(&self, _signature: &Signature, _attempt: usize) {}
+}
+
+// prints every step to stderr, enough for a CLI or a cron job
+pub struct LogHook;
+
+impl SubmitHook for LogHook {
+    fn on_send(&self, endpoint: &str, attempt: usize, signature: &Signature) {
+        eprintln!("[attempt {}] sent {} to {}", attempt, signature, endpoint);
+    }
+
+    fn on_retry(&self, endpoint: &str, attempt: usize, reason: &str) {
+        eprintln!("[attempt {}] {} on {}, retrying", attempt, reason, endpoint);
+    }
+
+    fn on_confirmed(&self, signature: &Signature, attempt: usize) {
+        eprintln!("[attempt {}] {} confirmed", attempt, signature);
+    }
+}
+
+enum Attempt {
+    Confirmed(Signature),
+    // the transaction may still land, the next attempt sends the same one again
+    Retry(String),
+    // the blockhash is past its last valid height, the next attempt re-signs
+    Expired(String),
+}
+
+// a signed transaction and the height after which it can never land
+struct Signed {
+    tx: Transaction,
+    last_valid_block_height: u64,
+}
+
+// the loop a production sender needs: sign against a fresh blockhash, send,
+// poll until confirmed or the blockhash expires, then re-sign and go again.
+// Each attempt moves on to the next endpoint, so one flaky RPC can't stall it.
+// Once a transaction was sent it is only ever rebroadcast, never re-signed,
+// until its blockhash expires. A timed out send may still land, and a second
+// signature for the same instructions could land next to it
+pub struct Submitter {
+    endpoints: Vec<RpcClient>,
+    commitment: CommitmentConfig,
+    max_attempts: usize,
+    hook: Option<Arc<dyn SubmitHook>>,
+}
+
+impl Submitter {
+    pub fn new(urls: &[String], commitment: CommitmentConfig) -> Self {
+        Self::with_clients(
+            urls.iter()
+                .map(|url| RpcClient::new_with_commitment(url.clone(), commitment))
+                .collect(),
+            commitment,
+        )
+    }
+
+    // for clients built some other way, e.g. on a mock sender in tests
+    pub fn with_clients(endpoints: Vec<RpcClient>, commitment: CommitmentConfig) -> Self {
+        Self {
+            endpoints,
+            commitment,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            hook: None,
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn hook(mut self, hook: Arc<dyn SubmitHook>) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    pub async fn send_and_confirm<T: Signers + ?Sized>(
+        &self,
+        ixs: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+    ) -> Result<Signature, BankClientError> {
+        let mut last_reason = String::from("no RPC endpoint configured");
+        let mut pending: Option<Signed> = None;
+
+        for attempt in 0..self.max_attempts {
+            let Some(rpc) = self.endpoints.get(attempt % self.endpoints.len().max(1)) else {
+                break;
+            };
+
+            let signed = match pending.take() {
+                Some(signed) => signed,
+                None => match self.sign(rpc, ixs, payer, signers).await? {
+                    Ok(signed) => signed,
+                    // nothing was sent, the next attempt signs from scratch
+                    Err(reason) => {
+                        self.retried(rpc, attempt, &reason);
+                        last_reason = reason;
+                        continue;
+                    }
+                },
+            };
+
+            match self.attempt(rpc, attempt, &signed).await? {
+                Attempt::Confirmed(signature) => {
+                    if let Some(hook) = &self.hook {
+                        hook.on_confirmed(&signature, attempt);
+                    }
+                    return Ok(signature);
+                }
+                Attempt::Retry(reason) => {
+                    self.retried(rpc, attempt, &reason);
+                    last_reason = reason;
+                    pending = Some(signed);
+                }
+                Attempt::Expired(reason) => {
+                    self.retried(rpc, attempt, &reason);
+                    last_reason = reason;
+                }
+            }
+        }
+
+        Err(BankClientError::RetriesExhausted(last_reason))
+    }
+
+    fn retried(&self, rpc: &RpcClient, attempt: usize, reason: &str) {
+        if let Some(hook) = &self.hook {
+            hook.on_retry(&rpc.url(), attempt, reason);
+        }
+    }
+
+    async fn sign<T: Signers + ?Sized>(
+        &self,
+        rpc: &RpcClient,
+        ixs: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+    ) -> Result<Result<Signed, String>, BankClientError> {
+        let (blockhash, last_valid_block_height) = match rpc
+            .get_latest_blockhash_with_commitment(self.commitment)
+            .await
+        {
+            Ok(latest) => latest,
+            Err(err) => return Ok(Err(err.to_string())),
+        };
+
+        let mut tx = Transaction::new_with_payer(ixs, Some(payer));
+        tx.try_sign(signers, blockhash)
+            .map_err(BankClientError::Signing)?;
+        Ok(Ok(Signed {
+            tx,
+            last_valid_block_height,
+        }))
+    }
+
+    async fn attempt(
+        &self,
+        rpc: &RpcClient,
+        attempt: usize,
+        signed: &Signed,
+    ) -> Result<Attempt, BankClientError> {
+        let signature = signed.tx.signatures[0];
+
+        if let Some(hook) = &self.hook {
+            hook.on_send(&rpc.url(), attempt, &signature);
+        }
+
+        let sent = rpc
+            .send_transaction_with_config(
+                &signed.tx,
+                RpcSendTransactionConfig {
+                    preflight_commitment: Some(self.commitment.commitment),
+                    ..RpcSendTransactionConfig::default()
+                },
+            )
+            .await;
+        if let Err(err) = sent {
+            match send_error(&err) {
+                // an earlier attempt already landed this exact transaction
+                SendError::AlreadyProcessed => {}
+                // it may have reached a leader anyway, look for it once before
+                // the next endpoint gets the same transaction
+                SendError::Retryable => {
+                    if let Some(signature) = self.confirmed(rpc, &signature).await? {
+                        return Ok(Attempt::Confirmed(signature));
+                    }
+                    return self.retry_or_expire(rpc, signed, err.to_string()).await;
+                }
+                SendError::Fatal => return Err(err.into()),
+            }
+        }
+
+        loop {
+            if let Some(signature) = self.confirmed(rpc, &signature).await? {
+                return Ok(Attempt::Confirmed(signature));
+            }
+
+            match rpc.get_block_height().await {
+                // past this height the transaction can never land, but it may have
+                // landed since the last look
+                Ok(height) if height > signed.last_valid_block_height => {
+                    if let Some(signature) = self.confirmed(rpc, &signature).await? {
+                        return Ok(Attempt::Confirmed(signature));
+                    }
+                    return Ok(Attempt::Expired("blockhash expired".to_string()));
+                }
+                Ok(_) => {}
+                Err(err) => return Ok(Attempt::Retry(err.to_string())),
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    // a failed send of an expired transaction is never going to succeed
+    async fn retry_or_expire(
+        &self,
+        rpc: &RpcClient,
+        signed: &Signed,
+        reason: String,
+    ) -> Result<Attempt, BankClientError> {
+        match rpc.get_block_height().await {
+            Ok(height) if height > signed.last_valid_block_height => Ok(Attempt::Expired(reason)),
+            _ => Ok(Attempt::Retry(reason)),
+        }
+    }
+
+    // an RPC error here only means this look found nothing
+    async fn confirmed(
+        &self,
+        rpc: &RpcClient,
+        signature: &Signature,
+    ) -> Result<Option<Signature>, BankClientError> {
+        let Ok(response) = rpc.get_signature_statuses(&[*signature]).await else {
+            return Ok(None);
+        };
+        let Some(Some(status)) = response.value.into_iter().next() else {
+            return Ok(None);
+        };
+        if let Some(err) = status.err {
+            return Err(BankClientError::TransactionFailed(*signature, err));
+        }
+        if status.satisfies_commitment(self.commitment) {
+            return Ok(Some(*signature));
+        }
+        Ok(None)
+    }
+}
+
+enum SendError {
+    AlreadyProcessed,
+    Retryable,
+    Fatal,
+}
+
+fn send_error(err: &RpcError) -> SendError {
+    match err.get_transaction_error() {
+        Some(TransactionError::AlreadyProcessed) => return SendError::AlreadyProcessed,
+        Some(TransactionError::BlockhashNotFound) => return SendError::Retryable,
+        Some(_) => return SendError::Fatal,
+        None => {}
+    }
+
+    match err.kind() {
+        // the preflight simulation failed, sending it again fails the same way
+        ClientErrorKind::RpcError(RpcRequestError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(_),
+            ..
+        }) => SendError::Fatal,
+        ClientErrorKind::SigningError(_) | ClientErrorKind::SerdeJson(_) => SendError::Fatal,
+        // timeouts, connection resets, rate limits, node behind
+        _ => SendError::Retryable,
+    }
+}
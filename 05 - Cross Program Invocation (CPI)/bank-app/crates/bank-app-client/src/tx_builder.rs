@@ -0,0 +1,204 @@
+use anchor_client::{
+    solana_client::{
+        nonblocking::rpc_client::RpcClient, nonce_utils::nonblocking as nonce_utils,
+        rpc_config::RpcSimulateTransactionConfig,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig, compute_budget::ComputeBudgetInstruction, hash::Hash,
+        instruction::Instruction, pubkey::Pubkey, signer::signers::Signers, system_instruction,
+        transaction::Transaction,
+    },
+};
+
+use crate::error::BankClientError;
+
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+pub const DEFAULT_CU_MARGIN_BPS: u32 = 1_000; // +10%
+pub const DEFAULT_FEE_PERCENTILE: u8 = 75;
+
+pub struct DurableNonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+// assembles a transaction the way a wallet should: simulate it once to learn the
+// compute units, then set the limit to that plus a margin and pay a priority fee
+// taken from what recently landed on the same writable accounts
+pub struct TxBuilder {
+    rpc: RpcClient,
+    payer: Pubkey,
+    commitment: CommitmentConfig,
+    instructions: Vec<Instruction>,
+    cu_margin_bps: u32,
+    fee_percentile: u8,
+    max_priority_fee: Option<u64>,
+    nonce: Option<DurableNonce>,
+}
+
+impl TxBuilder {
+    pub fn new(rpc: RpcClient, payer: Pubkey, commitment: CommitmentConfig) -> Self {
+        Self {
+            rpc,
+            payer,
+            commitment,
+            instructions: vec![],
+            cu_margin_bps: DEFAULT_CU_MARGIN_BPS,
+            fee_percentile: DEFAULT_FEE_PERCENTILE,
+            max_priority_fee: None,
+            nonce: None,
+        }
+    }
+
+    pub fn instruction(mut self, ix: Instruction) -> Self {
+        self.instructions.push(ix);
+        self
+    }
+
+    pub fn instructions(mut self, ixs: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(ixs);
+        self
+    }
+
+    pub fn cu_margin_bps(mut self, cu_margin_bps: u32) -> Self {
+        self.cu_margin_bps = cu_margin_bps;
+        self
+    }
+
+    pub fn fee_percentile(mut self, fee_percentile: u8) -> Self {
+        self.fee_percentile = fee_percentile.min(100);
+        self
+    }
+
+    // in micro-lamports per compute unit
+    pub fn max_priority_fee(mut self, max_priority_fee: u64) -> Self {
+        self.max_priority_fee = Some(max_priority_fee);
+        self
+    }
+
+    // the transaction uses the nonce instead of a recent blockhash, so it stays
+    // valid until the nonce is advanced (offline signing, multisig round trips)
+    pub fn durable_nonce(mut self, account: Pubkey, authority: Pubkey) -> Self {
+        self.nonce = Some(DurableNonce { account, authority });
+        self
+    }
+
+    pub async fn build<T: Signers + ?Sized>(
+        self,
+        signers: &T,
+    ) -> Result<Transaction, BankClientError> {
+        let priority_fee = self.priority_fee().await?;
+        let compute_unit_limit = self.compute_unit_limit(priority_fee).await?;
+        let blockhash = self.blockhash().await?;
+
+        let ixs = self.with_budget(compute_unit_limit, priority_fee);
+        let mut tx = Transaction::new_with_payer(&ixs, Some(&self.payer));
+        tx.try_sign(signers, blockhash)
+            .map_err(BankClientError::Signing)?;
+        Ok(tx)
+    }
+
+    pub async fn priority_fee(&self) -> Result<u64, BankClientError> {
+        let writable: Vec<Pubkey> = self
+            .instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+
+        let fees: Vec<u64> = self
+            .rpc
+            .get_recent_prioritization_fees(&writable)
+            .await?
+            .iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+
+        let fee = percentile(fees, self.fee_percentile);
+        Ok(match self.max_priority_fee {
+            Some(max) => fee.min(max),
+            None => fee,
+        })
+    }
+
+    // the simulated transaction already carries both compute budget instructions,
+    // so their own cost is part of `units_consumed`
+    pub async fn compute_unit_limit(&self, priority_fee: u64) -> Result<u32, BankClientError> {
+        let ixs = self.with_budget(MAX_COMPUTE_UNIT_LIMIT, priority_fee);
+        let tx = Transaction::new_with_payer(&ixs, Some(&self.payer));
+
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.commitment),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await?
+            .value;
+
+        if let Some(err) = result.err {
+            return Err(BankClientError::SimulationFailed(
+                err,
+                result.logs.unwrap_or_default(),
+            ));
+        }
+
+        let units = result
+            .units_consumed
+            .unwrap_or(MAX_COMPUTE_UNIT_LIMIT as u64);
+        let limit = units * (10_000 + self.cu_margin_bps as u64) / 10_000;
+        Ok(limit.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32)
+    }
+
+    async fn blockhash(&self) -> Result<Hash, BankClientError> {
+        match &self.nonce {
+            Some(nonce) => {
+                let account = nonce_utils::get_account_with_commitment(
+                    &self.rpc,
+                    &nonce.account,
+                    self.commitment,
+                )
+                .await
+                .map_err(|_| BankClientError::InvalidNonceAccount(nonce.account))?;
+                let data = nonce_utils::data_from_account(&account)
+                    .map_err(|_| BankClientError::InvalidNonceAccount(nonce.account))?;
+                Ok(data.blockhash())
+            }
+            None => Ok(self.rpc.get_latest_blockhash().await?),
+        }
+    }
+
+    // advance_nonce_account has to be the first instruction of a nonce transaction
+    fn with_budget(&self, compute_unit_limit: u32, priority_fee: u64) -> Vec<Instruction> {
+        let mut ixs = vec![];
+        if let Some(nonce) = &self.nonce {
+            ixs.push(system_instruction::advance_nonce_account(
+                &nonce.account,
+                &nonce.authority,
+            ));
+        }
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
+            compute_unit_limit,
+        ));
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            priority_fee,
+        ));
+        ixs.extend(self.instructions.iter().cloned());
+        ixs
+    }
+}
+
+// nearest-rank percentile, 0 when there is no fee data (an idle localnet)
+fn percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() - 1) * percentile as usize / 100;
+    fees[rank]
+}
@@ -0,0 +1,132 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientErrorKind, Result as RpcResult},
+        nonblocking::rpc_client::RpcClient,
+        rpc_client::RpcClientConfig,
+        rpc_request::RpcRequest,
+        rpc_sender::{RpcSender, RpcTransportStats},
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        hash::Hash,
+        signature::{Keypair, Signature, Signer},
+        system_instruction,
+    },
+};
+use async_trait::async_trait;
+use bank_app_client::{SubmitHook, Submitter};
+use serde_json::{json, Value};
+
+// every send times out, but the first one reached a leader: the second status
+// lookup finds it. Each blockhash request hands out a new blockhash, so a
+// re-signed transaction would have a new signature
+#[derive(Default)]
+struct TimeoutThenLanded {
+    blockhashes: AtomicUsize,
+    status_lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl RpcSender for TimeoutThenLanded {
+    async fn send(&self, request: RpcRequest, _params: Value) -> RpcResult<Value> {
+        match request {
+            RpcRequest::GetLatestBlockhash => {
+                self.blockhashes.fetch_add(1, Ordering::SeqCst);
+                Ok(json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "blockhash": Hash::new_unique().to_string(),
+                        "lastValidBlockHeight": 100,
+                    },
+                }))
+            }
+            RpcRequest::SendTransaction => Err(ClientErrorKind::Io(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "send timed out",
+            ))
+            .into()),
+            RpcRequest::GetSignatureStatuses => {
+                let status = match self.status_lookups.fetch_add(1, Ordering::SeqCst) {
+                    0 => Value::Null,
+                    _ => json!({
+                        "slot": 2,
+                        "confirmations": null,
+                        "err": null,
+                        "status": { "Ok": null },
+                        "confirmationStatus": "finalized",
+                    }),
+                };
+                Ok(json!({ "context": { "slot": 2 }, "value": [status] }))
+            }
+            RpcRequest::GetBlockHeight => Ok(json!(50)),
+            RpcRequest::GetVersion => Ok(json!({ "solana-core": "2.2.0", "feature-set": 0 })),
+            request => panic!("unexpected {request}"),
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+#[derive(Default)]
+struct SentSignatures(Mutex<Vec<Signature>>);
+
+impl SubmitHook for SentSignatures {
+    fn on_send(&self, _endpoint: &str, _attempt: usize, signature: &Signature) {
+        self.0.lock().unwrap().push(*signature);
+    }
+}
+
+#[tokio::test]
+async fn a_timed_out_send_is_rebroadcast_not_re_signed() {
+    let sender = Arc::new(TimeoutThenLanded::default());
+    let rpc = RpcClient::new_sender(
+        SharedSender(sender.clone()),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    );
+    let sent = Arc::new(SentSignatures::default());
+    let submitter =
+        Submitter::with_clients(vec![rpc], CommitmentConfig::confirmed()).hook(sent.clone());
+
+    let payer = Keypair::new();
+    let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1);
+    let signature = submitter
+        .send_and_confirm(&[ix], &payer.pubkey(), &[&payer])
+        .await
+        .unwrap();
+
+    // signed once, sent twice
+    assert_eq!(sender.blockhashes.load(Ordering::SeqCst), 1);
+    assert_eq!(*sent.0.lock().unwrap(), vec![signature, signature]);
+}
+
+// new_sender takes the sender by value, the test keeps a handle on its counters
+struct SharedSender(Arc<TimeoutThenLanded>);
+
+#[async_trait]
+impl RpcSender for SharedSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> RpcResult<Value> {
+        self.0.send(request, params).await
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.0.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.0.url()
+    }
+}
@@ -0,0 +1,45 @@
+use std::{fmt, path::PathBuf};
+
+use anchor_client::{
+    solana_client::client_error::ClientError as RpcError, solana_sdk::signature::Signature,
+};
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Rpc(RpcError),
+    TransactionNotFound(Signature),
+    // only base64 encoded transactions can be decoded back into a message
+    UndecodableTransaction(Signature),
+    InvalidTokenBalance(Signature),
+    Program(PathBuf),
+    RejectedAccount(String),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Rpc(err) => write!(f, "rpc: {}", err),
+            ReplayError::TransactionNotFound(signature) => {
+                write!(f, "transaction {} not found", signature)
+            }
+            ReplayError::UndecodableTransaction(signature) => {
+                write!(f, "transaction {} could not be decoded", signature)
+            }
+            ReplayError::InvalidTokenBalance(signature) => {
+                write!(f, "transaction {} has an invalid token balance", signature)
+            }
+            ReplayError::Program(path) => write!(f, "could not load {}", path.display()),
+            ReplayError::RejectedAccount(address) => {
+                write!(f, "LiteSVM rejected account {}", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<RpcError> for ReplayError {
+    fn from(err: RpcError) -> Self {
+        ReplayError::Rpc(err)
+    }
+}
@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{
+        account::Account, bpf_loader_upgradeable, commitment_config::CommitmentConfig,
+        pubkey::Pubkey, signature::Signature, sysvar, transaction::TransactionError,
+        transaction::VersionedTransaction,
+    },
+};
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiLoadedAddresses, UiTransactionEncoding,
+};
+
+use crate::error::ReplayError;
+
+// getMultipleAccounts takes at most 100 keys
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+// an SPL token account's amount, the same offset under Token-2022
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct RecordedTransaction {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: VersionedTransaction,
+    // static keys, then the writable and readonly keys loaded from lookup tables,
+    // the order `pre_balances` is indexed in
+    pub account_keys: Vec<Pubkey>,
+    pub pre_balances: Vec<u64>,
+    // (account index, raw amount) of every token account the transaction touched
+    pub pre_token_amounts: Vec<(usize, u64)>,
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+}
+
+pub fn fetch_transaction(
+    rpc: &RpcClient,
+    signature: &Signature,
+) -> Result<RecordedTransaction, ReplayError> {
+    let confirmed = rpc.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or(ReplayError::TransactionNotFound(*signature))?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(ReplayError::UndecodableTransaction(*signature))?;
+
+    let mut account_keys = transaction.message.static_account_keys().to_vec();
+    let loaded: Option<UiLoadedAddresses> = meta.loaded_addresses.into();
+    if let Some(loaded) = loaded {
+        for address in loaded.writable.iter().chain(&loaded.readonly) {
+            account_keys.push(
+                Pubkey::from_str(address)
+                    .map_err(|_| ReplayError::UndecodableTransaction(*signature))?,
+            );
+        }
+    }
+
+    let pre_token_amounts = match meta.pre_token_balances {
+        OptionSerializer::Some(balances) => balances
+            .iter()
+            .map(|balance| {
+                let amount = balance
+                    .ui_token_amount
+                    .amount
+                    .parse()
+                    .map_err(|_| ReplayError::InvalidTokenBalance(*signature))?;
+                Ok((balance.account_index as usize, amount))
+            })
+            .collect::<Result<_, ReplayError>>()?,
+        _ => vec![],
+    };
+    let logs: Option<Vec<String>> = meta.log_messages.into();
+
+    Ok(RecordedTransaction {
+        signature: *signature,
+        slot: confirmed.slot,
+        block_time: confirmed.block_time,
+        transaction,
+        account_keys,
+        pre_balances: meta.pre_balances,
+        pre_token_amounts,
+        err: meta.err,
+        logs: logs.unwrap_or_default(),
+    })
+}
+
+// what the accounts looked like right before the transaction, as far as RPC can tell.
+// A node only serves the current state, so each account is fetched now and then
+// rewound with what the transaction's meta recorded: lamports and token amounts.
+// Any other data is today's, `stale` lists the accounts a later transaction wrote,
+// whose data may not be what the recorded transaction saw
+#[derive(Debug, Clone, Default)]
+pub struct PreState {
+    // None for an account that didn't exist yet
+    pub accounts: Vec<(Pubkey, Option<Account>)>,
+    pub stale: Vec<Pubkey>,
+}
+
+pub fn fetch_pre_state(
+    rpc: &RpcClient,
+    recorded: &RecordedTransaction,
+) -> Result<PreState, ReplayError> {
+    let mut addresses = recorded.account_keys.clone();
+    // v0 transactions resolve their lookup tables in LiteSVM too
+    if let Some(lookups) = recorded.transaction.message.address_table_lookups() {
+        addresses.extend(lookups.iter().map(|lookup| lookup.account_key));
+    }
+    let mut accounts = fetch_accounts(rpc, &addresses)?;
+
+    // an upgradeable program's code lives in its programdata account, which
+    // LiteSVM needs before the program account itself
+    let programdata: Vec<Pubkey> = accounts
+        .iter()
+        .filter_map(|account| account.as_ref().and_then(programdata_address))
+        .collect();
+    let programdata_accounts = fetch_accounts(rpc, &programdata)?;
+
+    let mut pre_state = PreState::default();
+    for (address, account) in programdata.into_iter().zip(programdata_accounts) {
+        pre_state.accounts.push((address, account));
+    }
+
+    for (index, (address, account)) in addresses.iter().zip(accounts.iter_mut()).enumerate() {
+        let Some(pre_balance) = recorded.pre_balances.get(index).copied() else {
+            // a lookup table account, not part of the transaction's keys
+            pre_state.accounts.push((*address, account.take()));
+            continue;
+        };
+        if matches!(account, Some(account) if account.executable || account.owner == sysvar::ID) {
+            pre_state.accounts.push((*address, account.take()));
+            continue;
+        }
+        if pre_balance == 0 {
+            // created by this transaction or a later one
+            pre_state.accounts.push((*address, None));
+            continue;
+        }
+
+        if changed_since(rpc, address, &recorded.signature)? {
+            pre_state.stale.push(*address);
+        }
+        // closed since, only the lamports can be rebuilt
+        let mut rewound = account.take().unwrap_or_default();
+        rewound.lamports = pre_balance;
+        if let Some((_, amount)) = recorded
+            .pre_token_amounts
+            .iter()
+            .find(|(token_index, _)| *token_index == index)
+        {
+            rewind_token_amount(&mut rewound.data, *amount);
+        }
+        pre_state.accounts.push((*address, Some(rewound)));
+    }
+    Ok(pre_state)
+}
+
+pub fn rewind_token_amount(data: &mut [u8], amount: u64) {
+    if let Some(bytes) = data.get_mut(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8) {
+        bytes.copy_from_slice(&amount.to_le_bytes());
+    }
+}
+
+// UpgradeableLoaderState::Program: a u32 tag of 2, then the programdata address
+pub fn programdata_address(account: &Account) -> Option<Pubkey> {
+    if account.owner != bpf_loader_upgradeable::ID || !account.executable {
+        return None;
+    }
+    match account.data.get(..36) {
+        Some([2, 0, 0, 0, address @ ..]) => Pubkey::try_from(address).ok(),
+        _ => None,
+    }
+}
+
+fn fetch_accounts(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<Account>>, ReplayError> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        accounts.extend(rpc.get_multiple_accounts(chunk)?);
+    }
+    Ok(accounts)
+}
+
+// signatures come newest first and stop before `until`, so any result is a later write
+// (or a later read, RPC doesn't tell them apart)
+fn changed_since(
+    rpc: &RpcClient,
+    address: &Pubkey,
+    signature: &Signature,
+) -> Result<bool, ReplayError> {
+    let later = rpc.get_signatures_for_address_with_config(
+        address,
+        GetConfirmedSignaturesForAddress2Config {
+            until: Some(*signature),
+            limit: Some(1),
+            ..Default::default()
+        },
+    )?;
+    Ok(!later.is_empty())
+}
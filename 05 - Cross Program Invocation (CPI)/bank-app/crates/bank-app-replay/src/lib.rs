@@ -0,0 +1,9 @@
+// dev tool: fetch transactions that hit the bank on a cluster, rebuild the
+// accounts they saw and run them again in LiteSVM, against a local build
+pub mod error;
+pub mod fetch;
+pub mod replay;
+
+pub use error::ReplayError;
+pub use fetch::{fetch_pre_state, fetch_transaction, PreState, RecordedTransaction};
+pub use replay::{first_log_mismatch, ReplayReport, Replayer};
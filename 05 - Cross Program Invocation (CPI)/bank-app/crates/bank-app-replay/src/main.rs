@@ -0,0 +1,85 @@
+// replays devnet transactions in LiteSVM, oldest first:
+//   cargo run -p bank-app-replay -- <signature>...
+//   cargo run -p bank-app-replay -- --program target/deploy/bank_app.so <signature>...
+//   cargo run -p bank-app-replay -- --url http://127.0.0.1:8899 <signature>...
+use std::{path::PathBuf, process::ExitCode, str::FromStr};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signature::Signature},
+};
+use bank_app_replay::{fetch_pre_state, fetch_transaction, ReplayError, Replayer};
+
+const DEVNET_URL: &str = "https://api.devnet.solana.com";
+
+fn main() -> ExitCode {
+    let mut url = DEVNET_URL.to_string();
+    let mut program = None;
+    let mut signatures = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--url" => url = args.next().expect("--url needs a value"),
+            "--program" => program = args.next().map(PathBuf::from),
+            signature => match Signature::from_str(signature) {
+                Ok(signature) => signatures.push(signature),
+                Err(_) => {
+                    eprintln!("{signature} is not a transaction signature");
+                    return ExitCode::FAILURE;
+                }
+            },
+        }
+    }
+    if signatures.is_empty() {
+        eprintln!("usage: bank-replay [--url <rpc>] [--program <bank_app.so>] <signature>...");
+        return ExitCode::FAILURE;
+    }
+
+    match run(&url, program, &signatures) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(url: &str, program: Option<PathBuf>, signatures: &[Signature]) -> Result<bool, ReplayError> {
+    let rpc = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let mut replayer = Replayer::new();
+    if let Some(path) = program {
+        replayer = replayer.with_program(bank_app_types::ID, &path)?;
+    }
+
+    let mut recorded = signatures
+        .iter()
+        .map(|signature| fetch_transaction(&rpc, signature))
+        .collect::<Result<Vec<_>, _>>()?;
+    // each replay sees what the ones before it wrote
+    recorded.sort_by_key(|transaction| transaction.slot);
+
+    let mut all_reproduced = true;
+    for transaction in &recorded {
+        let pre_state = fetch_pre_state(&rpc, transaction)?;
+        let report = replayer.replay(transaction, &pre_state)?;
+
+        println!(
+            "{} (slot {}): recorded {:?}, replayed {:?}",
+            report.signature, transaction.slot, report.recorded_err, report.replayed_err
+        );
+        if let Some(line) = report.log_mismatch {
+            println!(
+                "  logs differ from line {}: {:?}",
+                line,
+                report.replayed_logs.get(line)
+            );
+        }
+        for address in &report.stale {
+            println!("  {address} was written after this transaction, its data is today's");
+        }
+        all_reproduced &= report.reproduced();
+    }
+    Ok(all_reproduced)
+}
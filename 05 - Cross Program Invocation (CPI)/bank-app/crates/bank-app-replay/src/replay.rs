@@ -0,0 +1,131 @@
+use std::{collections::HashSet, path::Path};
+
+use anchor_client::solana_sdk::{
+    clock::Clock, pubkey::Pubkey, signature::Signature, transaction::TransactionError,
+};
+use litesvm::LiteSVM;
+
+use crate::{
+    error::ReplayError,
+    fetch::{PreState, RecordedTransaction},
+};
+
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub signature: Signature,
+    pub recorded_err: Option<TransactionError>,
+    pub replayed_err: Option<TransactionError>,
+    pub replayed_logs: Vec<String>,
+    // first line where the replayed logs leave the recorded ones. A local build
+    // that spends other compute units differs already at the "consumed" line
+    pub log_mismatch: Option<usize>,
+    // accounts whose data came from after the transaction, see `PreState`
+    pub stale: Vec<Pubkey>,
+}
+
+impl ReplayReport {
+    // the same outcome, down to the instruction index and the error code
+    pub fn reproduced(&self) -> bool {
+        self.recorded_err == self.replayed_err
+    }
+}
+
+pub struct Replayer {
+    svm: LiteSVM,
+    // programs loaded from a local .so, the cluster's copy must not replace them
+    local_programs: Vec<Pubkey>,
+    // accounts an earlier replay already wrote, later transactions see those
+    // results instead of the rewound RPC state
+    replayed: HashSet<Pubkey>,
+}
+
+impl Default for Replayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Replayer {
+    pub fn new() -> Self {
+        // the recorded blockhash is long expired, the signatures are still checked
+        Self {
+            svm: LiteSVM::new().with_blockhash_check(false),
+            local_programs: vec![],
+            replayed: HashSet::new(),
+        }
+    }
+
+    // replays against this build instead of the deployed program, e.g. one with a fix
+    pub fn with_program(mut self, program_id: Pubkey, path: &Path) -> Result<Self, ReplayError> {
+        self.svm
+            .add_program_from_file(program_id, path)
+            .map_err(|_| ReplayError::Program(path.to_path_buf()))?;
+        self.local_programs.push(program_id);
+        Ok(self)
+    }
+
+    pub fn svm(&self) -> &LiteSVM {
+        &self.svm
+    }
+
+    pub fn replay(
+        &mut self,
+        recorded: &RecordedTransaction,
+        pre_state: &PreState,
+    ) -> Result<ReplayReport, ReplayError> {
+        self.load(pre_state)?;
+
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.slot = recorded.slot;
+        if let Some(block_time) = recorded.block_time {
+            clock.unix_timestamp = block_time;
+        }
+        self.svm.set_sysvar(&clock);
+
+        let (replayed_err, replayed_logs) =
+            match self.svm.send_transaction(recorded.transaction.clone()) {
+                Ok(meta) => (None, meta.logs),
+                Err(failed) => (Some(failed.err), failed.meta.logs),
+            };
+        self.replayed.extend(recorded.account_keys.iter().copied());
+
+        Ok(ReplayReport {
+            signature: recorded.signature,
+            recorded_err: recorded.err.clone(),
+            replayed_err,
+            log_mismatch: first_log_mismatch(&recorded.logs, &replayed_logs),
+            replayed_logs,
+            stale: pre_state.stale.clone(),
+        })
+    }
+
+    fn load(&mut self, pre_state: &PreState) -> Result<(), ReplayError> {
+        for (address, account) in &pre_state.accounts {
+            if self.local_programs.contains(address) || self.replayed.contains(address) {
+                continue;
+            }
+            // builtins and the SPL programs LiteSVM ships with stay as they are
+            if matches!(self.svm.get_account(address), Some(existing) if existing.executable) {
+                continue;
+            }
+            // a zero-lamport account is the same as a missing one to the runtime
+            let account = account.clone().unwrap_or_default();
+            self.svm
+                .set_account(*address, account)
+                .map_err(|_| ReplayError::RejectedAccount(address.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn first_log_mismatch(recorded: &[String], replayed: &[String]) -> Option<usize> {
+    let mismatch = recorded
+        .iter()
+        .zip(replayed)
+        .position(|(recorded, replayed)| recorded != replayed);
+    match mismatch {
+        Some(index) => Some(index),
+        None if recorded.len() != replayed.len() => Some(recorded.len().min(replayed.len())),
+        None => None,
+    }
+}
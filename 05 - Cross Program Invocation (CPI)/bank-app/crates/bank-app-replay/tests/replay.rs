@@ -0,0 +1,59 @@
+use anchor_client::solana_sdk::{account::Account, bpf_loader_upgradeable, pubkey::Pubkey};
+use bank_app_replay::{
+    fetch::{programdata_address, rewind_token_amount},
+    first_log_mismatch,
+};
+
+fn logs(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn identical_logs_have_no_mismatch() {
+    let recorded = logs(&["Program log: Instruction: Deposit", "Program success"]);
+    assert_eq!(first_log_mismatch(&recorded, &recorded.clone()), None);
+}
+
+#[test]
+fn the_first_differing_line_is_reported() {
+    let recorded = logs(&["Program log: Instruction: Deposit", "consumed 9000", "ok"]);
+    let replayed = logs(&["Program log: Instruction: Deposit", "consumed 8700", "ok"]);
+    assert_eq!(first_log_mismatch(&recorded, &replayed), Some(1));
+}
+
+#[test]
+fn a_shorter_replay_mismatches_where_it_stops() {
+    let recorded = logs(&["Program log: Instruction: Deposit", "Program success"]);
+    let replayed = logs(&["Program log: Instruction: Deposit"]);
+    assert_eq!(first_log_mismatch(&recorded, &replayed), Some(1));
+}
+
+#[test]
+fn only_the_token_amount_is_rewound() {
+    let mut data = vec![7u8; 165];
+    rewind_token_amount(&mut data, 1_000);
+
+    assert_eq!(&data[64..72], &1_000u64.to_le_bytes());
+    assert!(data[..64].iter().chain(&data[72..]).all(|byte| *byte == 7));
+}
+
+#[test]
+fn programdata_is_read_from_an_upgradeable_program() {
+    let programdata = Pubkey::new_unique();
+    let mut data = vec![2, 0, 0, 0];
+    data.extend_from_slice(programdata.as_ref());
+    let program = Account {
+        lamports: 1,
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        executable: true,
+        rent_epoch: 0,
+    };
+    assert_eq!(programdata_address(&program), Some(programdata));
+
+    let wallet = Account {
+        owner: Pubkey::default(),
+        ..program
+    };
+    assert_eq!(programdata_address(&wallet), None);
+}
@@ -0,0 +1,8 @@
+// helpers for Rust tests that run the bank in LiteSVM, dev-dependency only
+pub mod fixtures;
+pub mod snapshot;
+pub mod test_ids;
+
+pub use fixtures::{load_fixture, load_fixtures, Fixture, COMMITTED_FIXTURES, FIXTURES};
+pub use snapshot::StateSnapshot;
+pub use test_ids::{test_keypair, TestIds};
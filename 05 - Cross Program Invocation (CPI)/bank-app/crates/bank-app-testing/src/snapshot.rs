@@ -0,0 +1,51 @@
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_clock::Clock;
+use solana_pubkey::Pubkey;
+
+// LiteSVM can't list accounts by owner, so the harness says which addresses it
+// cares about. Addresses that didn't exist at capture time are remembered too,
+// restoring then wipes whatever a test created there
+pub struct StateSnapshot {
+    accounts: Vec<(Pubkey, Option<Account>)>,
+    clock: Clock,
+}
+
+impl StateSnapshot {
+    // keeps the watched accounts owned by one of `owners`, plus the empty ones
+    pub fn capture(svm: &LiteSVM, owners: &[Pubkey], watched: &[Pubkey]) -> Self {
+        let accounts = watched
+            .iter()
+            .filter_map(|address| match svm.get_account(address) {
+                Some(account) if owners.contains(&account.owner) => Some((*address, Some(account))),
+                Some(_) => None,
+                None => Some((*address, None)),
+            })
+            .collect();
+
+        Self {
+            accounts,
+            clock: svm.get_sysvar::<Clock>(),
+        }
+    }
+
+    pub fn restore(&self, svm: &mut LiteSVM) {
+        for (address, account) in &self.accounts {
+            // a zero-lamport account is the same as a missing one to the runtime
+            let account = account.clone().unwrap_or_default();
+            svm.set_account(*address, account)
+                .expect("LiteSVM rejected a captured account");
+        }
+        svm.set_sysvar(&self.clock);
+        // the same transaction signed in two test cases would otherwise be AlreadyProcessed
+        svm.expire_blockhash();
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
@@ -0,0 +1,77 @@
+use bank_app_types::BankPda;
+use sha2::{Digest, Sha256};
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+// the same derivation as app/test-ids.ts: the ed25519 secret of a named identity
+// is sha256("<seed>:<name>"), so a seed string reproduces every key and PDA
+// in the Rust tests, the TS tests and the CLI's test mode
+pub const DEFAULT_TEST_SEED: &str = "bank-app";
+
+pub const TEST_IDENTITIES: [&str; 4] = ["admin", "alice", "bob", "attacker"];
+
+pub fn test_keypair(seed: &str, name: &str) -> Keypair {
+    let secret: [u8; 32] = Sha256::digest(format!("{}:{}", seed, name)).into();
+    Keypair::new_from_array(secret)
+}
+
+pub struct TestIds {
+    pub admin: Keypair,
+    pub alice: Keypair,
+    pub bob: Keypair,
+    pub attacker: Keypair,
+}
+
+impl TestIds {
+    pub fn new(seed: &str) -> Self {
+        Self {
+            admin: test_keypair(seed, "admin"),
+            alice: test_keypair(seed, "alice"),
+            bob: test_keypair(seed, "bob"),
+            attacker: test_keypair(seed, "attacker"),
+        }
+    }
+
+    pub fn users(&self) -> [&Keypair; 3] {
+        [&self.alice, &self.bob, &self.attacker]
+    }
+
+    // the per-user PDAs a test usually touches, without indexed ones (records, snapshots)
+    pub fn user_pdas(user: &Pubkey) -> Vec<Pubkey> {
+        [
+            BankPda::UserReserve { user: *user },
+            BankPda::DepositCounter { user: *user },
+            BankPda::LuckyTicket { user: *user },
+            BankPda::SlothashTicket { user: *user },
+            BankPda::MilestoneBadges { user: *user },
+        ]
+        .iter()
+        .map(|pda| pda.find_address().0)
+        .collect()
+    }
+
+    // every singleton and per-user PDA, ready for StateSnapshot::capture
+    pub fn watched(&self) -> Vec<Pubkey> {
+        let mut watched: Vec<Pubkey> = [
+            BankPda::BankInfo,
+            BankPda::BankVault,
+            BankPda::BadgeConfig,
+            BankPda::WormholeEmitter,
+        ]
+        .iter()
+        .map(|pda| pda.find_address().0)
+        .collect();
+
+        for user in self.users() {
+            watched.extend(Self::user_pdas(&user.pubkey()));
+        }
+        watched
+    }
+}
+
+impl Default for TestIds {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEST_SEED)
+    }
+}
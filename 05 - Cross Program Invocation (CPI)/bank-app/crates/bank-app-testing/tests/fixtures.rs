@@ -0,0 +1,78 @@
+use bank_app_testing::fixtures::{
+    load_fixture, LOCAL_STAKE_POOL, PYTH_SOL_USD, STAKE_POOL, SWITCHBOARD_SOL_USD, USDC_MINT,
+};
+use litesvm::LiteSVM;
+use solana_pubkey::{pubkey, Pubkey};
+
+const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const SWITCHBOARD_PROGRAM: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+const STAKE_POOL_PROGRAM: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+// sha256("account:PullFeedAccountData")[..8]
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+// PullFeedAccountData.result, after the discriminator
+const FEED_RESULT: usize = 8 + 2256;
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[test]
+fn loads_the_switchboard_feed() {
+    let mut svm = LiteSVM::new();
+    let feed = load_fixture(&mut svm, &SWITCHBOARD_SOL_USD).unwrap();
+
+    assert_eq!(feed.owner, SWITCHBOARD_PROGRAM);
+    assert_eq!(feed.data.len(), 8 + 3200);
+    assert_eq!(feed.data[..8], PULL_FEED_DISCRIMINATOR);
+
+    // result.value, 150 with 18 decimals, and result.slot
+    let value = i128::from_le_bytes(feed.data[FEED_RESULT..FEED_RESULT + 16].try_into().unwrap());
+    assert_eq!(value, 150 * 10i128.pow(18));
+    assert_eq!(u64_at(&feed.data, FEED_RESULT + 104), 1000);
+
+    let loaded = svm.get_account(&SWITCHBOARD_SOL_USD.address).unwrap();
+    assert_eq!(loaded.data, feed.data);
+}
+
+#[test]
+fn loads_the_stake_pool() {
+    let mut svm = LiteSVM::new();
+    let pool = load_fixture(&mut svm, &LOCAL_STAKE_POOL).unwrap();
+
+    assert_eq!(pool.owner, STAKE_POOL_PROGRAM);
+    assert_eq!(pool.data[0], 1); // AccountType::StakePool
+    assert_eq!(pool.data[226..258], TOKEN_PROGRAM.to_bytes());
+
+    // the bump StakePoolInfo::withdraw_authority signs with has to be the pool's
+    let (_, bump) = Pubkey::find_program_address(
+        &[LOCAL_STAKE_POOL.address.as_ref(), b"withdraw"],
+        &STAKE_POOL_PROGRAM,
+    );
+    assert_eq!(pool.data[97], bump);
+
+    // total_lamports and pool_token_supply, the rate invest_stake_pool mints at
+    assert_eq!(u64_at(&pool.data, 258), 1_050_000_000_000);
+    assert_eq!(u64_at(&pool.data, 266), 1_000_000_000_000);
+
+    assert!(svm.get_account(&LOCAL_STAKE_POOL.address).is_some());
+}
+
+// the mainnet dumps aren't committed until someone runs scripts/dump-fixtures.sh
+// with network access
+#[test]
+#[ignore = "needs the mainnet dumps, run scripts/dump-fixtures.sh first"]
+fn loads_mainnet_fixtures() {
+    let mut svm = LiteSVM::new();
+
+    let usdc = load_fixture(&mut svm, &USDC_MINT).unwrap();
+    assert_eq!(usdc.owner, TOKEN_PROGRAM);
+    assert_eq!(usdc.data.len(), 82);
+    assert_eq!(usdc.data[44], 6); // decimals
+
+    let loaded = svm.get_account(&USDC_MINT.address).unwrap();
+    assert_eq!(loaded.data, usdc.data);
+
+    load_fixture(&mut svm, &PYTH_SOL_USD).unwrap();
+    load_fixture(&mut svm, &STAKE_POOL).unwrap();
+}
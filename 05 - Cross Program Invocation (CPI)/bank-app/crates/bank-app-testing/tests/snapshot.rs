@@ -0,0 +1,47 @@
+use bank_app_testing::StateSnapshot;
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_clock::Clock;
+use solana_pubkey::Pubkey;
+
+fn account(owner: Pubkey, data: &[u8]) -> Account {
+    Account {
+        lamports: 1_000_000,
+        data: data.to_vec(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn restores_watched_accounts_and_clock() {
+    let mut svm = LiteSVM::new();
+    let program = Pubkey::new_unique();
+    let existing = Pubkey::new_unique();
+    let created_later = Pubkey::new_unique();
+
+    svm.set_account(existing, account(program, &[1, 2, 3]))
+        .unwrap();
+    let snapshot = StateSnapshot::capture(&svm, &[program], &[existing, created_later]);
+    assert_eq!(snapshot.len(), 2);
+
+    svm.set_account(existing, account(program, &[9, 9, 9]))
+        .unwrap();
+    svm.set_account(created_later, account(program, &[4]))
+        .unwrap();
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += 3600;
+    svm.set_sysvar(&clock);
+
+    snapshot.restore(&mut svm);
+
+    assert_eq!(svm.get_account(&existing).unwrap().data, vec![1, 2, 3]);
+    assert!(svm
+        .get_account(&created_later)
+        .is_none_or(|account| account.lamports == 0));
+    assert_eq!(
+        svm.get_sysvar::<Clock>().unix_timestamp,
+        clock.unix_timestamp - 3600
+    );
+}
@@ -0,0 +1,22 @@
+use bank_app_testing::{test_keypair, TestIds};
+use solana_pubkey::pubkey;
+use solana_signer::Signer;
+
+#[test]
+fn identities_are_deterministic_and_distinct() {
+    let ids = TestIds::new("bank-app");
+    // app/test-ids.ts checks the same key
+    assert_eq!(
+        ids.alice.pubkey(),
+        pubkey!("8uTA6ta8AnahVS69ea5o3YjbcyuVYqc1GRarDutmoxs3")
+    );
+    assert_eq!(
+        ids.alice.pubkey(),
+        test_keypair("bank-app", "alice").pubkey()
+    );
+    assert_ne!(ids.alice.pubkey(), ids.bob.pubkey());
+    assert_ne!(
+        ids.alice.pubkey(),
+        TestIds::new("other-seed").alice.pubkey()
+    );
+}
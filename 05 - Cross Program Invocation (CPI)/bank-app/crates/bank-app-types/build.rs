@@ -0,0 +1,72 @@
+use std::{env, fs, path::PathBuf};
+
+// turns the #[constant] and error entries of the program's IDL into Rust for
+// src/constants.rs and src/errors.rs, so seeds, limits and error codes can't drift
+// from the program. Reads target/idl/bank_app.json after an `anchor build`, and the
+// checked-in idl/bank_app.json before one. `anchor build` compiles this crate before
+// it has written any IDL, so the snapshot is what lets a fresh clone build at all.
+// BANK_APP_IDL points at another IDL file
+fn main() {
+    println!("cargo:rerun-if-env-changed=BANK_APP_IDL");
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let snapshot_path = manifest_dir.join("idl/bank_app.json");
+    println!("cargo:rerun-if-changed={}", snapshot_path.display());
+
+    let idl_path = match env::var("BANK_APP_IDL") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let built = manifest_dir.join("../../target/idl/bank_app.json");
+            // rerun once `anchor build` writes it
+            println!("cargo:rerun-if-changed={}", built.display());
+            if built.exists() {
+                built
+            } else {
+                println!(
+                    "cargo:warning=no {}, using the snapshot in idl/bank_app.json. \
+                     Run scripts/snapshot-idl.sh after `anchor build` to refresh it",
+                    built.display()
+                );
+                snapshot_path
+            }
+        }
+    };
+
+    let idl = fs::read_to_string(&idl_path).unwrap_or_else(|err| {
+        panic!(
+            "can't read the bank-app IDL at {} ({err})",
+            idl_path.display()
+        )
+    });
+    let idl: serde_json::Value = serde_json::from_str(&idl).expect("the IDL is not valid JSON");
+
+    let mut out = String::new();
+    for constant in idl["constants"].as_array().into_iter().flatten() {
+        let name = constant["name"].as_str().expect("constant without a name");
+        let value = constant["value"]
+            .as_str()
+            .expect("constant without a value");
+        let line = match constant["type"].as_str() {
+            // byte strings are written as a list, "[66, 65, ...]"
+            Some("bytes") => format!("pub const {name}: &[u8] = &{value};\n"),
+            Some(ty @ ("u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64")) => {
+                format!("pub const {name}: {ty} = {value};\n")
+            }
+            // pubkeys, arrays and strings aren't needed off-chain yet
+            _ => continue,
+        };
+        out.push_str(&line);
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("constants.rs"), out).expect("can't write the generated constants");
+
+    let mut out = String::from("bank_app_errors! {\n");
+    for error in idl["errors"].as_array().into_iter().flatten() {
+        let name = error["name"].as_str().expect("error without a name");
+        let code = error["code"].as_u64().expect("error without a code");
+        let message = error["msg"].as_str().unwrap_or(name);
+        out.push_str(&format!("    {name} = {code} => {message:?},\n"));
+    }
+    out.push_str("}\n");
+    fs::write(out_dir.join("errors.rs"), out).expect("can't write the generated errors");
+}
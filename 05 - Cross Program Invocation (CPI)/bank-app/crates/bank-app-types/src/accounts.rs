@@ -0,0 +1,525 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_pubkey::Pubkey;
+
+use crate::error::DecodeError;
+
+// an account as Anchor stores it: 8 byte discriminator, then the Borsh fields.
+// DISCRIMINATOR is sha256("account:<Name>")[..8], the same as #[account]
+pub trait BankAccount: BorshDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        if data.len() < 8 {
+            return Err(DecodeError::AccountTooShort);
+        }
+        if data[..8] != Self::DISCRIMINATOR {
+            return Err(DecodeError::DiscriminatorMismatch);
+        }
+        // like Anchor, trailing bytes (unused space) are ignored
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+macro_rules! bank_account {
+    ($name:ident, $discriminator:expr) => {
+        impl BankAccount for $name {
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        }
+    };
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct BankInfo {
+    pub authority: Pubkey,
+    pub is_paused: bool,
+    pub bump: u8,
+    pub total_deposited: u64,
+    pub rent_destination: Pubkey,
+    pub token_configs: Vec<TokenConfig>,
+    pub guardian: Pubkey,
+    pub disabled_instructions: u64,
+    pub withdrawal_tiers: [WithdrawalTier; 3],
+    pub interest_rate_bps: u16,
+    pub stake_pool_principal: u64,
+    pub interest_clock: InterestClock,
+    pub last_harvest_slot: u64,
+    pub withdraw_cooldown: i64,
+    pub total_balance_seconds: BalanceAccumulator,
+    pub rent_floor: u64,
+    pub version: u32,
+    pub features: u64,
+}
+bank_account!(BankInfo, [36, 165, 248, 219, 203, 37, 18, 246]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawalTier {
+    pub max_age: i64,
+    pub daily_limit: u64,
+}
+
+// like bank_app::state::BalanceAccumulator, `at` brings a reading up to date
+// offchain the way the next instruction would onchain
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BalanceAccumulator {
+    pub balance_seconds: u128,
+    pub updated_at: i64,
+}
+
+impl BalanceAccumulator {
+    // None where the program would fail with BalanceSecondsOverflow
+    pub fn at(&self, balance: u64, now: i64) -> Option<BalanceAccumulator> {
+        if self.updated_at == 0 {
+            return Some(BalanceAccumulator {
+                balance_seconds: self.balance_seconds,
+                updated_at: now,
+            });
+        }
+
+        let elapsed = (now - self.updated_at).max(0) as u128;
+        let balance_seconds = (balance as u128)
+            .checked_mul(elapsed)
+            .and_then(|added| self.balance_seconds.checked_add(added))?;
+        Some(BalanceAccumulator {
+            balance_seconds,
+            updated_at: now.max(self.updated_at),
+        })
+    }
+
+    pub fn average_since(&self, earlier: &BalanceAccumulator) -> Option<u64> {
+        let seconds = self.updated_at.checked_sub(earlier.updated_at)?;
+        if seconds <= 0 {
+            return None;
+        }
+        let balance_seconds = self.balance_seconds.checked_sub(earlier.balance_seconds)?;
+        u64::try_from(balance_seconds / seconds as u128).ok()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterestClock {
+    #[default]
+    Timestamp,
+    Slot,
+}
+
+// same order as bank_app::state::BankInstruction, the bit is the position
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BankInstruction {
+    Deposit,
+    DepositToken,
+    DepositAndNotify,
+    DepositAnyToken,
+    DepositTagged,
+    Withdraw,
+    QueueWithdrawal,
+    Invest,
+    ProcessNextOp,
+    ExecuteRecurring,
+    ReceiveCrossChainDeposit,
+    ClaimSnapshotReward,
+    LuckyDrawSlothash,
+    CommitLuckyDraw,
+    SettleLuckyDraw,
+    MoveToGoal,
+    MoveFromGoal,
+    DepositJoint,
+    WithdrawJoint,
+    ClaimInheritance,
+    Execute,
+    DepositToken2022,
+    WithdrawWithPermit,
+    RequestWithdraw,
+    ExecuteWithdraw,
+    WithdrawToEscrow,
+    InvestStakePool,
+    DivestStakePool,
+    Harvest,
+    AccrueInterest,
+    ClaimMilestoneBadge,
+    FundInsurance,
+    SetupRecurring,
+    SyncReceiptRate,
+}
+
+impl BankInfo {
+    // what a UI checks before offering an action, paused or switched off fails onchain
+    pub fn is_enabled(&self, instruction: BankInstruction) -> bool {
+        !self.is_paused && self.disabled_instructions & (1 << instruction as u8) == 0
+    }
+
+    // the first used tier the reserve is still younger than, None is no daily limit
+    pub fn daily_withdrawal_limit(&self, reserve_age: i64) -> Option<u64> {
+        self.withdrawal_tiers
+            .iter()
+            .find(|tier| tier.max_age != 0 && reserve_age < tier.max_age)
+            .map(|tier| tier.daily_limit)
+    }
+
+    // the most a withdrawal can take from a vault holding `vault_lamports`
+    pub fn vault_liquidity(&self, vault_lamports: u64) -> u64 {
+        vault_lamports.saturating_sub(self.rent_floor)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TokenConfig {
+    pub mint: Pubkey,
+    pub total_deposited: u64,
+    pub interest_rate_bps: u16,
+    pub deposit_cap: u64,
+    pub is_paused: bool,
+    pub price_feed: Pubkey,
+    pub max_staleness_slots: u64,
+    pub decimals: u8,
+    pub deposit_cap_usd: u64,
+    pub borrow_ltv_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct UserReserve {
+    pub deposited_amount: u64,
+    pub version: u8,
+    pub owner: Pubkey,
+    pub created_at: i64,
+    pub deposit_timestamp: i64,
+    pub accrued_interest: u64,
+    pub accrual_slot: u64,
+    pub last_crank_slot: u64,
+    pub pending_withdrawal: u64,
+    pub unlock_at: i64,
+    pub balance_seconds: BalanceAccumulator,
+    pub last_activity_ts: i64,
+}
+
+impl UserReserve {
+    pub const V1_LEN: usize = 8 + 8;
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 8 + 8;
+}
+
+// v1 reserves (chapters 03/04) are only `deposited_amount`, the v2 to v7
+// fields come back zeroed (version 0) like UserReserve::load_versioned on-chain
+impl BankAccount for UserReserve {
+    const DISCRIMINATOR: [u8; 8] = [225, 22, 86, 68, 246, 169, 42, 23];
+
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        if data.len() < Self::V1_LEN {
+            return Err(DecodeError::AccountTooShort);
+        }
+        if data[..8] != Self::DISCRIMINATOR {
+            return Err(DecodeError::DiscriminatorMismatch);
+        }
+
+        let mut padded = data[8..].to_vec();
+        padded.resize(padded.len().max(Self::LEN - 8), 0);
+        Ok(Self::deserialize(&mut &padded[..])?)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub epoch: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub total_deposited: u64,
+    pub vault_balance: u64,
+    pub rewards_root: [u8; 32],
+    pub total_rewards: u64,
+    pub claimed_rewards: u64,
+}
+bank_account!(Snapshot, [137, 213, 28, 133, 224, 161, 48, 108]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct LuckyTicket {
+    pub next_draw_epoch: u64,
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+}
+bank_account!(LuckyTicket, [201, 118, 136, 27, 149, 45, 59, 216]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ForeignEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+bank_account!(ForeignEmitter, [209, 139, 241, 247, 96, 178, 159, 2]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReceivedVaa {
+    pub sequence: u64,
+}
+bank_account!(ReceivedVaa, [33, 131, 167, 101, 123, 236, 58, 68]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct DepositCounter {
+    pub count: u64,
+}
+bank_account!(DepositCounter, [100, 252, 57, 155, 242, 133, 130, 162]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct DepositRecord {
+    pub user: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub tag_hash: [u8; 32],
+    pub created_at: i64,
+}
+bank_account!(DepositRecord, [83, 232, 10, 31, 251, 49, 189, 167]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct OpRecord {
+    pub user: Pubkey,
+    pub op_id: [u8; 16],
+    pub amount: u64,
+    pub created_at: i64,
+}
+bank_account!(OpRecord, [154, 131, 132, 204, 10, 24, 173, 178]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Goal {
+    pub owner: Pubkey,
+    pub index: u8,
+    pub name: String,
+    pub target_amount: u64,
+    pub amount: u64,
+    pub lock_until: Option<i64>,
+}
+bank_account!(Goal, [163, 66, 166, 245, 130, 131, 207, 26]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct RecurringDeposit {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub next_due_at: i64,
+    pub bump: u8,
+}
+bank_account!(RecurringDeposit, [157, 37, 38, 28, 64, 1, 172, 33]);
+
+// the SOL balance is the account's lamports above rent, not a field
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct InsuranceFund {
+    pub total_funded: u64,
+    pub total_covered: u64,
+}
+bank_account!(InsuranceFund, [43, 134, 170, 87, 102, 16, 142, 147]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct CpiAllowlist {
+    pub programs: Vec<Pubkey>,
+}
+bank_account!(CpiAllowlist, [109, 193, 43, 176, 158, 240, 48, 79]);
+
+// the SOL left to match is `budget`, the account's lamports are that plus rent
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Campaign {
+    pub id: u64,
+    pub match_ratio_bps: u16,
+    pub budget: u64,
+    pub per_user_cap: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub total_matched: u64,
+}
+bank_account!(Campaign, [50, 40, 49, 11, 157, 220, 229, 192]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct CampaignMatch {
+    pub matched: u64,
+}
+bank_account!(CampaignMatch, [117, 19, 241, 91, 14, 173, 3, 7]);
+
+// `withdrawn` only counts for `day` (unix time / WITHDRAWAL_DAY), it's 0 on any other day
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct WithdrawalWindow {
+    pub day: i64,
+    pub withdrawn: u64,
+}
+bank_account!(WithdrawalWindow, [15, 162, 80, 215, 210, 205, 94, 9]);
+
+// not an account: the return data of a simulated verify_invariants
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct InvariantReport {
+    pub total_deposited: u64,
+    pub vault_balance: u64,
+    pub staked_amount: u64,
+    pub shortfall: u64,
+    pub stake_pool_principal: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Inheritance {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inactivity_period: i64,
+    pub last_activity_ts: i64,
+}
+bank_account!(Inheritance, [117, 83, 174, 6, 88, 161, 2, 179]);
+
+impl Inheritance {
+    // when the beneficiary can claim, unless the owner checks in, deposits or
+    // withdraws first. `reserve` is the owner's SOL reserve
+    pub fn claimable_at(&self, reserve: &UserReserve) -> i64 {
+        self.last_activity_ts
+            .max(reserve.last_activity_ts)
+            .saturating_add(self.inactivity_period)
+    }
+}
+
+// owner_a < owner_b, sort the two keys before deriving BankPda::JointReserve
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct JointReserve {
+    pub owner_a: Pubkey,
+    pub owner_b: Pubkey,
+    pub deposited_amount: u64,
+    pub single_signer_limit: u64,
+    pub bump: u8,
+    pub created_at: i64,
+}
+bank_account!(JointReserve, [50, 147, 65, 254, 113, 115, 158, 133]);
+
+impl Goal {
+    // same rule as the program: the lock ends at lock_until on its own
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.lock_until.is_some_and(|lock_until| now < lock_until)
+    }
+}
+
+// version is major << 16 | minor << 8 | patch, features are the FEATURE_* bits
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct VersionBeacon {
+    pub version: u32,
+    pub features: u64,
+    pub updated_slot: u64,
+}
+bank_account!(VersionBeacon, [153, 243, 92, 177, 242, 54, 158, 118]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct BadgeConfig {
+    pub merkle_tree: Pubkey,
+    pub badges_minted: u64,
+}
+bank_account!(BadgeConfig, [118, 207, 243, 185, 139, 238, 159, 60]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct MilestoneBadges {
+    pub minted: u8,
+}
+bank_account!(MilestoneBadges, [41, 51, 8, 236, 226, 155, 32, 253]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Reconciliation {
+    pub expected_reserves: u64,
+    pub processed_reserves: u64,
+    pub last_reserve: Pubkey,
+    pub tally: u64,
+    pub started_slot: u64,
+    pub is_done: bool,
+    pub is_matched: bool,
+}
+bank_account!(Reconciliation, [108, 228, 81, 90, 245, 10, 218, 5]);
+
+// bank_app::constant::OPS_QUEUE_CAPACITY, an array length isn't a #[constant]
+pub const OPS_QUEUE_CAPACITY: usize = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct QueuedOp {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub queued_at: i64,
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+// #[repr(C)] without implicit padding, so the zero-copy bytes read as Borsh
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct OpsQueue {
+    pub head: u64,
+    pub tail: u64,
+    pub ops: [QueuedOp; OPS_QUEUE_CAPACITY],
+}
+bank_account!(OpsQueue, [123, 31, 235, 229, 107, 11, 32, 243]);
+
+impl OpsQueue {
+    // queued ops in settlement order, the front one first
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedOp> {
+        (self.head..self.tail).map(|i| &self.ops[(i % OPS_QUEUE_CAPACITY as u64) as usize])
+    }
+}
+
+// bank_app::constant::USER_HISTORY_CAPACITY
+pub const USER_HISTORY_CAPACITY: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct HistoryEntry {
+    pub amount: u64,
+    pub timestamp: i64,
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+// zero-copy like OpsQueue, #[repr(C)] without implicit padding
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct UserHistory {
+    pub user: Pubkey,
+    pub count: u64,
+    pub entries: [HistoryEntry; USER_HISTORY_CAPACITY],
+}
+bank_account!(UserHistory, [179, 42, 226, 190, 44, 156, 132, 224]);
+
+impl UserHistory {
+    // the kept entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let len = self.count.min(USER_HISTORY_CAPACITY as u64);
+        (self.count - len..self.count)
+            .map(|i| &self.entries[(i % USER_HISTORY_CAPACITY as u64) as usize])
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ClaimBitmap {
+    pub snapshot: Pubkey,
+    pub bits: Vec<u8>,
+}
+bank_account!(ClaimBitmap, [61, 25, 148, 196, 164, 208, 65, 169]);
+
+impl ClaimBitmap {
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits
+            .get((index / 8) as usize)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct DenyEntry {
+    pub wallet: Pubkey,
+    pub denied_at: i64,
+}
+bank_account!(DenyEntry, [74, 49, 80, 88, 101, 251, 105, 16]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct PermitNonce {
+    pub user: Pubkey,
+    pub next_nonce: u64,
+}
+bank_account!(PermitNonce, [130, 180, 221, 197, 49, 138, 249, 89]);
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct BankLookupTable {
+    pub address: Pubkey,
+    pub bump: u8,
+}
+bank_account!(BankLookupTable, [207, 191, 183, 41, 193, 149, 85, 23]);
+
+// approved_at[action][approver] is the slot of the approval plus one, 0 is none.
+// The action index is the position in bank_app::state::ApprovalAction
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ApprovalSet {
+    pub approvers: [Pubkey; 5],
+    pub approver_count: u8,
+    pub threshold: u8,
+    pub expiry_slots: u64,
+    pub approved_at: [[u64; 5]; 4],
+}
+bank_account!(ApprovalSet, [175, 117, 177, 101, 181, 103, 151, 195]);
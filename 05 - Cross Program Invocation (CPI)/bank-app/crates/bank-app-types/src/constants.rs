@@ -0,0 +1,2 @@
+// the program's #[constant]s: seeds, limits and op kinds, generated from the IDL by build.rs
+include!(concat!(env!("OUT_DIR"), "/constants.rs"));
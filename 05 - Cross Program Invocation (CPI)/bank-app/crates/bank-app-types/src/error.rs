@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum DecodeError {
+    AccountTooShort,
+    DiscriminatorMismatch,
+    Borsh(std::io::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::AccountTooShort => write!(f, "account data is shorter than its layout"),
+            DecodeError::DiscriminatorMismatch => write!(f, "discriminator does not match"),
+            DecodeError::Borsh(err) => write!(f, "borsh: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<std::io::Error> for DecodeError {
+    fn from(err: std::io::Error) -> Self {
+        DecodeError::Borsh(err)
+    }
+}
@@ -0,0 +1,50 @@
+// bank_app::error::BankAppError with the codes Anchor gives it, generated from the
+// IDL's error list by build.rs
+pub use crate::constants::BANK_APP_ERROR_OFFSET;
+
+macro_rules! bank_app_errors {
+    ($($name:ident = $code:literal => $message:literal,)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(u32)]
+        pub enum BankAppError {
+            $($name = $code,)*
+        }
+
+        impl BankAppError {
+            pub const ALL: &'static [BankAppError] = &[$(BankAppError::$name,)*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(BankAppError::$name => stringify!($name),)*
+                }
+            }
+
+            pub fn message(self) -> &'static str {
+                match self {
+                    $(BankAppError::$name => $message,)*
+                }
+            }
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/errors.rs"));
+
+impl BankAppError {
+    pub fn from_code(code: u32) -> Option<BankAppError> {
+        BankAppError::ALL
+            .iter()
+            .find(|error| error.code() == code)
+            .copied()
+    }
+
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+}
+
+impl std::fmt::Display for BankAppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.name(), self.code(), self.message())
+    }
+}
@@ -0,0 +1,186 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+use crate::error::DecodeError;
+
+// an event as `emit!` logs it: "Program data: " + base64(discriminator || borsh).
+// DISCRIMINATOR is sha256("event:<Name>")[..8], the same as #[event]
+pub trait BankEvent: BorshDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        if data.len() < 8 {
+            return Err(DecodeError::AccountTooShort);
+        }
+        if data[..8] != Self::DISCRIMINATOR {
+            return Err(DecodeError::DiscriminatorMismatch);
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct InsuranceFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+impl BankEvent for InsuranceFunded {
+    const DISCRIMINATOR: [u8; 8] = [158, 72, 23, 80, 138, 56, 33, 206];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ShortfallCovered {
+    pub shortfall: u64,
+    pub covered: u64,
+    pub remaining_shortfall: u64,
+    pub total_covered: u64,
+}
+
+impl BankEvent for ShortfallCovered {
+    const DISCRIMINATOR: [u8; 8] = [161, 196, 94, 155, 141, 204, 95, 125];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct DepositDryRun {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub bonus: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+}
+
+impl BankEvent for DepositDryRun {
+    const DISCRIMINATOR: [u8; 8] = [160, 227, 7, 164, 16, 223, 236, 197];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawDryRun {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+    pub withdrawn_today: u64,
+}
+
+impl BankEvent for WithdrawDryRun {
+    const DISCRIMINATOR: [u8; 8] = [14, 161, 241, 115, 248, 114, 76, 49];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+}
+
+impl BankEvent for DepositEvent {
+    const DISCRIMINATOR: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+}
+
+impl BankEvent for WithdrawEvent {
+    const DISCRIMINATOR: [u8; 8] = [22, 9, 133, 26, 160, 44, 71, 192];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct InvestEvent {
+    pub target: Pubkey,
+    pub amount: u64,
+    pub is_invest: bool,
+}
+
+impl BankEvent for InvestEvent {
+    const DISCRIMINATOR: [u8; 8] = [46, 222, 143, 59, 183, 185, 118, 106];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PauseToggledEvent {
+    pub is_paused: bool,
+    pub by: Pubkey,
+}
+
+impl BankEvent for PauseToggledEvent {
+    const DISCRIMINATOR: [u8; 8] = [210, 185, 198, 169, 200, 181, 119, 167];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct GoalMoveEvent {
+    pub user: Pubkey,
+    pub goal_index: u8,
+    pub amount: u64,
+    pub to_goal: bool,
+    pub deposited_amount: u64,
+    pub goal_amount: u64,
+}
+
+impl BankEvent for GoalMoveEvent {
+    const DISCRIMINATOR: [u8; 8] = [74, 173, 148, 12, 222, 245, 18, 18];
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RewardPaidEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+impl BankEvent for RewardPaidEvent {
+    const DISCRIMINATOR: [u8; 8] = [209, 35, 148, 7, 238, 232, 124, 53];
+}
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+// every `E` the bank program logged, in order. Lines logged by other programs
+// (a CPI target can log "Program data:" too) are skipped by following the
+// invoke/success/failed lines, and other event types are left out
+pub fn decode_events<E: BankEvent>(logs: &[String], program_id: &Pubkey) -> Vec<E> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        let mut words = log.split_whitespace();
+        if let (Some("Program"), Some(program), Some(action)) =
+            (words.next(), words.next(), words.next())
+        {
+            match action {
+                "invoke" => {
+                    stack.push(program);
+                    continue;
+                }
+                "success" | "failed:" => {
+                    stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        if stack.last() != Some(&program_id.as_str()) {
+            continue;
+        }
+        let Some(encoded) = log.strip_prefix(PROGRAM_DATA) else {
+            continue;
+        };
+        let Ok(data) = STANDARD.decode(encoded) else {
+            continue;
+        };
+        if let Ok(event) = E::decode(&data) {
+            events.push(event);
+        }
+    }
+
+    events
+}
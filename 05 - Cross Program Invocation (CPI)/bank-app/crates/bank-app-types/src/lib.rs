@@ -0,0 +1,18 @@
+// everything a UI or indexer needs to read the Bank App, without pulling in
+// anchor-lang or anchor-client. Layouts here must follow programs/bank-app
+pub mod accounts;
+pub mod constants;
+pub mod error;
+pub mod errors;
+pub mod events;
+pub mod pda;
+
+pub use accounts::*;
+pub use error::DecodeError;
+pub use errors::BankAppError;
+pub use events::{decode_events, BankEvent};
+pub use pda::BankPda;
+
+pub use solana_pubkey::Pubkey;
+
+pub const ID: Pubkey = solana_pubkey::pubkey!("3q57ftWH75aKfxoNnV6Lu1n8LhV73xxKHAxPapL6Jvh7");
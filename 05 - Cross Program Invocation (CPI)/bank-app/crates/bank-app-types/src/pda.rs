@@ -0,0 +1,176 @@
+use solana_pubkey::Pubkey;
+
+use crate::constants::{
+    APPROVAL_SET_SEED, BADGE_CONFIG_SEED, BADGE_TREE_AUTHORITY_SEED, BANK_INFO_SEED,
+    BANK_VAULT_SEED, BRIDGE_ESCROW_SEED, CAMPAIGN_MATCH_SEED, CAMPAIGN_SEED, CLAIM_BITMAP_SEED,
+    CPI_ALLOWLIST_SEED, DENYLIST_SEED, DEPOSIT_COUNTER_SEED, DEPOSIT_RECORD_SEED, EMITTER_SEED,
+    FOREIGN_EMITTER_SEED, GOAL_SEED, INHERITANCE_SEED, INSURANCE_FUND_SEED, JOINT_RESERVE_SEED,
+    LOOKUP_TABLE_SEED, LUCKY_TICKET_SEED, MILESTONE_BADGES_SEED, OPS_QUEUE_SEED, OP_RECORD_SEED,
+    PERMIT_NONCE_SEED, RECEIPT_MINT_SEED, RECEIVED_VAA_SEED, RECONCILIATION_SEED,
+    RECURRING_DEPOSIT_SEED, SLOTHASH_TICKET_SEED, SNAPSHOT_SEED, USER_HISTORY_SEED,
+    USER_RESERVE_SEED, USER_VAULT_SEED, VERSION_BEACON_SEED, WITHDRAWAL_WINDOW_SEED,
+    WORMHOLE_MESSAGE_SEED,
+};
+
+// same variants and seed layouts as bank_app::seeds::BankPda
+pub enum BankPda {
+    BankInfo,
+    BankVault,
+    UserReserve { user: Pubkey },
+    UserTokenReserve { user: Pubkey, mint: Pubkey },
+    Snapshot { epoch: u64 },
+    ClaimBitmap { snapshot: Pubkey },
+    LuckyTicket { user: Pubkey },
+    SlothashTicket { user: Pubkey },
+    WormholeEmitter,
+    WormholeMessage { sequence: u64 },
+    ForeignEmitter { chain: u16 },
+    ReceivedVaa { vaa_hash: [u8; 32] },
+    DepositCounter { user: Pubkey },
+    DepositRecord { user: Pubkey, index: u64 },
+    BadgeConfig,
+    BadgeTreeAuthority,
+    MilestoneBadges { user: Pubkey },
+    Reconciliation,
+    OpRecord { user: Pubkey, op_id: [u8; 16] },
+    OpsQueue,
+    VersionBeacon,
+    Goal { user: Pubkey, index: u8 },
+    RecurringDeposit { user: Pubkey, mint: Pubkey },
+    JointReserve { owner_a: Pubkey, owner_b: Pubkey },
+    Inheritance { owner: Pubkey },
+    InsuranceFund,
+    WithdrawalWindow { user: Pubkey },
+    Campaign { id: u64 },
+    CampaignMatch { campaign: Pubkey, user: Pubkey },
+    UserVault { user: Pubkey },
+    CpiAllowlist,
+    ReceiptMint { mint: Pubkey },
+    DenyEntry { wallet: Pubkey },
+    PermitNonce { user: Pubkey },
+    BankLookupTable,
+    ApprovalSet,
+    UserHistory { user: Pubkey },
+    BridgeEscrow { vaa_hash: [u8; 32] },
+}
+
+impl BankPda {
+    pub fn seeds(&self) -> Vec<Vec<u8>> {
+        match self {
+            BankPda::BankInfo => vec![BANK_INFO_SEED.to_vec()],
+            BankPda::BankVault => vec![BANK_VAULT_SEED.to_vec()],
+            BankPda::UserReserve { user } => {
+                vec![USER_RESERVE_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::UserTokenReserve { user, mint } => vec![
+                USER_RESERVE_SEED.to_vec(),
+                user.to_bytes().to_vec(),
+                mint.to_bytes().to_vec(),
+            ],
+            BankPda::Snapshot { epoch } => {
+                vec![SNAPSHOT_SEED.to_vec(), epoch.to_le_bytes().to_vec()]
+            }
+            BankPda::ClaimBitmap { snapshot } => {
+                vec![CLAIM_BITMAP_SEED.to_vec(), snapshot.to_bytes().to_vec()]
+            }
+            BankPda::LuckyTicket { user } => {
+                vec![LUCKY_TICKET_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::SlothashTicket { user } => {
+                vec![SLOTHASH_TICKET_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::WormholeEmitter => vec![EMITTER_SEED.to_vec()],
+            BankPda::WormholeMessage { sequence } => {
+                vec![
+                    WORMHOLE_MESSAGE_SEED.to_vec(),
+                    sequence.to_le_bytes().to_vec(),
+                ]
+            }
+            BankPda::ForeignEmitter { chain } => {
+                vec![FOREIGN_EMITTER_SEED.to_vec(), chain.to_le_bytes().to_vec()]
+            }
+            BankPda::ReceivedVaa { vaa_hash } => {
+                vec![RECEIVED_VAA_SEED.to_vec(), vaa_hash.to_vec()]
+            }
+            BankPda::DepositCounter { user } => {
+                vec![DEPOSIT_COUNTER_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::DepositRecord { user, index } => vec![
+                DEPOSIT_RECORD_SEED.to_vec(),
+                user.to_bytes().to_vec(),
+                index.to_le_bytes().to_vec(),
+            ],
+            BankPda::BadgeConfig => vec![BADGE_CONFIG_SEED.to_vec()],
+            BankPda::BadgeTreeAuthority => vec![BADGE_TREE_AUTHORITY_SEED.to_vec()],
+            BankPda::MilestoneBadges { user } => {
+                vec![MILESTONE_BADGES_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::Reconciliation => vec![RECONCILIATION_SEED.to_vec()],
+            BankPda::OpRecord { user, op_id } => vec![
+                OP_RECORD_SEED.to_vec(),
+                user.to_bytes().to_vec(),
+                op_id.to_vec(),
+            ],
+            BankPda::OpsQueue => vec![OPS_QUEUE_SEED.to_vec()],
+            BankPda::VersionBeacon => vec![VERSION_BEACON_SEED.to_vec()],
+            BankPda::Goal { user, index } => {
+                vec![GOAL_SEED.to_vec(), user.to_bytes().to_vec(), vec![*index]]
+            }
+            BankPda::RecurringDeposit { user, mint } => vec![
+                RECURRING_DEPOSIT_SEED.to_vec(),
+                user.to_bytes().to_vec(),
+                mint.to_bytes().to_vec(),
+            ],
+            BankPda::JointReserve { owner_a, owner_b } => vec![
+                JOINT_RESERVE_SEED.to_vec(),
+                owner_a.to_bytes().to_vec(),
+                owner_b.to_bytes().to_vec(),
+            ],
+            BankPda::Inheritance { owner } => {
+                vec![INHERITANCE_SEED.to_vec(), owner.to_bytes().to_vec()]
+            }
+            BankPda::InsuranceFund => vec![INSURANCE_FUND_SEED.to_vec()],
+            BankPda::WithdrawalWindow { user } => {
+                vec![WITHDRAWAL_WINDOW_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::Campaign { id } => vec![CAMPAIGN_SEED.to_vec(), id.to_le_bytes().to_vec()],
+            BankPda::CampaignMatch { campaign, user } => vec![
+                CAMPAIGN_MATCH_SEED.to_vec(),
+                campaign.to_bytes().to_vec(),
+                user.to_bytes().to_vec(),
+            ],
+            BankPda::UserVault { user } => {
+                vec![USER_VAULT_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::CpiAllowlist => vec![CPI_ALLOWLIST_SEED.to_vec()],
+            BankPda::ReceiptMint { mint } => {
+                vec![RECEIPT_MINT_SEED.to_vec(), mint.to_bytes().to_vec()]
+            }
+            BankPda::DenyEntry { wallet } => {
+                vec![DENYLIST_SEED.to_vec(), wallet.to_bytes().to_vec()]
+            }
+            BankPda::PermitNonce { user } => {
+                vec![PERMIT_NONCE_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::BankLookupTable => vec![LOOKUP_TABLE_SEED.to_vec()],
+            BankPda::ApprovalSet => vec![APPROVAL_SET_SEED.to_vec()],
+            BankPda::UserHistory { user } => {
+                vec![USER_HISTORY_SEED.to_vec(), user.to_bytes().to_vec()]
+            }
+            BankPda::BridgeEscrow { vaa_hash } => {
+                vec![BRIDGE_ESCROW_SEED.to_vec(), vaa_hash.to_vec()]
+            }
+        }
+    }
+
+    pub fn find_address(&self) -> (Pubkey, u8) {
+        self.find_address_for(&crate::ID)
+    }
+
+    // for a bank deployed under another program id (devnet forks, tests)
+    pub fn find_address_for(&self, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = self.seeds();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+}
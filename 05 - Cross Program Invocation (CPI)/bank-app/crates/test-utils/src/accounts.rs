@@ -0,0 +1,36 @@
+use bank_app_types::BankAccount;
+use borsh::BorshSerialize;
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+// writes `value` the way Anchor would store it, owned by the bank program, so a
+// test can start from any state without sending the instructions that build it
+pub fn set_bank_account<T: BankAccount + BorshSerialize>(
+    svm: &mut LiteSVM,
+    address: Pubkey,
+    value: &T,
+) {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    value
+        .serialize(&mut data)
+        .expect("writing to a Vec can't fail");
+
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(
+        address,
+        Account {
+            lamports,
+            data,
+            owner: bank_app_types::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .expect("LiteSVM rejected the account");
+}
+
+pub fn read_bank_account<T: BankAccount>(svm: &LiteSVM, address: &Pubkey) -> Option<T> {
+    let account = svm.get_account(address)?;
+    Some(T::decode(&account.data).unwrap_or_else(|err| panic!("can't decode {address}: {err:?}")))
+}
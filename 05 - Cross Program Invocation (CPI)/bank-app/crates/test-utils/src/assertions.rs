@@ -0,0 +1,94 @@
+use bank_app_types::{BankAppError, BankInfo, BankPda, InvariantReport, UserReserve};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_instruction::error::InstructionError;
+use solana_pubkey::{pubkey, Pubkey};
+use solana_transaction_error::TransactionError;
+
+use crate::accounts::read_bank_account;
+
+pub const STAKING_APP_ID: Pubkey = pubkey!("EYdKY4wWuwNr7uVRQNBUEXeJyLCAatSELPck3quW7JvA");
+
+// `reserve` is BankPda::UserReserve or BankPda::UserTokenReserve. A reserve that
+// doesn't exist only passes for an expected balance of 0
+pub fn assert_reserve_balance(svm: &LiteSVM, reserve: &BankPda, expected: u64) {
+    let address = reserve.find_address().0;
+    let deposited_amount = read_bank_account::<UserReserve>(svm, &address)
+        .map(|reserve| reserve.deposited_amount)
+        .unwrap_or(0);
+
+    assert_eq!(
+        deposited_amount, expected,
+        "reserve {address} holds {deposited_amount}, expected {expected}"
+    );
+}
+
+// the same check as the verify_invariants instruction: the vault plus what it
+// staked or put in the stake pool covers every deposit. Returns the report for
+// further asserts
+pub fn assert_vault_matches_accounting(svm: &LiteSVM) -> InvariantReport {
+    let bank_info = read_bank_account::<BankInfo>(svm, &BankPda::BankInfo.find_address().0)
+        .expect("the bank is not initialized");
+
+    let bank_vault = BankPda::BankVault.find_address().0;
+    let vault_balance = svm
+        .get_account(&bank_vault)
+        .map(|account| account.lamports)
+        .unwrap_or(0);
+
+    // staking_app::UserInfo starts with `amount` right after the discriminator
+    let (staking_info, _) =
+        Pubkey::find_program_address(&[b"USER_INFO", bank_vault.as_ref()], &STAKING_APP_ID);
+    let staked_amount = svm
+        .get_account(&staking_info)
+        .filter(|account| account.data.len() >= 16)
+        .map(|account| u64::from_le_bytes(account.data[8..16].try_into().unwrap()))
+        .unwrap_or(0);
+
+    let report = InvariantReport {
+        total_deposited: bank_info.total_deposited,
+        vault_balance,
+        staked_amount,
+        shortfall: bank_info.total_deposited.saturating_sub(
+            vault_balance
+                .saturating_add(staked_amount)
+                .saturating_add(bank_info.stake_pool_principal),
+        ),
+        stake_pool_principal: bank_info.stake_pool_principal,
+    };
+
+    assert_eq!(
+        report.shortfall,
+        0,
+        "the bank owes {} but the vault holds {}, {} is staked and {} is in the stake pool",
+        report.total_deposited,
+        report.vault_balance,
+        report.staked_amount,
+        report.stake_pool_principal
+    );
+    report
+}
+
+// the transaction failed in one of its instructions with `expected`
+pub fn assert_custom_error(result: &TransactionResult, expected: BankAppError) {
+    let failed = match result {
+        Ok(_) => panic!("expected {expected}, the transaction succeeded"),
+        Err(failed) => failed,
+    };
+
+    match &failed.err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert!(
+                *code == expected.code(),
+                "expected {expected}, got {}\n{}",
+                BankAppError::from_code(*code)
+                    .map(|error| error.to_string())
+                    .unwrap_or_else(|| format!("custom error {code}")),
+                failed.meta.logs.join("\n")
+            );
+        }
+        err => panic!(
+            "expected {expected}, got {err:?}\n{}",
+            failed.meta.logs.join("\n")
+        ),
+    }
+}
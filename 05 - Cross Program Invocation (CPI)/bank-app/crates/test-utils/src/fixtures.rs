@@ -0,0 +1,94 @@
+use bank_app_types::{
+    constants::MAX_ORACLE_STALENESS_SLOTS, BankAppError, BankInfo, BankPda, UserReserve,
+};
+use litesvm::LiteSVM;
+use solana_clock::Clock;
+use solana_pubkey::Pubkey;
+
+use crate::accounts::{read_bank_account, set_bank_account};
+
+// each one puts an initialized bank into a state the program has to refuse, in
+// one call, and returns the error the next instruction of that kind fails with.
+// They write accounts directly, nothing checks the state is reachable onchain
+
+// every instruction that moves funds
+pub fn pause_bank(svm: &mut LiteSVM) -> BankAppError {
+    update_bank_info(svm, |bank_info| bank_info.is_paused = true);
+    BankAppError::BankAppPaused
+}
+
+// the next deposit of `mint`, of any amount
+pub fn fill_deposit_cap(svm: &mut LiteSVM, mint: &Pubkey) -> BankAppError {
+    update_bank_info(svm, |bank_info| {
+        let config = bank_info
+            .token_configs
+            .iter_mut()
+            .find(|config| config.mint == *mint)
+            .unwrap_or_else(|| panic!("{mint} is not a supported token"));
+        // 0 is no cap, so an empty mint gets a cap of 1 it already reached
+        config.total_deposited = config.total_deposited.max(1);
+        config.deposit_cap = config.total_deposited;
+    });
+    BankAppError::DepositCapExceeded
+}
+
+// withdrawals and SOL outflows, the vault keeps only its rent floor.
+// process_next_op fails with InsufficientLiquidity instead
+pub fn drain_vault(svm: &mut LiteSVM) -> BankAppError {
+    let bank_info = bank_info(svm);
+    let address = BankPda::BankVault.find_address().0;
+    let mut vault = svm
+        .get_account(&address)
+        .expect("the bank vault doesn't exist");
+    vault.lamports = bank_info.rent_floor;
+    svm.set_account(address, vault)
+        .expect("LiteSVM rejected the vault");
+    BankAppError::VaultBelowRentFloor
+}
+
+// anything that prices `mint`. The clock moves past the feed's staleness bound,
+// so a feed that was fresh is stale without touching the feed account
+pub fn stale_oracle(svm: &mut LiteSVM, mint: &Pubkey) -> BankAppError {
+    let config = bank_info(svm)
+        .token_configs
+        .into_iter()
+        .find(|config| config.mint == *mint)
+        .unwrap_or_else(|| panic!("{mint} is not a supported token"));
+    let max_staleness_slots = match config.max_staleness_slots {
+        0 => MAX_ORACLE_STALENESS_SLOTS,
+        slots => slots,
+    };
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.slot += max_staleness_slots + 1;
+    svm.set_sysvar(&clock);
+    BankAppError::StaleOraclePrice
+}
+
+// execute_withdraw of `user`, the reserve has `amount` pending for a day from now
+pub fn lock_reserve(svm: &mut LiteSVM, user: &Pubkey, amount: u64) -> BankAppError {
+    let address = BankPda::UserReserve { user: *user }.find_address().0;
+    let mut reserve = read_bank_account::<UserReserve>(svm, &address)
+        .unwrap_or_else(|| panic!("{user} has no reserve"));
+    assert!(
+        amount > 0 && amount <= reserve.deposited_amount,
+        "{user} has {} deposited, can't lock {amount}",
+        reserve.deposited_amount
+    );
+
+    reserve.pending_withdrawal = amount;
+    reserve.unlock_at = svm.get_sysvar::<Clock>().unix_timestamp + 86_400;
+    set_bank_account(svm, address, &reserve);
+    BankAppError::WithdrawalLocked
+}
+
+fn bank_info(svm: &LiteSVM) -> BankInfo {
+    read_bank_account(svm, &BankPda::BankInfo.find_address().0)
+        .expect("the bank is not initialized")
+}
+
+fn update_bank_info(svm: &mut LiteSVM, update: impl FnOnce(&mut BankInfo)) {
+    let mut bank_info = bank_info(svm);
+    update(&mut bank_info);
+    set_bank_account(svm, BankPda::BankInfo.find_address().0, &bank_info);
+}
@@ -0,0 +1,11 @@
+// the invariants the TS tests used to check one by one, written once for every
+// Rust test. They panic with the values involved, like assert_eq!
+pub mod accounts;
+pub mod assertions;
+pub mod fixtures;
+
+pub use accounts::{read_bank_account, set_bank_account};
+pub use assertions::{
+    assert_custom_error, assert_reserve_balance, assert_vault_matches_accounting,
+};
+pub use fixtures::{drain_vault, fill_deposit_cap, lock_reserve, pause_bank, stale_oracle};
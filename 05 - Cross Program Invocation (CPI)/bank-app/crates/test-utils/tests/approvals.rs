@@ -0,0 +1,95 @@
+mod common;
+
+use bank_app_client::{
+    instructions::{approve_action, close_bank, deposit, set_approvers, withdraw, ApprovalAction},
+    types::{BankAppError, BankPda},
+};
+use common::{bank, send, wallet, SOL};
+use litesvm::LiteSVM;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use test_utils::assert_custom_error;
+
+// 2 of 3 approvers, approvals last 100 slots
+fn approvers(svm: &mut LiteSVM, authority: &Keypair) -> Vec<Keypair> {
+    let approvers: Vec<Keypair> = (0..3).map(|_| wallet(svm, SOL)).collect();
+    let keys: Vec<Pubkey> = approvers.iter().map(|approver| approver.pubkey()).collect();
+    send(
+        svm,
+        set_approvers(&authority.pubkey(), &keys, 2, 100),
+        authority,
+    )
+    .unwrap();
+    approvers
+}
+
+fn approve(svm: &mut LiteSVM, approver: &Keypair, action: ApprovalAction) {
+    send(svm, approve_action(&approver.pubkey(), action), approver).unwrap();
+}
+
+fn balance(svm: &LiteSVM, address: &Pubkey) -> u64 {
+    svm.get_balance(address).unwrap_or(0)
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn one_approver_cant_close_the_bank_but_a_quorum_can() {
+    let (mut svm, authority) = bank();
+    let approvers = approvers(&mut svm, &authority);
+
+    approve(&mut svm, &approvers[0], ApprovalAction::CloseBank);
+    assert_custom_error(
+        &send(&mut svm, close_bank(&authority.pubkey()), &authority),
+        BankAppError::ApprovalThresholdNotMet,
+    );
+
+    // someone outside the set can't make up the quorum
+    let stranger = wallet(&mut svm, SOL);
+    assert_custom_error(
+        &send(
+            &mut svm,
+            approve_action(&stranger.pubkey(), ApprovalAction::CloseBank),
+            &stranger,
+        ),
+        BankAppError::NotAnApprover,
+    );
+    // nor can an approval of another action
+    approve(&mut svm, &approvers[1], ApprovalAction::RescueTokens);
+    assert_custom_error(
+        &send(&mut svm, close_bank(&authority.pubkey()), &authority),
+        BankAppError::ApprovalThresholdNotMet,
+    );
+
+    approve(&mut svm, &approvers[1], ApprovalAction::CloseBank);
+    let vault = BankPda::BankVault.find_address().0;
+    let swept = balance(&svm, &vault);
+    let before = balance(&svm, &authority.pubkey());
+    send(&mut svm, close_bank(&authority.pubkey()), &authority).unwrap();
+
+    assert_eq!(balance(&svm, &BankPda::BankInfo.find_address().0), 0);
+    assert_eq!(balance(&svm, &vault), 0);
+    // the vault and BankInfo's rent, less the fee
+    assert!(balance(&svm, &authority.pubkey()) > before + swept - 5_000);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn a_bank_that_owes_deposits_is_not_closed() {
+    let (mut svm, authority) = bank();
+    let approvers = approvers(&mut svm, &authority);
+    let user = wallet(&mut svm, 5 * SOL);
+    send(&mut svm, deposit(&user.pubkey(), SOL, None), &user).unwrap();
+
+    approve(&mut svm, &approvers[0], ApprovalAction::CloseBank);
+    approve(&mut svm, &approvers[2], ApprovalAction::CloseBank);
+    assert_custom_error(
+        &send(&mut svm, close_bank(&authority.pubkey()), &authority),
+        BankAppError::BankNotEmpty,
+    );
+
+    // the failed close didn't use the approvals up
+    send(&mut svm, withdraw(&user.pubkey(), SOL), &user).unwrap();
+    send(&mut svm, close_bank(&authority.pubkey()), &authority).unwrap();
+    assert_eq!(balance(&svm, &BankPda::BankInfo.find_address().0), 0);
+}
@@ -0,0 +1,116 @@
+use bank_app_types::{BankAppError, BankInfo, BankPda, UserReserve};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_account::Account;
+use solana_instruction::error::InstructionError;
+use solana_pubkey::Pubkey;
+use solana_transaction_error::TransactionError;
+use test_utils::{
+    assert_custom_error, assert_reserve_balance, assert_vault_matches_accounting, set_bank_account,
+};
+
+fn bank_with(total_deposited: u64, vault_balance: u64) -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    set_bank_account(
+        &mut svm,
+        BankPda::BankInfo.find_address().0,
+        &BankInfo {
+            total_deposited,
+            ..Default::default()
+        },
+    );
+    svm.set_account(
+        BankPda::BankVault.find_address().0,
+        Account {
+            lamports: vault_balance,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    svm
+}
+
+fn failed_with(err: TransactionError) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+    Err(FailedTransactionMetadata {
+        err,
+        meta: TransactionMetadata::default(),
+    })
+}
+
+#[test]
+fn reads_reserve_balances() {
+    let mut svm = LiteSVM::new();
+    let user = Pubkey::new_unique();
+    let reserve = BankPda::UserReserve { user };
+    set_bank_account(
+        &mut svm,
+        reserve.find_address().0,
+        &UserReserve {
+            deposited_amount: 1_000,
+            ..Default::default()
+        },
+    );
+
+    assert_reserve_balance(&svm, &reserve, 1_000);
+    assert_reserve_balance(
+        &svm,
+        &BankPda::UserReserve {
+            user: Pubkey::new_unique(),
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "expected 999")]
+fn reports_a_wrong_reserve_balance() {
+    let mut svm = LiteSVM::new();
+    let reserve = BankPda::UserReserve {
+        user: Pubkey::new_unique(),
+    };
+    set_bank_account(
+        &mut svm,
+        reserve.find_address().0,
+        &UserReserve {
+            deposited_amount: 1_000,
+            ..Default::default()
+        },
+    );
+
+    assert_reserve_balance(&svm, &reserve, 999);
+}
+
+#[test]
+fn passes_a_covered_vault() {
+    let report = assert_vault_matches_accounting(&bank_with(5_000_000, 6_000_000));
+    assert_eq!(report.total_deposited, 5_000_000);
+    assert_eq!(report.vault_balance, 6_000_000);
+    assert_eq!(report.staked_amount, 0);
+}
+
+#[test]
+#[should_panic(expected = "the bank owes 5000000")]
+fn reports_a_vault_shortfall() {
+    assert_vault_matches_accounting(&bank_with(5_000_000, 4_000_000));
+}
+
+#[test]
+fn matches_custom_errors() {
+    let result = failed_with(TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(BankAppError::InsufficientDeposit.code()),
+    ));
+    assert_custom_error(&result, BankAppError::InsufficientDeposit);
+}
+
+#[test]
+#[should_panic(expected = "got InsufficientDeposit")]
+fn reports_the_wrong_custom_error() {
+    let result = failed_with(TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(BankAppError::InsufficientDeposit.code()),
+    ));
+    assert_custom_error(&result, BankAppError::InvalidAmount);
+}
@@ -0,0 +1,233 @@
+mod common;
+
+use bank_app_client::{
+    instructions::{
+        deposit, deposit_expecting, guardian_pause, set_guardian, set_paused, withdraw,
+        withdraw_expecting,
+    },
+    types::{BankAppError, BankInfo, BankPda, UserReserve, VersionBeacon, ID as BANK_APP_ID},
+};
+use common::{bank, send, wallet, SOL};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_instruction::error::InstructionError;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+use test_utils::{
+    assert_custom_error, assert_reserve_balance, assert_vault_matches_accounting, read_bank_account,
+};
+
+fn bank_info(svm: &LiteSVM) -> BankInfo {
+    read_bank_account(svm, &BankPda::BankInfo.find_address().0).unwrap()
+}
+
+fn add_guardian(svm: &mut LiteSVM, authority: &Keypair) -> Keypair {
+    let guardian = wallet(svm, SOL);
+    send(
+        svm,
+        set_guardian(&authority.pubkey(), &guardian.pubkey()),
+        authority,
+    )
+    .unwrap();
+    guardian
+}
+
+// `address = ...` has no custom error, so it's Anchor's ConstraintAddress
+const CONSTRAINT_ADDRESS: u32 = 2012;
+
+fn assert_constraint_address(result: TransactionResult) {
+    match result.unwrap_err().err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, CONSTRAINT_ADDRESS);
+        }
+        err => panic!("expected ConstraintAddress, got {err:?}"),
+    }
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn the_bank_lives_at_its_pdas() {
+    let (svm, authority) = bank();
+
+    // the same seeds the TS tests use, as plain strings
+    let (info_address, _) = Pubkey::find_program_address(&[b"BANK_INFO_SEED"], &BANK_APP_ID);
+    let (bank_vault, vault_bump) =
+        Pubkey::find_program_address(&[b"BANK_VAULT_SEED"], &BANK_APP_ID);
+    assert_eq!(BankPda::BankInfo.find_address().0, info_address);
+    assert_eq!(BankPda::BankVault.find_address().0, bank_vault);
+    let user = Pubkey::new_unique();
+    assert_eq!(
+        BankPda::UserReserve { user }.find_address().0,
+        Pubkey::find_program_address(&[b"USER_RESERVE_SEED", user.as_ref()], &BANK_APP_ID).0
+    );
+
+    let info = bank_info(&svm);
+    assert_eq!(info.authority, authority.pubkey());
+    assert!(!info.is_paused);
+    // kept to sign for the vault in withdraw
+    assert_eq!(info.bump, vault_bump);
+    assert_eq!(
+        svm.get_account(&bank_vault).unwrap().owner,
+        Pubkey::default() // the system program
+    );
+    assert!(
+        read_bank_account::<VersionBeacon>(&svm, &BankPda::VersionBeacon.find_address().0)
+            .is_some()
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn a_deposit_is_withdrawn_through_the_vault() {
+    let (mut svm, _) = bank();
+    let user = wallet(&mut svm, 5 * SOL);
+    let reserve = BankPda::UserReserve {
+        user: user.pubkey(),
+    };
+
+    send(&mut svm, deposit(&user.pubkey(), 2 * SOL, None), &user).unwrap();
+    assert_reserve_balance(&svm, &reserve, 2 * SOL);
+    assert_eq!(bank_info(&svm).total_deposited, 2 * SOL);
+    assert_vault_matches_accounting(&svm);
+
+    send(&mut svm, withdraw(&user.pubkey(), SOL / 2), &user).unwrap();
+    assert_reserve_balance(&svm, &reserve, 3 * SOL / 2);
+    assert_eq!(bank_info(&svm).total_deposited, 3 * SOL / 2);
+    let report = assert_vault_matches_accounting(&svm);
+    // the vault keeps its rent floor on top of what it owes
+    assert_eq!(
+        report.vault_balance,
+        bank_info(&svm).rent_floor + 3 * SOL / 2
+    );
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn more_than_the_reserve_holds_is_not_withdrawn() {
+    let (mut svm, _) = bank();
+    let user = wallet(&mut svm, 5 * SOL);
+    send(&mut svm, deposit(&user.pubkey(), SOL, None), &user).unwrap();
+
+    assert_custom_error(
+        &send(&mut svm, withdraw(&user.pubkey(), SOL + 1), &user),
+        BankAppError::InsufficientDeposit,
+    );
+    assert_custom_error(
+        &send(&mut svm, withdraw(&user.pubkey(), 0), &user),
+        BankAppError::InvalidAmount,
+    );
+    let reserve: UserReserve = read_bank_account(
+        &svm,
+        &BankPda::UserReserve {
+            user: user.pubkey(),
+        }
+        .find_address()
+        .0,
+    )
+    .unwrap();
+    assert_eq!(reserve.deposited_amount, SOL);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn an_expected_version_from_another_major_is_refused() {
+    let (mut svm, _) = bank();
+    let user = wallet(&mut svm, 5 * SOL);
+
+    // initialize copied the beacon onto BankInfo
+    let beacon: VersionBeacon =
+        read_bank_account(&svm, &BankPda::VersionBeacon.find_address().0).unwrap();
+    let version = bank_info(&svm).version;
+    assert_eq!(version, beacon.version);
+    assert_eq!(bank_info(&svm).features, beacon.features);
+
+    let next_major = ((version >> 16) + 1) << 16;
+    assert_custom_error(
+        &send(
+            &mut svm,
+            deposit_expecting(&user.pubkey(), SOL, next_major),
+            &user,
+        ),
+        BankAppError::VersionMismatch,
+    );
+    send(
+        &mut svm,
+        deposit_expecting(&user.pubkey(), SOL, version),
+        &user,
+    )
+    .unwrap();
+
+    assert_custom_error(
+        &send(
+            &mut svm,
+            withdraw_expecting(&user.pubkey(), SOL, next_major),
+            &user,
+        ),
+        BankAppError::VersionMismatch,
+    );
+    send(
+        &mut svm,
+        withdraw_expecting(&user.pubkey(), SOL, version),
+        &user,
+    )
+    .unwrap();
+    assert_vault_matches_accounting(&svm);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn a_paused_bank_takes_and_pays_nothing() {
+    let (mut svm, authority) = bank();
+    let user = wallet(&mut svm, 5 * SOL);
+    send(&mut svm, deposit(&user.pubkey(), SOL, None), &user).unwrap();
+
+    send(&mut svm, set_paused(&authority.pubkey(), true), &authority).unwrap();
+    assert!(bank_info(&svm).is_paused);
+    assert_custom_error(
+        &send(&mut svm, deposit(&user.pubkey(), SOL, None), &user),
+        BankAppError::BankAppPaused,
+    );
+    assert_custom_error(
+        &send(&mut svm, withdraw(&user.pubkey(), SOL), &user),
+        BankAppError::BankAppPaused,
+    );
+
+    send(&mut svm, set_paused(&authority.pubkey(), false), &authority).unwrap();
+    send(&mut svm, withdraw(&user.pubkey(), SOL), &user).unwrap();
+    assert_vault_matches_accounting(&svm);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so, run anchor build first"]
+fn the_guardian_pauses_and_only_the_authority_unpauses() {
+    let (mut svm, authority) = bank();
+    let guardian = add_guardian(&mut svm, &authority);
+    let stranger = wallet(&mut svm, SOL);
+
+    assert_constraint_address(send(
+        &mut svm,
+        guardian_pause(&stranger.pubkey()),
+        &stranger,
+    ));
+    assert_constraint_address(send(
+        &mut svm,
+        set_paused(&stranger.pubkey(), true),
+        &stranger,
+    ));
+    assert!(!bank_info(&svm).is_paused);
+
+    send(&mut svm, guardian_pause(&guardian.pubkey()), &guardian).unwrap();
+    assert!(bank_info(&svm).is_paused);
+
+    // the guardian's key can't undo its own pause
+    assert_constraint_address(send(
+        &mut svm,
+        set_paused(&guardian.pubkey(), false),
+        &guardian,
+    ));
+    assert!(bank_info(&svm).is_paused);
+
+    send(&mut svm, set_paused(&authority.pubkey(), false), &authority).unwrap();
+    assert!(!bank_info(&svm).is_paused);
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use bank_app_client::{instructions::initialize, types::ID as BANK_APP_ID};
+use litesvm::{types::TransactionResult, LiteSVM};
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+// the whole bank in LiteSVM, no validator and no Node. The program is loaded from
+// target/deploy/bank_app.so, so these only run after an `anchor build`:
+// `cargo test -p test-utils -- --ignored`. They live here rather than in the
+// program crate so its own `cargo test` needs neither the .so nor LiteSVM
+
+pub const SOL: u64 = 1_000_000_000;
+
+// `name`.so, as anchor build names it
+pub fn program_path(name: &str) -> PathBuf {
+    let dir = std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    dir.join(format!("{name}.so"))
+}
+
+// the signer pays its own fee. A fresh blockhash each time, or a second
+// identical deposit would be the same transaction
+pub fn send(svm: &mut LiteSVM, ix: Instruction, signer: &Keypair) -> TransactionResult {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+}
+
+pub fn wallet(svm: &mut LiteSVM, lamports: u64) -> Keypair {
+    let wallet = Keypair::new();
+    svm.airdrop(&wallet.pubkey(), lamports).unwrap();
+    wallet
+}
+
+// an initialized bank and its authority
+pub fn bank() -> (LiteSVM, Keypair) {
+    let mut svm = LiteSVM::new();
+    let path = program_path("bank_app");
+    svm.add_program_from_file(BANK_APP_ID, &path)
+        .unwrap_or_else(|_| panic!("no {}, run anchor build", path.display()));
+
+    let authority = wallet(&mut svm, 10 * SOL);
+    send(&mut svm, initialize(&authority.pubkey()), &authority).unwrap();
+    (svm, authority)
+}
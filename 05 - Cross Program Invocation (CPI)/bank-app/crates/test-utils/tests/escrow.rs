@@ -0,0 +1,114 @@
+mod common;
+
+use bank_app_client::{
+    instructions::{deposit, escrow_address, withdraw_to_escrow, ESCROW_APP_ID},
+    types::{BankPda, UserReserve},
+};
+use common::{bank, program_path, send, wallet, SOL};
+use litesvm::LiteSVM;
+use solana_clock::Clock;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use test_utils::{assert_vault_matches_accounting, read_bank_account};
+
+// sha256("global:release")[..8] of escrow_app
+const RELEASE_DISCRIMINATOR: [u8; 8] = [253, 249, 15, 206, 28, 127, 193, 241];
+
+// the bank with escrow_app next to it, the same two programs a localnet runs
+fn bank_and_escrow() -> (LiteSVM, Keypair) {
+    let (mut svm, authority) = bank();
+    let path = program_path("escrow_app");
+    svm.add_program_from_file(ESCROW_APP_ID, &path)
+        .unwrap_or_else(|_| panic!("no {}, run anchor build", path.display()));
+    (svm, authority)
+}
+
+// permissionless, the escrow checks unlock_at itself
+fn release(depositor: &Pubkey, recipient: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ESCROW_APP_ID,
+        accounts: vec![
+            AccountMeta::new(escrow_address(depositor, recipient), false),
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(*recipient, false),
+        ],
+        data: RELEASE_DISCRIMINATOR.to_vec(),
+    }
+}
+
+fn deposited(svm: &LiteSVM, user: &Pubkey) -> u64 {
+    read_bank_account::<UserReserve>(svm, &BankPda::UserReserve { user: *user }.find_address().0)
+        .unwrap()
+        .deposited_amount
+}
+
+fn warp_to(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so and escrow_app.so, run anchor build first"]
+fn a_withdrawal_is_locked_in_escrow_and_released_to_the_recipient() {
+    let (mut svm, _) = bank_and_escrow();
+    let user = wallet(&mut svm, 5 * SOL);
+    let recipient = Pubkey::new_unique();
+    send(&mut svm, deposit(&user.pubkey(), 2 * SOL, None), &user).unwrap();
+
+    let unlock_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+    send(
+        &mut svm,
+        withdraw_to_escrow(&user.pubkey(), SOL, &recipient, unlock_at),
+        &user,
+    )
+    .unwrap();
+
+    // the bank counts it as withdrawn, and the SOL sits in the escrow
+    assert_eq!(deposited(&svm, &user.pubkey()), SOL);
+    assert_vault_matches_accounting(&svm);
+    let escrow = escrow_address(&user.pubkey(), &recipient);
+    assert!(svm.get_balance(&escrow).unwrap() > SOL);
+
+    // anyone can release it, but not before unlock_at
+    let stranger = wallet(&mut svm, SOL);
+    assert!(send(&mut svm, release(&user.pubkey(), &recipient), &stranger).is_err());
+
+    warp_to(&mut svm, unlock_at);
+    send(&mut svm, release(&user.pubkey(), &recipient), &stranger).unwrap();
+    assert_eq!(svm.get_balance(&recipient).unwrap(), SOL);
+    assert_eq!(svm.get_balance(&escrow).unwrap_or(0), 0);
+}
+
+#[test]
+#[ignore = "needs target/deploy/bank_app.so and escrow_app.so, run anchor build first"]
+fn a_failed_lock_rolls_the_withdrawal_back() {
+    let (mut svm, _) = bank_and_escrow();
+    let user = wallet(&mut svm, 5 * SOL);
+    let recipient = Pubkey::new_unique();
+    send(&mut svm, deposit(&user.pubkey(), 2 * SOL, None), &user).unwrap();
+    let unlock_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+    send(
+        &mut svm,
+        withdraw_to_escrow(&user.pubkey(), SOL, &recipient, unlock_at),
+        &user,
+    )
+    .unwrap();
+
+    // the escrow for this recipient already exists, so escrow_app's init fails
+    // and the vault's transfer to the user goes with it
+    let balance = svm.get_balance(&user.pubkey()).unwrap();
+    assert!(send(
+        &mut svm,
+        withdraw_to_escrow(&user.pubkey(), SOL / 2, &recipient, unlock_at),
+        &user,
+    )
+    .is_err());
+
+    assert_eq!(deposited(&svm, &user.pubkey()), SOL);
+    assert_vault_matches_accounting(&svm);
+    // the half SOL the vault sent never stayed with the user
+    assert!(svm.get_balance(&user.pubkey()).unwrap() <= balance);
+}
@@ -0,0 +1,163 @@
+use bank_app_types::{
+    constants::MAX_ORACLE_STALENESS_SLOTS, BankAppError, BankInfo, BankPda, TokenConfig,
+    UserReserve,
+};
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_clock::Clock;
+use solana_pubkey::Pubkey;
+use test_utils::{
+    drain_vault, fill_deposit_cap, lock_reserve, pause_bank, read_bank_account, set_bank_account,
+    stale_oracle,
+};
+
+const RENT_FLOOR: u64 = 890_880;
+
+fn bank(token_configs: Vec<TokenConfig>) -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    set_bank_account(
+        &mut svm,
+        BankPda::BankInfo.find_address().0,
+        &BankInfo {
+            total_deposited: 5_000_000,
+            token_configs,
+            rent_floor: RENT_FLOOR,
+            ..Default::default()
+        },
+    );
+    svm.set_account(
+        BankPda::BankVault.find_address().0,
+        Account {
+            lamports: RENT_FLOOR + 5_000_000,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    svm
+}
+
+fn bank_info(svm: &LiteSVM) -> BankInfo {
+    read_bank_account(svm, &BankPda::BankInfo.find_address().0).unwrap()
+}
+
+#[test]
+fn pauses_the_bank() {
+    let mut svm = bank(vec![]);
+    assert_eq!(pause_bank(&mut svm), BankAppError::BankAppPaused);
+    assert!(bank_info(&svm).is_paused);
+}
+
+#[test]
+fn fills_the_deposit_cap_of_one_mint() {
+    let (mint, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut svm = bank(vec![
+        TokenConfig {
+            mint,
+            total_deposited: 700,
+            ..Default::default()
+        },
+        TokenConfig {
+            mint: other,
+            ..Default::default()
+        },
+    ]);
+
+    assert_eq!(
+        fill_deposit_cap(&mut svm, &mint),
+        BankAppError::DepositCapExceeded
+    );
+    let configs = bank_info(&svm).token_configs;
+    assert_eq!(
+        (configs[0].total_deposited, configs[0].deposit_cap),
+        (700, 700)
+    );
+    assert_eq!(configs[1].deposit_cap, 0);
+
+    // an empty mint can't be capped at 0, that's no cap
+    fill_deposit_cap(&mut svm, &other);
+    let configs = bank_info(&svm).token_configs;
+    assert_eq!((configs[1].total_deposited, configs[1].deposit_cap), (1, 1));
+}
+
+#[test]
+fn drains_the_vault_to_its_rent_floor() {
+    let mut svm = bank(vec![]);
+    assert_eq!(drain_vault(&mut svm), BankAppError::VaultBelowRentFloor);
+
+    let vault = svm
+        .get_account(&BankPda::BankVault.find_address().0)
+        .unwrap();
+    assert_eq!(vault.lamports, RENT_FLOOR);
+    // the accounting is left alone, the bank now owes more than it holds
+    assert_eq!(bank_info(&svm).total_deposited, 5_000_000);
+}
+
+#[test]
+fn moves_the_clock_past_the_staleness_bound() {
+    let (mint, patient) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut svm = bank(vec![
+        TokenConfig {
+            mint,
+            ..Default::default()
+        },
+        TokenConfig {
+            mint: patient,
+            max_staleness_slots: 1_000,
+            ..Default::default()
+        },
+    ]);
+
+    let slot = svm.get_sysvar::<Clock>().slot;
+    assert_eq!(
+        stale_oracle(&mut svm, &mint),
+        BankAppError::StaleOraclePrice
+    );
+    assert_eq!(
+        svm.get_sysvar::<Clock>().slot,
+        slot + MAX_ORACLE_STALENESS_SLOTS + 1
+    );
+
+    let slot = svm.get_sysvar::<Clock>().slot;
+    stale_oracle(&mut svm, &patient);
+    assert_eq!(svm.get_sysvar::<Clock>().slot, slot + 1_001);
+}
+
+#[test]
+fn locks_a_pending_withdrawal() {
+    let mut svm = bank(vec![]);
+    let user = Pubkey::new_unique();
+    let reserve = BankPda::UserReserve { user }.find_address().0;
+    set_bank_account(
+        &mut svm,
+        reserve,
+        &UserReserve {
+            deposited_amount: 1_000,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        lock_reserve(&mut svm, &user, 400),
+        BankAppError::WithdrawalLocked
+    );
+    let locked = read_bank_account::<UserReserve>(&svm, &reserve).unwrap();
+    assert_eq!(locked.pending_withdrawal, 400);
+    assert!(locked.unlock_at > svm.get_sysvar::<Clock>().unix_timestamp);
+}
+
+#[test]
+#[should_panic(expected = "can't lock 1001")]
+fn cant_lock_more_than_was_deposited() {
+    let mut svm = bank(vec![]);
+    let user = Pubkey::new_unique();
+    set_bank_account(
+        &mut svm,
+        BankPda::UserReserve { user }.find_address().0,
+        &UserReserve {
+            deposited_amount: 1_000,
+            ..Default::default()
+        },
+    );
+
+    lock_reserve(&mut svm, &user, 1_001);
+}
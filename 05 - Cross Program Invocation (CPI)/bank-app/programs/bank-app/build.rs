@@ -0,0 +1,24 @@
+use std::{env, process::Command};
+
+// bakes the git commit into the program for program_info and security.txt.
+// A verifiable build (solana-verify) sets BANK_APP_GIT_COMMIT itself
+fn main() {
+    println!("cargo:rerun-if-env-changed=BANK_APP_GIT_COMMIT");
+
+    let commit = env::var("BANK_APP_GIT_COMMIT").unwrap_or_else(|_| {
+        if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+            println!("cargo:rerun-if-changed={git_dir}/HEAD");
+            println!("cargo:rerun-if-changed={git_dir}/refs");
+        }
+        git(&["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".to_string())
+    });
+    println!("cargo:rustc-env=BANK_APP_GIT_COMMIT={commit}");
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
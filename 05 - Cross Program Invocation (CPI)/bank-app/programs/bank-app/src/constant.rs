@@ -1,6 +1,230 @@
 use anchor_lang::prelude::*;
 
+// #[constant] puts a constant into the IDL, clients read seeds and limits from there.
+// The IDL has no usize, so limits are the smallest unsigned type that fits
+
 #[constant]
 pub const BANK_INFO_SEED: &[u8] = b"BANK_INFO_SEED";
+#[constant]
 pub const BANK_VAULT_SEED: &[u8] = b"BANK_VAULT_SEED";
+#[constant]
 pub const USER_RESERVE_SEED: &[u8] = b"USER_RESERVE_SEED";
+#[constant]
+pub const SNAPSHOT_SEED: &[u8] = b"SNAPSHOT_SEED";
+#[constant]
+pub const CLAIM_BITMAP_SEED: &[u8] = b"CLAIM_BITMAP_SEED";
+#[constant]
+pub const LUCKY_TICKET_SEED: &[u8] = b"LUCKY_TICKET_SEED";
+// the slothash draw keeps its own ticket, so it never blocks the VRF draw
+#[constant]
+pub const SLOTHASH_TICKET_SEED: &[u8] = b"SLOTHASH_TICKET_SEED";
+#[constant]
+pub const EMITTER_SEED: &[u8] = b"emitter";
+#[constant]
+pub const WORMHOLE_MESSAGE_SEED: &[u8] = b"WORMHOLE_MESSAGE_SEED";
+#[constant]
+pub const FOREIGN_EMITTER_SEED: &[u8] = b"FOREIGN_EMITTER_SEED";
+#[constant]
+pub const RECEIVED_VAA_SEED: &[u8] = b"RECEIVED_VAA_SEED";
+#[constant]
+pub const DEPOSIT_COUNTER_SEED: &[u8] = b"DEPOSIT_COUNTER_SEED";
+#[constant]
+pub const DEPOSIT_RECORD_SEED: &[u8] = b"DEPOSIT_RECORD_SEED";
+#[constant]
+pub const BADGE_CONFIG_SEED: &[u8] = b"BADGE_CONFIG_SEED";
+#[constant]
+pub const BADGE_TREE_AUTHORITY_SEED: &[u8] = b"BADGE_TREE_AUTHORITY_SEED";
+#[constant]
+pub const MILESTONE_BADGES_SEED: &[u8] = b"MILESTONE_BADGES_SEED";
+#[constant]
+pub const RECONCILIATION_SEED: &[u8] = b"RECONCILIATION_SEED";
+#[constant]
+pub const OP_RECORD_SEED: &[u8] = b"OP_RECORD_SEED";
+#[constant]
+pub const OPS_QUEUE_SEED: &[u8] = b"OPS_QUEUE_SEED";
+#[constant]
+pub const VERSION_BEACON_SEED: &[u8] = b"VERSION_BEACON_SEED";
+#[constant]
+pub const GOAL_SEED: &[u8] = b"GOAL_SEED";
+#[constant]
+pub const RECURRING_DEPOSIT_SEED: &[u8] = b"RECURRING_DEPOSIT_SEED";
+#[constant]
+pub const JOINT_RESERVE_SEED: &[u8] = b"JOINT_RESERVE_SEED";
+#[constant]
+pub const INHERITANCE_SEED: &[u8] = b"INHERITANCE_SEED";
+#[constant]
+pub const INSURANCE_FUND_SEED: &[u8] = b"INSURANCE_FUND_SEED";
+#[constant]
+pub const WITHDRAWAL_WINDOW_SEED: &[u8] = b"WITHDRAWAL_WINDOW_SEED";
+#[constant]
+pub const CAMPAIGN_SEED: &[u8] = b"CAMPAIGN_SEED";
+#[constant]
+pub const CAMPAIGN_MATCH_SEED: &[u8] = b"CAMPAIGN_MATCH_SEED";
+#[constant]
+pub const USER_VAULT_SEED: &[u8] = b"USER_VAULT_SEED";
+#[constant]
+pub const CPI_ALLOWLIST_SEED: &[u8] = b"CPI_ALLOWLIST_SEED";
+#[constant]
+pub const RECEIPT_MINT_SEED: &[u8] = b"RECEIPT_MINT_SEED";
+#[constant]
+pub const DENYLIST_SEED: &[u8] = b"DENYLIST_SEED";
+#[constant]
+pub const PERMIT_NONCE_SEED: &[u8] = b"PERMIT_NONCE_SEED";
+#[constant]
+pub const LOOKUP_TABLE_SEED: &[u8] = b"LOOKUP_TABLE_SEED";
+#[constant]
+pub const APPROVAL_SET_SEED: &[u8] = b"APPROVAL_SET_SEED";
+#[constant]
+pub const USER_HISTORY_SEED: &[u8] = b"USER_HISTORY_SEED";
+// where the bridge pays out an inbound transfer, one escrow per VAA hash.
+// receive_cross_chain_deposit moves it on to the vault
+#[constant]
+pub const BRIDGE_ESCROW_SEED: &[u8] = b"BRIDGE_ESCROW_SEED";
+// the first bytes of every signed permit message
+#[constant]
+pub const PERMIT_DOMAIN: &[u8] = b"BANK_APP_PERMIT";
+
+#[constant]
+pub const USER_RESERVE_VERSION: u8 = 7;
+
+// the version beacon's feature bitmap, one bit per capability a client can
+// require. Bits are only ever added
+#[constant]
+pub const FEATURE_DEPOSIT_OP_ID: u64 = 1;
+#[constant]
+pub const FEATURE_OPS_QUEUE: u64 = 2;
+#[constant]
+pub const FEATURE_TOKEN_TOTALS: u64 = 4;
+#[constant]
+pub const FEATURE_RECONCILIATION: u64 = 8;
+#[constant]
+pub const FEATURE_SAVINGS_GOALS: u64 = 16;
+#[constant]
+pub const FEATURE_INTEREST_ACCRUAL: u64 = 32;
+#[constant]
+pub const FEATURE_SLOT_INTEREST: u64 = 64;
+pub const PROGRAM_FEATURES: u64 = FEATURE_DEPOSIT_OP_ID
+    | FEATURE_OPS_QUEUE
+    | FEATURE_TOKEN_TOTALS
+    | FEATURE_RECONCILIATION
+    | FEATURE_SAVINGS_GOALS
+    | FEATURE_INTEREST_ACCRUAL
+    | FEATURE_SLOT_INTEREST;
+
+// the first BankAppError code, the offset in error.rs
+#[constant]
+pub const BANK_APP_ERROR_OFFSET: u32 = 7000;
+
+#[constant]
+pub const LUCKY_DRAW_ODDS: u64 = 10; // 1 in 10
+#[constant]
+pub const LUCKY_DRAW_PRIZE: u64 = 1_000_000; // 0.001 SOL
+
+#[constant]
+pub const LARGE_DEPOSIT_THRESHOLD: u64 = 100_000_000_000; // 100 SOL
+
+// bigger withdrawals have to wait in the ops queue for liquidity
+#[constant]
+pub const LARGE_WITHDRAWAL_THRESHOLD: u64 = 100_000_000_000; // 100 SOL
+
+// an array length, the IDL already has it in the OpsQueue type
+pub const OPS_QUEUE_CAPACITY: usize = 64;
+#[constant]
+pub const OP_KIND_WITHDRAW: u8 = 0;
+#[constant]
+pub const OP_KIND_DIVEST: u8 = 1;
+
+// an array length, the IDL already has it in the UserHistory type. 32 entries of
+// 24 bytes keep the account at 816 bytes, about 0.0066 SOL of rent
+pub const USER_HISTORY_CAPACITY: usize = 32;
+#[constant]
+pub const HISTORY_KIND_DEPOSIT: u8 = 0;
+#[constant]
+pub const HISTORY_KIND_WITHDRAW: u8 = 1;
+#[constant]
+pub const HISTORY_KIND_INVEST: u8 = 2;
+#[constant]
+pub const HISTORY_KIND_DIVEST: u8 = 3;
+
+// an array length, the IDL already has it in the BankInfo type
+pub const WITHDRAWAL_TIER_COUNT: usize = 3;
+// daily withdrawal limits reset at every multiple of this (UTC midnight)
+#[constant]
+pub const WITHDRAWAL_DAY: i64 = 86_400;
+
+// BankInfo.interest_rate_bps is per year of this many seconds
+#[constant]
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+// or per year of this many slots, at the 400ms target slot time
+#[constant]
+pub const SLOTS_PER_YEAR: u64 = 78_840_000;
+#[constant]
+pub const MAX_INTEREST_RATE_BPS: u16 = 10_000;
+
+// BankInfo.withdraw_cooldown can be at most this, 0 turns the cooldown off
+#[constant]
+pub const MAX_WITHDRAW_COOLDOWN: i64 = 604_800; // 7 days
+
+// set_beneficiary's inactivity_period can be at most this
+#[constant]
+pub const MAX_INACTIVITY_PERIOD: i64 = 315_360_000; // 10 years
+
+#[constant]
+pub const MAX_DEPOSIT_TAG_LEN: u8 = 32;
+#[constant]
+pub const MAX_GOAL_NAME_LEN: u8 = 32;
+
+// the receipt mint's on-mint metadata, the same limits as Metaplex
+#[constant]
+pub const MAX_RECEIPT_NAME_LEN: u8 = 32;
+#[constant]
+pub const MAX_RECEIPT_SYMBOL_LEN: u8 = 10;
+#[constant]
+pub const MAX_RECEIPT_URI_LEN: u8 = 200;
+
+// setup_recurring approves this many deposits, then the user sets it up again
+#[constant]
+pub const RECURRING_APPROVAL_PERIODS: u8 = 12;
+
+// programs a user vault may call through `execute`
+#[constant]
+pub const MAX_ALLOWED_PROGRAMS: u8 = 8;
+
+// BankInfo grows by 40 bytes per supported mint
+#[constant]
+pub const MAX_SUPPORTED_TOKENS: u8 = 16;
+
+// op records can be closed after this, long past any retry of the same transaction
+#[constant]
+pub const OP_RECORD_RETENTION: i64 = 86_400; // 1 day
+
+// reserves per reconcile_batch call, each one costs a PDA derivation
+#[constant]
+pub const MAX_RECONCILE_BATCH: u8 = 10;
+
+// wrapped SOL, swapped deposits are credited to the user's token reserve for this mint
+pub const BASE_MINT: Pubkey =
+    anchor_lang::solana_program::pubkey!("So11111111111111111111111111111111111111112");
+// Orca Whirlpool
+pub const SWAP_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+// executed swap price may be at most 1% worse than the oracle price
+#[constant]
+pub const MAX_PRICE_DEVIATION_BPS: u64 = 100;
+#[constant]
+pub const MAX_ORACLE_STALENESS_SLOTS: u64 = 25;
+// TVL, USD deposit caps and borrow limits are all in micro-dollars
+#[constant]
+pub const USD_DECIMALS: u8 = 6;
+#[constant]
+pub const MAX_BORROW_LTV_BPS: u16 = 10_000;
+
+pub const BADGE_MILESTONES: [u64; 3] = [1_000_000_000, 10_000_000_000, 100_000_000_000]; // 1/10/100 SOL
+pub const BADGE_SYMBOL: &str = "BANK";
+pub const BADGE_URI: &str = "https://example.com/bank-app/badges"; //you should put your badge metadata host here
+
+// array lengths of ApprovalSet. There is room for more ApprovalAction variants
+// than exist, so a new one doesn't grow the account
+pub const MAX_APPROVERS: usize = 5;
+pub const MAX_APPROVAL_ACTIONS: usize = 4;
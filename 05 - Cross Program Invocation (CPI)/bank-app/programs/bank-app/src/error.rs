@@ -1,7 +1,218 @@
 use anchor_lang::prelude::*;
 
-#[error_code]
+// Anchor's own errors end below 6000 and every program's custom errors start at
+// 6000 by default. Starting at 7000 keeps bank errors apart from the faucet's and
+// any other Anchor program's in CPI logs. Keep in sync with BANK_APP_ERROR_OFFSET
+#[error_code(offset = 7000)]
 pub enum BankAppError {
     #[msg("The bank app is currently paused.")]
     BankAppPaused,
+    #[msg("The snapshot epoch does not match the current epoch.")]
+    InvalidSnapshotEpoch,
+    #[msg("A rewards root has already been posted for this snapshot.")]
+    RewardsRootAlreadyPosted,
+    #[msg("No rewards root has been posted for this snapshot.")]
+    RewardsRootNotPosted,
+    #[msg("The Merkle proof does not match the posted rewards root.")]
+    InvalidMerkleProof,
+    #[msg("This reward has already been claimed.")]
+    RewardAlreadyClaimed,
+    #[msg("The claim index is outside the claim bitmap.")]
+    ClaimIndexOutOfRange,
+    #[msg("The claim exceeds the snapshot's reward budget.")]
+    RewardBudgetExceeded,
+    #[msg("The account is not a user reserve of this user.")]
+    InvalidUserReserve,
+    #[msg("The user reserve already uses the current layout.")]
+    ReserveAlreadyMigrated,
+    #[msg("The vault address is on the ed25519 curve, so it cannot be a PDA.")]
+    VaultOnCurve,
+    #[msg("The vault is not the bank vault PDA.")]
+    InvalidVault,
+    #[msg("Only depositors can enter the lucky draw.")]
+    NoDeposit,
+    #[msg("The lucky draw was already entered this epoch.")]
+    LuckyDrawUnavailable,
+    #[msg("The randomness was not committed in the previous slot.")]
+    RandomnessNotFresh,
+    #[msg("The randomness has not been revealed yet.")]
+    RandomnessNotResolved,
+    #[msg("The randomness account does not match the committed one.")]
+    RandomnessAccountMismatch,
+    #[msg("No lucky draw is waiting to be settled.")]
+    NoLuckyDrawCommitted,
+    #[msg("Only deposits above the large deposit threshold are broadcast.")]
+    DepositBelowThreshold,
+    #[msg("The Wormhole message account is not the next message PDA.")]
+    InvalidWormholeMessage,
+    #[msg("The account is not a posted VAA from the Wormhole core bridge.")]
+    InvalidVaa,
+    #[msg("The VAA was emitted by an unregistered foreign emitter.")]
+    UnknownForeignEmitter,
+    #[msg("The VAA payload is not a cross-chain deposit.")]
+    InvalidVaaPayload,
+    #[msg("Deposit tags must be 1 to 32 bytes.")]
+    InvalidDepositTag,
+    #[msg("The deposit deadline has passed.")]
+    DepositExpired,
+    #[msg("The amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("The amount is more than the user has deposited.")]
+    InsufficientDeposit,
+    #[msg("Rent must be refunded to the configured rent destination.")]
+    InvalidRentDestination,
+    #[msg("The reserve still holds a deposit.")]
+    ReserveNotEmpty,
+    #[msg("There is no badge for this milestone.")]
+    InvalidMilestone,
+    #[msg("The deposit milestone for this badge has not been reached.")]
+    MilestoneNotReached,
+    #[msg("The badge for this milestone was already minted.")]
+    BadgeAlreadyMinted,
+    #[msg("The swap route is not valid for this deposit.")]
+    InvalidSwapRoute,
+    #[msg("The swap returned less than the minimum output, slippage exceeded.")]
+    SlippageExceeded,
+    #[msg("The swap price is too far from the oracle price, the pool may be manipulated.")]
+    PriceDeviationTooHigh,
+    #[msg("The oracle price is not valid.")]
+    InvalidOraclePrice,
+    #[msg("The oracle price is too old.")]
+    StaleOraclePrice,
+    #[msg("The reconciliation is already done, start a new one.")]
+    ReconciliationDone,
+    #[msg("Reserves must come in increasing address order, after the cursor.")]
+    ReserveOutOfOrder,
+    #[msg("The batch has no reserves or more than the batch limit.")]
+    InvalidBatchSize,
+    #[msg("The op record must be passed exactly when the deposit has an op_id.")]
+    OpRecordMismatch,
+    #[msg("The op record is still within its retention period.")]
+    OpRecordTooRecent,
+    #[msg("Withdrawals above the large withdrawal threshold have to be queued.")]
+    WithdrawalTooLarge,
+    #[msg("The ops queue is full.")]
+    OpsQueueFull,
+    #[msg("There is no queued op to process.")]
+    OpsQueueEmpty,
+    #[msg("The vault does not hold enough SOL to settle the next op yet.")]
+    InsufficientLiquidity,
+    #[msg("The recipient is not the user of the next queued op.")]
+    OpRecipientMismatch,
+    #[msg("The token mint is not supported by the bank.")]
+    UnsupportedToken,
+    #[msg("The token mint is already supported.")]
+    TokenAlreadySupported,
+    #[msg("The bank supports no more token mints.")]
+    TooManySupportedTokens,
+    #[msg("The program version is not compatible with the client's expected version.")]
+    VersionMismatch,
+    #[msg("The program does not have every feature the client requires.")]
+    MissingFeatures,
+    #[msg("Deposits of this token are paused.")]
+    TokenPaused,
+    #[msg("The deposit would exceed the token's deposit cap.")]
+    DepositCapExceeded,
+    #[msg("Goal names must be 1 to 32 bytes.")]
+    InvalidGoalName,
+    #[msg("The goal is locked until its deadline.")]
+    GoalLocked,
+    #[msg("The goal doesn't hold that much.")]
+    InsufficientGoalBalance,
+    #[msg("A recurring deposit needs a positive amount and interval.")]
+    InvalidRecurringDeposit,
+    #[msg("The recurring deposit is not due yet.")]
+    RecurringNotDue,
+    #[msg("Joint owners must be two different keys, owner_a sorting first.")]
+    InvalidJointOwners,
+    #[msg("The signer is not an owner of the joint reserve.")]
+    NotJointOwner,
+    #[msg("Withdrawals above the single signer limit need both owners to sign.")]
+    CoSignerRequired,
+    #[msg("The inactivity period must be positive and at most MAX_INACTIVITY_PERIOD.")]
+    InvalidInactivityPeriod,
+    #[msg("The owner has been active within the inactivity period.")]
+    OwnerStillActive,
+    #[msg("The vault and the staked position cover every deposit.")]
+    NoShortfall,
+    #[msg("The insurance fund has nothing to pay out.")]
+    InsuranceFundEmpty,
+    #[msg("This instruction is disabled.")]
+    InstructionDisabled,
+    #[msg("Withdrawal tiers must grow in age and limit, unused tiers go last.")]
+    InvalidWithdrawalTiers,
+    #[msg("This withdrawal is over the daily limit for the reserve's age.")]
+    DailyWithdrawalLimitExceeded,
+    #[msg("A campaign needs a match ratio, a budget and an end after its start.")]
+    InvalidCampaign,
+    #[msg("The campaign can only be closed after it ends.")]
+    CampaignNotEnded,
+    #[msg("The allowlist has room for MAX_ALLOWED_PROGRAMS programs.")]
+    TooManyAllowedPrograms,
+    #[msg("The user vault may not call this program.")]
+    ProgramNotAllowed,
+    #[msg("The user vault must stay an empty system account.")]
+    UserVaultReassigned,
+    #[msg("Dry run finished, nothing was changed.")]
+    DryRunOk,
+    #[msg("Token-2022 interest rates go up to 32767 bps.")]
+    ReceiptRateTooHigh,
+    #[msg("The mint has an extension the bank can't take deposits of.")]
+    UnsupportedMintExtension,
+    #[msg("The bank's token account only accepts confidential credits.")]
+    PublicCreditsDisabled,
+    #[msg("The interest rate can be at most MAX_INTEREST_RATE_BPS.")]
+    InvalidInterestRate,
+    #[msg("The account is not the stake pool or one of its accounts.")]
+    InvalidStakePool,
+    #[msg("The receipt name must be 1-32 bytes, the symbol up to 10 and the uri up to 200.")]
+    InvalidReceiptMetadata,
+    #[msg("The price feed is missing or isn't the one configured for the mint.")]
+    InvalidPriceFeed,
+    #[msg("The borrow LTV can be at most MAX_BORROW_LTV_BPS.")]
+    InvalidBorrowLtv,
+    #[msg("The crank already ran in this slot.")]
+    CrankAlreadyRan,
+    #[msg("The transaction has no matching ed25519 signature of the permit.")]
+    InvalidPermit,
+    #[msg("The permit's deadline has passed.")]
+    PermitExpired,
+    #[msg("The permit's nonce is not the user's next nonce.")]
+    InvalidPermitNonce,
+    #[msg("The account is not the bank's address lookup table.")]
+    InvalidLookupTable,
+    #[msg("An address lookup table holds at most 256 addresses.")]
+    LookupTableFull,
+    #[msg("The cooldown can be at most MAX_WITHDRAW_COOLDOWN seconds.")]
+    InvalidWithdrawCooldown,
+    #[msg("Withdrawals go through request_withdraw while a cooldown is set.")]
+    WithdrawCooldownActive,
+    #[msg("There is no requested withdrawal to execute.")]
+    NoPendingWithdrawal,
+    #[msg("The requested withdrawal is still in its cooldown.")]
+    WithdrawalLocked,
+    #[msg("Approvers must be 1-5 distinct keys, with a threshold between 1 and their count.")]
+    InvalidApprovers,
+    #[msg("The signer is not one of the configured approvers.")]
+    NotAnApprover,
+    #[msg("The action doesn't have enough unexpired approvals.")]
+    ApprovalThresholdNotMet,
+    #[msg("The account is not owned by the bank program.")]
+    NotProgramOwned,
+    #[msg("The account is already rent exempt.")]
+    NoRentShortfall,
+    #[msg("The balance-seconds accumulator overflowed.")]
+    BalanceSecondsOverflow,
+    #[msg("The withdrawal would take the vault below its rent floor.")]
+    VaultBelowRentFloor,
+    #[msg("The bridged lamports haven't reached the bridge escrow yet.")]
+    BridgedFundsMissing,
+    #[msg("An amount overflowed.")]
+    Overflow,
+    #[msg("The SlotHashes sysvar holds no slot hash.")]
+    SlotHashesEmpty,
+    #[msg("The bank still owes deposits or holds stake pool investments.")]
+    BankNotEmpty,
+    #[msg("Only tokens above what the bank owes for the mint can be rescued.")]
+    RescueExceedsSurplus,
 }
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+// logged with emit!, crates/bank-app-types/src/events.rs has the same layouts
+
+#[event]
+pub struct InsuranceFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+#[event]
+pub struct ShortfallCovered {
+    pub shortfall: u64,
+    pub covered: u64,
+    // what's still missing when the fund couldn't cover all of it
+    pub remaining_shortfall: u64,
+    pub total_covered: u64,
+}
+
+// the outcome a dry run would have had, see DepositArgs::V4 and WithdrawOptions
+
+#[event]
+pub struct DepositDryRun {
+    pub user: Pubkey,
+    pub amount: u64,
+    // what a matching campaign would have added
+    pub bonus: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct WithdrawDryRun {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+    pub withdrawn_today: u64,
+}
+
+// one per state change an indexer follows. `mint` is Pubkey::default() for SOL
+
+#[event]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    // the bank's total for this mint
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub total_deposited: u64,
+}
+
+// vault SOL moved into or out of the staking app or a stake pool
+#[event]
+pub struct InvestEvent {
+    // the staking program, or the stake pool account
+    pub target: Pubkey,
+    pub amount: u64,
+    pub is_invest: bool,
+}
+
+#[event]
+pub struct PauseToggledEvent {
+    pub is_paused: bool,
+    pub by: Pubkey,
+}
+
+// SOL moved between a reserve and one of its goals, the vault doesn't change
+#[event]
+pub struct GoalMoveEvent {
+    pub user: Pubkey,
+    pub goal_index: u8,
+    pub amount: u64,
+    pub to_goal: bool,
+    pub deposited_amount: u64,
+    pub goal_amount: u64,
+}
+
+// paid from the vault without touching a reserve: snapshot rewards and lucky draw prizes
+#[event]
+pub struct RewardPaidEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::BankInfo};
+
+// BankInfo is sized for the tokens it has, each new one reallocs it by one
+// TokenConfig, paid by the authority. It starts unpaused, uncapped and at 0%
+#[derive(Accounts)]
+pub struct AddSupportedToken<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address(),
+        realloc = BankInfo::space(bank_info.token_configs.len() + 1),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddSupportedToken<'info> {
+    pub fn process(ctx: Context<AddSupportedToken>) -> Result<()> {
+        log_compute_units("add_supported_token: start");
+        let token_mint = ctx.accounts.token_mint.key();
+        ctx.accounts.bank_info.add_token(token_mint)?;
+
+        msg!("token {} supported", token_mint);
+        log_compute_units("add_supported_token: end");
+        Ok(())
+    }
+}
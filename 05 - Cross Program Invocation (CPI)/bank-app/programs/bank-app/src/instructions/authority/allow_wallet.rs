@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    seeds::BankPda,
+    state::{BankInfo, DenyEntry},
+};
+
+// takes a wallet off the denylist, the entry's rent goes back to the authority
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AllowWallet<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::DenyEntry { wallet }.address(),
+        close = authority,
+    )]
+    pub deny_entry: Account<'info, DenyEntry>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> AllowWallet<'info> {
+    pub fn process(_ctx: Context<AllowWallet>, wallet: Pubkey) -> Result<()> {
+        log_compute_units("allow_wallet: start");
+        msg!("{} allowed", wallet);
+        log_compute_units("allow_wallet: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    seeds::BankPda,
+    state::{ApprovalAction, ApprovalSet},
+};
+
+#[derive(Accounts)]
+pub struct ApproveAction<'info> {
+    #[account(
+        mut,
+        address = BankPda::ApprovalSet.address()
+    )]
+    pub approval_set: Box<Account<'info, ApprovalSet>>,
+
+    pub approver: Signer<'info>,
+}
+
+impl<'info> ApproveAction<'info> {
+    pub fn process(ctx: Context<ApproveAction>, action: ApprovalAction) -> Result<()> {
+        log_compute_units("approve_action: start");
+        let slot = Clock::get()?.slot;
+        let approval_set = &mut ctx.accounts.approval_set;
+        approval_set.approve(action, &ctx.accounts.approver.key(), slot)?;
+
+        msg!(
+            "{} of {} approvals",
+            approval_set.valid_approvals(action, slot),
+            approval_set.threshold
+        );
+        log_compute_units("approve_action: end");
+        Ok(())
+    }
+}
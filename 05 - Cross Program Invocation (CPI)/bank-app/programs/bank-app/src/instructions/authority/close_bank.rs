@@ -0,0 +1,77 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{ApprovalAction, ApprovalSet, BankInfo},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// winds the bank down once it owes nobody anything: the vault's lamports and
+// BankInfo's rent go to the authority. It needs M of N approvals of
+// ApprovalAction::CloseBank
+#[derive(Accounts)]
+pub struct CloseBank<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address(),
+        close = authority,
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::ApprovalSet.address()
+    )]
+    pub approval_set: Box<Account<'info, ApprovalSet>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseBank<'info> {
+    pub fn process(ctx: Context<CloseBank>) -> Result<()> {
+        log_compute_units("close_bank: start");
+        let bank_info = &ctx.accounts.bank_info;
+
+        // SOL in the stake pool is only counted in stake_pool_principal, it has
+        // to be divested before the bank can go
+        let owes_tokens = bank_info
+            .token_configs
+            .iter()
+            .any(|config| config.total_deposited > 0);
+        if bank_info.total_deposited > 0 || owes_tokens || bank_info.stake_pool_principal > 0 {
+            return Err(BankAppError::BankNotEmpty.into());
+        }
+        // checked last, a call that would fail anyway doesn't use the approvals up
+        ctx.accounts
+            .approval_set
+            .consume(ApprovalAction::CloseBank, Clock::get()?.slot)?;
+
+        // the vault holds no data, so it can be emptied down to 0 lamports
+        let swept = ctx.accounts.bank_vault.lamports();
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            swept,
+        )?;
+
+        msg!("bank closed, {} lamports swept from the vault", swept);
+        log_compute_units("close_bank: end");
+        Ok(())
+    }
+}
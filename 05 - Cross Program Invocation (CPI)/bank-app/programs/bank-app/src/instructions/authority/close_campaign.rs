@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, Campaign},
+};
+
+// the unmatched budget and the rent go back to the authority. Users' CampaignMatch
+// counters stay, they're worthless once the campaign is gone
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct CloseCampaign<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::Campaign { id: campaign_id }.address(),
+        close = authority,
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> CloseCampaign<'info> {
+    pub fn process(ctx: Context<CloseCampaign>, _campaign_id: u64) -> Result<()> {
+        log_compute_units("close_campaign: start");
+        let campaign = &ctx.accounts.campaign;
+
+        // closing early would take back a bonus users were promised
+        if Clock::get()?.unix_timestamp < campaign.end_at {
+            return Err(BankAppError::CampaignNotEnded.into());
+        }
+
+        msg!(
+            "campaign {} closed, {} matched and {} reclaimed",
+            campaign.id,
+            campaign.total_matched,
+            campaign.budget
+        );
+        log_compute_units("close_campaign: end");
+        Ok(())
+    }
+}
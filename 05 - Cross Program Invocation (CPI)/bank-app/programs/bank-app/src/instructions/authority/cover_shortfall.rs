@@ -0,0 +1,99 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    event::ShortfallCovered,
+    invariants::verify_invariants,
+    seeds::BankPda,
+    state::{ApprovalAction, ApprovalSet, BankInfo, InsuranceFund},
+};
+
+// the amount isn't an argument: the fund pays exactly the shortfall
+// verify_invariants finds, or all it has when that's less. It only pays out
+// with M of N approvals of ApprovalAction::CoverShortfall
+#[derive(Accounts)]
+pub struct CoverShortfall<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: the bank vault's UserInfo in the staking app, may not exist yet
+    #[account(
+        seeds = [b"USER_INFO", bank_vault.key().as_ref()],
+        bump,
+        seeds::program = staking_app::ID
+    )]
+    pub staking_info: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::InsuranceFund.address()
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+
+    #[account(
+        mut,
+        address = BankPda::ApprovalSet.address()
+    )]
+    pub approval_set: Box<Account<'info, ApprovalSet>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> CoverShortfall<'info> {
+    pub fn process(ctx: Context<CoverShortfall>) -> Result<()> {
+        log_compute_units("cover_shortfall: start");
+        let report = verify_invariants(
+            &ctx.accounts.bank_info,
+            &ctx.accounts.bank_vault,
+            &ctx.accounts.staking_info,
+        )?;
+        if report.shortfall == 0 {
+            return Err(BankAppError::NoShortfall.into());
+        }
+
+        // the fund keeps its rent-exempt minimum, everything above it can pay out
+        let fund_info = ctx.accounts.insurance_fund.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(fund_info.data_len());
+        let available = fund_info.lamports().saturating_sub(rent_exempt);
+        let covered = report.shortfall.min(available);
+        if covered == 0 {
+            return Err(BankAppError::InsuranceFundEmpty.into());
+        }
+        // checked last, a call that would fail anyway doesn't use the approvals up
+        ctx.accounts
+            .approval_set
+            .consume(ApprovalAction::CoverShortfall, Clock::get()?.slot)?;
+
+        // the fund is owned by this program, so it can be debited directly
+        // without a system transfer. Crediting the vault needs no signature
+        **fund_info.try_borrow_mut_lamports()? -= covered;
+        **ctx.accounts.bank_vault.try_borrow_mut_lamports()? += covered;
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_covered = insurance_fund
+            .total_covered
+            .checked_add(covered)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(ShortfallCovered {
+            shortfall: report.shortfall,
+            covered,
+            remaining_shortfall: report.shortfall - covered,
+            total_covered: insurance_fund.total_covered,
+        });
+        log_compute_units("cover_shortfall: end");
+        Ok(())
+    }
+}
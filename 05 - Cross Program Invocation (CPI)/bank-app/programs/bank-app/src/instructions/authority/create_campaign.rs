@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::CAMPAIGN_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, Campaign},
+    transfer_helper::sol_transfer_from_user,
+};
+
+// the authority pays the whole budget in up front, deposits draw it down
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct CreateCampaign<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init,
+        seeds = [CAMPAIGN_SEED, campaign_id.to_le_bytes().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Campaign>(),
+    )]
+    pub campaign: Box<Account<'info, Campaign>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCampaign<'info> {
+    pub fn process(
+        ctx: Context<CreateCampaign>,
+        campaign_id: u64,
+        match_ratio_bps: u16,
+        budget: u64,
+        per_user_cap: u64,
+        start_at: i64,
+        end_at: i64,
+    ) -> Result<()> {
+        log_compute_units("create_campaign: start");
+        if match_ratio_bps == 0 || budget == 0 || per_user_cap == 0 || end_at <= start_at {
+            return Err(BankAppError::InvalidCampaign.into());
+        }
+
+        sol_transfer_from_user(
+            &ctx.accounts.authority,
+            ctx.accounts.campaign.to_account_info(),
+            &ctx.accounts.system_program,
+            budget,
+        )?;
+
+        ctx.accounts.campaign.set_inner(Campaign {
+            id: campaign_id,
+            match_ratio_bps,
+            budget,
+            per_user_cap,
+            start_at,
+            end_at,
+            total_matched: 0,
+        });
+
+        msg!(
+            "campaign {} created with a budget of {}",
+            campaign_id,
+            budget
+        );
+        log_compute_units("create_campaign: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{associated_token, token, token_2022};
+
+use crate::{
+    compute::log_compute_units,
+    constant::LOOKUP_TABLE_SEED,
+    error::BankAppError,
+    lookup_table::{
+        self, LookupTableAccounts, ADDRESS_LOOKUP_TABLE_PROGRAM_ID, LOOKUP_TABLE_MAX_ADDRESSES,
+    },
+    seeds::BankPda,
+    state::{BankInfo, BankLookupTable},
+};
+
+// creates the bank's lookup table and fills it with the accounts most
+// instructions take: the bank PDAs, the programs and every supported mint.
+// Bank ATAs and newer mints are added with extend_lookup_table
+#[derive(Accounts)]
+#[instruction(recent_slot: u64)]
+pub struct CreateLookupTable<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [LOOKUP_TABLE_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<BankLookupTable>(),
+    )]
+    pub bank_lookup_table: Box<Account<'info, BankLookupTable>>,
+
+    ///CHECK: created by the lookup table program
+    #[account(
+        mut,
+        seeds = [bank_lookup_table.key().as_ref(), &recent_slot.to_le_bytes()],
+        bump,
+        seeds::program = ADDRESS_LOOKUP_TABLE_PROGRAM_ID
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+
+    ///CHECK:
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateLookupTable<'info> {
+    pub fn process(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        log_compute_units("create_lookup_table: start");
+        let bank_lookup_table = &mut ctx.accounts.bank_lookup_table;
+        bank_lookup_table.address = ctx.accounts.lookup_table.key();
+        bank_lookup_table.bump = ctx.bumps.bank_lookup_table;
+
+        let mut addresses = vec![
+            ctx.accounts.bank_info.key(),
+            ctx.accounts.bank_vault.key(),
+            crate::ID,
+            system_program::ID,
+            token::ID,
+            token_2022::ID,
+            associated_token::ID,
+        ];
+        addresses.extend(ctx.accounts.bank_info.token_configs.iter().map(|c| c.mint));
+        if addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return Err(BankAppError::LookupTableFull.into());
+        }
+
+        let lookup_table_seeds: &[&[&[u8]]] =
+            &[&[LOOKUP_TABLE_SEED, &[ctx.bumps.bank_lookup_table]]];
+        let lookup_table_program = ctx.accounts.address_lookup_table_program.to_account_info();
+        let accounts = || LookupTableAccounts {
+            lookup_table: ctx.accounts.lookup_table.to_account_info(),
+            lookup_table_authority: ctx.accounts.bank_lookup_table.to_account_info(),
+            payer: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        lookup_table::create_lookup_table(
+            lookup_table_program.clone(),
+            accounts(),
+            recent_slot,
+            ctx.bumps.lookup_table,
+            lookup_table_seeds,
+        )?;
+        let address_count = addresses.len();
+        lookup_table::extend_lookup_table(
+            lookup_table_program,
+            accounts(),
+            addresses,
+            lookup_table_seeds,
+        )?;
+
+        msg!(
+            "lookup table {} created with {} addresses",
+            ctx.accounts.lookup_table.key(),
+            address_count
+        );
+        log_compute_units("create_lookup_table: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, CreateAccount},
+};
+use anchor_spl::{
+    token_2022::{
+        initialize_mint2,
+        spl_token_2022::{extension::ExtensionType, state::Mint as SplMint},
+        InitializeMint2, Token2022,
+    },
+    token_2022_extensions::{
+        interest_bearing_mint_initialize, metadata_pointer_initialize,
+        non_transferable_mint_initialize, token_metadata_initialize, transfer_hook_initialize,
+        InterestBearingMintInitialize, MetadataPointerInitialize, NonTransferableMintInitialize,
+        TokenMetadataInitialize, TransferHookInitialize,
+    },
+    token_interface::Mint,
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        BANK_VAULT_SEED, MAX_RECEIPT_NAME_LEN, MAX_RECEIPT_SYMBOL_LEN, MAX_RECEIPT_URI_LEN,
+        RECEIPT_MINT_SEED,
+    },
+    error::BankAppError,
+    seeds::BankPda,
+    state::BankInfo,
+};
+
+// a Token-2022 mint per supported token, minted 1:1 on deposit_token. Wallets show
+// its balance grown by the mint's interest rate, which follows the token's
+// interest_rate_bps through sync_receipt_rate. Non-transferable, so a receipt
+// stays with the depositor, unless a transfer hook program is passed: then
+// receipts move, and the hook decides which transfers go through. The name,
+// symbol and uri are stored on the mint itself, so wallets can show them
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: String)]
+pub struct CreateReceiptMint<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK: mint and rate authority of every receipt mint
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    ///CHECK: created in process, the extensions have to be initialized before the mint
+    #[account(
+        mut,
+        seeds = [RECEIPT_MINT_SEED, token_mint.key().as_ref()],
+        bump,
+        constraint = !name.is_empty() && name.len() <= MAX_RECEIPT_NAME_LEN as usize
+            @ BankAppError::InvalidReceiptMetadata,
+        constraint = symbol.len() <= MAX_RECEIPT_SYMBOL_LEN as usize
+            @ BankAppError::InvalidReceiptMetadata,
+        constraint = uri.len() <= MAX_RECEIPT_URI_LEN as usize
+            @ BankAppError::InvalidReceiptMetadata,
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: any program implementing the transfer hook interface, e.g. receipt-hook
+    #[account(executable)]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CreateReceiptMint<'info> {
+    pub fn process(
+        ctx: Context<CreateReceiptMint>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        log_compute_units("create_receipt_mint: start");
+        let token_mint = ctx.accounts.token_mint.key();
+        let rate = ctx
+            .accounts
+            .bank_info
+            .token_config(&token_mint)?
+            .receipt_rate()?;
+        let bank_vault = ctx.accounts.bank_vault.key();
+        let receipt_mint = ctx.accounts.receipt_mint.to_account_info();
+        let token_2022_program = ctx.accounts.token_2022_program.to_account_info();
+
+        let transfer_hook_program = ctx.accounts.transfer_hook_program.as_ref().map(|p| p.key());
+        let transfer_extension = match transfer_hook_program {
+            Some(_) => ExtensionType::TransferHook,
+            None => ExtensionType::NonTransferable,
+        };
+        let space = ExtensionType::try_calculate_account_len::<SplMint>(&[
+            ExtensionType::InterestBearingConfig,
+            ExtensionType::MetadataPointer,
+            transfer_extension,
+        ])?;
+        // the metadata is variable-length, so token_metadata_initialize grows the
+        // account itself. It only pays for the bytes it already has, the rent for
+        // the TLV entry is prepaid here: type u16 + length u16, update_authority,
+        // mint, the three borsh strings and an empty additional_metadata vec
+        let metadata_len =
+            2 + 2 + 32 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 4;
+        let receipt_mint_seeds: &[&[&[u8]]] = &[&[
+            RECEIPT_MINT_SEED,
+            token_mint.as_ref(),
+            &[ctx.bumps.receipt_mint],
+        ]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: receipt_mint.clone(),
+                },
+                receipt_mint_seeds,
+            ),
+            Rent::get()?.minimum_balance(space + metadata_len),
+            space as u64,
+            &token_2022_program.key(),
+        )?;
+
+        interest_bearing_mint_initialize(
+            CpiContext::new(
+                token_2022_program.clone(),
+                InterestBearingMintInitialize {
+                    token_program_id: token_2022_program.clone(),
+                    mint: receipt_mint.clone(),
+                },
+            ),
+            Some(bank_vault),
+            rate,
+        )?;
+        // the metadata lives in the mint account, so the pointer points to itself
+        metadata_pointer_initialize(
+            CpiContext::new(
+                token_2022_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: token_2022_program.clone(),
+                    mint: receipt_mint.clone(),
+                },
+            ),
+            Some(bank_vault),
+            Some(receipt_mint.key()),
+        )?;
+        match transfer_hook_program {
+            // the vault could point the mint at another hook later, nothing does yet
+            Some(program_id) => transfer_hook_initialize(
+                CpiContext::new(
+                    token_2022_program.clone(),
+                    TransferHookInitialize {
+                        token_program_id: token_2022_program.clone(),
+                        mint: receipt_mint.clone(),
+                    },
+                ),
+                Some(bank_vault),
+                Some(program_id),
+            )?,
+            None => non_transferable_mint_initialize(CpiContext::new(
+                token_2022_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: token_2022_program.clone(),
+                    mint: receipt_mint.clone(),
+                },
+            ))?,
+        }
+        initialize_mint2(
+            CpiContext::new(
+                token_2022_program.clone(),
+                InitializeMint2 {
+                    mint: receipt_mint.clone(),
+                },
+            ),
+            ctx.accounts.token_mint.decimals,
+            &bank_vault,
+            None,
+        )?;
+
+        // after initialize_mint2, the mint authority has to sign
+        let vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_2022_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: token_2022_program,
+                    mint: receipt_mint.clone(),
+                    metadata: receipt_mint,
+                    mint_authority: ctx.accounts.bank_vault.to_account_info(),
+                    update_authority: ctx.accounts.bank_vault.to_account_info(),
+                },
+                vault_seeds,
+            ),
+            name,
+            symbol,
+            uri,
+        )?;
+
+        msg!("receipt mint for {} at {} bps", token_mint, rate);
+        log_compute_units("create_receipt_mint: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::DENYLIST_SEED,
+    seeds::BankPda,
+    state::{BankInfo, DenyEntry},
+};
+
+// puts a wallet on the denylist. Receipt mints created with receipt-hook refuse
+// transfers from or to it, denying it twice fails because the entry exists
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct DenyWallet<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init,
+        seeds = [DENYLIST_SEED, wallet.as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DenyEntry>(),
+    )]
+    pub deny_entry: Account<'info, DenyEntry>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DenyWallet<'info> {
+    pub fn process(ctx: Context<DenyWallet>, wallet: Pubkey) -> Result<()> {
+        log_compute_units("deny_wallet: start");
+        let deny_entry = &mut ctx.accounts.deny_entry;
+        deny_entry.wallet = wallet;
+        deny_entry.denied_at = Clock::get()?.unix_timestamp;
+
+        msg!("{} denied", wallet);
+        log_compute_units("deny_wallet: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::{clock, stake_history},
+    system_program,
+};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    event::InvestEvent,
+    seeds::BankPda,
+    stake_pool::{
+        self, StakePoolInfo, WithdrawSolAccounts, STAKE_POOL_PROGRAM_ID, STAKE_PROGRAM_ID,
+    },
+    state::{BankInfo, BankInstruction},
+};
+
+// the way back from invest_stake_pool: burns pool tokens from the vault's ATA and
+// takes SOL out of the pool's reserve into the vault
+#[derive(Accounts)]
+pub struct DivestStakePool<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: checked by StakePoolInfo::load, the accounts below are checked against it
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    ///CHECK:
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_pool_ata: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = STAKE_PROGRAM_ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DivestStakePool<'info> {
+    pub fn process(
+        ctx: Context<DivestStakePool>,
+        pool_tokens: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        log_compute_units("divest_stake_pool: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DivestStakePool)?;
+
+        let pool_tokens_held = ctx.accounts.bank_pool_ata.amount;
+        if pool_tokens == 0 || pool_tokens > pool_tokens_held {
+            return Err(BankAppError::InvalidAmount.into());
+        }
+
+        let pool = StakePoolInfo::load(&ctx.accounts.stake_pool)?;
+        if ctx.accounts.stake_pool_withdraw_authority.key()
+            != pool.withdraw_authority(&ctx.accounts.stake_pool.key())?
+            || ctx.accounts.reserve_stake.key() != pool.reserve_stake
+            || ctx.accounts.manager_fee_account.key() != pool.manager_fee_account
+            || ctx.accounts.pool_mint.key() != pool.pool_mint
+            || pool.token_program_id != ctx.accounts.token_program.key()
+        {
+            return Err(BankAppError::InvalidStakePool.into());
+        }
+
+        let divest_vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        let vault_before = ctx.accounts.bank_vault.lamports();
+
+        stake_pool::withdraw_sol(
+            ctx.accounts.stake_pool_program.to_account_info(),
+            WithdrawSolAccounts {
+                stake_pool: ctx.accounts.stake_pool.to_account_info(),
+                withdraw_authority: ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+                pool_tokens_authority: ctx.accounts.bank_vault.to_account_info(),
+                pool_tokens_from: ctx.accounts.bank_pool_ata.to_account_info(),
+                reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+                lamports_to: ctx.accounts.bank_vault.to_account_info(),
+                manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+                pool_mint: ctx.accounts.pool_mint.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+                stake_history: ctx.accounts.stake_history.to_account_info(),
+                stake_program: ctx.accounts.stake_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            pool_tokens,
+            divest_vault_seeds,
+        )?;
+
+        // slippage: the pool's rate and withdrawal fee can change between the
+        // quote and this transaction
+        let amount_out = ctx
+            .accounts
+            .bank_vault
+            .lamports()
+            .checked_sub(vault_before)
+            .ok_or(BankAppError::Overflow)?;
+        if amount_out < min_amount_out {
+            return Err(BankAppError::SlippageExceeded.into());
+        }
+
+        // the burned tokens' share of the principal, anything above it is yield
+        let bank_info = &mut ctx.accounts.bank_info;
+        let principal_out = (bank_info.stake_pool_principal as u128 * pool_tokens as u128
+            / pool_tokens_held as u128) as u64;
+        bank_info.stake_pool_principal = bank_info
+            .stake_pool_principal
+            .checked_sub(principal_out)
+            .ok_or(BankAppError::Overflow)?;
+
+        msg!(
+            "divested {} pool tokens for {} lamports, {} of it principal",
+            pool_tokens,
+            amount_out,
+            principal_out
+        );
+        emit!(InvestEvent {
+            target: ctx.accounts.stake_pool.key(),
+            amount: amount_out,
+            is_invest: false,
+        });
+        log_compute_units("divest_stake_pool: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::LOOKUP_TABLE_SEED,
+    error::BankAppError,
+    lookup_table::{
+        self, LookupTableAccounts, ADDRESS_LOOKUP_TABLE_PROGRAM_ID, LOOKUP_TABLE_MAX_ADDRESSES,
+    },
+    seeds::BankPda,
+    state::{BankInfo, BankLookupTable},
+};
+
+// adds addresses to the bank's lookup table, the ones already in it are skipped.
+// Added addresses can be looked up from the next slot on
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        address = BankPda::BankLookupTable.address_with_bump(bank_lookup_table.bump)
+    )]
+    pub bank_lookup_table: Box<Account<'info, BankLookupTable>>,
+
+    ///CHECK: the table bank_lookup_table created
+    #[account(
+        mut,
+        address = bank_lookup_table.address @ BankAppError::InvalidLookupTable
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+
+    ///CHECK:
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExtendLookupTable<'info> {
+    pub fn process(ctx: Context<ExtendLookupTable>, new_addresses: Vec<Pubkey>) -> Result<()> {
+        log_compute_units("extend_lookup_table: start");
+        let existing = lookup_table::lookup_table_addresses(&ctx.accounts.lookup_table)?;
+
+        let mut addresses: Vec<Pubkey> = Vec::new();
+        for address in new_addresses {
+            if !existing.contains(&address) && !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        // the lookup table program rejects an empty extension
+        if addresses.is_empty() {
+            msg!("all addresses are already in the lookup table");
+            return Ok(());
+        }
+        if existing.len() + addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return Err(BankAppError::LookupTableFull.into());
+        }
+
+        let added = addresses.len();
+        let lookup_table_seeds: &[&[&[u8]]] =
+            &[&[LOOKUP_TABLE_SEED, &[ctx.accounts.bank_lookup_table.bump]]];
+        lookup_table::extend_lookup_table(
+            ctx.accounts.address_lookup_table_program.to_account_info(),
+            LookupTableAccounts {
+                lookup_table: ctx.accounts.lookup_table.to_account_info(),
+                lookup_table_authority: ctx.accounts.bank_lookup_table.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            addresses,
+            lookup_table_seeds,
+        )?;
+
+        msg!(
+            "{} addresses added, {} in the lookup table",
+            added,
+            existing.len() + added
+        );
+        log_compute_units("extend_lookup_table: end");
+        Ok(())
+    }
+}
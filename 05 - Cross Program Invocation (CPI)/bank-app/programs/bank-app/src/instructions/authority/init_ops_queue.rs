@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::OPS_QUEUE_SEED,
+    seeds::BankPda,
+    state::{BankInfo, OpsQueue},
+};
+
+#[derive(Accounts)]
+pub struct InitOpsQueue<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init,
+        seeds = [OPS_QUEUE_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<OpsQueue>(),
+    )]
+    pub ops_queue: AccountLoader<'info, OpsQueue>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitOpsQueue<'info> {
+    pub fn process(ctx: Context<InitOpsQueue>) -> Result<()> {
+        log_compute_units("init_ops_queue: start");
+        ctx.accounts.ops_queue.load_init()?;
+        log_compute_units("init_ops_queue: end");
+        Ok(())
+    }
+}
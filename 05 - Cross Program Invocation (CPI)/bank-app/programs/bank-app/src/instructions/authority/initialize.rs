@@ -1,8 +1,10 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED},
-    state::BankInfo,
+    compute::log_compute_units,
+    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, VERSION_BEACON_SEED},
+    state::{BankInfo, VersionBeacon},
+    version::{write_bank_version, write_version_beacon},
 };
 
 #[derive(Accounts)]
@@ -12,7 +14,7 @@ pub struct Initialize<'info> {
         seeds = [BANK_INFO_SEED],
         bump,
         payer = authority,
-        space = 8 + std::mem::size_of::<BankInfo>(),
+        space = BankInfo::space(0),
     )]
     pub bank_info: Box<Account<'info, BankInfo>>,
 
@@ -27,6 +29,15 @@ pub struct Initialize<'info> {
     )]
     pub bank_vault: UncheckedAccount<'info>,
 
+    #[account(
+        init,
+        seeds = [VERSION_BEACON_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<VersionBeacon>(),
+    )]
+    pub version_beacon: Box<Account<'info, VersionBeacon>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -34,13 +45,19 @@ pub struct Initialize<'info> {
 
 impl<'info> Initialize<'info> {
     pub fn process(ctx: Context<Initialize>) -> Result<()> {
+        log_compute_units("initialize: start");
         let bank_info = &mut ctx.accounts.bank_info;
 
         bank_info.authority = ctx.accounts.authority.key();
         bank_info.is_paused = false;
         bank_info.bump = ctx.bumps.bank_vault;
+        bank_info.rent_floor = Rent::get()?.minimum_balance(0);
+        write_bank_version(bank_info);
+
+        write_version_beacon(&mut ctx.accounts.version_beacon)?;
 
         msg!("bank app initialized!");
+        log_compute_units("initialize: end");
         Ok(())
     }
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    bubblegum::{
+        self, CreateTreeAccounts, BUBBLEGUM_PROGRAM_ID, COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID,
+    },
+    compute::log_compute_units,
+    constant::{BADGE_CONFIG_SEED, BADGE_TREE_AUTHORITY_SEED},
+    seeds::BankPda,
+    state::{BadgeConfig, BankInfo},
+};
+
+#[derive(Accounts)]
+pub struct InitializeBadgeTree<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init,
+        seeds = [BADGE_CONFIG_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<BadgeConfig>(),
+    )]
+    pub badge_config: Box<Account<'info, BadgeConfig>>,
+
+    ///CHECK: tree creator and the only delegate allowed to mint
+    #[account(
+        seeds = [BADGE_TREE_AUTHORITY_SEED],
+        bump
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    ///CHECK: initialized by bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    ///CHECK: allocated by the client with the size for max_depth/max_buffer_size
+    #[account(mut, owner = COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+
+    ///CHECK:
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeBadgeTree<'info> {
+    pub fn process(
+        ctx: Context<InitializeBadgeTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        log_compute_units("initialize_badge_tree: start");
+        let tree_authority_seeds: &[&[u8]] =
+            &[BADGE_TREE_AUTHORITY_SEED, &[ctx.bumps.tree_authority]];
+
+        bubblegum::create_tree(
+            ctx.accounts.bubblegum_program.to_account_info(),
+            CreateTreeAccounts {
+                tree_config: ctx.accounts.tree_config.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                tree_creator: ctx.accounts.tree_authority.to_account_info(),
+                log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+                compression_program: ctx.accounts.compression_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            max_depth,
+            max_buffer_size,
+            &[tree_authority_seeds],
+        )?;
+
+        ctx.accounts.badge_config.merkle_tree = ctx.accounts.merkle_tree.key();
+
+        msg!("badge tree initialized with depth {}", max_depth);
+        log_compute_units("initialize_badge_tree: end");
+        Ok(())
+    }
+}
@@ -1,25 +1,25 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED},
-    error::BankAppError,
-    state::BankInfo,
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, HISTORY_KIND_DIVEST, HISTORY_KIND_INVEST},
+    event::InvestEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserHistory},
 };
 use staking_app::{cpi, program::StakingApp};
 
 #[derive(Accounts)]
 pub struct Invest<'info> {
     #[account(
-        seeds = [BANK_INFO_SEED],
-        bump
+        address = BankPda::BankInfo.address()
     )]
     pub bank_info: Box<Account<'info, BankInfo>>,
 
     ///CHECK:
     #[account(
         mut,
-        seeds = [BANK_VAULT_SEED],
-        bump,
+        address = BankPda::BankVault.address(),
         owner = system_program::ID
     )]
     pub bank_vault: UncheckedAccount<'info>,
@@ -35,12 +35,26 @@ pub struct Invest<'info> {
     #[account(mut, address = bank_info.authority)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // the authority's history, if they created one with init_history
+    #[account(
+        mut,
+        address = BankPda::UserHistory { user: authority.key() }.address(),
+    )]
+    pub user_history: Option<AccountLoader<'info, UserHistory>>,
 }
 
 impl<'info> Invest<'info> {
     pub fn process(ctx: Context<Invest>, amount: u64, is_stake: bool) -> Result<()> {
-        if ctx.accounts.bank_info.is_paused {
-            return Err(BankAppError::BankAppPaused.into());
+        log_compute_units("invest: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::Invest)?;
+        // staking moves SOL out of the vault, unstaking brings it back
+        if is_stake {
+            ctx.accounts
+                .bank_info
+                .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), amount)?;
         }
 
         let invest_vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
@@ -61,6 +75,23 @@ impl<'info> Invest<'info> {
             is_stake,
         )?;
 
+        if let Some(user_history) = &ctx.accounts.user_history {
+            let kind = if is_stake {
+                HISTORY_KIND_INVEST
+            } else {
+                HISTORY_KIND_DIVEST
+            };
+            user_history
+                .load_mut()?
+                .push(kind, amount, Clock::get()?.unix_timestamp);
+        }
+
+        emit!(InvestEvent {
+            target: ctx.accounts.staking_program.key(),
+            amount,
+            is_invest: is_stake,
+        });
+        log_compute_units("invest: end");
         Ok(())
     }
 }
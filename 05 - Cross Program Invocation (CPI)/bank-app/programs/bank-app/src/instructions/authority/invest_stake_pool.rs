@@ -0,0 +1,144 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    event::InvestEvent,
+    seeds::BankPda,
+    stake_pool::{self, DepositSolAccounts, StakePoolInfo, STAKE_POOL_PROGRAM_ID},
+    state::{BankInfo, BankInstruction},
+};
+
+// puts idle vault SOL into an SPL stake pool. The pool mints its LST to the bank
+// vault's ATA, the SOL is tracked at cost in stake_pool_principal
+#[derive(Accounts)]
+pub struct InvestStakePool<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: checked by StakePoolInfo::load, the accounts below are checked against it
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    ///CHECK:
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    // also the referrer, so the referral share of the fee comes back to the bank
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = pool_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_pool_ata: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InvestStakePool<'info> {
+    pub fn process(ctx: Context<InvestStakePool>, amount: u64) -> Result<()> {
+        log_compute_units("invest_stake_pool: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::InvestStakePool)?;
+
+        if amount == 0 {
+            return Err(BankAppError::InvalidAmount.into());
+        }
+
+        let pool = StakePoolInfo::load(&ctx.accounts.stake_pool)?;
+        if ctx.accounts.stake_pool_withdraw_authority.key()
+            != pool.withdraw_authority(&ctx.accounts.stake_pool.key())?
+            || ctx.accounts.reserve_stake.key() != pool.reserve_stake
+            || ctx.accounts.manager_fee_account.key() != pool.manager_fee_account
+            || ctx.accounts.pool_mint.key() != pool.pool_mint
+            || pool.token_program_id != ctx.accounts.token_program.key()
+        {
+            return Err(BankAppError::InvalidStakePool.into());
+        }
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), amount)?;
+
+        let invest_vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        let pool_tokens_before = ctx.accounts.bank_pool_ata.amount;
+
+        stake_pool::deposit_sol(
+            ctx.accounts.stake_pool_program.to_account_info(),
+            DepositSolAccounts {
+                stake_pool: ctx.accounts.stake_pool.to_account_info(),
+                withdraw_authority: ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+                reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+                lamports_from: ctx.accounts.bank_vault.to_account_info(),
+                pool_tokens_to: ctx.accounts.bank_pool_ata.to_account_info(),
+                manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+                referrer_pool_tokens: ctx.accounts.bank_pool_ata.to_account_info(),
+                pool_mint: ctx.accounts.pool_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            amount,
+            invest_vault_seeds,
+        )?;
+
+        ctx.accounts.bank_pool_ata.reload()?;
+        let pool_tokens = ctx
+            .accounts
+            .bank_pool_ata
+            .amount
+            .checked_sub(pool_tokens_before)
+            .ok_or(BankAppError::Overflow)?;
+        ctx.accounts.bank_info.stake_pool_principal = ctx
+            .accounts
+            .bank_info
+            .stake_pool_principal
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        msg!(
+            "invested {} lamports in {} for {} pool tokens",
+            amount,
+            ctx.accounts.stake_pool.key(),
+            pool_tokens
+        );
+        emit!(InvestEvent {
+            target: ctx.accounts.stake_pool.key(),
+            amount,
+            is_invest: true,
+        });
+        log_compute_units("invest_stake_pool: end");
+        Ok(())
+    }
+}
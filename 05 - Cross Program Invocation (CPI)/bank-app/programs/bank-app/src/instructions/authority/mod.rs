@@ -1,7 +1,73 @@
+pub mod add_supported_token;
+pub mod allow_wallet;
+pub mod approve_action;
+pub mod close_bank;
+pub mod close_campaign;
+pub mod cover_shortfall;
+pub mod create_campaign;
+pub mod create_lookup_table;
+pub mod create_receipt_mint;
+pub mod deny_wallet;
+pub mod divest_stake_pool;
+pub mod extend_lookup_table;
+pub mod init_ops_queue;
 pub mod initialize;
+pub mod initialize_badge_tree;
 pub mod invest;
-// pub mod pause;
+pub mod invest_stake_pool;
+pub mod pause;
+pub mod post_snapshot_root;
+pub mod queue_divest;
+pub mod register_foreign_emitter;
+pub mod rescue_tokens;
+pub mod set_accrual_mode;
+pub mod set_approvers;
+pub mod set_cpi_allowlist;
+pub mod set_guardian;
+pub mod set_instruction_enabled;
+pub mod set_interest_clock;
+pub mod set_interest_rate;
+pub mod set_rent_destination;
+pub mod set_token_config;
+pub mod set_token_oracle;
+pub mod set_withdraw_cooldown;
+pub mod set_withdrawal_tiers;
+pub mod start_reconciliation;
+pub mod sync_version_beacon;
 
+pub use add_supported_token::*;
+pub use allow_wallet::*;
+pub use approve_action::*;
+pub use close_bank::*;
+pub use close_campaign::*;
+pub use cover_shortfall::*;
+pub use create_campaign::*;
+pub use create_lookup_table::*;
+pub use create_receipt_mint::*;
+pub use deny_wallet::*;
+pub use divest_stake_pool::*;
+pub use extend_lookup_table::*;
+pub use init_ops_queue::*;
 pub use initialize::*;
+pub use initialize_badge_tree::*;
 pub use invest::*;
-// pub use pause::*;
+pub use invest_stake_pool::*;
+pub use pause::*;
+pub use post_snapshot_root::*;
+pub use queue_divest::*;
+pub use register_foreign_emitter::*;
+pub use rescue_tokens::*;
+pub use set_accrual_mode::*;
+pub use set_approvers::*;
+pub use set_cpi_allowlist::*;
+pub use set_guardian::*;
+pub use set_instruction_enabled::*;
+pub use set_interest_clock::*;
+pub use set_interest_rate::*;
+pub use set_rent_destination::*;
+pub use set_token_config::*;
+pub use set_token_oracle::*;
+pub use set_withdraw_cooldown::*;
+pub use set_withdrawal_tiers::*;
+pub use start_reconciliation::*;
+pub use sync_version_beacon::*;
@@ -1 +1,34 @@
-//Your code here
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, event::PauseToggledEvent, seeds::BankPda, state::BankInfo,
+};
+
+// the emergency stop: while paused, deposit and withdraw are rejected. Only the
+// authority can unpause, the guardian can only pause
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> Pause<'info> {
+    pub fn process(ctx: Context<Pause>, is_paused: bool) -> Result<()> {
+        log_compute_units("pause: start");
+        ctx.accounts.bank_info.is_paused = is_paused;
+
+        msg!("bank app paused: {}", is_paused);
+        emit!(PauseToggledEvent {
+            is_paused,
+            by: ctx.accounts.authority.key(),
+        });
+        log_compute_units("pause: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::CLAIM_BITMAP_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, ClaimBitmap, Snapshot},
+    transfer_helper::sol_transfer_from_user,
+};
+
+// the authority pays total_rewards into the vault as it posts the root, and claims
+// can't take more than that, so rewards are never paid with depositors' SOL
+
+#[derive(Accounts)]
+#[instruction(epoch: u64, rewards_root: [u8; 32], total_rewards: u64, max_claims: u32)]
+pub struct PostSnapshotRoot<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::Snapshot { epoch }.address()
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(
+        init,
+        seeds = [CLAIM_BITMAP_SEED, snapshot.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + 32 + 4 + (max_claims as usize).div_ceil(8),
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostSnapshotRoot<'info> {
+    pub fn process(
+        ctx: Context<PostSnapshotRoot>,
+        _epoch: u64,
+        rewards_root: [u8; 32],
+        total_rewards: u64,
+        max_claims: u32,
+    ) -> Result<()> {
+        log_compute_units("post_snapshot_root: start");
+        if ctx.accounts.snapshot.rewards_root != [0u8; 32] {
+            return Err(BankAppError::RewardsRootAlreadyPosted.into());
+        }
+
+        if total_rewards > 0 {
+            sol_transfer_from_user(
+                &ctx.accounts.authority,
+                ctx.accounts.bank_vault.to_account_info(),
+                &ctx.accounts.system_program,
+                total_rewards,
+            )?;
+        }
+
+        let snapshot = &mut ctx.accounts.snapshot;
+
+        snapshot.rewards_root = rewards_root;
+        snapshot.total_rewards = total_rewards;
+
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+        claim_bitmap.snapshot = snapshot.key();
+        claim_bitmap.bits = vec![0u8; (max_claims as usize).div_ceil(8)];
+
+        msg!("rewards root posted for epoch {}", snapshot.epoch);
+        log_compute_units("post_snapshot_root: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::OP_KIND_DIVEST,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, OpsQueue, QueuedOp},
+};
+
+// pulls SOL back from the staking app when process_next_op reaches it, queued
+// withdrawals behind it can then be paid
+#[derive(Accounts)]
+pub struct QueueDivest<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::OpsQueue.address()
+    )]
+    pub ops_queue: AccountLoader<'info, OpsQueue>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> QueueDivest<'info> {
+    pub fn process(ctx: Context<QueueDivest>, amount: u64) -> Result<()> {
+        log_compute_units("queue_divest: start");
+        if amount == 0 {
+            return Err(BankAppError::InvalidAmount.into());
+        }
+
+        ctx.accounts.ops_queue.load_mut()?.push(QueuedOp {
+            user: ctx.accounts.authority.key(),
+            amount,
+            queued_at: Clock::get()?.unix_timestamp,
+            kind: OP_KIND_DIVEST,
+            _padding: [0; 7],
+        })?;
+
+        msg!("divest of {} queued", amount);
+        log_compute_units("queue_divest: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::FOREIGN_EMITTER_SEED,
+    seeds::BankPda,
+    state::{BankInfo, ForeignEmitter},
+};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterForeignEmitter<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    // one trusted emitter per chain, registering again replaces it
+    #[account(
+        init_if_needed,
+        seeds = [FOREIGN_EMITTER_SEED, chain.to_le_bytes().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ForeignEmitter>(),
+    )]
+    pub foreign_emitter: Box<Account<'info, ForeignEmitter>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterForeignEmitter<'info> {
+    pub fn process(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        log_compute_units("register_foreign_emitter: start");
+        let foreign_emitter = &mut ctx.accounts.foreign_emitter;
+        foreign_emitter.chain = chain;
+        foreign_emitter.address = address;
+
+        msg!("registered emitter for chain {}", chain);
+        log_compute_units("register_foreign_emitter: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{ApprovalAction, ApprovalSet, BankInfo},
+};
+
+// takes tokens sent to the vault's ATA by mistake back out, to a token account
+// of the authority. Only what the bank doesn't owe can leave, and only with M of
+// N approvals of ApprovalAction::RescueTokens
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = BankPda::ApprovalSet.address()
+    )]
+    pub approval_set: Box<Account<'info, ApprovalSet>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RescueTokens<'info> {
+    pub fn process(ctx: Context<RescueTokens>, amount: u64) -> Result<()> {
+        log_compute_units("rescue_tokens: start");
+        let bank_info = &ctx.accounts.bank_info;
+
+        // a supported mint keeps what its depositors are owed. Any other mint is
+        // owed nothing, except the pool tokens invest_stake_pool holds: the bank
+        // doesn't record their mint, so nothing unsupported leaves while it's invested
+        let owed = match bank_info.token_config(&ctx.accounts.token_mint.key()) {
+            Ok(config) => config.total_deposited,
+            Err(_) if bank_info.stake_pool_principal > 0 => u64::MAX,
+            Err(_) => 0,
+        };
+        let surplus = ctx.accounts.bank_ata.amount.saturating_sub(owed);
+        if amount == 0 || amount > surplus {
+            return Err(BankAppError::RescueExceedsSurplus.into());
+        }
+        // checked last, a call that would fail anyway doesn't use the approvals up
+        ctx.accounts
+            .approval_set
+            .consume(ApprovalAction::RescueTokens, Clock::get()?.slot)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bank_ata.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.bank_vault.to_account_info(),
+                },
+                pda_seeds,
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "rescued {} of {} from the vault",
+            amount,
+            ctx.accounts.token_mint.key()
+        );
+        log_compute_units("rescue_tokens: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units, constant::BANK_VAULT_SEED, seeds::BankPda, state::BankInfo,
+};
+use staking_app::{cpi, program::StakingApp, AccrualMode};
+
+// the mode lives on the bank vault's UserInfo in the staking app, the only
+// place that accrues. Switching pays the interest so far under the old mode
+#[derive(Accounts)]
+pub struct SetAccrualMode<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(mut)]
+    pub staking_info: UncheckedAccount<'info>,
+    pub staking_program: Program<'info, StakingApp>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetAccrualMode<'info> {
+    pub fn process(ctx: Context<SetAccrualMode>, compound: bool) -> Result<()> {
+        log_compute_units("set_accrual_mode: start");
+        let invest_vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        cpi::set_accrual_mode(
+            CpiContext::new_with_signer(
+                ctx.accounts.staking_program.to_account_info(),
+                cpi::accounts::SetAccrualMode {
+                    user_info: ctx.accounts.staking_info.to_account_info(),
+                    user: ctx.accounts.bank_vault.to_account_info(),
+                },
+                invest_vault_seeds,
+            ),
+            if compound {
+                AccrualMode::Compound
+            } else {
+                AccrualMode::Simple
+            },
+        )?;
+
+        msg!("accrual mode set, compound: {}", compound);
+        log_compute_units("set_accrual_mode: end");
+        Ok(())
+    }
+}
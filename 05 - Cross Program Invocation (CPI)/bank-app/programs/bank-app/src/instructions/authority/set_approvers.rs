@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::APPROVAL_SET_SEED,
+    seeds::BankPda,
+    state::{ApprovalAction, ApprovalSet, BankInfo},
+};
+
+// the authority sets the first approvers alone. From then on a change needs
+// the current approvers' sign-off, or the authority could vote in itself
+#[derive(Accounts)]
+pub struct SetApprovers<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [APPROVAL_SET_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ApprovalSet>(),
+    )]
+    pub approval_set: Box<Account<'info, ApprovalSet>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetApprovers<'info> {
+    pub fn process(
+        ctx: Context<SetApprovers>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        log_compute_units("set_approvers: start");
+        let approval_set = &mut ctx.accounts.approval_set;
+        if approval_set.is_configured() {
+            approval_set.consume(ApprovalAction::SetApprovers, Clock::get()?.slot)?;
+        }
+        approval_set.configure(&approvers, threshold, expiry_slots)?;
+
+        msg!(
+            "{} of {} approvers, approvals expire after {} slots",
+            threshold,
+            approvers.len(),
+            expiry_slots
+        );
+        log_compute_units("set_approvers: end");
+        Ok(())
+    }
+}
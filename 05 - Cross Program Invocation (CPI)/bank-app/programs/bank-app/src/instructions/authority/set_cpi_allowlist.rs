@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{CPI_ALLOWLIST_SEED, MAX_ALLOWED_PROGRAMS},
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, CpiAllowlist},
+};
+
+// replaces the whole list, an empty list switches `execute` off
+#[derive(Accounts)]
+pub struct SetCpiAllowlist<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump,
+        payer = authority,
+        space = CpiAllowlist::SPACE,
+    )]
+    pub cpi_allowlist: Box<Account<'info, CpiAllowlist>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetCpiAllowlist<'info> {
+    pub fn process(ctx: Context<SetCpiAllowlist>, programs: Vec<Pubkey>) -> Result<()> {
+        log_compute_units("set_cpi_allowlist: start");
+        if programs.len() > MAX_ALLOWED_PROGRAMS as usize {
+            return Err(BankAppError::TooManyAllowedPrograms.into());
+        }
+        // the bank signing for a user vault inside its own instructions is never allowed
+        if programs.contains(&crate::ID) {
+            return Err(BankAppError::ProgramNotAllowed.into());
+        }
+
+        ctx.accounts.cpi_allowlist.programs = programs;
+
+        msg!(
+            "{} programs allowed",
+            ctx.accounts.cpi_allowlist.programs.len()
+        );
+        log_compute_units("set_cpi_allowlist: end");
+        Ok(())
+    }
+}
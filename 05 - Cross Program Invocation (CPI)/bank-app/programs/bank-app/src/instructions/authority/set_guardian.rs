@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::BankInfo};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetGuardian<'info> {
+    pub fn process(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        log_compute_units("set_guardian: start");
+        ctx.accounts.bank_info.guardian = guardian;
+
+        msg!("guardian set to {}", guardian);
+        log_compute_units("set_guardian: end");
+        Ok(())
+    }
+}
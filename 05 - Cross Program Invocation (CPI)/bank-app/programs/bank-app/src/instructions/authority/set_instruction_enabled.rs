@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction},
+};
+
+// switches a single instruction off (or back on) without an upgrade, the rest
+// of the bank keeps running
+#[derive(Accounts)]
+pub struct SetInstructionEnabled<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetInstructionEnabled<'info> {
+    pub fn process(
+        ctx: Context<SetInstructionEnabled>,
+        instruction: BankInstruction,
+        enabled: bool,
+    ) -> Result<()> {
+        log_compute_units("set_instruction_enabled: start");
+        let bank_info = &mut ctx.accounts.bank_info;
+        bank_info.set_instruction_enabled(instruction, enabled);
+
+        msg!(
+            "disabled instructions: {:#b}",
+            bank_info.disabled_instructions
+        );
+        log_compute_units("set_instruction_enabled: end");
+        Ok(())
+    }
+}
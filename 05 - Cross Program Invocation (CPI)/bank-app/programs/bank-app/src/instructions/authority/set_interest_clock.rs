@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    seeds::BankPda,
+    state::{BankInfo, InterestClock},
+};
+
+// every accrual stamps both the time and the slot, so reserves switch clocks
+// on their next accrual without paying any stretch twice
+#[derive(Accounts)]
+pub struct SetInterestClock<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetInterestClock<'info> {
+    pub fn process(ctx: Context<SetInterestClock>, interest_clock: InterestClock) -> Result<()> {
+        log_compute_units("set_interest_clock: start");
+        ctx.accounts.bank_info.interest_clock = interest_clock;
+
+        msg!(
+            "interest counted by {}",
+            match interest_clock {
+                InterestClock::Timestamp => "timestamp",
+                InterestClock::Slot => "slot",
+            }
+        );
+        log_compute_units("set_interest_clock: end");
+        Ok(())
+    }
+}
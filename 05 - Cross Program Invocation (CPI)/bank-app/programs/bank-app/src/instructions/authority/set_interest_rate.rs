@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, constant::MAX_INTEREST_RATE_BPS, error::BankAppError,
+    seeds::BankPda, state::BankInfo,
+};
+
+// the yearly rate for SOL reserves. A reserve accrues on its next deposit or
+// withdraw, so the time since its last accrual is paid at the new rate
+#[derive(Accounts)]
+pub struct SetInterestRate<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetInterestRate<'info> {
+    pub fn process(ctx: Context<SetInterestRate>, interest_rate_bps: u16) -> Result<()> {
+        log_compute_units("set_interest_rate: start");
+        if interest_rate_bps > MAX_INTEREST_RATE_BPS {
+            return Err(BankAppError::InvalidInterestRate.into());
+        }
+        ctx.accounts.bank_info.interest_rate_bps = interest_rate_bps;
+
+        msg!("interest rate set to {} bps a year", interest_rate_bps);
+        log_compute_units("set_interest_rate: end");
+        Ok(())
+    }
+}
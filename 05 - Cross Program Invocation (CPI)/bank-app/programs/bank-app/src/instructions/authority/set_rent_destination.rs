@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::BankInfo};
+
+#[derive(Accounts)]
+pub struct SetRentDestination<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetRentDestination<'info> {
+    pub fn process(ctx: Context<SetRentDestination>, rent_destination: Pubkey) -> Result<()> {
+        log_compute_units("set_rent_destination: start");
+        ctx.accounts.bank_info.rent_destination = rent_destination;
+
+        msg!("rent destination set to {}", rent_destination);
+        log_compute_units("set_rent_destination: end");
+        Ok(())
+    }
+}
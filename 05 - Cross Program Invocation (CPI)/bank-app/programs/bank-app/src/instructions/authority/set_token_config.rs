@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::BankInfo};
+
+// a cap below the current total only stops new deposits, nothing is forced out
+#[derive(Accounts)]
+pub struct SetTokenConfig<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetTokenConfig<'info> {
+    pub fn process(
+        ctx: Context<SetTokenConfig>,
+        interest_rate_bps: u16,
+        deposit_cap: u64,
+        is_paused: bool,
+    ) -> Result<()> {
+        log_compute_units("set_token_config: start");
+        let token_mint = ctx.accounts.token_mint.key();
+        let config = ctx.accounts.bank_info.token_config_mut(&token_mint)?;
+        config.interest_rate_bps = interest_rate_bps;
+        config.deposit_cap = deposit_cap;
+        config.is_paused = is_paused;
+
+        msg!(
+            "token {} config: {} bps, cap {}, paused {}",
+            token_mint,
+            interest_rate_bps,
+            deposit_cap,
+            is_paused
+        );
+        log_compute_units("set_token_config: end");
+        Ok(())
+    }
+}
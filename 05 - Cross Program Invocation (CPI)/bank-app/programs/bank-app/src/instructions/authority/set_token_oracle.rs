@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    compute::log_compute_units, constant::MAX_BORROW_LTV_BPS, error::BankAppError, seeds::BankPda,
+    state::BankInfo,
+};
+
+// prices a supported mint with its own feed and staleness bound. A default
+// price_feed unprices it again, then the USD cap and LTV have to be 0 too
+#[derive(Accounts)]
+pub struct SetTokenOracle<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetTokenOracle<'info> {
+    pub fn process(
+        ctx: Context<SetTokenOracle>,
+        price_feed: Pubkey,
+        max_staleness_slots: u64,
+        deposit_cap_usd: u64,
+        borrow_ltv_bps: u16,
+    ) -> Result<()> {
+        log_compute_units("set_token_oracle: start");
+        if borrow_ltv_bps > MAX_BORROW_LTV_BPS {
+            return Err(BankAppError::InvalidBorrowLtv.into());
+        }
+        if price_feed == Pubkey::default() && (deposit_cap_usd != 0 || borrow_ltv_bps != 0) {
+            return Err(BankAppError::InvalidPriceFeed.into());
+        }
+
+        let token_mint = ctx.accounts.token_mint.key();
+        let decimals = ctx.accounts.token_mint.decimals;
+        let config = ctx.accounts.bank_info.token_config_mut(&token_mint)?;
+        config.price_feed = price_feed;
+        config.max_staleness_slots = max_staleness_slots;
+        config.decimals = decimals;
+        config.deposit_cap_usd = deposit_cap_usd;
+        config.borrow_ltv_bps = borrow_ltv_bps;
+
+        msg!(
+            "token {} oracle: feed {}, {} slots, usd cap {}, ltv {} bps",
+            token_mint,
+            price_feed,
+            max_staleness_slots,
+            deposit_cap_usd,
+            borrow_ltv_bps
+        );
+        log_compute_units("set_token_oracle: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, constant::MAX_WITHDRAW_COOLDOWN, error::BankAppError,
+    seeds::BankPda, state::BankInfo,
+};
+
+// withdrawals already requested keep the unlock time they got
+#[derive(Accounts)]
+pub struct SetWithdrawCooldown<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetWithdrawCooldown<'info> {
+    pub fn process(ctx: Context<SetWithdrawCooldown>, withdraw_cooldown: i64) -> Result<()> {
+        log_compute_units("set_withdraw_cooldown: start");
+        if !(0..=MAX_WITHDRAW_COOLDOWN).contains(&withdraw_cooldown) {
+            return Err(BankAppError::InvalidWithdrawCooldown.into());
+        }
+        ctx.accounts.bank_info.withdraw_cooldown = withdraw_cooldown;
+
+        msg!("withdraw cooldown set to {}s", withdraw_cooldown);
+        log_compute_units("set_withdraw_cooldown: end");
+        Ok(())
+    }
+}
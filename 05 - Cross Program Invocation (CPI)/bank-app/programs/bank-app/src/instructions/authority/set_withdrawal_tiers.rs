@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::WITHDRAWAL_TIER_COUNT,
+    seeds::BankPda,
+    state::{BankInfo, WithdrawalTier},
+};
+
+#[derive(Accounts)]
+pub struct SetWithdrawalTiers<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.authority)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetWithdrawalTiers<'info> {
+    pub fn process(
+        ctx: Context<SetWithdrawalTiers>,
+        tiers: [WithdrawalTier; WITHDRAWAL_TIER_COUNT],
+    ) -> Result<()> {
+        log_compute_units("set_withdrawal_tiers: start");
+        ctx.accounts.bank_info.set_withdrawal_tiers(tiers)?;
+
+        for tier in tiers.iter().filter(|tier| tier.max_age != 0) {
+            msg!(
+                "reserves younger than {}s: {} lamports a day",
+                tier.max_age,
+                tier.daily_limit
+            );
+        }
+        log_compute_units("set_withdrawal_tiers: end");
+        Ok(())
+    }
+}
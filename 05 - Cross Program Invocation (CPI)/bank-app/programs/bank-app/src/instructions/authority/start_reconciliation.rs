@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::RECONCILIATION_SEED,
+    seeds::BankPda,
+    state::{BankInfo, Reconciliation},
+};
+
+#[derive(Accounts)]
+pub struct StartReconciliation<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [RECONCILIATION_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Reconciliation>(),
+    )]
+    pub reconciliation: Box<Account<'info, Reconciliation>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StartReconciliation<'info> {
+    // resets the cursor, an unfinished run is simply dropped
+    pub fn process(ctx: Context<StartReconciliation>, expected_reserves: u64) -> Result<()> {
+        log_compute_units("start_reconciliation: start");
+        let reconciliation = &mut ctx.accounts.reconciliation;
+
+        reconciliation.set_inner(Reconciliation {
+            expected_reserves,
+            started_slot: Clock::get()?.slot,
+            is_done: expected_reserves == 0,
+            is_matched: expected_reserves == 0 && ctx.accounts.bank_info.total_deposited == 0,
+            ..Reconciliation::default()
+        });
+
+        msg!("reconciliation started for {} reserves", expected_reserves);
+        log_compute_units("start_reconciliation: end");
+        Ok(())
+    }
+}
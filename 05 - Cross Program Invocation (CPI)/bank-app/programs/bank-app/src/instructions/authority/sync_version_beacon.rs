@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::VERSION_BEACON_SEED,
+    seeds::BankPda,
+    state::{BankInfo, VersionBeacon},
+    version::{write_bank_version, write_version_beacon},
+};
+
+// run after every upgrade, it rewrites the beacon and BankInfo's copy.
+// init_if_needed so banks initialized before the beacon existed can get one too
+#[derive(Accounts)]
+pub struct SyncVersionBeacon<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [VERSION_BEACON_SEED],
+        bump,
+        payer = authority,
+        space = 8 + std::mem::size_of::<VersionBeacon>(),
+    )]
+    pub version_beacon: Box<Account<'info, VersionBeacon>>,
+
+    #[account(mut, address = bank_info.authority)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SyncVersionBeacon<'info> {
+    pub fn process(ctx: Context<SyncVersionBeacon>) -> Result<()> {
+        log_compute_units("sync_version_beacon: start");
+        write_bank_version(&mut ctx.accounts.bank_info);
+        write_version_beacon(&mut ctx.accounts.version_beacon)
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
+};
+
+// anyone can bring a SOL reserve's accrued_interest up to date without a
+// deposit or withdraw, at most once per slot on the same reserve
+#[derive(Accounts)]
+pub struct AccrueInterest<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    ///CHECK: the reserve's owner, only its key is used
+    pub user: UncheckedAccount<'info>,
+}
+
+impl<'info> AccrueInterest<'info> {
+    pub fn process(ctx: Context<AccrueInterest>) -> Result<()> {
+        log_compute_units("accrue_interest: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::AccrueInterest)?;
+        let clock = Clock::get()?;
+        let bank_info = &ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.record_crank(clock.slot)?;
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+
+        msg!(
+            "accrued interest for {}: {}",
+            ctx.accounts.user.key(),
+            user_reserve.accrued_interest
+        );
+        log_compute_units("accrue_interest: end");
+        Ok(())
+    }
+}
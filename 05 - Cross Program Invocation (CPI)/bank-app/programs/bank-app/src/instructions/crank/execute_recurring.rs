@@ -0,0 +1,145 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{RECURRING_DEPOSIT_SEED, USER_RESERVE_SEED, USER_RESERVE_VERSION},
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, RecurringDeposit, UserReserve},
+};
+
+// anyone can crank, the plan decides the amount and the destination. The payer
+// only pays for the user's reserve the first time
+#[derive(Accounts)]
+pub struct ExecuteRecurring<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::RecurringDeposit {
+            user: user.key(),
+            mint: token_mint.key(),
+        }
+        .address_with_bump(recurring_deposit.bump),
+    )]
+    pub recurring_deposit: Box<Account<'info, RecurringDeposit>>,
+
+    ///CHECK: the owner of the plan, only used for the address and the ATA
+    pub user: UncheckedAccount<'info>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            USER_RESERVE_SEED,
+            user.key().as_ref(),
+            token_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteRecurring<'info> {
+    pub fn process(ctx: Context<ExecuteRecurring>) -> Result<()> {
+        log_compute_units("execute_recurring: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ExecuteRecurring)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let recurring_deposit = &mut ctx.accounts.recurring_deposit;
+        if now < recurring_deposit.next_due_at {
+            return Err(BankAppError::RecurringNotDue.into());
+        }
+
+        // the plan signs as the delegate, the user doesn't have to be here
+        let user = ctx.accounts.user.key();
+        let token_mint = ctx.accounts.token_mint.key();
+        let delegate_seeds: &[&[&[u8]]] = &[&[
+            RECURRING_DEPOSIT_SEED,
+            user.as_ref(),
+            token_mint.as_ref(),
+            &[recurring_deposit.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    to: ctx.accounts.bank_ata.to_account_info(),
+                    authority: recurring_deposit.to_account_info(),
+                },
+                delegate_seeds,
+            ),
+            recurring_deposit.amount,
+        )?;
+
+        // one pull per call, a missed period is caught up by the next crank
+        recurring_deposit.next_due_at += recurring_deposit.interval;
+
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = user;
+            user_reserve.created_at = now;
+        }
+
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(recurring_deposit.amount)
+            .ok_or(BankAppError::Overflow)?;
+        let config = ctx.accounts.bank_info.token_config_mut(&token_mint)?;
+        config.credit(recurring_deposit.amount)?;
+
+        emit!(DepositEvent {
+            user,
+            mint: token_mint,
+            amount: recurring_deposit.amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: config.total_deposited,
+        });
+
+        log_compute_units("execute_recurring: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use anchor_lang::{prelude::*, system_program};
+use staking_app::{cpi, program::StakingApp};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction},
+};
+
+// anyone can make the staking app accrue the vault's interest: a stake of 0
+// settles it without moving SOL. At most once per slot, a second call in the
+// same slot would only burn the cranker's fee
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: the staking app's vault
+    #[account(
+        mut,
+        seeds = [b"STAKING_VAULT"],
+        bump,
+        seeds::program = staking_app::ID
+    )]
+    pub staking_vault: UncheckedAccount<'info>,
+
+    ///CHECK: the bank vault's UserInfo in the staking app
+    #[account(
+        mut,
+        seeds = [b"USER_INFO", bank_vault.key().as_ref()],
+        bump,
+        seeds::program = staking_app::ID
+    )]
+    pub staking_info: UncheckedAccount<'info>,
+    pub staking_program: Program<'info, StakingApp>,
+
+    // pays for the UserInfo if the bank never invested
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Harvest<'info> {
+    pub fn process(ctx: Context<Harvest>) -> Result<()> {
+        log_compute_units("harvest: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::Harvest)?;
+        let slot = Clock::get()?.slot;
+        if ctx.accounts.bank_info.last_harvest_slot == slot {
+            return Err(BankAppError::CrankAlreadyRan.into());
+        }
+        ctx.accounts.bank_info.last_harvest_slot = slot;
+
+        let vault_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        cpi::stake(
+            CpiContext::new_with_signer(
+                ctx.accounts.staking_program.to_account_info(),
+                cpi::accounts::Stake {
+                    staking_vault: ctx.accounts.staking_vault.to_account_info(),
+                    user_info: ctx.accounts.staking_info.to_account_info(),
+                    user: ctx.accounts.bank_vault.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                vault_seeds,
+            ),
+            0,
+            true,
+        )?;
+
+        msg!("harvested staking interest at slot {}", slot);
+        log_compute_units("harvest: end");
+        Ok(())
+    }
+}
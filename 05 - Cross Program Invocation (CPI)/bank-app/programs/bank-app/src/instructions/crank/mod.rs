@@ -0,0 +1,19 @@
+pub mod accrue_interest;
+pub mod execute_recurring;
+pub mod harvest;
+pub mod process_next_op;
+pub mod receive_cross_chain_deposit;
+pub mod reconcile_batch;
+pub mod snapshot;
+pub mod sync_receipt_rate;
+pub mod top_up_rent;
+
+pub use accrue_interest::*;
+pub use execute_recurring::*;
+pub use harvest::*;
+pub use process_next_op::*;
+pub use receive_cross_chain_deposit::*;
+pub use reconcile_batch::*;
+pub use snapshot::*;
+pub use sync_receipt_rate::*;
+pub use top_up_rent::*;
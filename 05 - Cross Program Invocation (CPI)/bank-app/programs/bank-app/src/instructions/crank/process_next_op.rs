@@ -0,0 +1,123 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, OP_KIND_WITHDRAW},
+    error::BankAppError,
+    event::InvestEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, OpsQueue},
+    transfer_helper::sol_transfer_from_pda,
+};
+use staking_app::{cpi, program::StakingApp};
+
+// anyone can crank. The staking accounts are only used for a divest, the
+// recipient only for a withdrawal
+#[derive(Accounts)]
+pub struct ProcessNextOp<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::OpsQueue.address()
+    )]
+    pub ops_queue: AccountLoader<'info, OpsQueue>,
+
+    ///CHECK: must be the user of the next op when it's a withdrawal
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(mut)]
+    pub staking_vault: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub staking_info: UncheckedAccount<'info>,
+    pub staking_program: Program<'info, StakingApp>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProcessNextOp<'info> {
+    pub fn process(ctx: Context<ProcessNextOp>) -> Result<()> {
+        log_compute_units("process_next_op: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ProcessNextOp)?;
+
+        let op = *ctx
+            .accounts
+            .ops_queue
+            .load()?
+            .front()
+            .ok_or(BankAppError::OpsQueueEmpty)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        if op.kind == OP_KIND_WITHDRAW {
+            if ctx.accounts.recipient.key() != op.user {
+                return Err(BankAppError::OpRecipientMismatch.into());
+            }
+
+            // the vault has to stay rent exempt. If it can't pay, the op stays at
+            // the front and everything behind it waits
+            let available = ctx
+                .accounts
+                .bank_info
+                .vault_liquidity(ctx.accounts.bank_vault.lamports());
+            if available < op.amount {
+                return Err(BankAppError::InsufficientLiquidity.into());
+            }
+
+            sol_transfer_from_pda(
+                ctx.accounts.bank_vault.to_account_info(),
+                ctx.accounts.recipient.to_account_info(),
+                &ctx.accounts.system_program,
+                pda_seeds,
+                op.amount,
+            )?;
+        } else {
+            cpi::stake(
+                CpiContext::new_with_signer(
+                    ctx.accounts.staking_program.to_account_info(),
+                    cpi::accounts::Stake {
+                        staking_vault: ctx.accounts.staking_vault.to_account_info(),
+                        user_info: ctx.accounts.staking_info.to_account_info(),
+                        user: ctx.accounts.bank_vault.to_account_info(),
+                        payer: ctx.accounts.payer.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                    pda_seeds,
+                ),
+                op.amount,
+                false,
+            )?;
+
+            // a queued withdrawal was already logged by queue_withdrawal
+            emit!(InvestEvent {
+                target: ctx.accounts.staking_program.key(),
+                amount: op.amount,
+                is_invest: false,
+            });
+        }
+
+        ctx.accounts.ops_queue.load_mut()?.pop();
+
+        msg!("op of kind {} settled for {}", op.kind, op.amount);
+        log_compute_units("process_next_op: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BRIDGE_ESCROW_SEED, RECEIVED_VAA_SEED, USER_RESERVE_SEED, USER_RESERVE_VERSION},
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, ForeignEmitter, ReceivedVaa, UserReserve},
+    transfer_helper::sol_transfer_from_pda,
+    wormhole::{PostedVaa, CORE_BRIDGE_PROGRAM_ID, PAYLOAD_CROSS_CHAIN_DEPOSIT, POSTED_VAA_SEED},
+};
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveCrossChainDeposit<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: this VAA's payout lands here, no other VAA can spend it
+    #[account(
+        mut,
+        seeds = [BRIDGE_ESCROW_SEED, vaa_hash.as_ref()],
+        bump,
+        owner = system_program::ID
+    )]
+    pub bridge_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        address = BankPda::ForeignEmitter { chain: foreign_emitter.chain }.address()
+    )]
+    pub foreign_emitter: Box<Account<'info, ForeignEmitter>>,
+
+    ///CHECK: written by the core bridge once the guardian signatures are verified
+    #[account(
+        seeds = [POSTED_VAA_SEED, vaa_hash.as_ref()],
+        bump,
+        seeds::program = CORE_BRIDGE_PROGRAM_ID
+    )]
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    // init fails if the same VAA is redeemed twice
+    #[account(
+        init,
+        seeds = [RECEIVED_VAA_SEED, vaa_hash.as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ReceivedVaa>(),
+    )]
+    pub received_vaa: Box<Account<'info, ReceivedVaa>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [USER_RESERVE_SEED, recipient.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    ///CHECK: checked against the VAA payload
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReceiveCrossChainDeposit<'info> {
+    pub fn process(ctx: Context<ReceiveCrossChainDeposit>, vaa_hash: [u8; 32]) -> Result<()> {
+        log_compute_units("receive_cross_chain_deposit: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ReceiveCrossChainDeposit)?;
+
+        let vaa = PostedVaa::load(&ctx.accounts.posted_vaa)?;
+
+        let foreign_emitter = &ctx.accounts.foreign_emitter;
+        if vaa.emitter_chain != foreign_emitter.chain
+            || vaa.emitter_address != foreign_emitter.address
+        {
+            return Err(BankAppError::UnknownForeignEmitter.into());
+        }
+
+        // [payload id][recipient][amount], the same layout the bank emits
+        if vaa.payload.len() != 1 + 32 + 8 || vaa.payload[0] != PAYLOAD_CROSS_CHAIN_DEPOSIT {
+            return Err(BankAppError::InvalidVaaPayload.into());
+        }
+        let recipient = Pubkey::try_from(&vaa.payload[1..33]).unwrap();
+        let amount = u64::from_le_bytes(vaa.payload[33..41].try_into().unwrap());
+
+        if recipient != ctx.accounts.recipient.key() {
+            return Err(BankAppError::InvalidVaaPayload.into());
+        }
+
+        ctx.accounts.received_vaa.sequence = vaa.sequence;
+
+        // a message carries no lamports, so the credit is backed by lamports that
+        // reached this VAA's escrow and move on to the vault here. A shared escrow
+        // would let one VAA spend another's payout. The escrow keeps its rent, and
+        // a VAA whose funds haven't arrived yet can be redeemed later
+        let rent = Rent::get()?.minimum_balance(0);
+        let bridged = ctx.accounts.bridge_escrow.lamports().saturating_sub(rent);
+        if bridged < amount {
+            return Err(BankAppError::BridgedFundsMissing.into());
+        }
+        let escrow_seeds: &[&[&[u8]]] = &[&[
+            BRIDGE_ESCROW_SEED,
+            vaa_hash.as_ref(),
+            &[ctx.bumps.bridge_escrow],
+        ]];
+        sol_transfer_from_pda(
+            ctx.accounts.bridge_escrow.to_account_info(),
+            ctx.accounts.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            escrow_seeds,
+            amount,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = recipient;
+            user_reserve.created_at = Clock::get()?.unix_timestamp;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        user_reserve.accumulate_balance(now)?;
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        msg!(
+            "credited {} from chain {} sequence {}",
+            amount,
+            vaa.emitter_chain,
+            vaa.sequence
+        );
+        emit!(DepositEvent {
+            user: recipient,
+            mint: Pubkey::default(),
+            amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("receive_cross_chain_deposit: end");
+        Ok(())
+    }
+}
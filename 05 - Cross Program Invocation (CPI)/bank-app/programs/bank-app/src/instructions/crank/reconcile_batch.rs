@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::MAX_RECONCILE_BATCH,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, Reconciliation, UserReserve},
+};
+
+// anyone can crank, the reserves come in as remaining accounts
+#[derive(Accounts)]
+pub struct ReconcileBatch<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::Reconciliation.address(),
+        constraint = !reconciliation.is_done @ BankAppError::ReconciliationDone,
+    )]
+    pub reconciliation: Box<Account<'info, Reconciliation>>,
+}
+
+impl<'info> ReconcileBatch<'info> {
+    pub fn process(ctx: Context<ReconcileBatch>) -> Result<()> {
+        log_compute_units("reconcile_batch: start");
+        let reserves = ctx.remaining_accounts;
+        if reserves.is_empty() || reserves.len() > MAX_RECONCILE_BATCH as usize {
+            return Err(BankAppError::InvalidBatchSize.into());
+        }
+
+        let reconciliation = &mut ctx.accounts.reconciliation;
+
+        for reserve_info in reserves {
+            // strictly increasing keys: a reserve can't be counted twice, in this
+            // batch or in an earlier one
+            if reserve_info.key() <= reconciliation.last_reserve {
+                return Err(BankAppError::ReserveOutOfOrder.into());
+            }
+            if reserve_info.owner != &crate::ID {
+                return Err(BankAppError::InvalidUserReserve.into());
+            }
+
+            // only migrated SOL reserves: v1 has no owner to check the address against,
+            // token reserves share the layout but not the seeds
+            let reserve = UserReserve::load_versioned(&reserve_info.try_borrow_data()?)?;
+            let (expected_address, _) = BankPda::UserReserve {
+                user: reserve.owner,
+            }
+            .find_address();
+            if reserve.is_legacy() || expected_address != reserve_info.key() {
+                return Err(BankAppError::InvalidUserReserve.into());
+            }
+
+            reconciliation.tally = reconciliation
+                .tally
+                .checked_add(reserve.deposited_amount)
+                .ok_or(BankAppError::Overflow)?;
+            reconciliation.processed_reserves += 1;
+            reconciliation.last_reserve = reserve_info.key();
+        }
+
+        if reconciliation.processed_reserves >= reconciliation.expected_reserves {
+            reconciliation.is_done = true;
+            reconciliation.is_matched =
+                reconciliation.tally == ctx.accounts.bank_info.total_deposited;
+            msg!(
+                "reconciliation done, tally {} vs total deposited {}",
+                reconciliation.tally,
+                ctx.accounts.bank_info.total_deposited
+            );
+        }
+
+        log_compute_units("reconcile_batch: end");
+        Ok(())
+    }
+}
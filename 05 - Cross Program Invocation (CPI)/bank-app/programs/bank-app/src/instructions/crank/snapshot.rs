@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::SNAPSHOT_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, Snapshot},
+};
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct TakeSnapshot<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [SNAPSHOT_SEED, epoch.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Snapshot>(),
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeSnapshot<'info> {
+    pub fn process(ctx: Context<TakeSnapshot>, epoch: u64) -> Result<()> {
+        log_compute_units("snapshot: start");
+        let clock = Clock::get()?;
+
+        // one snapshot per epoch, and only while that epoch is live
+        if epoch != clock.epoch {
+            return Err(BankAppError::InvalidSnapshotEpoch.into());
+        }
+
+        let snapshot = &mut ctx.accounts.snapshot;
+
+        snapshot.epoch = epoch;
+        snapshot.slot = clock.slot;
+        snapshot.timestamp = clock.unix_timestamp;
+        snapshot.total_deposited = ctx.accounts.bank_info.total_deposited;
+        snapshot.vault_balance = ctx.accounts.bank_vault.lamports();
+
+        msg!("snapshot taken for epoch {}", epoch);
+        log_compute_units("snapshot: end");
+        Ok(())
+    }
+}
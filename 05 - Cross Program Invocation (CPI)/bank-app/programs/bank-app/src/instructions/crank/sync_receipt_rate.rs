@@ -0,0 +1,75 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token_2022::Token2022,
+    token_2022_extensions::{interest_bearing_mint_update_rate, InterestBearingMintUpdateRate},
+    token_interface::Mint,
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction},
+};
+
+// anyone can copy a token's interest_rate_bps onto its receipt mint. Interest
+// shown so far keeps the old rate, the new one applies from now on
+#[derive(Accounts)]
+pub struct SyncReceiptRate<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK: the receipt mint's rate authority
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = BankPda::ReceiptMint { mint: token_mint.key() }.address(),
+        mint::token_program = token_2022_program,
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+impl<'info> SyncReceiptRate<'info> {
+    pub fn process(ctx: Context<SyncReceiptRate>) -> Result<()> {
+        log_compute_units("sync_receipt_rate: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::SyncReceiptRate)?;
+        let token_mint = ctx.accounts.token_mint.key();
+        let rate = ctx
+            .accounts
+            .bank_info
+            .token_config(&token_mint)?
+            .receipt_rate()?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        let token_2022_program = ctx.accounts.token_2022_program.to_account_info();
+        interest_bearing_mint_update_rate(
+            CpiContext::new_with_signer(
+                token_2022_program.clone(),
+                InterestBearingMintUpdateRate {
+                    token_program_id: token_2022_program,
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    rate_authority: ctx.accounts.bank_vault.to_account_info(),
+                },
+                pda_seeds,
+            ),
+            rate,
+        )?;
+
+        msg!("receipt rate for {} set to {} bps", token_mint, rate);
+        log_compute_units("sync_receipt_rate: end");
+        Ok(())
+    }
+}
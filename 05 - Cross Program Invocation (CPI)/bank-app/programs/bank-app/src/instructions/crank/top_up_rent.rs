@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, error::BankAppError, transfer_helper::sol_transfer_from_user,
+};
+
+// anyone can bring one of the bank's accounts back to rent exemption, e.g. after
+// a realloc paid for less than the new size or a change of the rent rate
+#[derive(Accounts)]
+pub struct TopUpRent<'info> {
+    ///CHECK: any account of this program, its data is never read
+    #[account(mut, owner = crate::ID @ BankAppError::NotProgramOwned)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TopUpRent<'info> {
+    pub fn process(ctx: Context<TopUpRent>) -> Result<()> {
+        log_compute_units("top_up_rent: start");
+        let target = &ctx.accounts.target;
+        let shortfall = rent_shortfall(&Rent::get()?, target.data_len(), target.lamports());
+        if shortfall == 0 {
+            return Err(BankAppError::NoRentShortfall.into());
+        }
+
+        sol_transfer_from_user(
+            &ctx.accounts.payer,
+            target.to_account_info(),
+            &ctx.accounts.system_program,
+            shortfall,
+        )?;
+
+        msg!("topped up {} with {} lamports", target.key(), shortfall);
+        log_compute_units("top_up_rent: end");
+        Ok(())
+    }
+}
+
+// the lamports missing for `data_len` bytes to be rent exempt, 0 if none are
+pub fn rent_shortfall(rent: &Rent, data_len: usize, lamports: u64) -> u64 {
+    rent.minimum_balance(data_len).saturating_sub(lamports)
+}
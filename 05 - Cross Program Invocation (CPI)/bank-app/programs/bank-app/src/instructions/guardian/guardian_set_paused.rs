@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, event::PauseToggledEvent, seeds::BankPda, state::BankInfo,
+};
+
+// the guardian's only instruction. It can stop the bank fast without the
+// authority's key, but never unpauses, moves funds or touches config
+#[derive(Accounts)]
+pub struct GuardianSetPaused<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(address = bank_info.guardian)]
+    pub guardian: Signer<'info>,
+}
+
+impl<'info> GuardianSetPaused<'info> {
+    pub fn process(ctx: Context<GuardianSetPaused>) -> Result<()> {
+        log_compute_units("guardian_set_paused: start");
+        ctx.accounts.bank_info.is_paused = true;
+
+        msg!("bank paused by guardian");
+        emit!(PauseToggledEvent {
+            is_paused: true,
+            by: ctx.accounts.guardian.key(),
+        });
+        log_compute_units("guardian_set_paused: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod guardian_set_paused;
+
+pub use guardian_set_paused::*;
@@ -1,5 +1,9 @@
 pub mod authority;
+pub mod crank;
+pub mod guardian;
 pub mod user;
 
 pub use authority::*;
+pub use crank::*;
+pub use guardian::*;
 pub use user::*;
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::compute::log_compute_units;
+use crate::version::check_version;
+
+// a guard, put it first in a transaction and the whole transaction fails
+// against a program the client wasn't built for
+#[derive(Accounts)]
+pub struct AssertVersion {}
+
+impl AssertVersion {
+    pub fn process(
+        _ctx: Context<AssertVersion>,
+        expected_version: u32,
+        required_features: u64,
+    ) -> Result<()> {
+        log_compute_units("assert_version: start");
+        check_version(expected_version, required_features)
+    }
+}
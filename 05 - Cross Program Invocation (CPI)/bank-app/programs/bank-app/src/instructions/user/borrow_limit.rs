@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::MAX_BORROW_LTV_BPS,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, UserReserve},
+};
+
+// read-only, simulate it to get how much `user` can borrow in USD. Remaining
+// accounts: for every priced mint, in token_configs order, the user's token
+// reserve then the mint's price feed. A reserve that doesn't exist counts as 0
+#[derive(Accounts)]
+pub struct BorrowLimit<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK: any wallet, only its key is used
+    pub user: UncheckedAccount<'info>,
+}
+
+impl<'info> BorrowLimit<'info> {
+    pub fn process(ctx: Context<BorrowLimit>) -> Result<u64> {
+        log_compute_units("borrow_limit: start");
+        let user = ctx.accounts.user.key();
+        let clock = Clock::get()?;
+        let priced = ctx
+            .accounts
+            .bank_info
+            .token_configs
+            .iter()
+            .filter(|config| config.is_priced());
+        if priced.clone().count() * 2 != ctx.remaining_accounts.len() {
+            return Err(BankAppError::InvalidPriceFeed.into());
+        }
+
+        let mut limit: u128 = 0;
+        for (config, pair) in priced.zip(ctx.remaining_accounts.chunks(2)) {
+            let (reserve_info, feed) = (&pair[0], &pair[1]);
+            let (expected_address, _) = BankPda::UserTokenReserve {
+                user,
+                mint: config.mint,
+            }
+            .find_address();
+            if reserve_info.key() != expected_address {
+                return Err(BankAppError::InvalidUserReserve.into());
+            }
+            if reserve_info.owner != &crate::ID {
+                continue;
+            }
+
+            let reserve = UserReserve::load_versioned(&reserve_info.try_borrow_data()?)?;
+            let value = config.usd_value(reserve.deposited_amount, feed, &clock)?;
+            limit += value as u128 * config.borrow_ltv_bps as u128 / MAX_BORROW_LTV_BPS as u128;
+        }
+
+        Ok(u64::try_from(limit).map_err(|_| BankAppError::InvalidOraclePrice)?)
+    }
+}
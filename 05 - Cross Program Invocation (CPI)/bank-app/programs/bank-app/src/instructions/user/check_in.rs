@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::Inheritance};
+
+// proof of life, restarts the inactivity timer
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(
+        mut,
+        address = BankPda::Inheritance { owner: owner.key() }.address(),
+    )]
+    pub inheritance: Box<Account<'info, Inheritance>>,
+
+    pub owner: Signer<'info>,
+}
+
+impl<'info> CheckIn<'info> {
+    pub fn process(ctx: Context<CheckIn>) -> Result<()> {
+        log_compute_units("check_in: start");
+        ctx.accounts.inheritance.last_activity_ts = Clock::get()?.unix_timestamp;
+        log_compute_units("check_in: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    seeds::{assert_off_curve, BankPda},
+};
+
+// Deliberately has no `address` constraint on `vault`. If a withdraw trusted a
+// vault passed like this, an attacker could hand in a wallet they hold the key
+// for and route funds there. The checks below are what
+// `address = BankPda::BankVault.address()` gives the other instructions for free.
+#[derive(Accounts)]
+pub struct CheckVault<'info> {
+    ///CHECK: validated in process
+    pub vault: UncheckedAccount<'info>,
+}
+
+impl<'info> CheckVault<'info> {
+    pub fn process(ctx: Context<CheckVault>) -> Result<()> {
+        log_compute_units("check_vault: start");
+        let vault = ctx.accounts.vault.key();
+
+        // rejects any keypair-backed address
+        assert_off_curve(&vault)?;
+
+        // off-curve only proves it is *some* PDA, it still has to be ours
+        if vault != BankPda::BankVault.address() {
+            return Err(BankAppError::InvalidVault.into());
+        }
+
+        msg!("vault {} is the bank vault PDA", vault);
+        log_compute_units("check_vault: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, LARGE_WITHDRAWAL_THRESHOLD, WITHDRAWAL_WINDOW_SEED},
+    error::BankAppError,
+    event::WithdrawEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, Inheritance, UserReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// a withdraw of the owner's SOL reserve to the beneficiary, under the owner's
+// limits. A large reserve takes several claims, the switch closes with the last
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct ClaimInheritance<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::Inheritance { owner: owner.key() }.address(),
+        has_one = beneficiary,
+    )]
+    pub inheritance: Box<Account<'info, Inheritance>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: owner.key() }.address(),
+        constraint = amount > 0 @ BankAppError::InvalidAmount,
+        // a requested withdrawal stays for execute_withdraw
+        constraint = amount <= user_reserve.deposited_amount.saturating_sub(user_reserve.pending_withdrawal) @ BankAppError::InsufficientDeposit,
+        constraint = amount <= LARGE_WITHDRAWAL_THRESHOLD @ BankAppError::WithdrawalTooLarge,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    // the owner's, so claims share their daily limit
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, owner.key().as_ref()],
+        bump,
+        payer = beneficiary,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    ///CHECK: only used for the address
+    pub owner: UncheckedAccount<'info>,
+
+    ///CHECK: must match bank_info.rent_destination_for(owner)
+    #[account(
+        mut,
+        constraint = rent_destination.key() == bank_info.rent_destination_for(owner.key) @ BankAppError::InvalidRentDestination
+    )]
+    pub rent_destination: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimInheritance<'info> {
+    pub fn process(ctx: Context<ClaimInheritance>, amount: u64) -> Result<()> {
+        log_compute_units("claim_inheritance: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ClaimInheritance)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let reserve_activity_ts = ctx.accounts.user_reserve.last_activity_ts;
+        if !ctx
+            .accounts
+            .inheritance
+            .is_claimable(now, reserve_activity_ts)?
+        {
+            return Err(BankAppError::OwnerStillActive.into());
+        }
+
+        let bank_info = &ctx.accounts.bank_info;
+        ctx.accounts.user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.user_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, amount, daily_limit)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            amount,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accumulate_balance(now)?;
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // the last claim closes the switch, so nothing is left to claim twice
+        if user_reserve.deposited_amount == 0 {
+            ctx.accounts
+                .inheritance
+                .close(ctx.accounts.rent_destination.to_account_info())?;
+        }
+
+        msg!("{} inherited {}", ctx.accounts.beneficiary.key(), amount);
+        // the owner's reserve is what was withdrawn
+        emit!(WithdrawEvent {
+            user: ctx.accounts.owner.key(),
+            mint: Pubkey::default(),
+            amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("claim_inheritance: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    bubblegum::{
+        self, MetadataArgs, MintV1Accounts, TokenProgramVersion, TokenStandard,
+        BUBBLEGUM_PROGRAM_ID, COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID,
+    },
+    compute::log_compute_units,
+    constant::{
+        BADGE_MILESTONES, BADGE_SYMBOL, BADGE_TREE_AUTHORITY_SEED, BADGE_URI, MILESTONE_BADGES_SEED,
+    },
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BadgeConfig, BankInfo, BankInstruction, MilestoneBadges, UserReserve},
+};
+
+#[derive(Accounts)]
+pub struct ClaimMilestoneBadge<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        mut,
+        address = BankPda::BadgeConfig.address()
+    )]
+    pub badge_config: Box<Account<'info, BadgeConfig>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MILESTONE_BADGES_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<MilestoneBadges>(),
+    )]
+    pub milestone_badges: Box<Account<'info, MilestoneBadges>>,
+
+    ///CHECK:
+    #[account(
+        seeds = [BADGE_TREE_AUTHORITY_SEED],
+        bump
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    ///CHECK: owned by bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(mut, address = badge_config.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    ///CHECK:
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimMilestoneBadge<'info> {
+    pub fn process(ctx: Context<ClaimMilestoneBadge>, milestone: u8) -> Result<()> {
+        log_compute_units("claim_milestone_badge: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ClaimMilestoneBadge)?;
+        let Some(threshold) = BADGE_MILESTONES.get(milestone as usize) else {
+            return Err(BankAppError::InvalidMilestone.into());
+        };
+
+        if ctx.accounts.user_reserve.deposited_amount < *threshold {
+            return Err(BankAppError::MilestoneNotReached.into());
+        }
+
+        let milestone_badges = &mut ctx.accounts.milestone_badges;
+        if milestone_badges.minted & (1 << milestone) != 0 {
+            return Err(BankAppError::BadgeAlreadyMinted.into());
+        }
+        milestone_badges.minted |= 1 << milestone;
+
+        let sol = threshold / 1_000_000_000;
+        let metadata = MetadataArgs {
+            name: format!("Bank Depositor {} SOL", sol),
+            symbol: BADGE_SYMBOL.to_string(),
+            uri: format!("{}/{}.json", BADGE_URI, sol),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![],
+        };
+
+        let tree_authority_seeds: &[&[u8]] =
+            &[BADGE_TREE_AUTHORITY_SEED, &[ctx.bumps.tree_authority]];
+
+        bubblegum::mint_v1(
+            ctx.accounts.bubblegum_program.to_account_info(),
+            MintV1Accounts {
+                tree_config: ctx.accounts.tree_config.to_account_info(),
+                leaf_owner: ctx.accounts.user.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                tree_delegate: ctx.accounts.tree_authority.to_account_info(),
+                log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+                compression_program: ctx.accounts.compression_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            metadata,
+            &[tree_authority_seeds],
+        )?;
+
+        ctx.accounts.badge_config.badges_minted += 1;
+
+        msg!("minted {} SOL milestone badge", sol);
+        log_compute_units("claim_milestone_badge: end");
+        Ok(())
+    }
+}
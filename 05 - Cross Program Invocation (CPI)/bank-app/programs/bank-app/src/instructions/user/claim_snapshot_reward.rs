@@ -0,0 +1,109 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::BANK_VAULT_SEED,
+    error::BankAppError,
+    event::RewardPaidEvent,
+    merkle::{reward_leaf, verify_proof},
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, ClaimBitmap, Snapshot},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimSnapshotReward<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::Snapshot { epoch }.address()
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(
+        mut,
+        address = BankPda::ClaimBitmap { snapshot: snapshot.key() }.address()
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimSnapshotReward<'info> {
+    pub fn process(
+        ctx: Context<ClaimSnapshotReward>,
+        _epoch: u64,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        log_compute_units("claim_snapshot_reward: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ClaimSnapshotReward)?;
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+
+        if snapshot.rewards_root == [0u8; 32] {
+            return Err(BankAppError::RewardsRootNotPosted.into());
+        }
+        if index as usize >= claim_bitmap.bits.len() * 8 {
+            return Err(BankAppError::ClaimIndexOutOfRange.into());
+        }
+        if claim_bitmap.is_claimed(index) {
+            return Err(BankAppError::RewardAlreadyClaimed.into());
+        }
+
+        let leaf = reward_leaf(index, ctx.accounts.user.key.as_ref(), amount);
+        if !verify_proof(&proof, snapshot.rewards_root, leaf) {
+            return Err(BankAppError::InvalidMerkleProof.into());
+        }
+
+        // total_rewards is what post_snapshot_root funded
+        let claimed_rewards = snapshot
+            .claimed_rewards
+            .checked_add(amount)
+            .filter(|claimed| *claimed <= snapshot.total_rewards)
+            .ok_or(BankAppError::RewardBudgetExceeded)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            amount,
+        )?;
+
+        claim_bitmap.set_claimed(index);
+        snapshot.claimed_rewards = claimed_rewards;
+
+        emit!(RewardPaidEvent {
+            user: ctx.accounts.user.key(),
+            amount,
+        });
+        log_compute_units("claim_snapshot_reward: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::UserHistory};
+
+#[derive(Accounts)]
+pub struct ClearHistory<'info> {
+    #[account(
+        mut,
+        address = BankPda::UserHistory { user: user.key() }.address(),
+    )]
+    pub user_history: AccountLoader<'info, UserHistory>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> ClearHistory<'info> {
+    pub fn process(ctx: Context<ClearHistory>) -> Result<()> {
+        log_compute_units("clear_history: start");
+        ctx.accounts.user_history.load_mut()?.clear();
+        log_compute_units("clear_history: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, DepositRecord},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct CloseDepositRecord<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::DepositRecord { user: user.key(), index }.address(),
+        close = rent_destination
+    )]
+    pub deposit_record: Box<Account<'info, DepositRecord>>,
+
+    ///CHECK: must match bank_info.rent_destination_for(user)
+    #[account(
+        mut,
+        constraint = rent_destination.key() == bank_info.rent_destination_for(user.key) @ BankAppError::InvalidRentDestination
+    )]
+    pub rent_destination: UncheckedAccount<'info>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> CloseDepositRecord<'info> {
+    pub fn process(ctx: Context<CloseDepositRecord>, index: u64) -> Result<()> {
+        log_compute_units("close_deposit_record: start");
+        msg!(
+            "deposit record {} closed, rent refunded to {}",
+            index,
+            ctx.accounts.rent_destination.key()
+        );
+        log_compute_units("close_deposit_record: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::OP_RECORD_RETENTION,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, OpRecord},
+};
+
+#[derive(Accounts)]
+#[instruction(op_id: [u8; 16])]
+pub struct CloseOpRecord<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::OpRecord { user: user.key(), op_id }.address(),
+        close = rent_destination
+    )]
+    pub op_record: Box<Account<'info, OpRecord>>,
+
+    ///CHECK: must match bank_info.rent_destination_for(user)
+    #[account(
+        mut,
+        constraint = rent_destination.key() == bank_info.rent_destination_for(user.key) @ BankAppError::InvalidRentDestination
+    )]
+    pub rent_destination: UncheckedAccount<'info>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> CloseOpRecord<'info> {
+    // once closed the op_id can be used again, so only after every retry is long dead
+    pub fn process(ctx: Context<CloseOpRecord>, _op_id: [u8; 16]) -> Result<()> {
+        log_compute_units("close_op_record: start");
+        let op_record = &ctx.accounts.op_record;
+        if Clock::get()?.unix_timestamp < op_record.created_at + OP_RECORD_RETENTION {
+            return Err(BankAppError::OpRecordTooRecent.into());
+        }
+
+        msg!(
+            "op record closed, rent refunded to {}",
+            ctx.accounts.rent_destination.key()
+        );
+        log_compute_units("close_op_record: end");
+        Ok(())
+    }
+}
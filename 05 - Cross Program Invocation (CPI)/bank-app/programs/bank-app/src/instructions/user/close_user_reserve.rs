@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, UserReserve},
+};
+
+#[derive(Accounts)]
+pub struct CloseUserReserve<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = user_reserve.deposited_amount == 0 @ BankAppError::ReserveNotEmpty,
+        close = rent_destination
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    ///CHECK: must match bank_info.rent_destination_for(user)
+    #[account(
+        mut,
+        constraint = rent_destination.key() == bank_info.rent_destination_for(user.key) @ BankAppError::InvalidRentDestination
+    )]
+    pub rent_destination: UncheckedAccount<'info>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> CloseUserReserve<'info> {
+    pub fn process(ctx: Context<CloseUserReserve>) -> Result<()> {
+        log_compute_units("close_user_reserve: start");
+        msg!(
+            "user reserve closed, rent refunded to {}",
+            ctx.accounts.rent_destination.key()
+        );
+        log_compute_units("close_user_reserve: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::{
+    compute::log_compute_units,
+    constant::LUCKY_TICKET_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, LuckyTicket, UserReserve},
+};
+
+// step 1 of the VRF draw: bind the ticket to a Switchboard randomness account
+// committed in the previous slot, before anyone (including the oracle) can
+// know the value it will reveal
+#[derive(Accounts)]
+pub struct CommitLuckyDraw<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [LUCKY_TICKET_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<LuckyTicket>(),
+    )]
+    pub lucky_ticket: Box<Account<'info, LuckyTicket>>,
+
+    ///CHECK: parsed with `RandomnessAccountData::parse`
+    pub randomness_account_data: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitLuckyDraw<'info> {
+    pub fn process(ctx: Context<CommitLuckyDraw>) -> Result<()> {
+        log_compute_units("commit_lucky_draw: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::CommitLuckyDraw)?;
+        if ctx.accounts.user_reserve.deposited_amount == 0 {
+            return Err(BankAppError::NoDeposit.into());
+        }
+
+        let clock = Clock::get()?;
+        let lucky_ticket = &mut ctx.accounts.lucky_ticket;
+
+        if clock.epoch < lucky_ticket.next_draw_epoch {
+            return Err(BankAppError::LuckyDrawUnavailable.into());
+        }
+
+        let randomness_data =
+            RandomnessAccountData::parse(ctx.accounts.randomness_account_data.data.borrow())
+                .map_err(|_| BankAppError::RandomnessNotFresh)?;
+        if randomness_data.seed_slot != clock.slot - 1 {
+            return Err(BankAppError::RandomnessNotFresh.into());
+        }
+
+        lucky_ticket.next_draw_epoch = clock.epoch + 1;
+        lucky_ticket.randomness_account = ctx.accounts.randomness_account_data.key();
+        lucky_ticket.commit_slot = clock.slot;
+
+        log_compute_units("commit_lucky_draw: end");
+        Ok(())
+    }
+}
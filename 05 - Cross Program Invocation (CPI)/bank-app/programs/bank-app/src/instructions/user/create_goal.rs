@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{GOAL_SEED, MAX_GOAL_NAME_LEN},
+    error::BankAppError,
+    state::Goal,
+};
+
+// goals are numbered by the user, any free index works
+#[derive(Accounts)]
+#[instruction(goal_index: u8, name: String, target_amount: u64)]
+pub struct CreateGoal<'info> {
+    #[account(
+        init,
+        seeds = [GOAL_SEED, user.key().as_ref(), &[goal_index]],
+        bump,
+        payer = user,
+        space = Goal::SPACE,
+        constraint = !name.is_empty() && name.len() <= MAX_GOAL_NAME_LEN as usize @ BankAppError::InvalidGoalName,
+        constraint = target_amount > 0 @ BankAppError::InvalidAmount,
+    )]
+    pub goal: Box<Account<'info, Goal>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateGoal<'info> {
+    pub fn process(
+        ctx: Context<CreateGoal>,
+        goal_index: u8,
+        name: String,
+        target_amount: u64,
+        lock_until: Option<i64>,
+    ) -> Result<()> {
+        log_compute_units("create_goal: start");
+        let goal = &mut ctx.accounts.goal;
+        goal.owner = ctx.accounts.user.key();
+        goal.index = goal_index;
+        goal.name = name;
+        goal.target_amount = target_amount;
+        goal.lock_until = lock_until;
+
+        msg!("goal {} created: {}", goal_index, goal.name);
+        log_compute_units("create_goal: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, constant::JOINT_RESERVE_SEED, error::BankAppError,
+    state::JointReserve,
+};
+
+// both owners sign, nobody is added to a joint reserve without agreeing to it
+#[derive(Accounts)]
+pub struct CreateJointReserve<'info> {
+    #[account(
+        init,
+        seeds = [JOINT_RESERVE_SEED, owner_a.key().as_ref(), owner_b.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<JointReserve>(),
+        constraint = owner_a.key() < owner_b.key() @ BankAppError::InvalidJointOwners,
+    )]
+    pub joint_reserve: Box<Account<'info, JointReserve>>,
+
+    pub owner_a: Signer<'info>,
+    pub owner_b: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateJointReserve<'info> {
+    pub fn process(ctx: Context<CreateJointReserve>, single_signer_limit: u64) -> Result<()> {
+        log_compute_units("create_joint_reserve: start");
+        let joint_reserve = &mut ctx.accounts.joint_reserve;
+        joint_reserve.owner_a = ctx.accounts.owner_a.key();
+        joint_reserve.owner_b = ctx.accounts.owner_b.key();
+        joint_reserve.single_signer_limit = single_signer_limit;
+        joint_reserve.bump = ctx.bumps.joint_reserve;
+        joint_reserve.created_at = Clock::get()?.unix_timestamp;
+
+        log_compute_units("create_joint_reserve: end");
+        Ok(())
+    }
+}
@@ -1,25 +1,163 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{
+        CAMPAIGN_MATCH_SEED, HISTORY_KIND_DEPOSIT, OP_RECORD_SEED, USER_RESERVE_SEED,
+        USER_RESERVE_VERSION,
+    },
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    event::{DepositDryRun, DepositEvent},
+    seeds::BankPda,
+    state::{
+        BankInfo, BankInstruction, Campaign, CampaignMatch, OpRecord, UserHistory, UserReserve,
+    },
     transfer_helper::sol_transfer_from_user,
+    version::check_expected_version,
 };
 
+// every field added after launch goes into a new variant, so an old client keeps
+// sending the layout it was built with
+#[derive(AnchorSerialize, Clone)]
+pub enum DepositArgs {
+    V1 {
+        amount: u64,
+    },
+    V2 {
+        amount: u64,
+        deadline: Option<i64>,
+    },
+    V3 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+    },
+    V4 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+        dry_run: bool,
+    },
+    V5 {
+        amount: u64,
+        deadline: Option<i64>,
+        op_id: Option<[u8; 16]>,
+        dry_run: bool,
+        expected_version: Option<u32>,
+    },
+}
+
+impl DepositArgs {
+    pub fn amount(&self) -> u64 {
+        match self {
+            DepositArgs::V1 { amount } => *amount,
+            DepositArgs::V2 { amount, .. } => *amount,
+            DepositArgs::V3 { amount, .. } => *amount,
+            DepositArgs::V4 { amount, .. } => *amount,
+            DepositArgs::V5 { amount, .. } => *amount,
+        }
+    }
+
+    pub fn deadline(&self) -> Option<i64> {
+        match self {
+            DepositArgs::V1 { .. } => None,
+            DepositArgs::V2 { deadline, .. } => *deadline,
+            DepositArgs::V3 { deadline, .. } => *deadline,
+            DepositArgs::V4 { deadline, .. } => *deadline,
+            DepositArgs::V5 { deadline, .. } => *deadline,
+        }
+    }
+
+    pub fn op_id(&self) -> Option<[u8; 16]> {
+        match self {
+            DepositArgs::V3 { op_id, .. } => *op_id,
+            DepositArgs::V4 { op_id, .. } => *op_id,
+            DepositArgs::V5 { op_id, .. } => *op_id,
+            _ => None,
+        }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        match self {
+            DepositArgs::V4 { dry_run, .. } => *dry_run,
+            DepositArgs::V5 { dry_run, .. } => *dry_run,
+            _ => false,
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<u32> {
+        match self {
+            DepositArgs::V5 {
+                expected_version, ..
+            } => *expected_version,
+            _ => None,
+        }
+    }
+}
+
+// clients from before DepositArgs send a bare u64, so exactly 8 bytes is read as V1.
+// DepositArgs has to stay the last argument and no variant may encode to 8 bytes
+impl AnchorDeserialize for DepositArgs {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+
+        if data.len() == 8 {
+            return Ok(DepositArgs::V1 {
+                amount: u64::from_le_bytes(data.try_into().unwrap()),
+            });
+        }
+
+        let (version, mut rest) = data
+            .split_first()
+            .ok_or(std::io::ErrorKind::UnexpectedEof)?;
+        match version {
+            0 => Ok(DepositArgs::V1 {
+                amount: u64::deserialize(&mut rest)?,
+            }),
+            1 => Ok(DepositArgs::V2 {
+                amount: u64::deserialize(&mut rest)?,
+                deadline: Option::<i64>::deserialize(&mut rest)?,
+            }),
+            2 => Ok(DepositArgs::V3 {
+                amount: u64::deserialize(&mut rest)?,
+                deadline: Option::<i64>::deserialize(&mut rest)?,
+                op_id: Option::<[u8; 16]>::deserialize(&mut rest)?,
+            }),
+            3 => Ok(DepositArgs::V4 {
+                amount: u64::deserialize(&mut rest)?,
+                deadline: Option::<i64>::deserialize(&mut rest)?,
+                op_id: Option::<[u8; 16]>::deserialize(&mut rest)?,
+                dry_run: bool::deserialize(&mut rest)?,
+            }),
+            4 => Ok(DepositArgs::V5 {
+                amount: u64::deserialize(&mut rest)?,
+                deadline: Option::<i64>::deserialize(&mut rest)?,
+                op_id: Option::<[u8; 16]>::deserialize(&mut rest)?,
+                dry_run: bool::deserialize(&mut rest)?,
+                expected_version: Option::<u32>::deserialize(&mut rest)?,
+            }),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unknown DepositArgs version",
+            )),
+        }
+    }
+}
+
 #[derive(Accounts)]
+#[instruction(args: DepositArgs)]
 pub struct Deposit<'info> {
     #[account(
-        seeds = [BANK_INFO_SEED],
-        bump
+        mut,
+        address = BankPda::BankInfo.address()
     )]
     pub bank_info: Box<Account<'info, BankInfo>>,
 
     ///CHECK:
     #[account(
         mut,
-        seeds = [BANK_VAULT_SEED],
-        bump,
+        address = BankPda::BankVault.address(),
         owner = system_program::ID
     )]
     pub bank_vault: UncheckedAccount<'info>,
@@ -36,14 +174,69 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // only with an op_id. Creating it fails if it exists, so a retried deposit with
+    // the same op_id can't be credited twice
+    #[account(
+        init,
+        seeds = [OP_RECORD_SEED, user.key().as_ref(), args.op_id().unwrap_or_default().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<OpRecord>(),
+    )]
+    pub op_record: Option<Box<Account<'info, OpRecord>>>,
+
+    // a matching campaign to pay a bonus from, with the user's match counter
+    #[account(mut)]
+    pub campaign: Option<Box<Account<'info, Campaign>>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [CAMPAIGN_MATCH_SEED, campaign.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<CampaignMatch>(),
+    )]
+    pub campaign_match: Option<Box<Account<'info, CampaignMatch>>>,
+
+    // the user's history, if they created one with init_history
+    #[account(
+        mut,
+        address = BankPda::UserHistory { user: user.key() }.address(),
+    )]
+    pub user_history: Option<AccountLoader<'info, UserHistory>>,
 }
 
 impl<'info> Deposit<'info> {
-    pub fn process(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
-        if ctx.accounts.bank_info.is_paused {
-            return Err(BankAppError::BankAppPaused.into());
+    pub fn process(ctx: Context<Deposit>, args: DepositArgs) -> Result<()> {
+        log_compute_units("deposit: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::Deposit)?;
+        check_expected_version(args.expected_version())?;
+
+        if let Some(deadline) = args.deadline() {
+            if Clock::get()?.unix_timestamp > deadline {
+                return Err(BankAppError::DepositExpired.into());
+            }
+        }
+
+        let deposit_amount = args.amount();
+
+        match (args.op_id(), &mut ctx.accounts.op_record) {
+            (Some(op_id), Some(op_record)) => {
+                op_record.set_inner(OpRecord {
+                    user: ctx.accounts.user.key(),
+                    op_id,
+                    amount: deposit_amount,
+                    created_at: Clock::get()?.unix_timestamp,
+                });
+            }
+            (None, None) => {}
+            _ => return Err(BankAppError::OpRecordMismatch.into()),
         }
 
+        let bank_info = &mut ctx.accounts.bank_info;
         let user_reserve = &mut ctx.accounts.user_reserve;
 
         sol_transfer_from_user(
@@ -53,8 +246,85 @@ impl<'info> Deposit<'info> {
             deposit_amount,
         )?;
 
-        user_reserve.deposited_amount += deposit_amount;
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = Clock::get()?.unix_timestamp;
+        }
+
+        let clock = Clock::get()?;
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+        user_reserve.accumulate_balance(clock.unix_timestamp)?;
+        user_reserve.record_activity(clock.unix_timestamp);
+        bank_info.accumulate_total(clock.unix_timestamp)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        let mut bonus = 0;
+        if let (Some(campaign), Some(campaign_match)) =
+            (&mut ctx.accounts.campaign, &mut ctx.accounts.campaign_match)
+        {
+            bonus = campaign.match_deposit(
+                campaign_match,
+                deposit_amount,
+                Clock::get()?.unix_timestamp,
+            );
+            if bonus > 0 {
+                // the campaign is owned by this program, its lamports move without a CPI
+                **campaign.to_account_info().try_borrow_mut_lamports()? -= bonus;
+                **ctx.accounts.bank_vault.try_borrow_mut_lamports()? += bonus;
+
+                user_reserve.deposited_amount = user_reserve
+                    .deposited_amount
+                    .checked_add(bonus)
+                    .ok_or(BankAppError::Overflow)?;
+                bank_info.total_deposited = bank_info
+                    .total_deposited
+                    .checked_add(bonus)
+                    .ok_or(BankAppError::Overflow)?;
+                msg!("campaign {} matched {}", campaign.id, bonus);
+            }
+        }
+        let credited = deposit_amount
+            .checked_add(bonus)
+            .ok_or(BankAppError::Overflow)?;
+
+        // everything above ran, failing now rolls it back so only the logs are left
+        if args.dry_run() {
+            emit!(DepositDryRun {
+                user: ctx.accounts.user.key(),
+                amount: deposit_amount,
+                bonus,
+                deposited_amount: user_reserve.deposited_amount,
+                total_deposited: bank_info.total_deposited,
+            });
+            return Err(BankAppError::DryRunOk.into());
+        }
+
+        if let Some(user_history) = &ctx.accounts.user_history {
+            user_history
+                .load_mut()?
+                .push(HISTORY_KIND_DEPOSIT, credited, clock.unix_timestamp);
+        }
 
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: credited,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("deposit: end");
         Ok(())
     }
 }
@@ -0,0 +1,189 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        EMITTER_SEED, LARGE_DEPOSIT_THRESHOLD, USER_RESERVE_SEED, USER_RESERVE_VERSION,
+        WORMHOLE_MESSAGE_SEED,
+    },
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
+    transfer_helper::sol_transfer_from_user,
+    wormhole::{
+        self, PostMessageAccounts, BRIDGE_CONFIG_SEED, CONSISTENCY_FINALIZED,
+        CORE_BRIDGE_PROGRAM_ID, FEE_COLLECTOR_SEED, PAYLOAD_LARGE_DEPOSIT, SEQUENCE_SEED,
+    },
+};
+
+#[derive(Accounts)]
+pub struct DepositAndNotify<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    ///CHECK: created by the core bridge
+    #[account(
+        mut,
+        seeds = [BRIDGE_CONFIG_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    ///CHECK: created by the core bridge, address is checked against the sequence
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    ///CHECK: only signs the message, holds no data
+    #[account(
+        seeds = [EMITTER_SEED],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    ///CHECK: created by the core bridge on the first message
+    #[account(
+        mut,
+        seeds = [SEQUENCE_SEED, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    ///CHECK: created by the core bridge
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(address = CORE_BRIDGE_PROGRAM_ID)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositAndNotify<'info> {
+    pub fn process(ctx: Context<DepositAndNotify>, deposit_amount: u64) -> Result<()> {
+        log_compute_units("deposit_and_notify: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DepositAndNotify)?;
+
+        if deposit_amount < LARGE_DEPOSIT_THRESHOLD {
+            return Err(BankAppError::DepositBelowThreshold.into());
+        }
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        sol_transfer_from_user(
+            &ctx.accounts.user,
+            ctx.accounts.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            deposit_amount,
+        )?;
+
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = Clock::get()?.unix_timestamp;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // one message account per sequence so a new deposit never reuses an old one
+        let sequence = wormhole::next_sequence(&ctx.accounts.wormhole_sequence)?;
+        let (message_address, message_bump) = BankPda::WormholeMessage { sequence }.find_address();
+        if ctx.accounts.wormhole_message.key() != message_address {
+            return Err(BankAppError::InvalidWormholeMessage.into());
+        }
+
+        let fee = wormhole::bridge_fee(&ctx.accounts.wormhole_bridge)?;
+        if fee > 0 {
+            sol_transfer_from_user(
+                &ctx.accounts.user,
+                ctx.accounts.wormhole_fee_collector.to_account_info(),
+                &ctx.accounts.system_program,
+                fee,
+            )?;
+        }
+
+        let mut payload = vec![PAYLOAD_LARGE_DEPOSIT];
+        payload.extend_from_slice(ctx.accounts.user.key().as_ref());
+        payload.extend_from_slice(&deposit_amount.to_le_bytes());
+
+        let sequence_bytes = sequence.to_le_bytes();
+        let emitter_seeds: &[&[u8]] = &[EMITTER_SEED, &[ctx.bumps.wormhole_emitter]];
+        let message_seeds: &[&[u8]] = &[WORMHOLE_MESSAGE_SEED, &sequence_bytes, &[message_bump]];
+
+        wormhole::post_message(
+            ctx.accounts.wormhole_program.to_account_info(),
+            PostMessageAccounts {
+                bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+                message: ctx.accounts.wormhole_message.to_account_info(),
+                emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+                sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            0,
+            payload,
+            CONSISTENCY_FINALIZED,
+            &[emitter_seeds, message_seeds],
+        )?;
+
+        msg!("large deposit broadcast with sequence {}", sequence);
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: deposit_amount,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            total_deposited: ctx.accounts.bank_info.total_deposited,
+        });
+        log_compute_units("deposit_and_notify: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+    },
+    system_program,
+};
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        BASE_MINT, MAX_PRICE_DEVIATION_BPS, SWAP_PROGRAM_ID, USER_RESERVE_SEED,
+        USER_RESERVE_VERSION,
+    },
+    error::BankAppError,
+    event::DepositEvent,
+    oracle::oracle_amount_out,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
+};
+
+#[derive(Accounts)]
+pub struct DepositAnyToken<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = input_mint.key() != BASE_MINT @ BankAppError::InvalidSwapRoute
+    )]
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = BASE_MINT)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = input_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // the route has to send its output here
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            USER_RESERVE_SEED,
+            user.key().as_ref(),
+            base_mint.key().as_ref()
+        ],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    // the feeds the authority configured for each mint, so a caller can't bring a
+    // feed that agrees with a skewed pool. Owner and staleness are checked when read
+    ///CHECK: parsed with `PullFeedAccountData::parse`
+    #[account(
+        address = bank_info.token_config(&input_mint.key())?.price_feed @ BankAppError::InvalidPriceFeed
+    )]
+    pub input_price_feed: UncheckedAccount<'info>,
+
+    ///CHECK: parsed with `PullFeedAccountData::parse`
+    #[account(
+        address = bank_info.token_config(&base_mint.key())?.price_feed @ BankAppError::InvalidPriceFeed
+    )]
+    pub base_price_feed: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(address = SWAP_PROGRAM_ID)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositAnyToken<'info> {
+    pub fn process(
+        ctx: Context<DepositAnyToken>,
+        min_base_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        log_compute_units("deposit_any_token: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DepositAnyToken)?;
+
+        // the route (pool, tick arrays, oracle...) comes in as remaining accounts and
+        // is forwarded as-is, only the user's signature is passed through
+        let ix = Instruction {
+            program_id: SWAP_PROGRAM_ID,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: swap_data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.swap_program.to_account_info());
+
+        // credit what actually arrived instead of trusting the route
+        let input_before = ctx.accounts.user_ata.amount;
+        let balance_before = ctx.accounts.bank_ata.amount;
+        invoke(&ix, &account_infos)?;
+        ctx.accounts.user_ata.reload()?;
+        ctx.accounts.bank_ata.reload()?;
+        let amount_in = input_before.saturating_sub(ctx.accounts.user_ata.amount);
+        let base_out = ctx
+            .accounts
+            .bank_ata
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(BankAppError::Overflow)?;
+
+        // slippage: the caller's own bound, protects against the pool moving
+        if base_out == 0 || base_out < min_base_out {
+            return Err(BankAppError::SlippageExceeded.into());
+        }
+
+        // manipulation: a min_base_out computed from a skewed pool would pass the
+        // check above, so the executed price is also compared with the oracle
+        let clock = Clock::get()?;
+        let bank_info = &ctx.accounts.bank_info;
+        let input_price = bank_info
+            .token_config(&ctx.accounts.input_mint.key())?
+            .price(&ctx.accounts.input_price_feed, &clock)?;
+        let base_price = bank_info
+            .token_config(&ctx.accounts.base_mint.key())?
+            .price(&ctx.accounts.base_price_feed, &clock)?;
+        let oracle_out = oracle_amount_out(
+            amount_in,
+            input_price,
+            ctx.accounts.input_mint.decimals,
+            base_price,
+            ctx.accounts.base_mint.decimals,
+        )?;
+        let oracle_min_out =
+            oracle_out as u128 * (10_000 - MAX_PRICE_DEVIATION_BPS) as u128 / 10_000;
+        if (base_out as u128) < oracle_min_out {
+            return Err(BankAppError::PriceDeviationTooHigh.into());
+        }
+
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = clock.unix_timestamp;
+        }
+
+        user_reserve.accumulate_balance(clock.unix_timestamp)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(base_out)
+            .ok_or(BankAppError::Overflow)?;
+        let config = ctx
+            .accounts
+            .bank_info
+            .token_config_mut(&ctx.accounts.base_mint.key())?;
+        config.credit(base_out)?;
+        let total_deposited = config.total_deposited;
+
+        msg!(
+            "swapped {} into {} of the base asset",
+            ctx.accounts.input_mint.key(),
+            base_out
+        );
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.base_mint.key(),
+            amount: base_out,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            total_deposited,
+        });
+        log_compute_units("deposit_any_token: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, JointReserve},
+    transfer_helper::sol_transfer_from_user,
+};
+
+#[derive(Accounts)]
+pub struct DepositJoint<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::JointReserve {
+            owner_a: joint_reserve.owner_a,
+            owner_b: joint_reserve.owner_b,
+        }
+        .address_with_bump(joint_reserve.bump),
+        constraint = joint_reserve.is_owner(&owner.key()) @ BankAppError::NotJointOwner,
+    )]
+    pub joint_reserve: Box<Account<'info, JointReserve>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositJoint<'info> {
+    pub fn process(ctx: Context<DepositJoint>, deposit_amount: u64) -> Result<()> {
+        log_compute_units("deposit_joint: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DepositJoint)?;
+
+        sol_transfer_from_user(
+            &ctx.accounts.owner,
+            ctx.accounts.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            deposit_amount,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.bank_info.accumulate_total(now)?;
+        ctx.accounts.joint_reserve.deposited_amount = ctx
+            .accounts
+            .joint_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+        ctx.accounts.bank_info.total_deposited = ctx
+            .accounts
+            .bank_info
+            .total_deposited
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // deposited_amount is the joint reserve's balance
+        emit!(DepositEvent {
+            user: ctx.accounts.owner.key(),
+            mint: Pubkey::default(),
+            amount: deposit_amount,
+            deposited_amount: ctx.accounts.joint_reserve.deposited_amount,
+            total_deposited: ctx.accounts.bank_info.total_deposited,
+        });
+        log_compute_units("deposit_joint: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use anchor_lang::{prelude::*, solana_program::hash::hash, system_program};
+use anchor_spl::memo::{self, BuildMemo, Memo};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        DEPOSIT_COUNTER_SEED, DEPOSIT_RECORD_SEED, MAX_DEPOSIT_TAG_LEN, USER_RESERVE_SEED,
+        USER_RESERVE_VERSION,
+    },
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, DepositCounter, DepositRecord, UserReserve},
+    transfer_helper::sol_transfer_from_user,
+};
+
+#[derive(Accounts)]
+pub struct DepositTagged<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [DEPOSIT_COUNTER_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<DepositCounter>(),
+    )]
+    pub deposit_counter: Box<Account<'info, DepositCounter>>,
+
+    // records are numbered per user so a client can find every one of them
+    #[account(
+        init,
+        seeds = [
+            DEPOSIT_RECORD_SEED,
+            user.key().as_ref(),
+            deposit_counter.count.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<DepositRecord>(),
+    )]
+    pub deposit_record: Box<Account<'info, DepositRecord>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositTagged<'info> {
+    pub fn process(ctx: Context<DepositTagged>, deposit_amount: u64, tag: String) -> Result<()> {
+        log_compute_units("deposit_tagged: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DepositTagged)?;
+
+        if tag.is_empty() || tag.len() > MAX_DEPOSIT_TAG_LEN as usize {
+            return Err(BankAppError::InvalidDepositTag.into());
+        }
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        let now = Clock::get()?.unix_timestamp;
+
+        sol_transfer_from_user(
+            &ctx.accounts.user,
+            ctx.accounts.bank_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            deposit_amount,
+        )?;
+
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = now;
+        }
+
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // the tag is written to the transaction as a memo, the record only keeps its
+        // hash so it stays fixed-size and the client can check the memo it finds
+        memo::build_memo(
+            CpiContext::new(ctx.accounts.memo_program.to_account_info(), BuildMemo {}),
+            tag.as_bytes(),
+        )?;
+
+        let deposit_counter = &mut ctx.accounts.deposit_counter;
+        let deposit_record = &mut ctx.accounts.deposit_record;
+        deposit_record.user = ctx.accounts.user.key();
+        deposit_record.index = deposit_counter.count;
+        deposit_record.amount = deposit_amount;
+        deposit_record.tag_hash = hash(tag.as_bytes()).to_bytes();
+        deposit_record.created_at = now;
+
+        deposit_counter.count += 1;
+
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: deposit_amount,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            total_deposited: ctx.accounts.bank_info.total_deposited,
+        });
+        log_compute_units("deposit_tagged: end");
+        Ok(())
+    }
+}
@@ -1,29 +1,32 @@
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     token::Token,
+    token_2022::{mint_to, MintTo, Token2022},
     token_interface::{Mint, TokenAccount},
 };
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, USER_RESERVE_SEED, USER_RESERVE_VERSION},
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
     transfer_helper::token_transfer_from_user,
 };
 
 #[derive(Accounts)]
 pub struct DepositToken<'info> {
     #[account(
-        seeds = [BANK_INFO_SEED],
-        bump
+        mut,
+        address = BankPda::BankInfo.address()
     )]
     pub bank_info: Box<Account<'info, BankInfo>>,
 
     ///CHECK:
     #[account(
         mut,
-        seeds = [BANK_VAULT_SEED],
-        bump,
+        address = BankPda::BankVault.address(),
         owner = system_program::ID
     )]
     pub bank_vault: UncheckedAccount<'info>,
@@ -62,15 +65,36 @@ pub struct DepositToken<'info> {
     pub user: Signer<'info>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+
+    // with a receipt mint for the token, the deposit is also minted as receipts
+    // into the user's Token-2022 ATA, which the client creates
+    #[account(
+        mut,
+        address = BankPda::ReceiptMint { mint: token_mint.key() }.address(),
+        mint::token_program = token_2022_program,
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub user_receipt_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+
+    ///CHECK: the mint's configured feed, needed when it has a USD deposit cap
+    pub price_feed: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> DepositToken<'info> {
     pub fn process(ctx: Context<DepositToken>, deposit_amount: u64) -> Result<()> {
+        log_compute_units("deposit_token: start");
         let bank_info = &mut ctx.accounts.bank_info;
 
-        if bank_info.is_paused {
-            return Err(BankAppError::BankAppPaused.into());
-        }
+        bank_info.require_enabled(BankInstruction::DepositToken)?;
 
         let user_reserve = &mut ctx.accounts.user_reserve;
 
@@ -82,8 +106,51 @@ impl<'info> DepositToken<'info> {
             deposit_amount,
         )?;
 
-        user_reserve.deposited_amount += deposit_amount;
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = Clock::get()?.unix_timestamp;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(deposit_amount)
+            .ok_or(BankAppError::Overflow)?;
+        let config = bank_info.token_config_mut(&ctx.accounts.token_mint.key())?;
+        config.credit(deposit_amount)?;
+        config.check_usd_cap(ctx.accounts.price_feed.as_deref(), &Clock::get()?)?;
+        let total_deposited = config.total_deposited;
+
+        if let (Some(receipt_mint), Some(user_receipt_ata), Some(token_2022_program)) = (
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.user_receipt_ata,
+            &ctx.accounts.token_2022_program,
+        ) {
+            let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[bank_info.bump]]];
+            mint_to(
+                CpiContext::new_with_signer(
+                    token_2022_program.to_account_info(),
+                    MintTo {
+                        mint: receipt_mint.to_account_info(),
+                        to: user_receipt_ata.to_account_info(),
+                        authority: ctx.accounts.bank_vault.to_account_info(),
+                    },
+                    pda_seeds,
+                ),
+                deposit_amount,
+            )?;
+        }
 
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.token_mint.key(),
+            amount: deposit_amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited,
+        });
+        log_compute_units("deposit_token: end");
         Ok(())
     }
 }
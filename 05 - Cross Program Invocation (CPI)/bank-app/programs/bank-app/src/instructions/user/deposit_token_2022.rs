@@ -0,0 +1,149 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token_2022::{transfer_checked, Token2022, TransferChecked},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{USER_RESERVE_SEED, USER_RESERVE_VERSION},
+    error::BankAppError,
+    event::DepositEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
+    token_extensions::{check_deposit_mint, check_public_credits},
+};
+
+// deposit_token for Token-2022 mints, including ones with confidential transfers.
+// Only the public balance is moved, see token_extensions.rs for why
+#[derive(Accounts)]
+pub struct DepositToken2022<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bank_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub bank_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            USER_RESERVE_SEED,
+            user.key().as_ref(),
+            token_mint.key().as_ref()
+        ],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: the mint's configured feed, needed when it has a USD deposit cap
+    pub price_feed: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> DepositToken2022<'info> {
+    pub fn process(ctx: Context<DepositToken2022>, deposit_amount: u64) -> Result<()> {
+        log_compute_units("deposit_token_2022: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::DepositToken2022)?;
+
+        if deposit_amount == 0 {
+            return Err(BankAppError::InvalidAmount.into());
+        }
+        check_deposit_mint(&ctx.accounts.token_mint.to_account_info())?;
+        check_public_credits(&ctx.accounts.bank_ata.to_account_info())?;
+
+        let balance_before = ctx.accounts.bank_ata.amount;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_ata.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.bank_ata.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            deposit_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        // a transfer fee is held back in the bank ATA, only what arrived is credited
+        ctx.accounts.bank_ata.reload()?;
+        let received = ctx
+            .accounts
+            .bank_ata
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(BankAppError::Overflow)?;
+
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        if user_reserve.version == 0 {
+            user_reserve.version = USER_RESERVE_VERSION;
+            user_reserve.owner = ctx.accounts.user.key();
+            user_reserve.created_at = Clock::get()?.unix_timestamp;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_add(received)
+            .ok_or(BankAppError::Overflow)?;
+        let config = ctx
+            .accounts
+            .bank_info
+            .token_config_mut(&ctx.accounts.token_mint.key())?;
+        config.credit(received)?;
+        config.check_usd_cap(ctx.accounts.price_feed.as_deref(), &Clock::get()?)?;
+        let total_deposited = config.total_deposited;
+
+        msg!(
+            "deposited {} of {}, {} arrived",
+            deposit_amount,
+            ctx.accounts.token_mint.key(),
+            received
+        );
+        emit!(DepositEvent {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.token_mint.key(),
+            amount: received,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            total_deposited,
+        });
+        log_compute_units("deposit_token_2022: end");
+        Ok(())
+    }
+}
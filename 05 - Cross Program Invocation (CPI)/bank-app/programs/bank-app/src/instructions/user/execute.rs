@@ -0,0 +1,96 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+    system_program,
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::USER_VAULT_SEED,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, CpiAllowlist},
+};
+
+// the user's own vault acts as a smart wallet: any instruction of an allowed
+// program, signed by the vault PDA. The bank vault never signs here
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        address = BankPda::CpiAllowlist.address()
+    )]
+    pub cpi_allowlist: Box<Account<'info, CpiAllowlist>>,
+
+    ///CHECK: holds the user's SOL, funded with a plain system transfer
+    #[account(
+        mut,
+        seeds = [USER_VAULT_SEED, user.key().as_ref()],
+        bump,
+        owner = system_program::ID
+    )]
+    pub user_vault: UncheckedAccount<'info>,
+
+    ///CHECK: checked against the allowlist
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> Execute<'info> {
+    pub fn process(ctx: Context<Execute>, data: Vec<u8>) -> Result<()> {
+        log_compute_units("execute: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::Execute)?;
+
+        let program_id = ctx.accounts.target_program.key();
+        if !ctx.accounts.cpi_allowlist.programs.contains(&program_id) {
+            return Err(BankAppError::ProgramNotAllowed.into());
+        }
+
+        // the instruction's accounts come in as remaining accounts, in order. The
+        // vault is marked as a signer wherever it appears, invoke_signed provides it
+        let user_vault = ctx.accounts.user_vault.key();
+        let ix = Instruction {
+            program_id,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == user_vault,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.target_program.to_account_info());
+
+        let user_key = ctx.accounts.user.key();
+        let vault_seeds: &[&[&[u8]]] =
+            &[&[USER_VAULT_SEED, user_key.as_ref(), &[ctx.bumps.user_vault]]];
+        invoke_signed(&ix, &account_infos, vault_seeds)?;
+
+        // an allowed System Program could `assign` or `allocate` the vault, after
+        // which it's no longer a place to keep SOL
+        let vault_info = ctx.accounts.user_vault.to_account_info();
+        if *vault_info.owner != system_program::ID || !vault_info.data_is_empty() {
+            return Err(BankAppError::UserVaultReassigned.into());
+        }
+
+        msg!("user vault called {}", program_id);
+        log_compute_units("execute: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, WITHDRAWAL_WINDOW_SEED},
+    error::BankAppError,
+    event::WithdrawEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// pays the requested withdrawal once its cooldown is over, with the same
+// limits as withdraw
+#[derive(Accounts)]
+pub struct ExecuteWithdraw<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteWithdraw<'info> {
+    pub fn process(ctx: Context<ExecuteWithdraw>) -> Result<()> {
+        log_compute_units("execute_withdraw: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::ExecuteWithdraw)?;
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let withdraw_amount = ctx.accounts.user_reserve.take_pending_withdrawal(now)?;
+        // the reserve may have shrunk since the request, e.g. into a goal
+        if withdraw_amount > ctx.accounts.user_reserve.deposited_amount {
+            return Err(BankAppError::InsufficientDeposit.into());
+        }
+
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.user_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, withdraw_amount, daily_limit)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), withdraw_amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(WithdrawEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("execute_withdraw: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::INSURANCE_FUND_SEED,
+    error::BankAppError,
+    event::InsuranceFunded,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, InsuranceFund},
+    transfer_helper::sol_transfer_from_user,
+};
+
+// anyone can pay in. The bank charges no fees yet, so the authority forwards
+// its slice of income (staking yield, swap rebates...) here by hand
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+        payer = funder,
+        space = 8 + std::mem::size_of::<InsuranceFund>(),
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundInsurance<'info> {
+    pub fn process(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+        log_compute_units("fund_insurance: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::FundInsurance)?;
+        sol_transfer_from_user(
+            &ctx.accounts.funder,
+            ctx.accounts.insurance_fund.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_funded = insurance_fund
+            .total_funded
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(InsuranceFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            total_funded: insurance_fund.total_funded,
+        });
+        log_compute_units("fund_insurance: end");
+        Ok(())
+    }
+}
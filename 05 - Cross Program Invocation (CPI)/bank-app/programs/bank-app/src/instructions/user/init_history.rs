@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, constant::USER_HISTORY_SEED, state::UserHistory};
+
+// opt-in: deposit, withdraw and invest only write to a history that exists
+#[derive(Accounts)]
+pub struct InitHistory<'info> {
+    #[account(
+        init,
+        seeds = [USER_HISTORY_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserHistory>(),
+    )]
+    pub user_history: AccountLoader<'info, UserHistory>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitHistory<'info> {
+    pub fn process(ctx: Context<InitHistory>) -> Result<()> {
+        log_compute_units("init_history: start");
+        ctx.accounts.user_history.load_init()?.user = ctx.accounts.user.key();
+        log_compute_units("init_history: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::slot_hashes, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, LUCKY_DRAW_PRIZE, SLOTHASH_TICKET_SEED},
+    error::BankAppError,
+    event::RewardPaidEvent,
+    randomness::{is_lucky, latest_slot_hash},
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, LuckyTicket, UserReserve},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// DO NOT use this in production: the newest slot hash is public before the
+// transaction lands, so a user can simulate the draw and only send it when it
+// wins. See `commit_lucky_draw` / `settle_lucky_draw` for the VRF version.
+#[derive(Accounts)]
+pub struct LuckyDrawSlothash<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SLOTHASH_TICKET_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<LuckyTicket>(),
+    )]
+    pub lucky_ticket: Box<Account<'info, LuckyTicket>>,
+
+    ///CHECK: read manually in `latest_slot_hash`
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> LuckyDrawSlothash<'info> {
+    pub fn process(ctx: Context<LuckyDrawSlothash>) -> Result<()> {
+        log_compute_units("lucky_draw_slothash: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::LuckyDrawSlothash)?;
+        if ctx.accounts.user_reserve.deposited_amount == 0 {
+            return Err(BankAppError::NoDeposit.into());
+        }
+
+        let clock = Clock::get()?;
+        let lucky_ticket = &mut ctx.accounts.lucky_ticket;
+
+        if clock.epoch < lucky_ticket.next_draw_epoch {
+            return Err(BankAppError::LuckyDrawUnavailable.into());
+        }
+        lucky_ticket.next_draw_epoch = clock.epoch + 1;
+
+        let random = latest_slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?)?;
+
+        if is_lucky(&random, ctx.accounts.user.key) {
+            ctx.accounts
+                .bank_info
+                .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), LUCKY_DRAW_PRIZE)?;
+
+            let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+            sol_transfer_from_pda(
+                ctx.accounts.bank_vault.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program,
+                pda_seeds,
+                LUCKY_DRAW_PRIZE,
+            )?;
+            emit!(RewardPaidEvent {
+                user: ctx.accounts.user.key(),
+                amount: LUCKY_DRAW_PRIZE,
+            });
+            msg!("lucky depositor!");
+        } else {
+            msg!("better luck next epoch");
+        }
+
+        log_compute_units("lucky_draw_slothash: end");
+        Ok(())
+    }
+}
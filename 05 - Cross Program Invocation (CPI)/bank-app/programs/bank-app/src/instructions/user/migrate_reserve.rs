@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units, constant::USER_RESERVE_VERSION, error::BankAppError,
+    seeds::BankPda, state::UserReserve,
+};
+
+#[derive(Accounts)]
+pub struct MigrateReserve<'info> {
+    ///CHECK: may still hold the v1 layout, decoded with `UserReserve::load_versioned`
+    #[account(
+        mut,
+        owner = crate::ID,
+        realloc = 8 + std::mem::size_of::<UserReserve>(),
+        realloc::payer = user,
+        realloc::zero = false,
+    )]
+    pub user_reserve: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateReserve<'info> {
+    pub fn process(ctx: Context<MigrateReserve>, token_mint: Option<Pubkey>) -> Result<()> {
+        log_compute_units("migrate_reserve: start");
+        let user_key = ctx.accounts.user.key();
+        let expected_reserve = match token_mint {
+            Some(mint) => BankPda::UserTokenReserve {
+                user: user_key,
+                mint,
+            },
+            None => BankPda::UserReserve { user: user_key },
+        }
+        .find_address()
+        .0;
+
+        if ctx.accounts.user_reserve.key() != expected_reserve {
+            return Err(BankAppError::InvalidUserReserve.into());
+        }
+
+        let user_reserve_info = ctx.accounts.user_reserve.to_account_info();
+        let mut user_reserve = UserReserve::load_versioned(&user_reserve_info.try_borrow_data()?)?;
+
+        if !user_reserve.is_legacy() {
+            return Err(BankAppError::ReserveAlreadyMigrated.into());
+        }
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        // v1 has no owner or age yet, a v2 reserve keeps its created_at
+        if user_reserve.version == 0 {
+            user_reserve.owner = user_key;
+            user_reserve.created_at = now;
+        }
+        // interest starts now, nothing is paid for the time before the migration.
+        // A v3 reserve keeps its deposit_timestamp, only slots start counting now
+        if user_reserve.version < 3 {
+            user_reserve.deposit_timestamp = now;
+        }
+        user_reserve.accrual_slot = clock.slot;
+        // the balance counts from now, like interest
+        if user_reserve.version < 6 {
+            user_reserve.balance_seconds.updated_at = now;
+        }
+        // the owner signed the migration
+        user_reserve.record_activity(now);
+        user_reserve.version = USER_RESERVE_VERSION;
+
+        user_reserve.try_serialize(&mut &mut user_reserve_info.try_borrow_mut_data()?[..])?;
+
+        msg!("user reserve migrated to v{}", USER_RESERVE_VERSION);
+        log_compute_units("migrate_reserve: end");
+        Ok(())
+    }
+}
@@ -1,9 +1,87 @@
+pub mod assert_version;
+pub mod borrow_limit;
+pub mod check_in;
+pub mod check_vault;
+pub mod claim_inheritance;
+pub mod claim_milestone_badge;
+pub mod claim_snapshot_reward;
+pub mod clear_history;
+pub mod close_deposit_record;
+pub mod close_op_record;
+pub mod close_user_reserve;
+pub mod commit_lucky_draw;
+pub mod create_goal;
+pub mod create_joint_reserve;
 pub mod deposit;
+pub mod deposit_and_notify;
+pub mod deposit_any_token;
+pub mod deposit_joint;
+pub mod deposit_tagged;
 pub mod deposit_token;
-// pub mod withdraw;
+pub mod deposit_token_2022;
+pub mod execute;
+pub mod execute_withdraw;
+pub mod fund_insurance;
+pub mod init_history;
+pub mod lucky_draw_slothash;
+pub mod migrate_reserve;
+pub mod move_from_goal;
+pub mod move_to_goal;
+pub mod program_info;
+pub mod queue_withdrawal;
+pub mod request_withdraw;
+pub mod revoke_beneficiary;
+pub mod set_beneficiary;
+pub mod settle_lucky_draw;
+pub mod setup_recurring;
+pub mod total_value_locked;
+pub mod verify_invariants;
+pub mod withdraw;
+pub mod withdraw_joint;
+pub mod withdraw_to_escrow;
+pub mod withdraw_with_permit;
 // pub mod withdraw_token;
 
+pub use assert_version::*;
+pub use borrow_limit::*;
+pub use check_in::*;
+pub use check_vault::*;
+pub use claim_inheritance::*;
+pub use claim_milestone_badge::*;
+pub use claim_snapshot_reward::*;
+pub use clear_history::*;
+pub use close_deposit_record::*;
+pub use close_op_record::*;
+pub use close_user_reserve::*;
+pub use commit_lucky_draw::*;
+pub use create_goal::*;
+pub use create_joint_reserve::*;
 pub use deposit::*;
+pub use deposit_and_notify::*;
+pub use deposit_any_token::*;
+pub use deposit_joint::*;
+pub use deposit_tagged::*;
 pub use deposit_token::*;
-// pub use withdraw::*;
+pub use deposit_token_2022::*;
+pub use execute::*;
+pub use execute_withdraw::*;
+pub use fund_insurance::*;
+pub use init_history::*;
+pub use lucky_draw_slothash::*;
+pub use migrate_reserve::*;
+pub use move_from_goal::*;
+pub use move_to_goal::*;
+pub use program_info::*;
+pub use queue_withdrawal::*;
+pub use request_withdraw::*;
+pub use revoke_beneficiary::*;
+pub use set_beneficiary::*;
+pub use settle_lucky_draw::*;
+pub use setup_recurring::*;
+pub use total_value_locked::*;
+pub use verify_invariants::*;
+pub use withdraw::*;
+pub use withdraw_joint::*;
+pub use withdraw_to_escrow::*;
+pub use withdraw_with_permit::*;
 // pub use withdraw_token::*;
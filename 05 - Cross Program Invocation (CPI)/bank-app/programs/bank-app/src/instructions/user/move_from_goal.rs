@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    event::GoalMoveEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, Goal, UserReserve},
+};
+
+#[derive(Accounts)]
+#[instruction(goal_index: u8, amount: u64)]
+pub struct MoveFromGoal<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        mut,
+        address = BankPda::Goal { user: user.key(), index: goal_index }.address(),
+        constraint = amount > 0 @ BankAppError::InvalidAmount,
+        constraint = amount <= goal.amount @ BankAppError::InsufficientGoalBalance,
+    )]
+    pub goal: Box<Account<'info, Goal>>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> MoveFromGoal<'info> {
+    pub fn process(ctx: Context<MoveFromGoal>, goal_index: u8, amount: u64) -> Result<()> {
+        log_compute_units("move_from_goal: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::MoveFromGoal)?;
+
+        if ctx.accounts.goal.is_locked(Clock::get()?.unix_timestamp) {
+            return Err(BankAppError::GoalLocked.into());
+        }
+
+        ctx.accounts.goal.amount = ctx
+            .accounts
+            .goal
+            .amount
+            .checked_sub(amount)
+            .ok_or(BankAppError::Overflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.user_reserve.accumulate_balance(now)?;
+        ctx.accounts.user_reserve.deposited_amount = ctx
+            .accounts
+            .user_reserve
+            .deposited_amount
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(GoalMoveEvent {
+            user: ctx.accounts.user.key(),
+            goal_index,
+            amount,
+            to_goal: false,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            goal_amount: ctx.accounts.goal.amount,
+        });
+        log_compute_units("move_from_goal: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    event::GoalMoveEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, Goal, UserReserve},
+};
+
+// no SOL moves, the amount only changes which of the user's accounts holds it
+#[derive(Accounts)]
+#[instruction(goal_index: u8, amount: u64)]
+pub struct MoveToGoal<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = amount > 0 @ BankAppError::InvalidAmount,
+        constraint = amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        mut,
+        address = BankPda::Goal { user: user.key(), index: goal_index }.address(),
+    )]
+    pub goal: Box<Account<'info, Goal>>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> MoveToGoal<'info> {
+    pub fn process(ctx: Context<MoveToGoal>, goal_index: u8, amount: u64) -> Result<()> {
+        log_compute_units("move_to_goal: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::MoveToGoal)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.user_reserve.accumulate_balance(now)?;
+        ctx.accounts.user_reserve.deposited_amount = ctx
+            .accounts
+            .user_reserve
+            .deposited_amount
+            .checked_sub(amount)
+            .ok_or(BankAppError::Overflow)?;
+        ctx.accounts.goal.amount = ctx
+            .accounts
+            .goal
+            .amount
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(GoalMoveEvent {
+            user: ctx.accounts.user.key(),
+            goal_index,
+            amount,
+            to_goal: true,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            goal_amount: ctx.accounts.goal.amount,
+        });
+        log_compute_units("move_to_goal: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::compute::log_compute_units;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProgramVersion {
+    pub version: String,
+    pub git_commit: String,
+}
+
+// no accounts, it's meant to be simulated: the answer comes back as return data
+#[derive(Accounts)]
+pub struct ProgramInfo {}
+
+impl ProgramInfo {
+    pub fn process(_ctx: Context<ProgramInfo>) -> Result<ProgramVersion> {
+        log_compute_units("program_info: start");
+        Ok(ProgramVersion {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BANK_APP_GIT_COMMIT").to_string(),
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{OP_KIND_WITHDRAW, WITHDRAWAL_WINDOW_SEED},
+    error::BankAppError,
+    event::WithdrawEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, OpsQueue, QueuedOp, UserReserve, WithdrawalWindow},
+};
+
+#[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    #[account(
+        mut,
+        address = BankPda::OpsQueue.address()
+    )]
+    pub ops_queue: AccountLoader<'info, OpsQueue>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueWithdrawal<'info> {
+    pub fn process(ctx: Context<QueueWithdrawal>, withdraw_amount: u64) -> Result<()> {
+        log_compute_units("queue_withdrawal: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::QueueWithdrawal)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        // counted when queued, otherwise queueing would get around the limit
+        let now = Clock::get()?.unix_timestamp;
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.user_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, withdraw_amount, daily_limit)?;
+
+        ctx.accounts.ops_queue.load_mut()?.push(QueuedOp {
+            user: ctx.accounts.user.key(),
+            amount: withdraw_amount,
+            queued_at: now,
+            kind: OP_KIND_WITHDRAW,
+            _padding: [0; 7],
+        })?;
+
+        // debited now, the queued op is what the bank owes the user
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        msg!("withdrawal of {} queued", withdraw_amount);
+        emit!(WithdrawEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: ctx.accounts.user_reserve.deposited_amount,
+            total_deposited: ctx.accounts.bank_info.total_deposited,
+        });
+        log_compute_units("queue_withdrawal: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::LARGE_WITHDRAWAL_THRESHOLD,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve},
+};
+
+// the first half of a withdrawal under a cooldown. Nothing is debited yet, the
+// SOL keeps earning interest until execute_withdraw
+#[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
+pub struct RequestWithdraw<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+        constraint = withdraw_amount <= LARGE_WITHDRAWAL_THRESHOLD @ BankAppError::WithdrawalTooLarge,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    pub user: Signer<'info>,
+}
+
+impl<'info> RequestWithdraw<'info> {
+    pub fn process(ctx: Context<RequestWithdraw>, withdraw_amount: u64) -> Result<()> {
+        log_compute_units("request_withdraw: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::RequestWithdraw)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let cooldown = ctx.accounts.bank_info.withdraw_cooldown;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+        user_reserve.request_withdrawal(withdraw_amount, now, cooldown);
+        user_reserve.record_activity(now);
+
+        msg!(
+            "withdrawal of {} unlocks at {}",
+            withdraw_amount,
+            user_reserve.unlock_at
+        );
+        log_compute_units("request_withdraw: end");
+        Ok(())
+    }
+}
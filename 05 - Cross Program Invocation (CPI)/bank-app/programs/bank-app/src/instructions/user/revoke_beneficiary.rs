@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, Inheritance},
+};
+
+#[derive(Accounts)]
+pub struct RevokeBeneficiary<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        mut,
+        address = BankPda::Inheritance { owner: owner.key() }.address(),
+        close = rent_destination
+    )]
+    pub inheritance: Box<Account<'info, Inheritance>>,
+
+    ///CHECK: must match bank_info.rent_destination_for(owner)
+    #[account(
+        mut,
+        constraint = rent_destination.key() == bank_info.rent_destination_for(owner.key) @ BankAppError::InvalidRentDestination
+    )]
+    pub rent_destination: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+}
+
+impl<'info> RevokeBeneficiary<'info> {
+    pub fn process(_ctx: Context<RevokeBeneficiary>) -> Result<()> {
+        log_compute_units("revoke_beneficiary: start");
+        msg!("beneficiary revoked");
+        log_compute_units("revoke_beneficiary: end");
+        Ok(())
+    }
+}
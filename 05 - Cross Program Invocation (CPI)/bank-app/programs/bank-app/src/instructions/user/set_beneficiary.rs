@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{INHERITANCE_SEED, MAX_INACTIVITY_PERIOD},
+    error::BankAppError,
+    state::Inheritance,
+};
+
+// also how the owner changes the beneficiary or the period, it restarts the timer
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey, inactivity_period: i64)]
+pub struct SetBeneficiary<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [INHERITANCE_SEED, owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + std::mem::size_of::<Inheritance>(),
+        constraint = (1..=MAX_INACTIVITY_PERIOD).contains(&inactivity_period) @ BankAppError::InvalidInactivityPeriod,
+    )]
+    pub inheritance: Box<Account<'info, Inheritance>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetBeneficiary<'info> {
+    pub fn process(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Pubkey,
+        inactivity_period: i64,
+    ) -> Result<()> {
+        log_compute_units("set_beneficiary: start");
+        let inheritance = &mut ctx.accounts.inheritance;
+        inheritance.owner = ctx.accounts.owner.key();
+        inheritance.beneficiary = beneficiary;
+        inheritance.inactivity_period = inactivity_period;
+        inheritance.last_activity_ts = Clock::get()?.unix_timestamp;
+
+        msg!(
+            "beneficiary {} after {}s of inactivity",
+            beneficiary,
+            inactivity_period
+        );
+        log_compute_units("set_beneficiary: end");
+        Ok(())
+    }
+}
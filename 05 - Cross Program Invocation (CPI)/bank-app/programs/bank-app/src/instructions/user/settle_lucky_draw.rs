@@ -0,0 +1,97 @@
+use anchor_lang::{prelude::*, system_program};
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, LUCKY_DRAW_PRIZE},
+    error::BankAppError,
+    event::RewardPaidEvent,
+    randomness::is_lucky,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, LuckyTicket},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// step 2 of the VRF draw: read the value the oracle revealed for the
+// committed randomness account and pay out if it wins
+#[derive(Accounts)]
+pub struct SettleLuckyDraw<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::LuckyTicket { user: user.key() }.address(),
+    )]
+    pub lucky_ticket: Box<Account<'info, LuckyTicket>>,
+
+    ///CHECK: must be the account stored on the ticket at commit
+    #[account(address = lucky_ticket.randomness_account @ BankAppError::RandomnessAccountMismatch)]
+    pub randomness_account_data: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleLuckyDraw<'info> {
+    pub fn process(ctx: Context<SettleLuckyDraw>) -> Result<()> {
+        log_compute_units("settle_lucky_draw: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::SettleLuckyDraw)?;
+
+        let clock = Clock::get()?;
+        let lucky_ticket = &mut ctx.accounts.lucky_ticket;
+
+        if lucky_ticket.commit_slot == 0 {
+            return Err(BankAppError::NoLuckyDrawCommitted.into());
+        }
+
+        let randomness_data =
+            RandomnessAccountData::parse(ctx.accounts.randomness_account_data.data.borrow())
+                .map_err(|_| BankAppError::RandomnessNotResolved)?;
+        let random = randomness_data
+            .get_value(&clock)
+            .map_err(|_| BankAppError::RandomnessNotResolved)?;
+
+        lucky_ticket.randomness_account = Pubkey::default();
+        lucky_ticket.commit_slot = 0;
+
+        if is_lucky(&random, ctx.accounts.user.key) {
+            ctx.accounts
+                .bank_info
+                .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), LUCKY_DRAW_PRIZE)?;
+
+            let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+            sol_transfer_from_pda(
+                ctx.accounts.bank_vault.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program,
+                pda_seeds,
+                LUCKY_DRAW_PRIZE,
+            )?;
+            emit!(RewardPaidEvent {
+                user: ctx.accounts.user.key(),
+                amount: LUCKY_DRAW_PRIZE,
+            });
+            msg!("lucky depositor!");
+        } else {
+            msg!("better luck next epoch");
+        }
+
+        log_compute_units("settle_lucky_draw: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Approve, Token},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{RECURRING_APPROVAL_PERIODS, RECURRING_DEPOSIT_SEED},
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, RecurringDeposit},
+};
+
+// running it again replaces the plan and renews the approval
+#[derive(Accounts)]
+#[instruction(amount: u64, interval: i64)]
+pub struct SetupRecurring<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            RECURRING_DEPOSIT_SEED,
+            user.key().as_ref(),
+            token_mint.key().as_ref()
+        ],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<RecurringDeposit>(),
+        constraint = amount > 0 && interval > 0 @ BankAppError::InvalidRecurringDeposit,
+    )]
+    pub recurring_deposit: Box<Account<'info, RecurringDeposit>>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetupRecurring<'info> {
+    pub fn process(ctx: Context<SetupRecurring>, amount: u64, interval: i64) -> Result<()> {
+        log_compute_units("setup_recurring: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::SetupRecurring)?;
+        let recurring_deposit = &mut ctx.accounts.recurring_deposit;
+        recurring_deposit.user = ctx.accounts.user.key();
+        recurring_deposit.mint = ctx.accounts.token_mint.key();
+        recurring_deposit.amount = amount;
+        recurring_deposit.interval = interval;
+        recurring_deposit.next_due_at = Clock::get()?.unix_timestamp;
+        recurring_deposit.bump = ctx.bumps.recurring_deposit;
+
+        // a token account has one delegate, this replaces any earlier one. The
+        // allowance runs out after RECURRING_APPROVAL_PERIODS pulls, and the
+        // user can end it sooner with spl-token's revoke
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    delegate: recurring_deposit.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount.saturating_mul(RECURRING_APPROVAL_PERIODS as u64),
+        )?;
+
+        msg!("recurring deposit of {} every {}s", amount, interval);
+        log_compute_units("setup_recurring: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{compute::log_compute_units, seeds::BankPda, state::BankInfo};
+
+// read-only, simulate it to get the TVL in USD as return data. Remaining
+// accounts: the price feed of every priced mint, in token_configs order
+#[derive(Accounts)]
+pub struct TotalValueLocked<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+}
+
+impl<'info> TotalValueLocked<'info> {
+    pub fn process(ctx: Context<TotalValueLocked>) -> Result<u64> {
+        log_compute_units("total_value_locked: start");
+        ctx.accounts
+            .bank_info
+            .total_value_locked(ctx.remaining_accounts, &Clock::get()?)
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    invariants::{self, InvariantReport},
+    seeds::BankPda,
+    state::BankInfo,
+};
+
+// read-only, simulate it to get the report as return data
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    ///CHECK: the bank vault's UserInfo in the staking app, may not exist yet
+    #[account(
+        seeds = [b"USER_INFO", bank_vault.key().as_ref()],
+        bump,
+        seeds::program = staking_app::ID
+    )]
+    pub staking_info: UncheckedAccount<'info>,
+}
+
+impl<'info> VerifyInvariants<'info> {
+    pub fn process(ctx: Context<VerifyInvariants>) -> Result<InvariantReport> {
+        log_compute_units("verify_invariants: start");
+        invariants::verify_invariants(
+            &ctx.accounts.bank_info,
+            &ctx.accounts.bank_vault,
+            &ctx.accounts.staking_info,
+        )
+    }
+}
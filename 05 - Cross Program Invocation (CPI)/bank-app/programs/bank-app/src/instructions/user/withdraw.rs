@@ -1,50 +1,182 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constant::{BANK_INFO_SEED, BANK_VAULT_SEED, USER_RESERVE_SEED},
+    compute::log_compute_units,
+    constant::{
+        BANK_VAULT_SEED, HISTORY_KIND_WITHDRAW, LARGE_WITHDRAWAL_THRESHOLD, WITHDRAWAL_WINDOW_SEED,
+    },
     error::BankAppError,
-    state::{BankInfo, UserReserve},
+    event::{WithdrawDryRun, WithdrawEvent},
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserHistory, UserReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+    version::check_expected_version,
 };
 
+// trailing withdraw argument. Clients from before it send only the amount, so no
+// bytes at all read as the default, and fields added later may be missing too
+#[derive(AnchorSerialize, Clone, Copy, Default)]
+pub struct WithdrawOptions {
+    // run every check and the transfer, log a WithdrawDryRun and fail with DryRunOk
+    pub dry_run: bool,
+    // fail with VersionMismatch unless the program is compatible, see check_version
+    pub expected_version: Option<u32>,
+}
+
+impl AnchorDeserialize for WithdrawOptions {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+
+        if data.is_empty() {
+            return Ok(WithdrawOptions::default());
+        }
+        let mut rest = data.as_slice();
+        let dry_run = bool::deserialize(&mut rest)?;
+        let expected_version = if rest.is_empty() {
+            None
+        } else {
+            Option::<u32>::deserialize(&mut rest)?
+        };
+        Ok(WithdrawOptions {
+            dry_run,
+            expected_version,
+        })
+    }
+}
+
+// checks on the argument that only need account data live in the constraints,
+// they run before `process` and name the account that failed
 #[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
 pub struct Withdraw<'info> {
     #[account(
         mut,
-        seeds = [BANK_INFO_SEED],
-        bump
+        address = BankPda::BankInfo.address()
     )]
     pub bank_info: Box<Account<'info, BankInfo>>,
 
     ///CHECK:
     #[account(
         mut,
-        seeds = [BANK_VAULT_SEED],
-        bump,
+        address = BankPda::BankVault.address(),
         owner = system_program::ID
     )]
     pub bank_vault: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [USER_RESERVE_SEED, user.key().as_ref()],
-        bump,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+        constraint = withdraw_amount <= LARGE_WITHDRAWAL_THRESHOLD @ BankAppError::WithdrawalTooLarge,
     )]
     pub user_reserve: Box<Account<'info, UserReserve>>,
 
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // the user's history, if they created one with init_history
+    #[account(
+        mut,
+        address = BankPda::UserHistory { user: user.key() }.address(),
+    )]
+    pub user_history: Option<AccountLoader<'info, UserHistory>>,
 }
 
 impl<'info> Withdraw<'info> {
-    pub fn process(ctx: Context<Withdraw>, withdraw_amount: u64) -> Result<()> {
-        if ctx.accounts.bank_info.is_paused {
-            return Err(BankAppError::BankAppPaused.into());
-        }
+    pub fn process(
+        ctx: Context<Withdraw>,
+        withdraw_amount: u64,
+        options: WithdrawOptions,
+    ) -> Result<()> {
+        log_compute_units("withdraw: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::Withdraw)?;
+        check_expected_version(options.expected_version)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.user_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, withdraw_amount, daily_limit)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), withdraw_amount)?;
 
         let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
-        // Your code here
 
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // everything above ran, failing now rolls it back so only the logs are left
+        if options.dry_run {
+            emit!(WithdrawDryRun {
+                user: ctx.accounts.user.key(),
+                amount: withdraw_amount,
+                deposited_amount: user_reserve.deposited_amount,
+                total_deposited: bank_info.total_deposited,
+                withdrawn_today: ctx.accounts.withdrawal_window.withdrawn,
+            });
+            return Err(BankAppError::DryRunOk.into());
+        }
+
+        if let Some(user_history) = &ctx.accounts.user_history {
+            user_history
+                .load_mut()?
+                .push(HISTORY_KIND_WITHDRAW, withdraw_amount, now);
+        }
+
+        emit!(WithdrawEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("withdraw: end");
         Ok(())
     }
 }
@@ -0,0 +1,122 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, WITHDRAWAL_WINDOW_SEED},
+    error::BankAppError,
+    event::WithdrawEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, JointReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// `owner` signs and receives the SOL. `co_owner` is only needed above the
+// single signer limit, and then it has to be the other owner. The daily limit is
+// counted in the signing owner's own window, the same one their withdraws fill
+#[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
+pub struct WithdrawJoint<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::JointReserve {
+            owner_a: joint_reserve.owner_a,
+            owner_b: joint_reserve.owner_b,
+        }
+        .address_with_bump(joint_reserve.bump),
+        constraint = joint_reserve.is_owner(&owner.key()) @ BankAppError::NotJointOwner,
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= joint_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+        constraint = withdraw_amount <= joint_reserve.single_signer_limit || co_owner.is_some() @ BankAppError::CoSignerRequired,
+    )]
+    pub joint_reserve: Box<Account<'info, JointReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        address = joint_reserve.other_owner(&owner.key()) @ BankAppError::NotJointOwner,
+    )]
+    pub co_owner: Option<Signer<'info>>,
+}
+
+impl<'info> WithdrawJoint<'info> {
+    pub fn process(ctx: Context<WithdrawJoint>, withdraw_amount: u64) -> Result<()> {
+        log_compute_units("withdraw_joint: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::WithdrawJoint)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.joint_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, withdraw_amount, daily_limit)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), withdraw_amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        ctx.accounts.bank_info.accumulate_total(now)?;
+        ctx.accounts.joint_reserve.deposited_amount = ctx
+            .accounts
+            .joint_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        ctx.accounts.bank_info.total_deposited = ctx
+            .accounts
+            .bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        // deposited_amount is the joint reserve's balance
+        emit!(WithdrawEvent {
+            user: ctx.accounts.owner.key(),
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: ctx.accounts.joint_reserve.deposited_amount,
+            total_deposited: ctx.accounts.bank_info.total_deposited,
+        });
+        log_compute_units("withdraw_joint: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{BANK_VAULT_SEED, LARGE_WITHDRAWAL_THRESHOLD, WITHDRAWAL_WINDOW_SEED},
+    error::BankAppError,
+    event::WithdrawEvent,
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, UserReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+};
+use escrow_app::{cpi, program::EscrowApp};
+
+// a withdraw whose SOL goes straight into escrow_app, locked for `recipient`
+// until `unlock_at`. The vault pays the user and the user locks it in the same
+// instruction, so either both happen or neither does. The escrow program checks
+// its own seeds, only its id is pinned here
+#[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
+pub struct WithdrawToEscrow<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+        constraint = withdraw_amount <= LARGE_WITHDRAWAL_THRESHOLD @ BankAppError::WithdrawalTooLarge,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    ///CHECK:
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+    pub escrow_program: Program<'info, EscrowApp>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawToEscrow<'info> {
+    pub fn process(
+        ctx: Context<WithdrawToEscrow>,
+        withdraw_amount: u64,
+        recipient: Pubkey,
+        unlock_at: i64,
+    ) -> Result<()> {
+        log_compute_units("withdraw_to_escrow: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::WithdrawToEscrow)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(now - ctx.accounts.user_reserve.created_at);
+        ctx.accounts
+            .withdrawal_window
+            .record(now, withdraw_amount, daily_limit)?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), withdraw_amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        // the user's signature carries into the escrow, they are its depositor
+        cpi::lock(
+            CpiContext::new(
+                ctx.accounts.escrow_program.to_account_info(),
+                cpi::accounts::Lock {
+                    escrow: ctx.accounts.escrow.to_account_info(),
+                    depositor: ctx.accounts.user.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            ),
+            withdraw_amount,
+            recipient,
+            unlock_at,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+        user_reserve.accumulate_balance(now)?;
+        user_reserve.record_activity(now);
+        bank_info.accumulate_total(now)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(WithdrawEvent {
+            user: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("withdraw_to_escrow: end");
+        Ok(())
+    }
+}
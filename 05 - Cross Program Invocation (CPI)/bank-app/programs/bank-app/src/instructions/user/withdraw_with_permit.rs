@@ -0,0 +1,157 @@
+use anchor_lang::{prelude::*, solana_program::sysvar, system_program};
+
+use crate::{
+    compute::log_compute_units,
+    constant::{
+        BANK_VAULT_SEED, LARGE_WITHDRAWAL_THRESHOLD, PERMIT_NONCE_SEED, WITHDRAWAL_WINDOW_SEED,
+    },
+    error::BankAppError,
+    event::WithdrawEvent,
+    permit::{permit_message, verify_ed25519_permit},
+    seeds::BankPda,
+    state::{BankInfo, BankInstruction, PermitNonce, UserReserve, WithdrawalWindow},
+    transfer_helper::sol_transfer_from_pda,
+};
+
+// a withdraw the user signed off-chain and a relayer submits and pays for. The
+// SOL still only goes to the user, with the same limits as withdraw
+#[derive(Accounts)]
+#[instruction(withdraw_amount: u64)]
+pub struct WithdrawWithPermit<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Box<Account<'info, BankInfo>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = BankPda::UserReserve { user: user.key() }.address(),
+        constraint = withdraw_amount > 0 @ BankAppError::InvalidAmount,
+        constraint = withdraw_amount <= user_reserve.deposited_amount @ BankAppError::InsufficientDeposit,
+        constraint = withdraw_amount <= LARGE_WITHDRAWAL_THRESHOLD @ BankAppError::WithdrawalTooLarge,
+    )]
+    pub user_reserve: Box<Account<'info, UserReserve>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [WITHDRAWAL_WINDOW_SEED, user.key().as_ref()],
+        bump,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<WithdrawalWindow>(),
+    )]
+    pub withdrawal_window: Box<Account<'info, WithdrawalWindow>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [PERMIT_NONCE_SEED, user.key().as_ref()],
+        bump,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<PermitNonce>(),
+    )]
+    pub permit_nonce: Box<Account<'info, PermitNonce>>,
+
+    ///CHECK: signed the permit, checked against the ed25519 instruction. Receives the SOL
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    ///CHECK: the instructions sysvar, where the ed25519 instruction is read from
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawWithPermit<'info> {
+    pub fn process(
+        ctx: Context<WithdrawWithPermit>,
+        withdraw_amount: u64,
+        nonce: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        log_compute_units("withdraw_with_permit: start");
+        ctx.accounts
+            .bank_info
+            .require_enabled(BankInstruction::WithdrawWithPermit)?;
+        ctx.accounts.bank_info.require_no_withdraw_cooldown()?;
+
+        let clock = Clock::get()?;
+        if clock.unix_timestamp > deadline {
+            return Err(BankAppError::PermitExpired.into());
+        }
+
+        let user = ctx.accounts.user.key();
+        verify_ed25519_permit(
+            &ctx.accounts.instructions,
+            &user,
+            &permit_message(&user, withdraw_amount, nonce, deadline),
+        )?;
+
+        let permit_nonce = &mut ctx.accounts.permit_nonce;
+        permit_nonce.user = user;
+        permit_nonce.use_nonce(nonce)?;
+
+        let daily_limit = ctx
+            .accounts
+            .bank_info
+            .daily_withdrawal_limit(clock.unix_timestamp - ctx.accounts.user_reserve.created_at);
+        ctx.accounts.withdrawal_window.record(
+            clock.unix_timestamp,
+            withdraw_amount,
+            daily_limit,
+        )?;
+
+        ctx.accounts
+            .bank_info
+            .require_above_rent_floor(ctx.accounts.bank_vault.lamports(), withdraw_amount)?;
+
+        let pda_seeds: &[&[&[u8]]] = &[&[BANK_VAULT_SEED, &[ctx.accounts.bank_info.bump]]];
+        sol_transfer_from_pda(
+            ctx.accounts.bank_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            pda_seeds,
+            withdraw_amount,
+        )?;
+
+        let bank_info = &mut ctx.accounts.bank_info;
+        let user_reserve = &mut ctx.accounts.user_reserve;
+
+        user_reserve.accrue_interest(
+            bank_info.interest_rate_bps,
+            bank_info.interest_clock,
+            &clock,
+        )?;
+        user_reserve.accumulate_balance(clock.unix_timestamp)?;
+        user_reserve.record_activity(clock.unix_timestamp);
+        bank_info.accumulate_total(clock.unix_timestamp)?;
+        user_reserve.deposited_amount = user_reserve
+            .deposited_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+        bank_info.total_deposited = bank_info
+            .total_deposited
+            .checked_sub(withdraw_amount)
+            .ok_or(BankAppError::Overflow)?;
+
+        emit!(WithdrawEvent {
+            user,
+            mint: Pubkey::default(),
+            amount: withdraw_amount,
+            deposited_amount: user_reserve.deposited_amount,
+            total_deposited: bank_info.total_deposited,
+        });
+        log_compute_units("withdraw_with_permit: end");
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::BankInfo;
+
+// what the bank owes against what it holds. Every deposit sits in the vault,
+// the staking app or the stake pool, so together they must cover total_deposited
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InvariantReport {
+    pub total_deposited: u64,
+    pub vault_balance: u64,
+    pub staked_amount: u64,
+    pub shortfall: u64,
+    pub stake_pool_principal: u64,
+}
+
+// `staking_info` must be the bank vault's UserInfo address, it's empty before
+// the first invest
+pub fn verify_invariants(
+    bank_info: &BankInfo,
+    bank_vault: &AccountInfo,
+    staking_info: &AccountInfo,
+) -> Result<InvariantReport> {
+    let staked_amount = if staking_info.data_is_empty() {
+        0
+    } else {
+        staking_app::UserInfo::try_deserialize(&mut &staking_info.try_borrow_data()?[..])?.amount
+    };
+    let vault_balance = bank_vault.lamports();
+
+    Ok(InvariantReport {
+        total_deposited: bank_info.total_deposited,
+        vault_balance,
+        staked_amount,
+        shortfall: bank_info.total_deposited.saturating_sub(
+            vault_balance
+                .saturating_add(staked_amount)
+                .saturating_add(bank_info.stake_pool_principal),
+        ),
+        stake_pool_principal: bank_info.stake_pool_principal,
+    })
+}
@@ -1,15 +1,47 @@
 use anchor_lang::prelude::*;
 
+pub mod bubblegum;
+pub mod compute;
 pub mod constant;
 pub mod error;
+pub mod event;
 pub mod instructions;
+pub mod invariants;
+pub mod lookup_table;
+pub mod merkle;
+pub mod oracle;
+pub mod permit;
+pub mod randomness;
+pub mod seeds;
+#[cfg(feature = "stack-bench")]
+pub mod stack_bench;
+pub mod stake_pool;
 pub mod state;
+pub mod token_extensions;
 pub mod transfer_helper;
+pub mod version;
+pub mod wormhole;
 
+use constant::WITHDRAWAL_TIER_COUNT;
 use instructions::*;
+use invariants::InvariantReport;
+use state::{ApprovalAction, BankInstruction, InterestClock, WithdrawalTier};
 
 declare_id!("3q57ftWH75aKfxoNnV6Lu1n8LhV73xxKHAxPapL6Jvh7");
 
+// read by explorers and by `query-security-txt`, it points researchers at the
+// source and at the exact commit that was deployed
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "Bank App",
+    project_url: "https://github.com/nhatbui97/solana-tutorials",
+    contacts: "link:https://github.com/nhatbui97/solana-tutorials/issues",
+    policy: "https://github.com/nhatbui97/solana-tutorials/security/policy",
+    source_code: "https://github.com/nhatbui97/solana-tutorials",
+    source_release: env!("CARGO_PKG_VERSION"),
+    source_revision: env!("BANK_APP_GIT_COMMIT")
+}
+
 #[program]
 pub mod bank_app {
     use super::*;
@@ -18,15 +50,487 @@ pub mod bank_app {
         return Initialize::process(ctx);
     }
 
+    pub fn initialize_badge_tree(
+        ctx: Context<InitializeBadgeTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        return InitializeBadgeTree::process(ctx, max_depth, max_buffer_size);
+    }
+
+    pub fn set_rent_destination(
+        ctx: Context<SetRentDestination>,
+        rent_destination: Pubkey,
+    ) -> Result<()> {
+        return SetRentDestination::process(ctx, rent_destination);
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        return SetGuardian::process(ctx, guardian);
+    }
+
+    pub fn guardian_set_paused(ctx: Context<GuardianSetPaused>) -> Result<()> {
+        return GuardianSetPaused::process(ctx);
+    }
+
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        return Pause::process(ctx, true);
+    }
+
+    pub fn unpause(ctx: Context<Pause>) -> Result<()> {
+        return Pause::process(ctx, false);
+    }
+
+    pub fn set_instruction_enabled(
+        ctx: Context<SetInstructionEnabled>,
+        instruction: BankInstruction,
+        enabled: bool,
+    ) -> Result<()> {
+        return SetInstructionEnabled::process(ctx, instruction, enabled);
+    }
+
+    pub fn set_cpi_allowlist(ctx: Context<SetCpiAllowlist>, programs: Vec<Pubkey>) -> Result<()> {
+        return SetCpiAllowlist::process(ctx, programs);
+    }
+
+    pub fn add_supported_token(ctx: Context<AddSupportedToken>) -> Result<()> {
+        return AddSupportedToken::process(ctx);
+    }
+
+    pub fn set_token_config(
+        ctx: Context<SetTokenConfig>,
+        interest_rate_bps: u16,
+        deposit_cap: u64,
+        is_paused: bool,
+    ) -> Result<()> {
+        return SetTokenConfig::process(ctx, interest_rate_bps, deposit_cap, is_paused);
+    }
+
+    pub fn set_token_oracle(
+        ctx: Context<SetTokenOracle>,
+        price_feed: Pubkey,
+        max_staleness_slots: u64,
+        deposit_cap_usd: u64,
+        borrow_ltv_bps: u16,
+    ) -> Result<()> {
+        return SetTokenOracle::process(
+            ctx,
+            price_feed,
+            max_staleness_slots,
+            deposit_cap_usd,
+            borrow_ltv_bps,
+        );
+    }
+
+    pub fn create_receipt_mint(
+        ctx: Context<CreateReceiptMint>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        return CreateReceiptMint::process(ctx, name, symbol, uri);
+    }
+
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        return CreateLookupTable::process(ctx, recent_slot);
+    }
+
+    pub fn extend_lookup_table(
+        ctx: Context<ExtendLookupTable>,
+        new_addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        return ExtendLookupTable::process(ctx, new_addresses);
+    }
+
+    pub fn sync_receipt_rate(ctx: Context<SyncReceiptRate>) -> Result<()> {
+        return SyncReceiptRate::process(ctx);
+    }
+
+    pub fn deny_wallet(ctx: Context<DenyWallet>, wallet: Pubkey) -> Result<()> {
+        return DenyWallet::process(ctx, wallet);
+    }
+
+    pub fn allow_wallet(ctx: Context<AllowWallet>, wallet: Pubkey) -> Result<()> {
+        return AllowWallet::process(ctx, wallet);
+    }
+
+    pub fn set_interest_rate(ctx: Context<SetInterestRate>, interest_rate_bps: u16) -> Result<()> {
+        return SetInterestRate::process(ctx, interest_rate_bps);
+    }
+
+    pub fn set_interest_clock(
+        ctx: Context<SetInterestClock>,
+        interest_clock: InterestClock,
+    ) -> Result<()> {
+        return SetInterestClock::process(ctx, interest_clock);
+    }
+
+    pub fn set_withdraw_cooldown(
+        ctx: Context<SetWithdrawCooldown>,
+        withdraw_cooldown: i64,
+    ) -> Result<()> {
+        return SetWithdrawCooldown::process(ctx, withdraw_cooldown);
+    }
+
+    pub fn set_withdrawal_tiers(
+        ctx: Context<SetWithdrawalTiers>,
+        tiers: [WithdrawalTier; WITHDRAWAL_TIER_COUNT],
+    ) -> Result<()> {
+        return SetWithdrawalTiers::process(ctx, tiers);
+    }
+
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        campaign_id: u64,
+        match_ratio_bps: u16,
+        budget: u64,
+        per_user_cap: u64,
+        start_at: i64,
+        end_at: i64,
+    ) -> Result<()> {
+        return CreateCampaign::process(
+            ctx,
+            campaign_id,
+            match_ratio_bps,
+            budget,
+            per_user_cap,
+            start_at,
+            end_at,
+        );
+    }
+
+    pub fn close_campaign(ctx: Context<CloseCampaign>, campaign_id: u64) -> Result<()> {
+        return CloseCampaign::process(ctx, campaign_id);
+    }
+
     pub fn invest(ctx: Context<Invest>, amount: u64, is_stake: bool) -> Result<()> {
         return Invest::process(ctx, amount, is_stake);
     }
 
-    pub fn deposit(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
-        return Deposit::process(ctx, deposit_amount);
+    pub fn invest_stake_pool(ctx: Context<InvestStakePool>, amount: u64) -> Result<()> {
+        return InvestStakePool::process(ctx, amount);
+    }
+
+    pub fn divest_stake_pool(
+        ctx: Context<DivestStakePool>,
+        pool_tokens: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        return DivestStakePool::process(ctx, pool_tokens, min_amount_out);
+    }
+
+    pub fn set_approvers(
+        ctx: Context<SetApprovers>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        return SetApprovers::process(ctx, approvers, threshold, expiry_slots);
+    }
+
+    pub fn approve_action(ctx: Context<ApproveAction>, action: ApprovalAction) -> Result<()> {
+        return ApproveAction::process(ctx, action);
+    }
+
+    pub fn cover_shortfall(ctx: Context<CoverShortfall>) -> Result<()> {
+        return CoverShortfall::process(ctx);
+    }
+
+    pub fn close_bank(ctx: Context<CloseBank>) -> Result<()> {
+        return CloseBank::process(ctx);
+    }
+
+    pub fn rescue_tokens(ctx: Context<RescueTokens>, amount: u64) -> Result<()> {
+        return RescueTokens::process(ctx, amount);
+    }
+
+    pub fn set_accrual_mode(ctx: Context<SetAccrualMode>, compound: bool) -> Result<()> {
+        return SetAccrualMode::process(ctx, compound);
+    }
+
+    pub fn sync_version_beacon(ctx: Context<SyncVersionBeacon>) -> Result<()> {
+        return SyncVersionBeacon::process(ctx);
+    }
+
+    pub fn init_ops_queue(ctx: Context<InitOpsQueue>) -> Result<()> {
+        return InitOpsQueue::process(ctx);
+    }
+
+    pub fn queue_divest(ctx: Context<QueueDivest>, amount: u64) -> Result<()> {
+        return QueueDivest::process(ctx, amount);
+    }
+
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, withdraw_amount: u64) -> Result<()> {
+        return QueueWithdrawal::process(ctx, withdraw_amount);
+    }
+
+    pub fn process_next_op(ctx: Context<ProcessNextOp>) -> Result<()> {
+        return ProcessNextOp::process(ctx);
+    }
+
+    pub fn setup_recurring(ctx: Context<SetupRecurring>, amount: u64, interval: i64) -> Result<()> {
+        return SetupRecurring::process(ctx, amount, interval);
+    }
+
+    pub fn execute_recurring(ctx: Context<ExecuteRecurring>) -> Result<()> {
+        return ExecuteRecurring::process(ctx);
+    }
+
+    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        return AccrueInterest::process(ctx);
+    }
+
+    pub fn harvest(ctx: Context<Harvest>) -> Result<()> {
+        return Harvest::process(ctx);
+    }
+
+    pub fn top_up_rent(ctx: Context<TopUpRent>) -> Result<()> {
+        return TopUpRent::process(ctx);
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, args: DepositArgs) -> Result<()> {
+        return Deposit::process(ctx, args);
+    }
+
+    // `options` has to stay the last argument, see WithdrawOptions
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        withdraw_amount: u64,
+        options: WithdrawOptions,
+    ) -> Result<()> {
+        return Withdraw::process(ctx, withdraw_amount, options);
+    }
+
+    pub fn request_withdraw(ctx: Context<RequestWithdraw>, withdraw_amount: u64) -> Result<()> {
+        return RequestWithdraw::process(ctx, withdraw_amount);
+    }
+
+    pub fn execute_withdraw(ctx: Context<ExecuteWithdraw>) -> Result<()> {
+        return ExecuteWithdraw::process(ctx);
+    }
+
+    pub fn withdraw_with_permit(
+        ctx: Context<WithdrawWithPermit>,
+        withdraw_amount: u64,
+        nonce: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        return WithdrawWithPermit::process(ctx, withdraw_amount, nonce, deadline);
     }
 
     pub fn deposit_token(ctx: Context<DepositToken>, deposit_amount: u64) -> Result<()> {
         return DepositToken::process(ctx, deposit_amount);
     }
+
+    pub fn deposit_token_2022(ctx: Context<DepositToken2022>, deposit_amount: u64) -> Result<()> {
+        return DepositToken2022::process(ctx, deposit_amount);
+    }
+
+    pub fn deposit_and_notify(ctx: Context<DepositAndNotify>, deposit_amount: u64) -> Result<()> {
+        return DepositAndNotify::process(ctx, deposit_amount);
+    }
+
+    pub fn deposit_any_token(
+        ctx: Context<DepositAnyToken>,
+        min_base_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        return DepositAnyToken::process(ctx, min_base_out, swap_data);
+    }
+
+    pub fn deposit_tagged(
+        ctx: Context<DepositTagged>,
+        deposit_amount: u64,
+        tag: String,
+    ) -> Result<()> {
+        return DepositTagged::process(ctx, deposit_amount, tag);
+    }
+
+    pub fn register_foreign_emitter(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        return RegisterForeignEmitter::process(ctx, chain, address);
+    }
+
+    pub fn receive_cross_chain_deposit(
+        ctx: Context<ReceiveCrossChainDeposit>,
+        vaa_hash: [u8; 32],
+    ) -> Result<()> {
+        return ReceiveCrossChainDeposit::process(ctx, vaa_hash);
+    }
+
+    pub fn claim_milestone_badge(ctx: Context<ClaimMilestoneBadge>, milestone: u8) -> Result<()> {
+        return ClaimMilestoneBadge::process(ctx, milestone);
+    }
+
+    pub fn close_user_reserve(ctx: Context<CloseUserReserve>) -> Result<()> {
+        return CloseUserReserve::process(ctx);
+    }
+
+    pub fn close_deposit_record(ctx: Context<CloseDepositRecord>, index: u64) -> Result<()> {
+        return CloseDepositRecord::process(ctx, index);
+    }
+
+    pub fn close_op_record(ctx: Context<CloseOpRecord>, op_id: [u8; 16]) -> Result<()> {
+        return CloseOpRecord::process(ctx, op_id);
+    }
+
+    pub fn init_history(ctx: Context<InitHistory>) -> Result<()> {
+        return InitHistory::process(ctx);
+    }
+
+    pub fn clear_history(ctx: Context<ClearHistory>) -> Result<()> {
+        return ClearHistory::process(ctx);
+    }
+
+    pub fn check_vault(ctx: Context<CheckVault>) -> Result<()> {
+        return CheckVault::process(ctx);
+    }
+
+    pub fn program_info(ctx: Context<ProgramInfo>) -> Result<ProgramVersion> {
+        return ProgramInfo::process(ctx);
+    }
+
+    pub fn create_goal(
+        ctx: Context<CreateGoal>,
+        goal_index: u8,
+        name: String,
+        target_amount: u64,
+        lock_until: Option<i64>,
+    ) -> Result<()> {
+        return CreateGoal::process(ctx, goal_index, name, target_amount, lock_until);
+    }
+
+    pub fn move_to_goal(ctx: Context<MoveToGoal>, goal_index: u8, amount: u64) -> Result<()> {
+        return MoveToGoal::process(ctx, goal_index, amount);
+    }
+
+    pub fn move_from_goal(ctx: Context<MoveFromGoal>, goal_index: u8, amount: u64) -> Result<()> {
+        return MoveFromGoal::process(ctx, goal_index, amount);
+    }
+
+    pub fn create_joint_reserve(
+        ctx: Context<CreateJointReserve>,
+        single_signer_limit: u64,
+    ) -> Result<()> {
+        return CreateJointReserve::process(ctx, single_signer_limit);
+    }
+
+    pub fn deposit_joint(ctx: Context<DepositJoint>, deposit_amount: u64) -> Result<()> {
+        return DepositJoint::process(ctx, deposit_amount);
+    }
+
+    pub fn withdraw_joint(ctx: Context<WithdrawJoint>, withdraw_amount: u64) -> Result<()> {
+        return WithdrawJoint::process(ctx, withdraw_amount);
+    }
+
+    pub fn withdraw_to_escrow(
+        ctx: Context<WithdrawToEscrow>,
+        withdraw_amount: u64,
+        recipient: Pubkey,
+        unlock_at: i64,
+    ) -> Result<()> {
+        return WithdrawToEscrow::process(ctx, withdraw_amount, recipient, unlock_at);
+    }
+
+    pub fn set_beneficiary(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Pubkey,
+        inactivity_period: i64,
+    ) -> Result<()> {
+        return SetBeneficiary::process(ctx, beneficiary, inactivity_period);
+    }
+
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        return CheckIn::process(ctx);
+    }
+
+    pub fn revoke_beneficiary(ctx: Context<RevokeBeneficiary>) -> Result<()> {
+        return RevokeBeneficiary::process(ctx);
+    }
+
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>, amount: u64) -> Result<()> {
+        return ClaimInheritance::process(ctx, amount);
+    }
+
+    pub fn execute(ctx: Context<Execute>, data: Vec<u8>) -> Result<()> {
+        return Execute::process(ctx, data);
+    }
+
+    pub fn fund_insurance(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+        return FundInsurance::process(ctx, amount);
+    }
+
+    pub fn verify_invariants(ctx: Context<VerifyInvariants>) -> Result<InvariantReport> {
+        return VerifyInvariants::process(ctx);
+    }
+
+    pub fn total_value_locked(ctx: Context<TotalValueLocked>) -> Result<u64> {
+        return TotalValueLocked::process(ctx);
+    }
+
+    pub fn borrow_limit(ctx: Context<BorrowLimit>) -> Result<u64> {
+        return BorrowLimit::process(ctx);
+    }
+
+    pub fn assert_version(
+        ctx: Context<AssertVersion>,
+        expected_version: u32,
+        required_features: u64,
+    ) -> Result<()> {
+        return AssertVersion::process(ctx, expected_version, required_features);
+    }
+
+    pub fn lucky_draw_slothash(ctx: Context<LuckyDrawSlothash>) -> Result<()> {
+        return LuckyDrawSlothash::process(ctx);
+    }
+
+    pub fn commit_lucky_draw(ctx: Context<CommitLuckyDraw>) -> Result<()> {
+        return CommitLuckyDraw::process(ctx);
+    }
+
+    pub fn settle_lucky_draw(ctx: Context<SettleLuckyDraw>) -> Result<()> {
+        return SettleLuckyDraw::process(ctx);
+    }
+
+    pub fn migrate_reserve(ctx: Context<MigrateReserve>, token_mint: Option<Pubkey>) -> Result<()> {
+        return MigrateReserve::process(ctx, token_mint);
+    }
+
+    pub fn snapshot(ctx: Context<TakeSnapshot>, epoch: u64) -> Result<()> {
+        return TakeSnapshot::process(ctx, epoch);
+    }
+
+    pub fn post_snapshot_root(
+        ctx: Context<PostSnapshotRoot>,
+        epoch: u64,
+        rewards_root: [u8; 32],
+        total_rewards: u64,
+        max_claims: u32,
+    ) -> Result<()> {
+        return PostSnapshotRoot::process(ctx, epoch, rewards_root, total_rewards, max_claims);
+    }
+
+    pub fn start_reconciliation(
+        ctx: Context<StartReconciliation>,
+        expected_reserves: u64,
+    ) -> Result<()> {
+        return StartReconciliation::process(ctx, expected_reserves);
+    }
+
+    pub fn reconcile_batch(ctx: Context<ReconcileBatch>) -> Result<()> {
+        return ReconcileBatch::process(ctx);
+    }
+
+    pub fn claim_snapshot_reward(
+        ctx: Context<ClaimSnapshotReward>,
+        epoch: u64,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        return ClaimSnapshotReward::process(ctx, epoch, index, amount, proof);
+    }
 }
@@ -0,0 +1,121 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::error::BankAppError;
+
+// the native address lookup table program, the same id on every cluster
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("AddressLookupTab1e1111111111111111111111111");
+
+// a table holds at most 256 addresses, after a 56 byte header
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+// ProgramInstruction is bincode encoded, the variant is a u32
+const CREATE_LOOKUP_TABLE_INSTRUCTION: u32 = 0;
+const EXTEND_LOOKUP_TABLE_INSTRUCTION: u32 = 2;
+
+pub struct LookupTableAccounts<'info> {
+    pub lookup_table: AccountInfo<'info>,
+    pub lookup_table_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// the table lives at [authority, recent_slot] under the lookup table program,
+// recent_slot has to still be in SlotHashes
+pub fn create_lookup_table<'info>(
+    lookup_table_program: AccountInfo<'info>,
+    accounts: LookupTableAccounts<'info>,
+    recent_slot: u64,
+    bump: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = CREATE_LOOKUP_TABLE_INSTRUCTION.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(bump);
+
+    let ix = Instruction {
+        program_id: lookup_table_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.lookup_table.key(), false),
+            AccountMeta::new_readonly(accounts.lookup_table_authority.key(), true),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.lookup_table,
+            accounts.lookup_table_authority,
+            accounts.payer,
+            accounts.system_program,
+            lookup_table_program,
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+// the payer tops up the rent for the longer table
+pub fn extend_lookup_table<'info>(
+    lookup_table_program: AccountInfo<'info>,
+    accounts: LookupTableAccounts<'info>,
+    new_addresses: Vec<Pubkey>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // a bincode Vec is prefixed with a u64 length
+    let mut data = EXTEND_LOOKUP_TABLE_INSTRUCTION.to_le_bytes().to_vec();
+    data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+    for address in &new_addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+
+    let ix = Instruction {
+        program_id: lookup_table_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.lookup_table.key(), false),
+            AccountMeta::new_readonly(accounts.lookup_table_authority.key(), true),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.lookup_table,
+            accounts.lookup_table_authority,
+            accounts.payer,
+            accounts.system_program,
+            lookup_table_program,
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+// the addresses already in the table, so extending skips them
+pub fn lookup_table_addresses(lookup_table: &AccountInfo) -> Result<Vec<Pubkey>> {
+    if lookup_table.owner != &ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+        return Err(BankAppError::InvalidLookupTable.into());
+    }
+
+    let data = lookup_table.try_borrow_data()?;
+    if data.len() < LOOKUP_TABLE_META_SIZE {
+        return Err(BankAppError::InvalidLookupTable.into());
+    }
+    Ok(data[LOOKUP_TABLE_META_SIZE..]
+        .chunks_exact(32)
+        .map(|address| Pubkey::try_from(address).unwrap())
+        .collect())
+}
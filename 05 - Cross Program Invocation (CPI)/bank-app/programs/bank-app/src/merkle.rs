@@ -0,0 +1,19 @@
+use anchor_lang::solana_program::hash::hashv;
+
+// leaf = sha256(index || user || amount), parents hash the sorted pair so
+// proofs don't need left/right flags
+pub fn reward_leaf(index: u32, user: &[u8], amount: u64) -> [u8; 32] {
+    hashv(&[&index.to_le_bytes(), user, &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::PullFeedAccountData;
+
+use crate::{constant::USD_DECIMALS, error::BankAppError};
+
+// switchboard feeds store the median as an i128 with 18 decimals, it's cut
+// down to 9 so amount * price fits in a u128
+const FEED_DECIMALS: u32 = 18;
+const PRICE_DECIMALS: u32 = 9;
+
+// the on-demand program on mainnet and on devnet, a feed owned by anything else
+// could hold any price
+pub const SWITCHBOARD_PROGRAM_IDS: [Pubkey; 2] = [
+    anchor_lang::solana_program::pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv"),
+    anchor_lang::solana_program::pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2"),
+];
+
+// feeds update at different speeds, a mint's config can allow its feed more slots
+pub fn feed_price_within(
+    feed: &AccountInfo,
+    clock: &Clock,
+    max_staleness_slots: u64,
+) -> Result<u128> {
+    if !SWITCHBOARD_PROGRAM_IDS.contains(feed.owner) {
+        return Err(BankAppError::InvalidPriceFeed.into());
+    }
+
+    let data = feed.try_borrow_data()?;
+    let feed = PullFeedAccountData::parse(data).map_err(|_| BankAppError::InvalidOraclePrice)?;
+
+    if clock.slot.saturating_sub(feed.result.slot) > max_staleness_slots {
+        return Err(BankAppError::StaleOraclePrice.into());
+    }
+    // a price below 1e-9 would round to 0 and later be divided by
+    let price = feed.result.value.max(0) as u128 / 10u128.pow(FEED_DECIMALS - PRICE_DECIMALS);
+    if price == 0 {
+        return Err(BankAppError::InvalidOraclePrice.into());
+    }
+
+    Ok(price)
+}
+
+// how much of the output token `amount_in` is worth at oracle prices
+pub fn oracle_amount_out(
+    amount_in: u64,
+    price_in: u128,
+    decimals_in: u8,
+    price_out: u128,
+    decimals_out: u8,
+) -> Result<u64> {
+    let value = (amount_in as u128)
+        .checked_mul(price_in)
+        .and_then(|value| value.checked_mul(10u128.pow(decimals_out as u32)))
+        .ok_or(BankAppError::InvalidOraclePrice)?;
+    let amount_out = value / (price_out * 10u128.pow(decimals_in as u32));
+
+    Ok(u64::try_from(amount_out).map_err(|_| BankAppError::InvalidOraclePrice)?)
+}
+
+// what `amount` is worth in USD with USD_DECIMALS, the common unit for TVL,
+// USD caps and borrow limits across mints
+pub fn usd_value(amount: u64, price: u128, decimals: u8) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(price)
+        .and_then(|value| value.checked_mul(10u128.pow(USD_DECIMALS as u32)))
+        .ok_or(BankAppError::InvalidOraclePrice)?;
+    let value = value / 10u128.pow(PRICE_DECIMALS + decimals as u32);
+
+    Ok(u64::try_from(value).map_err(|_| BankAppError::InvalidOraclePrice)?)
+}
@@ -0,0 +1,78 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked,
+    },
+};
+
+use crate::{constant::PERMIT_DOMAIN, error::BankAppError};
+
+pub const ED25519_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+// one Ed25519SignatureOffsets entry after the 2 byte header: the signature,
+// public key and message offsets, each followed by the index of the
+// instruction holding that data
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+
+// what the user signs off-chain. The program id ties a permit to this
+// deployment, the nonce makes each permit usable once
+pub fn permit_message(user: &Pubkey, amount: u64, nonce: u64, deadline: i64) -> Vec<u8> {
+    [
+        PERMIT_DOMAIN,
+        crate::ID.as_ref(),
+        user.as_ref(),
+        &amount.to_le_bytes(),
+        &nonce.to_le_bytes(),
+        &deadline.to_le_bytes(),
+    ]
+    .concat()
+}
+
+// a program can't check an ed25519 signature itself, the ed25519 precompile
+// does it in the instruction right before this one and the whole transaction
+// fails if the signature is bad. What's left is checking that it signed the
+// expected key and message, read from the instructions sysvar
+pub fn verify_ed25519_permit(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions)? as usize;
+    if current == 0 {
+        return Err(BankAppError::InvalidPermit.into());
+    }
+    let ed25519 = load_instruction_at_checked(current - 1, instructions)?;
+    if ed25519.program_id != ED25519_PROGRAM_ID {
+        return Err(BankAppError::InvalidPermit.into());
+    }
+
+    let data = &ed25519.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN || data[0] != 1 {
+        return Err(BankAppError::InvalidPermit.into());
+    }
+    let read = |field: usize| {
+        let at = SIGNATURE_OFFSETS_START + field * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+
+    // u16::MAX is "this instruction". Data from another instruction could be
+    // anything the relayer put there
+    if [read(1), read(3), read(6)]
+        .iter()
+        .any(|index| *index != u16::MAX)
+    {
+        return Err(BankAppError::InvalidPermit.into());
+    }
+
+    let public_key_offset = read(2) as usize;
+    let message_offset = read(4) as usize;
+    let message_size = read(5) as usize;
+    let public_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed = data.get(message_offset..message_offset + message_size);
+    if public_key != Some(signer.as_ref()) || signed != Some(message) {
+        return Err(BankAppError::InvalidPermit.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{constant::LUCKY_DRAW_ODDS, error::BankAppError};
+
+// SlotHashes is [len: u64][slot: u64, hash: [u8; 32]]... newest first, far too
+// large to deserialize on-chain, so only the newest hash is read
+pub fn latest_slot_hash(slot_hashes_data: &[u8]) -> Result<[u8; 32]> {
+    // an empty list (len 0) has no hash to read
+    if slot_hashes_data.len() < 48 || slot_hashes_data[..8] == [0u8; 8] {
+        return Err(BankAppError::SlotHashesEmpty.into());
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&slot_hashes_data[16..48]);
+    Ok(hash)
+}
+
+pub fn is_lucky(random: &[u8; 32], user: &Pubkey) -> bool {
+    let roll = hashv(&[random, user.as_ref()]).to_bytes();
+    u64::from_le_bytes(roll[..8].try_into().unwrap()) % LUCKY_DRAW_ODDS == 0
+}
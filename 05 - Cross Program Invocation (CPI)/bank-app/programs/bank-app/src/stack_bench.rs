@@ -0,0 +1,98 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constant::{BASE_MINT, SWAP_PROGRAM_ID, USER_RESERVE_SEED},
+    error::BankAppError,
+    seeds::BankPda,
+    state::{BankInfo, UserReserve},
+};
+
+// DepositAnyToken (the largest context) with every Box removed and the same
+// constraints, only built with `stack-bench`. tests/stack_usage.rs and
+// scripts/stack-usage.sh compare the two
+#[derive(Accounts)]
+pub struct DepositAnyTokenUnboxed<'info> {
+    #[account(
+        mut,
+        address = BankPda::BankInfo.address()
+    )]
+    pub bank_info: Account<'info, BankInfo>,
+
+    ///CHECK:
+    #[account(
+        address = BankPda::BankVault.address(),
+        owner = system_program::ID
+    )]
+    pub bank_vault: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = input_mint.key() != BASE_MINT @ BankAppError::InvalidSwapRoute
+    )]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = BASE_MINT)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = input_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = bank_vault
+    )]
+    pub bank_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            USER_RESERVE_SEED,
+            user.key().as_ref(),
+            base_mint.key().as_ref()
+        ],
+        bump,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserReserve>(),
+    )]
+    pub user_reserve: Account<'info, UserReserve>,
+
+    ///CHECK:
+    #[account(
+        address = bank_info.token_config(&input_mint.key())?.price_feed @ BankAppError::InvalidPriceFeed
+    )]
+    pub input_price_feed: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(
+        address = bank_info.token_config(&base_mint.key())?.price_feed @ BankAppError::InvalidPriceFeed
+    )]
+    pub base_price_feed: UncheckedAccount<'info>,
+
+    ///CHECK:
+    #[account(address = SWAP_PROGRAM_ID)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// a concrete caller, so the SBF build compiles and sizes try_accounts
+// for the unboxed context
+pub fn try_unboxed<'info>(
+    program_id: &Pubkey,
+    accounts: &mut &'info [AccountInfo<'info>],
+) -> Result<DepositAnyTokenUnboxed<'info>> {
+    let mut bumps = DepositAnyTokenUnboxedBumps::default();
+    let mut reallocs = std::collections::BTreeSet::new();
+    DepositAnyTokenUnboxed::try_accounts(program_id, accounts, &[], &mut bumps, &mut reallocs)
+}
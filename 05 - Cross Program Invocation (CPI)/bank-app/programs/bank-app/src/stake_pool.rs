@@ -0,0 +1,202 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::error::BankAppError;
+
+// the SPL stake pool program, the same id on every cluster
+pub const STAKE_POOL_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+// the native stake program, withdraw_sol splits the reserve stake account
+pub const STAKE_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("Stake11111111111111111111111111111111111111");
+
+// the pool's withdraw authority is the PDA [stake_pool, AUTHORITY_WITHDRAW, bump]
+// under the stake pool program, it mints the pool tokens
+pub const AUTHORITY_WITHDRAW: &[u8] = b"withdraw";
+
+const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+const DEPOSIT_SOL_INSTRUCTION: u8 = 14;
+const WITHDRAW_SOL_INSTRUCTION: u8 = 16;
+
+// the fixed-size head of StakePool: account_type u8, manager, staker,
+// stake_deposit_authority, stake_withdraw_bump_seed u8, validator_list,
+// reserve_stake, pool_mint, manager_fee_account, token_program_id. What follows
+// is read by the stake pool program itself
+pub struct StakePoolInfo {
+    pub stake_withdraw_bump_seed: u8,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub token_program_id: Pubkey,
+}
+
+impl StakePoolInfo {
+    const LEN: usize = 258;
+
+    pub fn load(stake_pool: &AccountInfo) -> Result<StakePoolInfo> {
+        if stake_pool.owner != &STAKE_POOL_PROGRAM_ID {
+            return Err(BankAppError::InvalidStakePool.into());
+        }
+
+        let data = stake_pool.try_borrow_data()?;
+        if data.len() < Self::LEN || data[0] != ACCOUNT_TYPE_STAKE_POOL {
+            return Err(BankAppError::InvalidStakePool.into());
+        }
+
+        let pubkey_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+        Ok(StakePoolInfo {
+            stake_withdraw_bump_seed: data[97],
+            reserve_stake: pubkey_at(130),
+            pool_mint: pubkey_at(162),
+            manager_fee_account: pubkey_at(194),
+            token_program_id: pubkey_at(226),
+        })
+    }
+
+    pub fn withdraw_authority(&self, stake_pool: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[
+                stake_pool.as_ref(),
+                AUTHORITY_WITHDRAW,
+                &[self.stake_withdraw_bump_seed],
+            ],
+            &STAKE_POOL_PROGRAM_ID,
+        )
+        .map_err(|_| BankAppError::InvalidStakePool.into())
+    }
+}
+
+pub struct DepositSolAccounts<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub lamports_from: AccountInfo<'info>,
+    pub pool_tokens_to: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub referrer_pool_tokens: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+// moves `lamports` from `lamports_from` into the pool's reserve and mints pool
+// tokens at the pool's rate. `signer_seeds` sign for `lamports_from`. A pool
+// with a sol_deposit_authority needs it as an 11th signer, that isn't supported
+pub fn deposit_sol<'info>(
+    stake_pool_program: AccountInfo<'info>,
+    accounts: DepositSolAccounts<'info>,
+    lamports: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![DEPOSIT_SOL_INSTRUCTION];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: stake_pool_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.withdraw_authority.key(), false),
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.lamports_from.key(), true),
+            AccountMeta::new(accounts.pool_tokens_to.key(), false),
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            AccountMeta::new(accounts.referrer_pool_tokens.key(), false),
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.stake_pool,
+            accounts.withdraw_authority,
+            accounts.reserve_stake,
+            accounts.lamports_from,
+            accounts.pool_tokens_to,
+            accounts.manager_fee_account,
+            accounts.referrer_pool_tokens,
+            accounts.pool_mint,
+            accounts.system_program,
+            accounts.token_program,
+            stake_pool_program,
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+pub struct WithdrawSolAccounts<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub pool_tokens_authority: AccountInfo<'info>,
+    pub pool_tokens_from: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub lamports_to: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub stake_history: AccountInfo<'info>,
+    pub stake_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+// burns `pool_tokens` from `pool_tokens_from` and pays their SOL out of the
+// pool's reserve, minus the withdrawal fee. `signer_seeds` sign for
+// `pool_tokens_authority`. A pool with a sol_withdraw_authority isn't supported
+pub fn withdraw_sol<'info>(
+    stake_pool_program: AccountInfo<'info>,
+    accounts: WithdrawSolAccounts<'info>,
+    pool_tokens: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![WITHDRAW_SOL_INSTRUCTION];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: stake_pool_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.withdraw_authority.key(), false),
+            AccountMeta::new_readonly(accounts.pool_tokens_authority.key(), true),
+            AccountMeta::new(accounts.pool_tokens_from.key(), false),
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.lamports_to.key(), false),
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(accounts.clock.key(), false),
+            AccountMeta::new_readonly(accounts.stake_history.key(), false),
+            AccountMeta::new_readonly(accounts.stake_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.stake_pool,
+            accounts.withdraw_authority,
+            accounts.pool_tokens_authority,
+            accounts.pool_tokens_from,
+            accounts.reserve_stake,
+            accounts.lamports_to,
+            accounts.manager_fee_account,
+            accounts.pool_mint,
+            accounts.clock,
+            accounts.stake_history,
+            accounts.stake_program,
+            accounts.token_program,
+            stake_pool_program,
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -1,15 +1,1059 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constant::{
+        MAX_ALLOWED_PROGRAMS, MAX_APPROVAL_ACTIONS, MAX_APPROVERS, MAX_GOAL_NAME_LEN,
+        MAX_ORACLE_STALENESS_SLOTS, MAX_SUPPORTED_TOKENS, OPS_QUEUE_CAPACITY, SECONDS_PER_YEAR,
+        SLOTS_PER_YEAR, USER_HISTORY_CAPACITY, USER_RESERVE_VERSION, WITHDRAWAL_DAY,
+        WITHDRAWAL_TIER_COUNT,
+    },
+    error::BankAppError,
+    oracle::{feed_price_within, usd_value},
+};
+
 #[account]
 #[derive(Default)]
 pub struct BankInfo {
     pub authority: Pubkey,
     pub is_paused: bool,
     pub bump: u8,
+    pub total_deposited: u64,
+    pub rent_destination: Pubkey,
+    // one per supported mint, grown by add_supported_token
+    pub token_configs: Vec<TokenConfig>,
+    // may only pause and unpause, Pubkey::default() means no guardian
+    pub guardian: Pubkey,
+    // one bit per BankInstruction, a set bit switches that instruction off
+    pub disabled_instructions: u64,
+    // daily withdrawal ceilings for young reserves, all zero means no limits
+    pub withdrawal_tiers: [WithdrawalTier; WITHDRAWAL_TIER_COUNT],
+    // yearly interest on SOL reserves, accrued on deposit and withdraw
+    pub interest_rate_bps: u16,
+    // SOL put into the SPL stake pool, at cost. The pool tokens are worth more,
+    // divesting takes out the share of the principal the burned tokens stood for
+    pub stake_pool_principal: u64,
+    // what interest_rate_bps is measured against
+    pub interest_clock: InterestClock,
+    // the harvest crank's guard, it runs once per slot
+    pub last_harvest_slot: u64,
+    // seconds between request_withdraw and execute_withdraw, 0 is no cooldown
+    pub withdraw_cooldown: i64,
+    // total_deposited over time, see BalanceAccumulator
+    pub total_balance_seconds: BalanceAccumulator,
+    // the vault's rent-exempt minimum, recorded at initialize. Those lamports
+    // belong to no depositor, so no withdrawal may take the vault below it
+    pub rent_floor: u64,
+    // the VersionBeacon's version and features, written by initialize and
+    // sync_version_beacon so a client that already reads BankInfo needn't fetch both
+    pub version: u32,
+    pub features: u64,
+}
+
+// timestamps follow the validators' clock estimate, slots can't be nudged by
+// it but drift from wall time when slots run slower or faster than 400ms
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterestClock {
+    #[default]
+    Timestamp,
+    Slot,
+}
+
+// applies while a reserve is younger than max_age, 0 leaves the tier unused
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WithdrawalTier {
+    pub max_age: i64,
+    pub daily_limit: u64,
+}
+
+impl WithdrawalTier {
+    pub const SPACE: usize = 8 + 8;
+}
+
+// the instructions that can be switched off one by one. The bit is the
+// variant's position, so new variants only ever go at the end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BankInstruction {
+    Deposit,
+    DepositToken,
+    DepositAndNotify,
+    DepositAnyToken,
+    DepositTagged,
+    Withdraw,
+    QueueWithdrawal,
+    Invest,
+    ProcessNextOp,
+    ExecuteRecurring,
+    ReceiveCrossChainDeposit,
+    ClaimSnapshotReward,
+    LuckyDrawSlothash,
+    CommitLuckyDraw,
+    SettleLuckyDraw,
+    MoveToGoal,
+    MoveFromGoal,
+    DepositJoint,
+    WithdrawJoint,
+    ClaimInheritance,
+    Execute,
+    DepositToken2022,
+    WithdrawWithPermit,
+    RequestWithdraw,
+    ExecuteWithdraw,
+    WithdrawToEscrow,
+    InvestStakePool,
+    DivestStakePool,
+    Harvest,
+    AccrueInterest,
+    ClaimMilestoneBadge,
+    FundInsurance,
+    SetupRecurring,
+    SyncReceiptRate,
+}
+
+impl BankInstruction {
+    pub fn bit(self) -> u64 {
+        1 << self as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TokenConfig {
+    pub mint: Pubkey,
+    pub total_deposited: u64,
+    pub interest_rate_bps: u16,
+    // 0 is no cap
+    pub deposit_cap: u64,
+    pub is_paused: bool,
+    // Pubkey::default() leaves the mint unpriced: out of TVL and borrow limits
+    pub price_feed: Pubkey,
+    // 0 is MAX_ORACLE_STALENESS_SLOTS
+    pub max_staleness_slots: u64,
+    // the mint's, copied by set_token_oracle so pricing needs no mint account
+    pub decimals: u8,
+    // in USD_DECIMALS, 0 is no cap
+    pub deposit_cap_usd: u64,
+    // share of the deposit's USD value that can be borrowed against
+    pub borrow_ltv_bps: u16,
+}
+
+impl TokenConfig {
+    pub const SPACE: usize = 32 + 8 + 2 + 8 + 1 + 32 + 8 + 1 + 8 + 2;
+
+    // every deposit path credits through here so the per-mint limits can't be skipped
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        if self.is_paused {
+            return Err(BankAppError::TokenPaused.into());
+        }
+
+        let total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+        if self.deposit_cap != 0 && total_deposited > self.deposit_cap {
+            return Err(BankAppError::DepositCapExceeded.into());
+        }
+
+        self.total_deposited = total_deposited;
+        Ok(())
+    }
+
+    pub fn is_priced(&self) -> bool {
+        self.price_feed != Pubkey::default()
+    }
+
+    // `feed` has to be the configured one, checked against this mint's staleness bound
+    pub fn price(&self, feed: &AccountInfo, clock: &Clock) -> Result<u128> {
+        if !self.is_priced() || feed.key() != self.price_feed {
+            return Err(BankAppError::InvalidPriceFeed.into());
+        }
+
+        let max_staleness_slots = match self.max_staleness_slots {
+            0 => MAX_ORACLE_STALENESS_SLOTS,
+            slots => slots,
+        };
+        feed_price_within(feed, clock, max_staleness_slots)
+    }
+
+    pub fn usd_value(&self, amount: u64, feed: &AccountInfo, clock: &Clock) -> Result<u64> {
+        let price = self.price(feed, clock)?;
+        usd_value(amount, price, self.decimals)
+    }
+
+    // called after credit. A mint with a USD cap needs its feed on every deposit
+    pub fn check_usd_cap(&self, feed: Option<&AccountInfo>, clock: &Clock) -> Result<()> {
+        if self.deposit_cap_usd == 0 {
+            return Ok(());
+        }
+
+        let feed = feed.ok_or(BankAppError::InvalidPriceFeed)?;
+        if self.usd_value(self.total_deposited, feed, clock)? > self.deposit_cap_usd {
+            return Err(BankAppError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+
+    // the interest-bearing extension takes the rate as an i16
+    pub fn receipt_rate(&self) -> Result<i16> {
+        i16::try_from(self.interest_rate_bps).map_err(|_| BankAppError::ReceiptRateTooHigh.into())
+    }
+}
+
+impl BankInfo {
+    // Borsh size, the Vec is 4 bytes of length then its items
+    pub fn space(token_count: usize) -> usize {
+        8 + 32
+            + 1
+            + 1
+            + 8
+            + 32
+            + 4
+            + token_count * TokenConfig::SPACE
+            + 32
+            + 8
+            + WITHDRAWAL_TIER_COUNT * WithdrawalTier::SPACE
+            + 2
+            + 8
+            + 1
+            + 8
+            + 8
+            + BalanceAccumulator::SPACE
+            + 8
+            + 4
+            + 8
+    }
+
+    // every user and crank handler starts here: the global pause first,
+    // then the instruction's own kill-switch
+    pub fn require_enabled(&self, instruction: BankInstruction) -> Result<()> {
+        if self.is_paused {
+            return Err(BankAppError::BankAppPaused.into());
+        }
+        if self.disabled_instructions & instruction.bit() != 0 {
+            return Err(BankAppError::InstructionDisabled.into());
+        }
+        Ok(())
+    }
+
+    // with a cooldown, SOL only leaves a reserve through request_withdraw and
+    // execute_withdraw, the direct paths would skip the wait
+    pub fn require_no_withdraw_cooldown(&self) -> Result<()> {
+        if self.withdraw_cooldown > 0 {
+            return Err(BankAppError::WithdrawCooldownActive.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_instruction_enabled(&mut self, instruction: BankInstruction, enabled: bool) {
+        if enabled {
+            self.disabled_instructions &= !instruction.bit();
+        } else {
+            self.disabled_instructions |= instruction.bit();
+        }
+    }
+
+    // used tiers come first, each one for older reserves and with a higher
+    // limit than the one before
+    pub fn set_withdrawal_tiers(
+        &mut self,
+        tiers: [WithdrawalTier; WITHDRAWAL_TIER_COUNT],
+    ) -> Result<()> {
+        let used = tiers.iter().take_while(|tier| tier.max_age != 0).count();
+        if tiers[used..]
+            .iter()
+            .any(|tier| tier.max_age != 0 || tier.daily_limit != 0)
+        {
+            return Err(BankAppError::InvalidWithdrawalTiers.into());
+        }
+        if tiers[..used].iter().any(|tier| tier.max_age < 0) {
+            return Err(BankAppError::InvalidWithdrawalTiers.into());
+        }
+        if tiers[..used].windows(2).any(|pair| {
+            pair[1].max_age <= pair[0].max_age || pair[1].daily_limit < pair[0].daily_limit
+        }) {
+            return Err(BankAppError::InvalidWithdrawalTiers.into());
+        }
+
+        self.withdrawal_tiers = tiers;
+        Ok(())
+    }
+
+    // None once the reserve is older than every used tier
+    pub fn daily_withdrawal_limit(&self, reserve_age: i64) -> Option<u64> {
+        self.withdrawal_tiers
+            .iter()
+            .find(|tier| tier.max_age != 0 && reserve_age < tier.max_age)
+            .map(|tier| tier.daily_limit)
+    }
+
+    pub fn add_token(&mut self, mint: Pubkey) -> Result<()> {
+        if self.token_configs.iter().any(|config| config.mint == mint) {
+            return Err(BankAppError::TokenAlreadySupported.into());
+        }
+        if self.token_configs.len() >= MAX_SUPPORTED_TOKENS as usize {
+            return Err(BankAppError::TooManySupportedTokens.into());
+        }
+
+        self.token_configs.push(TokenConfig {
+            mint,
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    pub fn token_config(&self, mint: &Pubkey) -> Result<&TokenConfig> {
+        self.token_configs
+            .iter()
+            .find(|config| config.mint == *mint)
+            .ok_or(BankAppError::UnsupportedToken.into())
+    }
+
+    pub fn token_config_mut(&mut self, mint: &Pubkey) -> Result<&mut TokenConfig> {
+        self.token_configs
+            .iter_mut()
+            .find(|config| config.mint == *mint)
+            .ok_or(BankAppError::UnsupportedToken.into())
+    }
+
+    // TVL in USD over the priced mints. `feeds` holds one feed per priced
+    // config, in token_configs order
+    pub fn total_value_locked(&self, feeds: &[AccountInfo], clock: &Clock) -> Result<u64> {
+        let priced = self
+            .token_configs
+            .iter()
+            .filter(|config| config.is_priced());
+        if priced.clone().count() != feeds.len() {
+            return Err(BankAppError::InvalidPriceFeed.into());
+        }
+
+        priced
+            .zip(feeds)
+            .try_fold(0u64, |tvl, (config, feed)| -> Result<u64> {
+                let value = config.usd_value(config.total_deposited, feed, clock)?;
+                tvl.checked_add(value)
+                    .ok_or(BankAppError::InvalidOraclePrice.into())
+            })
+    }
+
+    // where closed accounts send their rent, Pubkey::default() refunds the user
+    pub fn rent_destination_for(&self, user: &Pubkey) -> Pubkey {
+        if self.rent_destination == Pubkey::default() {
+            *user
+        } else {
+            self.rent_destination
+        }
+    }
+
+    // before every change to total_deposited
+    pub fn accumulate_total(&mut self, now: i64) -> Result<()> {
+        self.total_balance_seconds
+            .accumulate(self.total_deposited, now)
+    }
+
+    // what the vault can pay out, its lamports above rent_floor
+    pub fn vault_liquidity(&self, vault_lamports: u64) -> u64 {
+        vault_lamports.saturating_sub(self.rent_floor)
+    }
+
+    // before every transfer out of the vault
+    pub fn require_above_rent_floor(&self, vault_lamports: u64, amount: u64) -> Result<()> {
+        if amount > self.vault_liquidity(vault_lamports) {
+            return Err(BankAppError::VaultBelowRentFloor.into());
+        }
+        Ok(())
+    }
+}
+
+// a balance integrated over time, brought up to date lazily by whatever changes
+// the balance. Two readings of it give the average balance held between them, so
+// rewards and points can be split by time held without snapshotting every reserve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct BalanceAccumulator {
+    // u128: u64::MAX lamports held for ~584 billion years still fit
+    pub balance_seconds: u128,
+    pub updated_at: i64,
+}
+
+impl BalanceAccumulator {
+    pub const SPACE: usize = 16 + 8;
+
+    // the reading at `now` for `balance` held since updated_at, without writing it.
+    // One that never ran starts at `now`, a balance from before it earns nothing.
+    // A clock behind updated_at adds nothing and keeps updated_at
+    pub fn at(&self, balance: u64, now: i64) -> Result<BalanceAccumulator> {
+        if self.updated_at == 0 {
+            return Ok(BalanceAccumulator {
+                balance_seconds: self.balance_seconds,
+                updated_at: now,
+            });
+        }
+
+        let elapsed = (now - self.updated_at).max(0) as u128;
+        let balance_seconds = (balance as u128)
+            .checked_mul(elapsed)
+            .and_then(|added| self.balance_seconds.checked_add(added))
+            .ok_or(BankAppError::BalanceSecondsOverflow)?;
+        Ok(BalanceAccumulator {
+            balance_seconds,
+            updated_at: now.max(self.updated_at),
+        })
+    }
+
+    // with the balance held until now, before it changes
+    pub fn accumulate(&mut self, balance: u64, now: i64) -> Result<()> {
+        *self = self.at(balance, now)?;
+        Ok(())
+    }
+
+    // the average balance between an earlier reading and this one, None if no
+    // time passed between them or they're out of order
+    pub fn average_since(&self, earlier: &BalanceAccumulator) -> Option<u64> {
+        let seconds = self.updated_at.checked_sub(earlier.updated_at)?;
+        if seconds <= 0 {
+            return None;
+        }
+        let balance_seconds = self.balance_seconds.checked_sub(earlier.balance_seconds)?;
+        u64::try_from(balance_seconds / seconds as u128).ok()
+    }
+}
+
+// what the deployed program is, readable without a simulation. Rewritten by
+// sync_version_beacon after every upgrade
+#[account]
+#[derive(Default)]
+pub struct VersionBeacon {
+    pub version: u32,
+    pub features: u64,
+    pub updated_slot: u64,
 }
 
+// v1 (chapters 03/04) only had `deposited_amount`; v2 and v3 append fields so
+// the old bytes stay a valid prefix of the new layout
 #[account]
 #[derive(Default)]
 pub struct UserReserve {
     pub deposited_amount: u64,
+    pub version: u8,
+    pub owner: Pubkey,
+    pub created_at: i64,
+    // v3: when interest was last accrued, and the total so far
+    pub deposit_timestamp: i64,
+    pub accrued_interest: u64,
+    // v4: the slot of the last accrual, and of the last accrue_interest crank
+    pub accrual_slot: u64,
+    pub last_crank_slot: u64,
+    // v5: the requested withdrawal and when execute_withdraw may pay it
+    pub pending_withdrawal: u64,
+    pub unlock_at: i64,
+    // v6: deposited_amount over time, see BalanceAccumulator
+    pub balance_seconds: BalanceAccumulator,
+    // v7: the owner's last deposit or withdrawal, Inheritance counts it as a check-in
+    pub last_activity_ts: i64,
+}
+
+impl UserReserve {
+    pub const V1_LEN: usize = 8 + 8;
+
+    // reads both layouts, missing v2 fields come back zeroed (version 0)
+    pub fn load_versioned(data: &[u8]) -> Result<UserReserve> {
+        if data.len() < Self::V1_LEN || &data[..8] != UserReserve::DISCRIMINATOR {
+            return Err(BankAppError::InvalidUserReserve.into());
+        }
+
+        let mut padded = data.to_vec();
+        padded.resize(8 + std::mem::size_of::<UserReserve>(), 0);
+        UserReserve::try_deserialize(&mut &padded[..])
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.version < USER_RESERVE_VERSION
+    }
+
+    // simple interest on deposited_amount since the last accrual. Called before
+    // every balance change, so each stretch of time is paid on the balance it had.
+    // Both stamps move on every accrual, so switching the clock pays no stretch
+    // twice. Reserves created by a path that doesn't accrue start from created_at,
+    // or from their first accrual when counted in slots
+    pub fn accrue_interest(
+        &mut self,
+        interest_rate_bps: u16,
+        interest_clock: InterestClock,
+        clock: &Clock,
+    ) -> Result<()> {
+        let (elapsed, per_year) = match interest_clock {
+            InterestClock::Timestamp => {
+                let since = self.deposit_timestamp.max(self.created_at);
+                let elapsed = (clock.unix_timestamp - since).max(0) as u128;
+                (elapsed, SECONDS_PER_YEAR as u128)
+            }
+            InterestClock::Slot if self.accrual_slot == 0 => (0, SLOTS_PER_YEAR as u128),
+            InterestClock::Slot => {
+                let elapsed = clock.slot.saturating_sub(self.accrual_slot) as u128;
+                (elapsed, SLOTS_PER_YEAR as u128)
+            }
+        };
+        let interest = self.deposited_amount as u128 * interest_rate_bps as u128 * elapsed
+            / (10_000 * per_year);
+
+        let interest = u64::try_from(interest).map_err(|_| BankAppError::Overflow)?;
+
+        self.accrued_interest = self
+            .accrued_interest
+            .checked_add(interest)
+            .ok_or(BankAppError::Overflow)?;
+        self.deposit_timestamp = clock.unix_timestamp;
+        self.accrual_slot = clock.slot;
+        Ok(())
+    }
+
+    // before every change to deposited_amount
+    pub fn accumulate_balance(&mut self, now: i64) -> Result<()> {
+        self.balance_seconds.accumulate(self.deposited_amount, now)
+    }
+
+    // only where the owner signed, a crank moving the balance isn't a sign of life
+    pub fn record_activity(&mut self, now: i64) {
+        self.last_activity_ts = now;
+    }
+
+    // a permissionless crank runs at most once per slot on a reserve
+    pub fn record_crank(&mut self, slot: u64) -> Result<()> {
+        if self.last_crank_slot == slot {
+            return Err(BankAppError::CrankAlreadyRan.into());
+        }
+        self.last_crank_slot = slot;
+        Ok(())
+    }
+
+    // a new request replaces the pending one and starts the cooldown over
+    pub fn request_withdrawal(&mut self, amount: u64, now: i64, cooldown: i64) {
+        self.pending_withdrawal = amount;
+        self.unlock_at = now + cooldown;
+    }
+
+    pub fn take_pending_withdrawal(&mut self, now: i64) -> Result<u64> {
+        if self.pending_withdrawal == 0 {
+            return Err(BankAppError::NoPendingWithdrawal.into());
+        }
+        if now < self.unlock_at {
+            return Err(BankAppError::WithdrawalLocked.into());
+        }
+        let amount = self.pending_withdrawal;
+        self.pending_withdrawal = 0;
+        self.unlock_at = 0;
+        Ok(amount)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Snapshot {
+    pub epoch: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub total_deposited: u64,
+    pub vault_balance: u64,
+    pub rewards_root: [u8; 32],
+    pub total_rewards: u64,
+    pub claimed_rewards: u64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct LuckyTicket {
+    pub next_draw_epoch: u64,
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct ForeignEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+#[account]
+#[derive(Default)]
+pub struct ReceivedVaa {
+    pub sequence: u64,
+}
+
+// the programs `execute` may call with a user vault's signature
+#[account]
+#[derive(Default)]
+pub struct CpiAllowlist {
+    pub programs: Vec<Pubkey>,
+}
+
+impl CpiAllowlist {
+    pub const SPACE: usize = 8 + 4 + MAX_ALLOWED_PROGRAMS as usize * 32;
+}
+
+// matches deposits made in [start_at, end_at) by match_ratio_bps, paid from the
+// budget it holds as its own lamports above rent
+#[account]
+#[derive(Default)]
+pub struct Campaign {
+    pub id: u64,
+    pub match_ratio_bps: u16,
+    // what's left to pay out
+    pub budget: u64,
+    pub per_user_cap: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub total_matched: u64,
+}
+
+impl Campaign {
+    // the bonus for a deposit, 0 outside the campaign window or once the budget
+    // or the user's cap runs out. Updates both counters
+    pub fn match_deposit(&mut self, user_match: &mut CampaignMatch, amount: u64, now: i64) -> u64 {
+        if now < self.start_at || now >= self.end_at {
+            return 0;
+        }
+
+        let bonus = (amount as u128 * self.match_ratio_bps as u128 / 10_000) as u64;
+        let bonus = bonus
+            .min(self.per_user_cap.saturating_sub(user_match.matched))
+            .min(self.budget);
+
+        self.budget -= bonus;
+        self.total_matched += bonus;
+        user_match.matched += bonus;
+        bonus
+    }
+}
+
+// how much of a campaign's per-user cap a user has used
+#[account]
+#[derive(Default)]
+pub struct CampaignMatch {
+    pub matched: u64,
+}
+
+// what a user withdrew on the current UTC day, checked against the tier limit
+#[account]
+#[derive(Default)]
+pub struct WithdrawalWindow {
+    pub day: i64,
+    pub withdrawn: u64,
+}
+
+impl WithdrawalWindow {
+    // every withdrawal path records here, with the limit from
+    // BankInfo::daily_withdrawal_limit
+    pub fn record(&mut self, now: i64, amount: u64, daily_limit: Option<u64>) -> Result<()> {
+        let day = now / WITHDRAWAL_DAY;
+        if day != self.day {
+            self.day = day;
+            self.withdrawn = 0;
+        }
+
+        let withdrawn = self
+            .withdrawn
+            .checked_add(amount)
+            .ok_or(BankAppError::Overflow)?;
+        if daily_limit.is_some_and(|limit| withdrawn > limit) {
+            return Err(BankAppError::DailyWithdrawalLimitExceeded.into());
+        }
+
+        self.withdrawn = withdrawn;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct DepositCounter {
+    pub count: u64,
+}
+
+// one per tagged deposit, the tag itself is only in the memo of the transaction
+#[account]
+#[derive(Default)]
+pub struct DepositRecord {
+    pub user: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub tag_hash: [u8; 32],
+    pub created_at: i64,
+}
+
+// part of a user's balance set aside, moved in from and back out to their reserve
+#[account]
+#[derive(Default)]
+pub struct Goal {
+    pub owner: Pubkey,
+    pub index: u8,
+    pub name: String,
+    pub target_amount: u64,
+    pub amount: u64,
+    // nothing moves out before this unix timestamp, None is never locked
+    pub lock_until: Option<i64>,
+}
+
+impl Goal {
+    pub const SPACE: usize = 8 + 32 + 1 + (4 + MAX_GOAL_NAME_LEN as usize) + 8 + 8 + (1 + 8);
+
+    // a lock ends on its own at the deadline, no instruction has to lift it
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.lock_until.is_some_and(|lock_until| now < lock_until)
+    }
+}
+
+// holds its SOL as its own lamports, above the rent-exempt minimum
+#[account]
+#[derive(Default)]
+pub struct InsuranceFund {
+    pub total_funded: u64,
+    pub total_covered: u64,
+}
+
+// the owner's dead man's switch: if they neither check in nor deposit or withdraw
+// for inactivity_period, the beneficiary can claim their reserve
+#[account]
+#[derive(Default)]
+pub struct Inheritance {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inactivity_period: i64,
+    pub last_activity_ts: i64,
+}
+
+impl Inheritance {
+    // `reserve_activity_ts` is the owner reserve's last_activity_ts
+    pub fn is_claimable(&self, now: i64, reserve_activity_ts: i64) -> Result<bool> {
+        let claimable_at = self
+            .last_activity_ts
+            .max(reserve_activity_ts)
+            .checked_add(self.inactivity_period)
+            .ok_or(BankAppError::Overflow)?;
+        Ok(now >= claimable_at)
+    }
+}
+
+// a reserve owned by two users. Either one can withdraw up to
+// single_signer_limit alone, more needs both signatures in the same transaction
+#[account]
+#[derive(Default)]
+pub struct JointReserve {
+    // sorted, owner_a < owner_b, so a pair of owners has one address
+    pub owner_a: Pubkey,
+    pub owner_b: Pubkey,
+    pub deposited_amount: u64,
+    pub single_signer_limit: u64,
+    pub bump: u8,
+    // the age the withdrawal tiers go by, like UserReserve.created_at
+    pub created_at: i64,
+}
+
+impl JointReserve {
+    pub fn is_owner(&self, key: &Pubkey) -> bool {
+        *key == self.owner_a || *key == self.owner_b
+    }
+
+    // the owner that isn't `key`
+    pub fn other_owner(&self, key: &Pubkey) -> Pubkey {
+        if *key == self.owner_a {
+            self.owner_b
+        } else {
+            self.owner_a
+        }
+    }
+}
+
+// also the SPL delegate of the user's token account, so only this plan can pull
+// from it and only through execute_recurring
+#[account]
+#[derive(Default)]
+pub struct RecurringDeposit {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub next_due_at: i64,
+    pub bump: u8,
+}
+
+// proof that a deposit with this op_id went through, a retry of it fails on init
+#[account]
+#[derive(Default)]
+pub struct OpRecord {
+    pub user: Pubkey,
+    pub op_id: [u8; 16],
+    pub amount: u64,
+    pub created_at: i64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct BadgeConfig {
+    pub merkle_tree: Pubkey,
+    pub badges_minted: u64,
+}
+
+// bit i is set once the badge for BADGE_MILESTONES[i] is minted
+#[account]
+#[derive(Default)]
+pub struct MilestoneBadges {
+    pub minted: u8,
+}
+
+// a tally of every SOL reserve that is too big for one transaction. Each
+// reconcile_batch call resumes after `last_reserve`, reserves have to come in
+// increasing address order so none is counted twice
+#[account]
+#[derive(Default)]
+pub struct Reconciliation {
+    pub expected_reserves: u64,
+    pub processed_reserves: u64,
+    pub last_reserve: Pubkey,
+    pub tally: u64,
+    pub started_slot: u64,
+    pub is_done: bool,
+    pub is_matched: bool,
+}
+
+#[account]
+#[derive(Default)]
+pub struct ClaimBitmap {
+    pub snapshot: Pubkey,
+    pub bits: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.bits[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+#[zero_copy]
+pub struct QueuedOp {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub queued_at: i64,
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+// a ring buffer of ops waiting for liquidity, settled FIFO by process_next_op.
+// `head` and `tail` only grow, slot i lives at ops[i % OPS_QUEUE_CAPACITY]
+#[account(zero_copy)]
+pub struct OpsQueue {
+    pub head: u64,
+    pub tail: u64,
+    pub ops: [QueuedOp; OPS_QUEUE_CAPACITY],
+}
+
+impl OpsQueue {
+    pub fn len(&self) -> u64 {
+        self.tail - self.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&mut self, op: QueuedOp) -> Result<()> {
+        if self.len() == OPS_QUEUE_CAPACITY as u64 {
+            return Err(BankAppError::OpsQueueFull.into());
+        }
+
+        self.ops[(self.tail % OPS_QUEUE_CAPACITY as u64) as usize] = op;
+        self.tail += 1;
+        Ok(())
+    }
+
+    pub fn front(&self) -> Option<&QueuedOp> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(&self.ops[(self.head % OPS_QUEUE_CAPACITY as u64) as usize])
+    }
+
+    pub fn pop(&mut self) {
+        self.head += 1;
+    }
+}
+
+#[zero_copy]
+pub struct HistoryEntry {
+    pub amount: u64,
+    pub timestamp: i64,
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+// the user's last USER_HISTORY_CAPACITY operations. `count` only grows, entry i
+// lives at entries[i % USER_HISTORY_CAPACITY], so a push past capacity
+// overwrites the oldest one
+#[account(zero_copy)]
+pub struct UserHistory {
+    pub user: Pubkey,
+    pub count: u64,
+    pub entries: [HistoryEntry; USER_HISTORY_CAPACITY],
+}
+
+impl UserHistory {
+    pub fn len(&self) -> usize {
+        self.count.min(USER_HISTORY_CAPACITY as u64) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn push(&mut self, kind: u8, amount: u64, timestamp: i64) {
+        self.entries[(self.count % USER_HISTORY_CAPACITY as u64) as usize] = HistoryEntry {
+            amount,
+            timestamp,
+            kind,
+            _padding: [0; 7],
+        };
+        self.count += 1;
+    }
+
+    // oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryEntry> {
+        (self.count - self.len() as u64..self.count)
+            .map(|i| &self.entries[(i % USER_HISTORY_CAPACITY as u64) as usize])
+    }
+
+    pub fn clear(&mut self) {
+        self.count = 0;
+        self.entries = [HistoryEntry {
+            amount: 0,
+            timestamp: 0,
+            kind: 0,
+            _padding: [0; 7],
+        }; USER_HISTORY_CAPACITY];
+    }
+}
+
+// a wallet on the bank's denylist. The entry existing is the flag, receipt-hook
+// checks for it on every receipt transfer
+#[account]
+#[derive(Default)]
+pub struct DenyEntry {
+    pub wallet: Pubkey,
+    pub denied_at: i64,
+}
+
+// the next nonce a user's permit must carry. Strictly in order, so a used or
+// skipped-ahead permit can't run
+#[account]
+#[derive(Default)]
+pub struct PermitNonce {
+    pub user: Pubkey,
+    pub next_nonce: u64,
+}
+
+impl PermitNonce {
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<()> {
+        if nonce != self.next_nonce {
+            return Err(BankAppError::InvalidPermitNonce.into());
+        }
+        self.next_nonce += 1;
+        Ok(())
+    }
+}
+
+// the bank's address lookup table. This PDA is the table's authority, so only
+// the program can extend it, and clients find the table through `address`
+#[account]
+#[derive(Default)]
+pub struct BankLookupTable {
+    pub address: Pubkey,
+    pub bump: u8,
+}
+
+// the actions that need M of N approvals. The variant's position indexes
+// ApprovalSet::approved_at, so new variants only ever go at the end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalAction {
+    SetApprovers,
+    CoverShortfall,
+    CloseBank,
+    RescueTokens,
+}
+
+// M of N approvers have to sign off a destructive action within expiry_slots.
+// Executing the action uses the approvals up, the next run needs new ones
+#[account]
+#[derive(Default)]
+pub struct ApprovalSet {
+    pub approvers: [Pubkey; MAX_APPROVERS],
+    pub approver_count: u8,
+    pub threshold: u8,
+    pub expiry_slots: u64,
+    // the slot each approver approved each action at, plus one. 0 is no
+    // approval, and slot 0 is a real slot on a fresh cluster
+    pub approved_at: [[u64; MAX_APPROVERS]; MAX_APPROVAL_ACTIONS],
+}
+
+impl ApprovalSet {
+    pub fn is_configured(&self) -> bool {
+        self.threshold > 0
+    }
+
+    // approvals given to the old approvers are dropped, their indexes change
+    pub fn configure(
+        &mut self,
+        approvers: &[Pubkey],
+        threshold: u8,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        // Pubkey::default() can't sign, it would never approve anything
+        let distinct_keys = approvers.iter().enumerate().all(|(index, approver)| {
+            *approver != Pubkey::default() && !approvers[..index].contains(approver)
+        });
+        if approvers.is_empty()
+            || approvers.len() > MAX_APPROVERS
+            || !distinct_keys
+            || threshold == 0
+            || threshold as usize > approvers.len()
+            || expiry_slots == 0
+        {
+            return Err(BankAppError::InvalidApprovers.into());
+        }
+
+        self.approvers = [Pubkey::default(); MAX_APPROVERS];
+        self.approvers[..approvers.len()].copy_from_slice(approvers);
+        self.approver_count = approvers.len() as u8;
+        self.threshold = threshold;
+        self.expiry_slots = expiry_slots;
+        self.approved_at = [[0; MAX_APPROVERS]; MAX_APPROVAL_ACTIONS];
+        Ok(())
+    }
+
+    // approving again refreshes the approval's slot
+    pub fn approve(&mut self, action: ApprovalAction, approver: &Pubkey, slot: u64) -> Result<()> {
+        let index = self.approvers[..self.approver_count as usize]
+            .iter()
+            .position(|key| key == approver)
+            .ok_or(BankAppError::NotAnApprover)?;
+        self.approved_at[action as usize][index] = slot.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn valid_approvals(&self, action: ApprovalAction, slot: u64) -> u8 {
+        self.approved_at[action as usize]
+            .iter()
+            .filter(|&&approved_at| {
+                approved_at != 0 && slot.saturating_sub(approved_at - 1) <= self.expiry_slots
+            })
+            .count() as u8
+    }
+
+    pub fn consume(&mut self, action: ApprovalAction, slot: u64) -> Result<()> {
+        if !self.is_configured() || self.valid_approvals(action, slot) < self.threshold {
+            return Err(BankAppError::ApprovalThresholdNotMet.into());
+        }
+        self.approved_at[action as usize] = [0; MAX_APPROVERS];
+        Ok(())
+    }
 }
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account as SplAccount, Mint as SplMint},
+};
+
+use crate::error::BankAppError;
+
+// what the bank can check about a Token-2022 deposit, and what it can't.
+//
+// It can read everything in plain state: the mint's extension list and a token
+// account's public `amount`. A public transfer_checked moves that amount like
+// any SPL transfer, and the bank ATA's balance before and after is the credit.
+//
+// It can't read a confidential balance. Pending and available balances are
+// ElGamal ciphertexts that only the owner's key decrypts, and a confidential
+// transfer carries the amount encrypted too, checked against zero-knowledge
+// proofs by the ZK ElGamal proof program. Nothing the bank sees says how much
+// arrived, so it can't credit one. Only the public balance path is taken,
+// a user with a confidential balance withdraws it to public first
+
+// extensions that would let a deposit be taken back or need accounts the bank
+// doesn't pass. Fees are fine, the credit is what arrived
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    // the delegate can move tokens out of the bank ATA at any time
+    ExtensionType::PermanentDelegate,
+    // runs another program on every transfer, with extra accounts
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+];
+
+pub fn check_deposit_mint(mint: &AccountInfo) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&data)?;
+    for extension in mint.get_extension_types()? {
+        if UNSUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("unsupported mint extension {:?}", extension);
+            return Err(BankAppError::UnsupportedMintExtension.into());
+        }
+    }
+    Ok(())
+}
+
+// a token account configured for confidential transfers can refuse public
+// credits, then a public deposit into it would fail inside the token program
+pub fn check_public_credits(token_account: &AccountInfo) -> Result<()> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<SplAccount>::unpack(&data)?;
+    if let Ok(confidential) = account.get_extension::<ConfidentialTransferAccount>() {
+        if !bool::from(confidential.allow_non_confidential_credits) {
+            return Err(BankAppError::PublicCreditsDisabled.into());
+        }
+    }
+    Ok(())
+}
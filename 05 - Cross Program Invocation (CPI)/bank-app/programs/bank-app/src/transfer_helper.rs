@@ -32,7 +32,12 @@ pub fn sol_transfer_from_pda<'info>(
     pda_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
-    //Your code here
+    let ix = transfer(source.key, destination.key, amount);
+    invoke_signed(
+        &ix,
+        &[source, destination, system_program.to_account_info()],
+        pda_seeds,
+    )?;
     Ok(())
 }
 
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::PROGRAM_FEATURES,
+    error::BankAppError,
+    state::{BankInfo, VersionBeacon},
+};
+
+// the crate version packed as major << 16 | minor << 8 | patch, so versions
+// compare as integers. Cargo.toml is the only place it's written down
+pub const PROGRAM_VERSION: u32 = (parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")) << 16)
+    | (parse_version_part(env!("CARGO_PKG_VERSION_MINOR")) << 8)
+    | parse_version_part(env!("CARGO_PKG_VERSION_PATCH"));
+
+const fn parse_version_part(part: &str) -> u32 {
+    let bytes = part.as_bytes();
+    let mut value = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
+}
+
+// a client built for `expected_version` works with the same major and any
+// later minor or patch, those only add. Every feature it relies on has to be here
+pub fn check_version(expected_version: u32, required_features: u64) -> Result<()> {
+    if expected_version >> 16 != PROGRAM_VERSION >> 16 || expected_version > PROGRAM_VERSION {
+        return Err(BankAppError::VersionMismatch.into());
+    }
+    if required_features & !PROGRAM_FEATURES != 0 {
+        return Err(BankAppError::MissingFeatures.into());
+    }
+    Ok(())
+}
+
+pub fn write_version_beacon(beacon: &mut VersionBeacon) -> Result<()> {
+    beacon.version = PROGRAM_VERSION;
+    beacon.features = PROGRAM_FEATURES;
+    beacon.updated_slot = Clock::get()?.slot;
+    Ok(())
+}
+
+pub fn write_bank_version(bank_info: &mut BankInfo) {
+    bank_info.version = PROGRAM_VERSION;
+    bank_info.features = PROGRAM_FEATURES;
+}
+
+// the trailing expected_version of deposit and withdraw. None is a client that
+// doesn't check, the same as leaving assert_version out
+pub fn check_expected_version(expected_version: Option<u32>) -> Result<()> {
+    match expected_version {
+        Some(expected_version) => check_version(expected_version, 0),
+        None => Ok(()),
+    }
+}
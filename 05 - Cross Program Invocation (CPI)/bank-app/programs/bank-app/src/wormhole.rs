@@ -0,0 +1,143 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::error::BankAppError;
+
+// Wormhole Core Bridge on devnet, mainnet is worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth
+pub const CORE_BRIDGE_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+// PDAs of the core bridge, derived with its program id
+pub const BRIDGE_CONFIG_SEED: &[u8] = b"Bridge";
+pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+pub const SEQUENCE_SEED: &[u8] = b"Sequence";
+pub const POSTED_VAA_SEED: &[u8] = b"PostedVAA";
+
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+pub const CONSISTENCY_FINALIZED: u8 = 1;
+
+pub const PAYLOAD_LARGE_DEPOSIT: u8 = 1;
+pub const PAYLOAD_CROSS_CHAIN_DEPOSIT: u8 = 2;
+
+pub struct PostMessageAccounts<'info> {
+    pub bridge: AccountInfo<'info>,
+    pub message: AccountInfo<'info>,
+    pub emitter: AccountInfo<'info>,
+    pub sequence: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub fee_collector: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// BridgeData: guardian_set_index u32, last_lamports u64, expiration_time u32, fee u64
+pub fn bridge_fee(bridge: &AccountInfo) -> Result<u64> {
+    let data = bridge.try_borrow_data()?;
+    Ok(u64::from_le_bytes(data[16..24].try_into().unwrap()))
+}
+
+// the sequence account is created by the bridge on the emitter's first message
+pub fn next_sequence(sequence: &AccountInfo) -> Result<u64> {
+    let data = sequence.try_borrow_data()?;
+    if data.len() < 8 {
+        return Ok(0);
+    }
+    Ok(u64::from_le_bytes(data[..8].try_into().unwrap()))
+}
+
+// `signer_seeds` must sign for both the emitter and the message account
+pub fn post_message<'info>(
+    wormhole_program: AccountInfo<'info>,
+    accounts: PostMessageAccounts<'info>,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![POST_MESSAGE_INSTRUCTION];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    data.push(consistency_level);
+
+    let ix = Instruction {
+        program_id: wormhole_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.bridge.key(), false),
+            AccountMeta::new(accounts.message.key(), true),
+            AccountMeta::new_readonly(accounts.emitter.key(), true),
+            AccountMeta::new(accounts.sequence.key(), false),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new(accounts.fee_collector.key(), false),
+            AccountMeta::new_readonly(accounts.clock.key(), false),
+            AccountMeta::new_readonly(accounts.rent.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.bridge,
+            accounts.message,
+            accounts.emitter,
+            accounts.sequence,
+            accounts.payer,
+            accounts.fee_collector,
+            accounts.clock,
+            accounts.rent,
+            accounts.system_program,
+            wormhole_program,
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+// PostedVAAData as written by the core bridge after the guardian signatures
+// were verified, so owner + magic is all the trust that is needed
+pub struct PostedVaa {
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    const PAYLOAD_OFFSET: usize = 3 + 1 + 1 + 4 + 32 + 4 + 4 + 8 + 2 + 32;
+
+    pub fn load(posted_vaa: &AccountInfo) -> Result<PostedVaa> {
+        if posted_vaa.owner != &CORE_BRIDGE_PROGRAM_ID {
+            return Err(BankAppError::InvalidVaa.into());
+        }
+
+        let data = posted_vaa.try_borrow_data()?;
+        if data.len() < Self::PAYLOAD_OFFSET + 4 || &data[..3] != b"vaa" {
+            return Err(BankAppError::InvalidVaa.into());
+        }
+
+        let payload_len = u32::from_le_bytes(
+            data[Self::PAYLOAD_OFFSET..Self::PAYLOAD_OFFSET + 4]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        let payload_start = Self::PAYLOAD_OFFSET + 4;
+        if data.len() < payload_start + payload_len {
+            return Err(BankAppError::InvalidVaa.into());
+        }
+
+        Ok(PostedVaa {
+            sequence: u64::from_le_bytes(data[49..57].try_into().unwrap()),
+            emitter_chain: u16::from_le_bytes(data[57..59].try_into().unwrap()),
+            emitter_address: data[59..91].try_into().unwrap(),
+            payload: data[payload_start..payload_start + payload_len].to_vec(),
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::Pubkey;
+use bank_app::{
+    error::BankAppError,
+    state::{ApprovalAction, ApprovalSet},
+};
+
+fn two_of_three() -> (ApprovalSet, Vec<Pubkey>) {
+    let approvers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let mut approval_set = ApprovalSet::default();
+    approval_set.configure(&approvers, 2, 100).unwrap();
+    (approval_set, approvers)
+}
+
+#[test]
+fn approvals_at_slot_0_count() {
+    let (mut approval_set, approvers) = two_of_three();
+    approval_set
+        .approve(ApprovalAction::CloseBank, &approvers[0], 0)
+        .unwrap();
+    approval_set
+        .approve(ApprovalAction::CloseBank, &approvers[1], 0)
+        .unwrap();
+
+    assert_eq!(
+        approval_set.valid_approvals(ApprovalAction::CloseBank, 0),
+        2
+    );
+    approval_set.consume(ApprovalAction::CloseBank, 0).unwrap();
+    assert_eq!(
+        approval_set.valid_approvals(ApprovalAction::CloseBank, 0),
+        0
+    );
+}
+
+#[test]
+fn an_approval_expires_after_expiry_slots() {
+    let (mut approval_set, approvers) = two_of_three();
+    approval_set
+        .approve(ApprovalAction::CloseBank, &approvers[0], 10)
+        .unwrap();
+    approval_set
+        .approve(ApprovalAction::CloseBank, &approvers[1], 50)
+        .unwrap();
+
+    assert_eq!(
+        approval_set.valid_approvals(ApprovalAction::CloseBank, 110),
+        2
+    );
+    assert_eq!(
+        approval_set
+            .consume(ApprovalAction::CloseBank, 111)
+            .unwrap_err(),
+        BankAppError::ApprovalThresholdNotMet.into()
+    );
+}
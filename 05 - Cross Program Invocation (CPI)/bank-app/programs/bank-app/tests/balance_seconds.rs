@@ -0,0 +1,129 @@
+use bank_app::{
+    error::BankAppError,
+    state::{BalanceAccumulator, BankInfo, UserReserve},
+};
+
+const SOL: u64 = 1_000_000_000;
+const DAY: i64 = 86_400;
+const YEAR: i64 = 365 * DAY;
+const START: i64 = 1_700_000_000;
+
+fn started() -> BalanceAccumulator {
+    let mut accumulator = BalanceAccumulator::default();
+    accumulator.accumulate(0, START).unwrap();
+    accumulator
+}
+
+#[test]
+fn the_first_reading_only_starts_the_clock() {
+    let mut accumulator = BalanceAccumulator::default();
+    accumulator.accumulate(100 * SOL, START).unwrap();
+    assert_eq!(accumulator.balance_seconds, 0);
+    assert_eq!(accumulator.updated_at, START);
+}
+
+#[test]
+fn a_steady_balance_averages_to_itself() {
+    let start = started();
+    let mut accumulator = start;
+    accumulator.accumulate(0, START).unwrap();
+    let later = accumulator.at(5 * SOL, START + 30 * DAY).unwrap();
+    assert_eq!(later.average_since(&start), Some(5 * SOL));
+}
+
+#[test]
+fn the_average_weighs_each_balance_by_how_long_it_was_held() {
+    let start = started();
+    let mut accumulator = start;
+    // 10 SOL for 3 days, then 2 SOL for a day
+    accumulator.accumulate(0, START).unwrap();
+    accumulator.accumulate(10 * SOL, START + 3 * DAY).unwrap();
+    accumulator.accumulate(2 * SOL, START + 4 * DAY).unwrap();
+
+    assert_eq!(accumulator.average_since(&start), Some(8 * SOL));
+}
+
+#[test]
+fn many_small_updates_sum_to_one_large_one() {
+    let mut lazy = started();
+    let mut eager = started();
+    for hour in 1..=24 * 365 {
+        eager.accumulate(3 * SOL, START + hour * 3_600).unwrap();
+    }
+    lazy.accumulate(3 * SOL, START + YEAR).unwrap();
+    assert_eq!(eager, lazy);
+}
+
+#[test]
+fn the_largest_balance_for_a_thousand_years_fits() {
+    let accumulator = started().at(u64::MAX, START + 1_000 * YEAR).unwrap();
+    assert_eq!(
+        accumulator.balance_seconds,
+        u64::MAX as u128 * (1_000 * YEAR) as u128
+    );
+    assert_eq!(accumulator.average_since(&started()), Some(u64::MAX));
+}
+
+#[test]
+fn an_overflow_fails_instead_of_wrapping() {
+    let accumulator = BalanceAccumulator {
+        balance_seconds: u128::MAX - 1,
+        updated_at: START,
+    };
+    assert_eq!(
+        accumulator.at(1, START + 2).unwrap_err(),
+        BankAppError::BalanceSecondsOverflow.into()
+    );
+}
+
+#[test]
+fn a_clock_behind_the_last_reading_adds_nothing() {
+    let mut accumulator = started();
+    accumulator.accumulate(SOL, START + DAY).unwrap();
+    let before = accumulator;
+    accumulator.accumulate(SOL, START + DAY - 60).unwrap();
+    assert_eq!(accumulator, before);
+}
+
+#[test]
+fn no_time_between_readings_has_no_average() {
+    let start = started();
+    assert_eq!(start.average_since(&start), None);
+}
+
+#[test]
+fn a_user_share_of_the_total_is_their_part_of_the_balance_seconds() {
+    let mut alice = UserReserve::default();
+    let mut bob = UserReserve::default();
+    let mut bank = BankInfo::default();
+    for reserve in [&mut alice, &mut bob] {
+        reserve.accumulate_balance(START).unwrap();
+    }
+    bank.accumulate_total(START).unwrap();
+    let (alice_start, bob_start, bank_start) = (
+        alice.balance_seconds,
+        bob.balance_seconds,
+        bank.total_balance_seconds,
+    );
+
+    // alice holds 30 SOL all month, bob 90 SOL for the last 10 days
+    alice.deposited_amount = 30 * SOL;
+    bank.total_deposited = 30 * SOL;
+    bob.accumulate_balance(START + 20 * DAY).unwrap();
+    bank.accumulate_total(START + 20 * DAY).unwrap();
+    bob.deposited_amount = 90 * SOL;
+    bank.total_deposited += 90 * SOL;
+
+    let end = START + 30 * DAY;
+    for reserve in [&mut alice, &mut bob] {
+        reserve.accumulate_balance(end).unwrap();
+    }
+    bank.accumulate_total(end).unwrap();
+
+    let alice_part = alice.balance_seconds.balance_seconds - alice_start.balance_seconds;
+    let bob_part = bob.balance_seconds.balance_seconds - bob_start.balance_seconds;
+    let total = bank.total_balance_seconds.balance_seconds - bank_start.balance_seconds;
+    assert_eq!(alice_part + bob_part, total);
+    // 30 × 30 days against 90 × 10 days, an even split
+    assert_eq!(alice_part, bob_part);
+}
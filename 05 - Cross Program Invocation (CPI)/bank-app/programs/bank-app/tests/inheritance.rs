@@ -0,0 +1,39 @@
+use bank_app::{error::BankAppError, state::Inheritance};
+
+const NOW: i64 = 1_700_000_000;
+const DAY: i64 = 86_400;
+
+fn switch(last_activity_ts: i64, inactivity_period: i64) -> Inheritance {
+    Inheritance {
+        last_activity_ts,
+        inactivity_period,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn claimable_once_the_owner_has_been_quiet_for_the_period() {
+    let inheritance = switch(NOW, DAY);
+
+    assert!(!inheritance.is_claimable(NOW + DAY - 1, 0).unwrap());
+    assert!(inheritance.is_claimable(NOW + DAY, 0).unwrap());
+}
+
+#[test]
+fn a_deposit_or_withdrawal_restarts_the_timer() {
+    let inheritance = switch(NOW, DAY);
+
+    // the reserve saw the owner later than the last check_in
+    assert!(!inheritance.is_claimable(NOW + DAY, NOW + 1).unwrap());
+    assert!(inheritance.is_claimable(NOW + DAY + 1, NOW + 1).unwrap());
+}
+
+#[test]
+fn a_period_past_i64_max_is_an_error() {
+    let inheritance = switch(NOW, i64::MAX);
+
+    assert_eq!(
+        inheritance.is_claimable(NOW, 0).unwrap_err(),
+        BankAppError::Overflow.into()
+    );
+}
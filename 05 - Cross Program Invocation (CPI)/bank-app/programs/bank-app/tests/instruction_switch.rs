@@ -0,0 +1,35 @@
+use bank_app::{
+    error::BankAppError,
+    state::{BankInfo, BankInstruction},
+};
+
+#[test]
+fn existing_bits_keep_their_positions() {
+    assert_eq!(BankInstruction::Deposit.bit(), 1);
+    assert_eq!(BankInstruction::Withdraw.bit(), 1 << 5);
+    assert_eq!(BankInstruction::DepositToken2022.bit(), 1 << 21);
+    assert_eq!(BankInstruction::SyncReceiptRate.bit(), 1 << 33);
+}
+
+#[test]
+fn switching_off_withdraw_leaves_the_other_withdraw_paths_on() {
+    let bank_info = BankInfo {
+        disabled_instructions: BankInstruction::Withdraw.bit(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        bank_info
+            .require_enabled(BankInstruction::Withdraw)
+            .unwrap_err(),
+        BankAppError::InstructionDisabled.into()
+    );
+    for instruction in [
+        BankInstruction::WithdrawWithPermit,
+        BankInstruction::RequestWithdraw,
+        BankInstruction::ExecuteWithdraw,
+        BankInstruction::WithdrawToEscrow,
+    ] {
+        assert!(bank_info.require_enabled(instruction).is_ok());
+    }
+}
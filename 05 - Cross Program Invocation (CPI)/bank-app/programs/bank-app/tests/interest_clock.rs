@@ -0,0 +1,114 @@
+use anchor_lang::prelude::Clock;
+use bank_app::{
+    constant::{SECONDS_PER_YEAR, SLOTS_PER_YEAR},
+    state::{InterestClock, UserReserve},
+};
+
+const SOL: u64 = 1_000_000_000;
+// 10% a year
+const RATE_BPS: u16 = 1_000;
+
+fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+    Clock {
+        slot,
+        unix_timestamp,
+        ..Clock::default()
+    }
+}
+
+fn reserve(start: &Clock) -> UserReserve {
+    let mut reserve = UserReserve {
+        deposited_amount: 100 * SOL,
+        created_at: start.unix_timestamp,
+        ..UserReserve::default()
+    };
+    reserve
+        .accrue_interest(RATE_BPS, InterestClock::Timestamp, start)
+        .unwrap();
+    reserve
+}
+
+#[test]
+fn a_year_pays_the_rate_on_either_clock() {
+    let start = clock(1_000, 1_700_000_000);
+    let a_year_later = clock(
+        start.slot + SLOTS_PER_YEAR,
+        start.unix_timestamp + SECONDS_PER_YEAR,
+    );
+
+    for interest_clock in [InterestClock::Timestamp, InterestClock::Slot] {
+        let mut reserve = reserve(&start);
+        reserve
+            .accrue_interest(RATE_BPS, interest_clock, &a_year_later)
+            .unwrap();
+        assert_eq!(reserve.accrued_interest, 10 * SOL);
+    }
+}
+
+#[test]
+fn each_clock_ignores_the_other_drifting() {
+    let start = clock(1_000, 1_700_000_000);
+    // slots ran at half speed: a day of time, half a day of slots
+    let day = 86_400;
+    let slots_per_day = SLOTS_PER_YEAR / 365;
+    let later = clock(start.slot + slots_per_day / 2, start.unix_timestamp + day);
+
+    let mut by_time = reserve(&start);
+    by_time
+        .accrue_interest(RATE_BPS, InterestClock::Timestamp, &later)
+        .unwrap();
+    let mut by_slot = reserve(&start);
+    by_slot
+        .accrue_interest(RATE_BPS, InterestClock::Slot, &later)
+        .unwrap();
+
+    assert_eq!(by_time.accrued_interest, 100 * SOL / 10 / 365);
+    assert_eq!(by_slot.accrued_interest, by_time.accrued_interest / 2);
+}
+
+#[test]
+fn switching_clocks_pays_no_stretch_twice() {
+    let start = clock(1_000, 1_700_000_000);
+    let middle = clock(
+        start.slot + SLOTS_PER_YEAR / 2,
+        start.unix_timestamp + SECONDS_PER_YEAR / 2,
+    );
+    let end = clock(
+        start.slot + SLOTS_PER_YEAR,
+        start.unix_timestamp + SECONDS_PER_YEAR,
+    );
+
+    let mut reserve = reserve(&start);
+    reserve
+        .accrue_interest(RATE_BPS, InterestClock::Timestamp, &middle)
+        .unwrap();
+    reserve
+        .accrue_interest(RATE_BPS, InterestClock::Slot, &end)
+        .unwrap();
+    assert_eq!(reserve.accrued_interest, 10 * SOL);
+}
+
+#[test]
+fn a_reserve_never_accrued_by_slot_starts_at_its_first_accrual() {
+    let start = clock(5_000_000, 1_700_000_000);
+    let mut reserve = UserReserve {
+        deposited_amount: 100 * SOL,
+        ..UserReserve::default()
+    };
+
+    reserve
+        .accrue_interest(RATE_BPS, InterestClock::Slot, &start)
+        .unwrap();
+    assert_eq!(reserve.accrued_interest, 0);
+    assert_eq!(reserve.accrual_slot, start.slot);
+}
+
+#[test]
+fn a_crank_runs_once_per_slot() {
+    let mut reserve = UserReserve::default();
+
+    assert!(reserve.record_crank(10).is_ok());
+    assert!(reserve.record_crank(10).is_err());
+    assert!(reserve.record_crank(11).is_ok());
+    assert_eq!(reserve.last_crank_slot, 11);
+}
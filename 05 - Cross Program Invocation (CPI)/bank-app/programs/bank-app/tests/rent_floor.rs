@@ -0,0 +1,65 @@
+use anchor_lang::prelude::Rent;
+use bank_app::{error::BankAppError, state::BankInfo};
+
+const SOL: u64 = 1_000_000_000;
+
+fn bank() -> BankInfo {
+    BankInfo {
+        rent_floor: Rent::default().minimum_balance(0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn only_lamports_above_the_floor_are_liquid() {
+    let bank = bank();
+    assert_eq!(bank.vault_liquidity(bank.rent_floor + 5 * SOL), 5 * SOL);
+    assert_eq!(bank.vault_liquidity(bank.rent_floor), 0);
+    // a vault somehow under its floor has nothing to pay out, not an underflow
+    assert_eq!(bank.vault_liquidity(bank.rent_floor - 1), 0);
+}
+
+#[test]
+fn the_vault_can_be_drained_to_the_floor() {
+    let bank = bank();
+    let vault = bank.rent_floor + 5 * SOL;
+    bank.require_above_rent_floor(vault, 5 * SOL).unwrap();
+}
+
+#[test]
+fn one_lamport_past_the_floor_fails() {
+    let bank = bank();
+    let vault = bank.rent_floor + 5 * SOL;
+    assert_eq!(
+        bank.require_above_rent_floor(vault, 5 * SOL + 1)
+            .unwrap_err(),
+        BankAppError::VaultBelowRentFloor.into()
+    );
+}
+
+#[test]
+fn withdrawals_drain_to_the_edge_one_by_one() {
+    let bank = bank();
+    let mut vault = bank.rent_floor + 3 * SOL;
+    for _ in 0..3 {
+        bank.require_above_rent_floor(vault, SOL).unwrap();
+        vault -= SOL;
+    }
+    assert_eq!(vault, bank.rent_floor);
+    assert_eq!(
+        bank.require_above_rent_floor(vault, 1).unwrap_err(),
+        BankAppError::VaultBelowRentFloor.into()
+    );
+    // nothing is still fine, it leaves the vault where it is
+    bank.require_above_rent_floor(vault, 0).unwrap();
+}
+
+#[test]
+fn the_floor_is_what_was_recorded_not_todays_rent() {
+    // a vault funded under a cheaper rent keeps its own floor
+    let bank = BankInfo {
+        rent_floor: 1_000,
+        ..Default::default()
+    };
+    assert_eq!(bank.vault_liquidity(1_000 + SOL), SOL);
+}
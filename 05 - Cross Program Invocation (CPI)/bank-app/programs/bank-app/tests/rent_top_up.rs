@@ -0,0 +1,40 @@
+use anchor_lang::prelude::Rent;
+use bank_app::instructions::rent_shortfall;
+
+#[test]
+fn an_exempt_account_has_no_shortfall() {
+    let rent = Rent::default();
+    let minimum = rent.minimum_balance(200);
+    assert_eq!(rent_shortfall(&rent, 200, minimum), 0);
+    assert_eq!(rent_shortfall(&rent, 200, minimum + 1), 0);
+}
+
+#[test]
+fn the_shortfall_is_exactly_what_is_missing() {
+    let rent = Rent::default();
+    let minimum = rent.minimum_balance(200);
+    assert_eq!(rent_shortfall(&rent, 200, minimum - 1), 1);
+    assert_eq!(rent_shortfall(&rent, 200, 0), minimum);
+}
+
+#[test]
+fn a_realloc_without_funding_leaves_a_shortfall() {
+    let rent = Rent::default();
+    let before = rent.minimum_balance(200);
+    // 100 bytes more, paid for nothing
+    assert_eq!(
+        rent_shortfall(&rent, 300, before),
+        rent.minimum_balance(300) - before
+    );
+}
+
+#[test]
+fn a_higher_rent_rate_leaves_a_shortfall() {
+    let old = Rent::default();
+    let lamports = old.minimum_balance(200);
+    let new = Rent {
+        lamports_per_byte_year: old.lamports_per_byte_year * 2,
+        ..old
+    };
+    assert_eq!(rent_shortfall(&new, 200, lamports), lamports);
+}
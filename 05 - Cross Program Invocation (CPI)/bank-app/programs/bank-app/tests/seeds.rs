@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet};
+
+use anchor_lang::prelude::Pubkey;
+use bank_app::{
+    constant::{BANK_VAULT_SEED, USER_RESERVE_SEED},
+    seeds::BankPda,
+};
+
+// one of every variant, with random keys so different users and mints are covered
+fn every_pda(i: u64) -> Vec<(&'static str, BankPda)> {
+    let user = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut op_id = [0; 16];
+    op_id[..8].copy_from_slice(&i.to_le_bytes());
+    let mut vaa_hash = [0; 32];
+    vaa_hash[..8].copy_from_slice(&i.to_le_bytes());
+
+    vec![
+        ("BankInfo", BankPda::BankInfo),
+        ("BankVault", BankPda::BankVault),
+        ("UserReserve", BankPda::UserReserve { user }),
+        ("UserTokenReserve", BankPda::UserTokenReserve { user, mint }),
+        ("Snapshot", BankPda::Snapshot { epoch: i }),
+        ("ClaimBitmap", BankPda::ClaimBitmap { snapshot: other }),
+        ("LuckyTicket", BankPda::LuckyTicket { user }),
+        ("SlothashTicket", BankPda::SlothashTicket { user }),
+        ("WormholeEmitter", BankPda::WormholeEmitter),
+        ("WormholeMessage", BankPda::WormholeMessage { sequence: i }),
+        (
+            "ForeignEmitter",
+            BankPda::ForeignEmitter { chain: i as u16 },
+        ),
+        ("ReceivedVaa", BankPda::ReceivedVaa { vaa_hash }),
+        ("DepositCounter", BankPda::DepositCounter { user }),
+        ("DepositRecord", BankPda::DepositRecord { user, index: i }),
+        ("BadgeConfig", BankPda::BadgeConfig),
+        ("BadgeTreeAuthority", BankPda::BadgeTreeAuthority),
+        ("MilestoneBadges", BankPda::MilestoneBadges { user }),
+        ("Reconciliation", BankPda::Reconciliation),
+        ("OpRecord", BankPda::OpRecord { user, op_id }),
+        ("OpsQueue", BankPda::OpsQueue),
+        ("VersionBeacon", BankPda::VersionBeacon),
+        (
+            "Goal",
+            BankPda::Goal {
+                user,
+                index: i as u8,
+            },
+        ),
+        ("RecurringDeposit", BankPda::RecurringDeposit { user, mint }),
+        (
+            "JointReserve",
+            BankPda::JointReserve {
+                owner_a: user,
+                owner_b: other,
+            },
+        ),
+        ("Inheritance", BankPda::Inheritance { owner: user }),
+        ("InsuranceFund", BankPda::InsuranceFund),
+        ("WithdrawalWindow", BankPda::WithdrawalWindow { user }),
+        ("Campaign", BankPda::Campaign { id: i }),
+        (
+            "CampaignMatch",
+            BankPda::CampaignMatch {
+                campaign: other,
+                user,
+            },
+        ),
+        ("UserVault", BankPda::UserVault { user }),
+        ("CpiAllowlist", BankPda::CpiAllowlist),
+        ("ReceiptMint", BankPda::ReceiptMint { mint }),
+        ("DenyEntry", BankPda::DenyEntry { wallet: user }),
+        ("PermitNonce", BankPda::PermitNonce { user }),
+        ("BankLookupTable", BankPda::BankLookupTable),
+        ("ApprovalSet", BankPda::ApprovalSet),
+        ("UserHistory", BankPda::UserHistory { user }),
+        ("BridgeEscrow", BankPda::BridgeEscrow { vaa_hash }),
+    ]
+}
+
+#[test]
+fn no_two_layouts_share_a_preimage() {
+    // seeds are hashed as one concatenated byte string, so two different seed
+    // lists that concatenate to the same bytes would derive the same address
+    let mut preimages: HashMap<Vec<u8>, &str> = HashMap::new();
+    let mut addresses = HashSet::new();
+    for i in 0..20 {
+        for (kind, pda) in every_pda(i) {
+            let preimage = pda.seeds().concat();
+            if let Some(existing) = preimages.insert(preimage, kind) {
+                // a PDA without arguments comes back the same every round
+                assert_eq!(existing, kind, "{kind} collides with {existing}");
+                continue;
+            }
+            assert!(addresses.insert(pda.address()), "{kind} reuses an address");
+        }
+    }
+    assert_eq!(addresses.len(), preimages.len());
+}
+
+#[test]
+fn layouts_match_the_seed_constants() {
+    let user = Pubkey::new_unique();
+    assert_eq!(
+        BankPda::BankVault.address(),
+        Pubkey::find_program_address(&[BANK_VAULT_SEED], &bank_app::ID).0
+    );
+    assert_eq!(
+        BankPda::UserReserve { user }.address(),
+        Pubkey::find_program_address(&[USER_RESERVE_SEED, user.as_ref()], &bank_app::ID).0
+    );
+}
+
+#[test]
+fn the_stored_bump_gives_the_same_address() {
+    for (kind, pda) in every_pda(7) {
+        let (address, bump) = pda.find_address();
+        assert_eq!(pda.address_with_bump(bump), address, "{kind}");
+        // any other bump is another address, or none at all
+        assert_ne!(
+            pda.address_with_bump(bump.wrapping_sub(1)),
+            address,
+            "{kind}"
+        );
+    }
+}
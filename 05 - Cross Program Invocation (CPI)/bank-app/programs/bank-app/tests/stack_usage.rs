@@ -0,0 +1,28 @@
+use std::mem::size_of;
+
+use bank_app::{instructions::DepositAnyToken, stack_bench::DepositAnyTokenUnboxed};
+
+// an SBF stack frame, a function whose locals don't fit fails at runtime
+const SBF_STACK_FRAME: usize = 4096;
+
+// the struct try_accounts returns is the floor of its stack frame, the real
+// frame also holds the temporaries of every constraint (scripts/stack-usage.sh
+// reads those from the SBF build)
+#[test]
+fn boxing_moves_accounts_off_the_stack() {
+    let boxed = size_of::<DepositAnyToken>();
+    let unboxed = size_of::<DepositAnyTokenUnboxed>();
+
+    println!("DepositAnyToken (6 boxed): {} bytes", boxed);
+    println!("DepositAnyTokenUnboxed:    {} bytes", unboxed);
+    println!(
+        "saved {} bytes, {}% of a {} byte frame",
+        unboxed - boxed,
+        (unboxed - boxed) * 100 / SBF_STACK_FRAME,
+        SBF_STACK_FRAME
+    );
+
+    // every boxed account is a pointer in the struct
+    assert!(boxed < unboxed);
+    assert!(boxed < SBF_STACK_FRAME / 4);
+}
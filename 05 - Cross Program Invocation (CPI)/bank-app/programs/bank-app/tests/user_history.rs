@@ -0,0 +1,88 @@
+use anchor_lang::prelude::Pubkey;
+use bank_app::{
+    constant::{
+        HISTORY_KIND_DEPOSIT, HISTORY_KIND_INVEST, HISTORY_KIND_WITHDRAW, USER_HISTORY_CAPACITY,
+    },
+    state::{HistoryEntry, UserHistory},
+};
+
+fn history() -> UserHistory {
+    UserHistory {
+        user: Pubkey::new_unique(),
+        count: 0,
+        entries: [HistoryEntry {
+            amount: 0,
+            timestamp: 0,
+            kind: 0,
+            _padding: [0; 7],
+        }; USER_HISTORY_CAPACITY],
+    }
+}
+
+#[test]
+fn the_account_size_is_planned() {
+    // user, count and 24 bytes per entry, no implicit padding
+    assert_eq!(std::mem::size_of::<HistoryEntry>(), 24);
+    assert_eq!(
+        std::mem::size_of::<UserHistory>(),
+        32 + 8 + 24 * USER_HISTORY_CAPACITY
+    );
+}
+
+#[test]
+fn entries_are_read_oldest_first() {
+    let mut history = history();
+    assert!(history.is_empty());
+
+    history.push(HISTORY_KIND_DEPOSIT, 100, 1);
+    history.push(HISTORY_KIND_WITHDRAW, 40, 2);
+    history.push(HISTORY_KIND_INVEST, 60, 3);
+
+    let entries: Vec<(u8, u64, i64)> = history
+        .iter()
+        .map(|entry| (entry.kind, entry.amount, entry.timestamp))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (HISTORY_KIND_DEPOSIT, 100, 1),
+            (HISTORY_KIND_WITHDRAW, 40, 2),
+            (HISTORY_KIND_INVEST, 60, 3),
+        ]
+    );
+}
+
+#[test]
+fn a_full_history_overwrites_the_oldest_entry() {
+    let mut history = history();
+    for i in 0..USER_HISTORY_CAPACITY as u64 + 5 {
+        history.push(HISTORY_KIND_DEPOSIT, i, i as i64);
+    }
+
+    assert_eq!(history.count, USER_HISTORY_CAPACITY as u64 + 5);
+    assert_eq!(history.len(), USER_HISTORY_CAPACITY);
+    let amounts: Vec<u64> = history.iter().map(|entry| entry.amount).collect();
+    assert_eq!(
+        amounts,
+        (5..USER_HISTORY_CAPACITY as u64 + 5).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn clearing_keeps_the_owner_and_starts_over() {
+    let mut history = history();
+    let user = history.user;
+    for i in 0..USER_HISTORY_CAPACITY as u64 * 2 {
+        history.push(HISTORY_KIND_WITHDRAW, i, i as i64);
+    }
+
+    history.clear();
+    assert_eq!(history.user, user);
+    assert!(history.is_empty());
+    assert_eq!(history.iter().count(), 0);
+    assert!(history.entries.iter().all(|entry| entry.amount == 0));
+
+    history.push(HISTORY_KIND_DEPOSIT, 7, 1);
+    assert_eq!(history.entries[0].amount, 7);
+    assert_eq!(history.len(), 1);
+}
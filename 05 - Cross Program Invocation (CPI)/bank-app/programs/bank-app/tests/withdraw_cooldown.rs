@@ -0,0 +1,49 @@
+use bank_app::{
+    error::BankAppError,
+    state::{BankInfo, UserReserve},
+};
+
+const NOW: i64 = 1_700_000_000;
+const HOUR: i64 = 3_600;
+
+#[test]
+fn a_withdrawal_unlocks_after_the_cooldown() {
+    let mut reserve = UserReserve::default();
+    reserve.request_withdrawal(500, NOW, HOUR);
+
+    assert_eq!(
+        reserve.take_pending_withdrawal(NOW + HOUR - 1).unwrap_err(),
+        BankAppError::WithdrawalLocked.into()
+    );
+    assert_eq!(reserve.take_pending_withdrawal(NOW + HOUR).unwrap(), 500);
+    // paid once, the request is gone
+    assert_eq!(
+        reserve.take_pending_withdrawal(NOW + HOUR).unwrap_err(),
+        BankAppError::NoPendingWithdrawal.into()
+    );
+}
+
+#[test]
+fn a_new_request_starts_the_cooldown_over() {
+    let mut reserve = UserReserve::default();
+    reserve.request_withdrawal(500, NOW, HOUR);
+    reserve.request_withdrawal(200, NOW + HOUR - 1, HOUR);
+
+    assert!(reserve.take_pending_withdrawal(NOW + HOUR).is_err());
+    assert_eq!(
+        reserve.take_pending_withdrawal(NOW + 2 * HOUR).unwrap(),
+        200
+    );
+}
+
+#[test]
+fn direct_withdrawals_stop_while_a_cooldown_is_set() {
+    let mut bank_info = BankInfo::default();
+    assert!(bank_info.require_no_withdraw_cooldown().is_ok());
+
+    bank_info.withdraw_cooldown = HOUR;
+    assert_eq!(
+        bank_info.require_no_withdraw_cooldown().unwrap_err(),
+        BankAppError::WithdrawCooldownActive.into()
+    );
+}
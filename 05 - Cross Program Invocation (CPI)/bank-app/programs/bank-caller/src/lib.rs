@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use bank_app_client::{cpi, types::ID as BANK_APP_ID};
+
+declare_id!("Wp6NyUgZZs2mAmjgY88P3Z2G49QpiFFQdSQDcZLpHeq");
+
+// the smallest program that banks through CPI. It depends on bank-app-client's
+// `cpi` feature instead of the bank-app crate, so it doesn't build Switchboard or
+// staking-app and doesn't copy any account metas. The user signs the outer
+// instruction and their signature carries into the bank's
+#[program]
+pub mod bank_caller {
+    use super::*;
+
+    pub fn deposit(ctx: Context<CallDeposit>, amount: u64) -> Result<()> {
+        cpi::deposit(
+            CpiContext::new(
+                ctx.accounts.bank_program.to_account_info(),
+                cpi::accounts::Deposit {
+                    bank_info: ctx.accounts.bank_info.to_account_info(),
+                    bank_vault: ctx.accounts.bank_vault.to_account_info(),
+                    user_reserve: ctx.accounts.user_reserve.to_account_info(),
+                    user: ctx.accounts.user.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            ),
+            amount,
+            None,
+        )?;
+
+        msg!("deposited {} through bank_caller", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<CallWithdraw>, amount: u64) -> Result<()> {
+        cpi::withdraw(
+            CpiContext::new(
+                ctx.accounts.bank_program.to_account_info(),
+                cpi::accounts::Withdraw {
+                    bank_info: ctx.accounts.bank_info.to_account_info(),
+                    bank_vault: ctx.accounts.bank_vault.to_account_info(),
+                    user_reserve: ctx.accounts.user_reserve.to_account_info(),
+                    withdrawal_window: ctx.accounts.withdrawal_window.to_account_info(),
+                    user: ctx.accounts.user.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("withdrew {} through bank_caller", amount);
+        Ok(())
+    }
+}
+
+// the bank checks its own seeds and owners, there's nothing to check twice here
+#[derive(Accounts)]
+pub struct CallDeposit<'info> {
+    ///CHECK:
+    #[account(mut)]
+    pub bank_info: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub bank_vault: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub user_reserve: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    ///CHECK:
+    #[account(address = BANK_APP_ID)]
+    pub bank_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CallWithdraw<'info> {
+    ///CHECK:
+    #[account(mut)]
+    pub bank_info: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub bank_vault: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub user_reserve: UncheckedAccount<'info>,
+    ///CHECK:
+    #[account(mut)]
+    pub withdrawal_window: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    ///CHECK:
+    #[account(address = BANK_APP_ID)]
+    pub bank_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,110 @@
+use anchor_lang::{prelude::*, system_program};
+
+declare_id!("E9w3kzAJW37EBDbYko4Tw6YckfcMzwPKgk9f3XDDKCRu");
+
+#[constant]
+pub const ESCROW_SEED: &[u8] = b"ESCROW";
+
+// a time locked escrow: the depositor locks SOL for a recipient, and once
+// unlock_at has passed anyone can release it. One escrow per depositor and
+// recipient at a time. The bank's withdraw_to_escrow locks into it through CPI
+#[program]
+pub mod escrow_app {
+    use super::*;
+
+    pub fn lock(ctx: Context<Lock>, amount: u64, recipient: Pubkey, unlock_at: i64) -> Result<()> {
+        if amount == 0 {
+            return Err(EscrowError::InvalidAmount.into());
+        }
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.depositor = ctx.accounts.depositor.key();
+        escrow.recipient = recipient;
+        escrow.amount = amount;
+        escrow.unlock_at = unlock_at;
+        escrow.bump = ctx.bumps.escrow;
+
+        msg!("locked {} for {} until {}", amount, recipient, unlock_at);
+        Ok(())
+    }
+
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        if Clock::get()?.unix_timestamp < escrow.unlock_at {
+            return Err(EscrowError::StillLocked.into());
+        }
+
+        // the escrow is ours, so its lamports move without the system program.
+        // The rent left goes back to the depositor when it closes
+        let amount = escrow.amount;
+        escrow.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        msg!("released {} to {}", amount, escrow.recipient);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, recipient: Pubkey)]
+pub struct Lock<'info> {
+    #[account(
+        init,
+        seeds = [ESCROW_SEED, depositor.key().as_ref(), recipient.as_ref()],
+        bump,
+        payer = depositor,
+        space = 8 + std::mem::size_of::<Escrow>(),
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, depositor.key().as_ref(), recipient.key().as_ref()],
+        bump = escrow.bump,
+        close = depositor,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    ///CHECK: gets the rent back, pinned by the escrow's seeds
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+    ///CHECK: gets the amount, pinned by the escrow's seeds
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct Escrow {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("The escrow is still locked")]
+    StillLocked,
+}
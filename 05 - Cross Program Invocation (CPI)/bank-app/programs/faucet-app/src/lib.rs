@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+declare_id!("CVa29bwQjoiJXmMdyQTe4YrV5f55PexSTyviJj5UGGaW");
+
+// test-only: anyone can mint the faucet token, never deploy this next to real funds
+#[program]
+pub mod faucet_app {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, drip_amount: u64, cooldown: i64) -> Result<()> {
+        let faucet_config = &mut ctx.accounts.faucet_config;
+
+        faucet_config.mint = ctx.accounts.faucet_mint.key();
+        faucet_config.drip_amount = drip_amount;
+        faucet_config.cooldown = cooldown;
+        faucet_config.bump = ctx.bumps.faucet_config;
+
+        msg!("faucet mint {}", faucet_config.mint);
+        Ok(())
+    }
+
+    pub fn drip(ctx: Context<Drip>) -> Result<()> {
+        let faucet_config = &ctx.accounts.faucet_config;
+        let drip_record = &mut ctx.accounts.drip_record;
+
+        let now = Clock::get()?.unix_timestamp;
+        if drip_record.last_drip_at != 0 && now < drip_record.last_drip_at + faucet_config.cooldown
+        {
+            return Err(FaucetError::CooldownActive.into());
+        }
+
+        let pda_seeds: &[&[&[u8]]] = &[&[FAUCET_CONFIG_SEED, &[faucet_config.bump]]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.faucet_mint.to_account_info(),
+                    to: ctx.accounts.user_ata.to_account_info(),
+                    authority: ctx.accounts.faucet_config.to_account_info(),
+                },
+                pda_seeds,
+            ),
+            faucet_config.drip_amount,
+        )?;
+
+        drip_record.last_drip_at = now;
+
+        Ok(())
+    }
+}
+
+pub const FAUCET_CONFIG_SEED: &[u8] = b"FAUCET_CONFIG";
+pub const FAUCET_MINT_SEED: &[u8] = b"FAUCET_MINT";
+pub const DRIP_RECORD_SEED: &[u8] = b"DRIP_RECORD";
+
+pub const FAUCET_DECIMALS: u8 = 9;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FAUCET_CONFIG_SEED],
+        bump,
+        space = 8 + std::mem::size_of::<FaucetConfig>(),
+    )]
+    pub faucet_config: Box<Account<'info, FaucetConfig>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FAUCET_MINT_SEED],
+        bump,
+        mint::decimals = FAUCET_DECIMALS,
+        mint::authority = faucet_config,
+    )]
+    pub faucet_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Drip<'info> {
+    #[account(
+        seeds = [FAUCET_CONFIG_SEED],
+        bump = faucet_config.bump,
+        has_one = mint @ FaucetError::InvalidMint,
+    )]
+    pub faucet_config: Box<Account<'info, FaucetConfig>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [DRIP_RECORD_SEED, user.key().as_ref()],
+        bump,
+        space = 8 + std::mem::size_of::<DripRecord>(),
+    )]
+    pub drip_record: Box<Account<'info, DripRecord>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct FaucetConfig {
+    pub mint: Pubkey,
+    pub drip_amount: u64,
+    pub cooldown: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(Default)]
+pub struct DripRecord {
+    pub last_drip_at: i64,
+}
+
+#[error_code]
+pub enum FaucetError {
+    #[msg("The faucet can only be used once per cooldown.")]
+    CooldownActive,
+    #[msg("The mint is not the faucet mint.")]
+    InvalidMint,
+}
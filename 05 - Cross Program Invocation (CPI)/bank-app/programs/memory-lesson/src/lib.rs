@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+
+declare_id!("4w85YfHpSeiz4bCb2wHyfesydYT55LpkVqRh5YT2xn5a");
+
+// every Account<BigState> is ~1KB on the stack, six of them don't fit in a 4KB
+// SBF frame. `overflow` is the broken version, the other three are the fixes
+pub const BIG_LEN: usize = 1024;
+pub const BIG_STATE_SEED: &[u8] = b"BIG_STATE";
+pub const BIG_ZERO_COPY_SEED: &[u8] = b"BIG_ZERO_COPY";
+
+// the heap is a 32KB bump allocator that never frees, `heap_naive` runs out of it
+pub const LABEL_LEN: usize = 16;
+pub const LABEL_CAPACITY: usize = 512;
+pub const LABEL_BOOK_SEED: &[u8] = b"LABEL_BOOK";
+
+#[program]
+pub mod memory_lesson {
+    use super::*;
+
+    pub fn init_big_state(ctx: Context<InitBigState>, index: u8) -> Result<()> {
+        ctx.accounts.big_state.data[0] = index;
+        Ok(())
+    }
+
+    pub fn init_big_zero_copy(ctx: Context<InitBigZeroCopy>, index: u8) -> Result<()> {
+        let mut big = ctx.accounts.big_zero_copy.load_init()?;
+        big.data[0] = index;
+        Ok(())
+    }
+
+    // builds fine (with a linker warning) and fails at runtime with
+    // "Access violation in stack frame"
+    pub fn overflow(ctx: Context<Overflow>) -> Result<()> {
+        let a = &ctx.accounts;
+        let sum = [&a.big_0, &a.big_1, &a.big_2, &a.big_3, &a.big_4, &a.big_5]
+            .iter()
+            .map(|big| big.data[0] as u64)
+            .sum::<u64>();
+        msg!("sum {}", sum);
+        Ok(())
+    }
+
+    // fix 1: the data moves to the heap, the frame keeps six pointers
+    pub fn boxed(ctx: Context<Boxed>) -> Result<()> {
+        let a = &ctx.accounts;
+        let sum = [&a.big_0, &a.big_1, &a.big_2, &a.big_3, &a.big_4, &a.big_5]
+            .iter()
+            .map(|big| big.data[0] as u64)
+            .sum::<u64>();
+        msg!("sum {}", sum);
+        Ok(())
+    }
+
+    // fix 2: nothing is deserialized, `load` borrows the account data in place
+    pub fn zero_copy(ctx: Context<ZeroCopy>) -> Result<()> {
+        let a = &ctx.accounts;
+        let mut sum = 0u64;
+        for big in [&a.big_0, &a.big_1, &a.big_2, &a.big_3, &a.big_4, &a.big_5] {
+            sum += big.load()?.data[0] as u64;
+        }
+        msg!("sum {}", sum);
+        Ok(())
+    }
+
+    // fix 3: two instructions with three accounts each, both can go in one transaction
+    pub fn split_first(ctx: Context<SplitHalf>) -> Result<()> {
+        let a = &ctx.accounts;
+        let sum = [&a.big_0, &a.big_1, &a.big_2]
+            .iter()
+            .map(|big| big.data[0] as u64)
+            .sum::<u64>();
+        msg!("first half sum {}", sum);
+        Ok(())
+    }
+
+    pub fn split_second(ctx: Context<SplitHalf>) -> Result<()> {
+        let a = &ctx.accounts;
+        let sum = [&a.big_0, &a.big_1, &a.big_2]
+            .iter()
+            .map(|big| big.data[0] as u64)
+            .sum::<u64>();
+        msg!("second half sum {}", sum);
+        Ok(())
+    }
+
+    pub fn init_label_book(ctx: Context<InitLabelBook>) -> Result<()> {
+        ctx.accounts.label_book.load_init()?;
+        Ok(())
+    }
+
+    // every label is its own allocation and the Vec reallocates as it grows, the old
+    // buffers are never freed. A few hundred labels fill the heap and the program
+    // aborts with "memory allocation failed, out of memory"
+    pub fn heap_naive(_ctx: Context<HeapNaive>, count: u32) -> Result<()> {
+        let mut labels: Vec<String> = Vec::new();
+        for i in 0..count {
+            labels.push(format!("reserve-{}", i));
+        }
+        msg!("built {} labels", labels.len());
+        Ok(())
+    }
+
+    // the labels are written in place into a zero-copy account, nothing is allocated
+    pub fn heap_fixed(ctx: Context<HeapFixed>, count: u32) -> Result<()> {
+        if count as usize > LABEL_CAPACITY {
+            return Err(MemoryLessonError::TooManyLabels.into());
+        }
+
+        let mut book = ctx.accounts.label_book.load_mut()?;
+        for i in 0..count {
+            write_label(&mut book.labels[i as usize], i);
+        }
+        book.count = count;
+        msg!("built {} labels", count);
+        Ok(())
+    }
+}
+
+// "reserve-<i>" into a fixed buffer, the unused tail stays zeroed
+fn write_label(label: &mut [u8; LABEL_LEN], i: u32) {
+    const PREFIX: &[u8] = b"reserve-";
+    *label = [0; LABEL_LEN];
+    label[..PREFIX.len()].copy_from_slice(PREFIX);
+
+    let mut digits = [0u8; 10];
+    let mut len = 0;
+    let mut n = i;
+    loop {
+        digits[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    for (offset, digit) in digits[..len].iter().rev().enumerate() {
+        label[PREFIX.len() + offset] = *digit;
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct InitBigState<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BIG_STATE_SEED, &[index]],
+        bump,
+        space = 8 + BIG_LEN,
+    )]
+    pub big_state: Box<Account<'info, BigState>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct InitBigZeroCopy<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BIG_ZERO_COPY_SEED, &[index]],
+        bump,
+        space = 8 + std::mem::size_of::<BigZeroCopy>(),
+    )]
+    pub big_zero_copy: AccountLoader<'info, BigZeroCopy>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Overflow<'info> {
+    pub big_0: Account<'info, BigState>,
+    pub big_1: Account<'info, BigState>,
+    pub big_2: Account<'info, BigState>,
+    pub big_3: Account<'info, BigState>,
+    pub big_4: Account<'info, BigState>,
+    pub big_5: Account<'info, BigState>,
+}
+
+#[derive(Accounts)]
+pub struct Boxed<'info> {
+    pub big_0: Box<Account<'info, BigState>>,
+    pub big_1: Box<Account<'info, BigState>>,
+    pub big_2: Box<Account<'info, BigState>>,
+    pub big_3: Box<Account<'info, BigState>>,
+    pub big_4: Box<Account<'info, BigState>>,
+    pub big_5: Box<Account<'info, BigState>>,
+}
+
+#[derive(Accounts)]
+pub struct ZeroCopy<'info> {
+    pub big_0: AccountLoader<'info, BigZeroCopy>,
+    pub big_1: AccountLoader<'info, BigZeroCopy>,
+    pub big_2: AccountLoader<'info, BigZeroCopy>,
+    pub big_3: AccountLoader<'info, BigZeroCopy>,
+    pub big_4: AccountLoader<'info, BigZeroCopy>,
+    pub big_5: AccountLoader<'info, BigZeroCopy>,
+}
+
+#[derive(Accounts)]
+pub struct SplitHalf<'info> {
+    pub big_0: Account<'info, BigState>,
+    pub big_1: Account<'info, BigState>,
+    pub big_2: Account<'info, BigState>,
+}
+
+#[derive(Accounts)]
+pub struct InitLabelBook<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [LABEL_BOOK_SEED, payer.key().as_ref()],
+        bump,
+        space = 8 + std::mem::size_of::<LabelBook>(),
+    )]
+    pub label_book: AccountLoader<'info, LabelBook>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct HeapNaive<'info> {
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct HeapFixed<'info> {
+    #[account(
+        mut,
+        seeds = [LABEL_BOOK_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub label_book: AccountLoader<'info, LabelBook>,
+
+    pub payer: Signer<'info>,
+}
+
+#[account]
+pub struct BigState {
+    pub data: [u8; BIG_LEN],
+}
+
+#[account(zero_copy)]
+pub struct BigZeroCopy {
+    pub data: [u8; BIG_LEN],
+}
+
+#[account(zero_copy)]
+pub struct LabelBook {
+    pub labels: [[u8; LABEL_LEN]; LABEL_CAPACITY],
+    pub count: u32,
+    pub _padding: [u8; 4],
+}
+
+#[error_code]
+pub enum MemoryLessonError {
+    #[msg("The label book holds at most 512 labels.")]
+    TooManyLabels,
+}
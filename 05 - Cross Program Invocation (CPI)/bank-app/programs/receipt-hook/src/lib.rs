@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("8GUNx5QmwW7qVrE3R34W7yzrv7UfNrPumcBahkWsxWiX");
+
+// Token-2022 calls `transfer_hook` on every transfer of a mint whose TransferHook
+// extension names this program, and fails the transfer if it fails. This one
+// refuses transfers from or to a wallet on the bank's denylist
+#[program]
+pub mod receipt_hook {
+    use super::*;
+
+    // the setup side of the interface: the accounts Token-2022 appends to every
+    // execute, stored where it looks for them. The list is the same for every
+    // mint, so anyone can create it
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+
+        msg!("extra accounts for {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    // the execute side, called by Token-2022 with the interface's discriminator
+    // instead of Anchor's
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        if is_denied(&ctx.accounts.source_deny_entry)
+            || is_denied(&ctx.accounts.destination_deny_entry)
+        {
+            return Err(ReceiptHookError::WalletDenied.into());
+        }
+
+        msg!("transfer of {} allowed", amount);
+        Ok(())
+    }
+}
+
+// bank_app's id and DENYLIST_SEED, copied so the hook doesn't build the bank
+pub const BANK_APP_ID: Pubkey = pubkey!("3q57ftWH75aKfxoNnV6Lu1n8LhV73xxKHAxPapL6Jvh7");
+pub const DENYLIST_SEED: &[u8] = b"DENYLIST_SEED";
+// fixed by the interface, Token-2022 derives it from the mint
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// execute starts with source, mint, destination, owner and the meta list (0-4),
+// these follow at 5-7. A token account's owner is bytes 32..64 of its data
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    let deny_entry_of = |token_account_index: u8| {
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: DENYLIST_SEED.to_vec(),
+                },
+                Seed::AccountData {
+                    account_index: token_account_index,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&BANK_APP_ID, false, false)?,
+        deny_entry_of(0)?,
+        deny_entry_of(2)?,
+    ])
+}
+
+// a deny entry exists only while the wallet is denied, otherwise the address
+// is empty
+fn is_denied(deny_entry: &AccountInfo) -> bool {
+    deny_entry.owner == &BANK_APP_ID && !deny_entry.data_is_empty()
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    ///CHECK: written by ExtraAccountMetaList::init
+    #[account(
+        init,
+        payer = payer,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    ///CHECK: the source owner or delegate, Token-2022 already checked its signature
+    pub owner: UncheckedAccount<'info>,
+
+    ///CHECK: read by Token-2022 to find the accounts below
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    ///CHECK: only the program id the deny entries are derived from
+    #[account(address = BANK_APP_ID)]
+    pub bank_program: UncheckedAccount<'info>,
+
+    ///CHECK: checked by is_denied
+    #[account(
+        seeds = [DENYLIST_SEED, source_token.owner.as_ref()],
+        bump,
+        seeds::program = bank_program.key(),
+    )]
+    pub source_deny_entry: UncheckedAccount<'info>,
+
+    ///CHECK: checked by is_denied
+    #[account(
+        seeds = [DENYLIST_SEED, destination_token.owner.as_ref()],
+        bump,
+        seeds::program = bank_program.key(),
+    )]
+    pub destination_deny_entry: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ReceiptHookError {
+    #[msg("The sender or the recipient is on the bank's denylist.")]
+    WalletDenied,
+}
@@ -0,0 +1,38 @@
+// WAD fixed point: 1.0 is 10^18. Values are u128 so the product of two WADs
+// fits before it's divided back down
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+pub const SECOND_PER_YEAR: u128 = 31_536_000;
+
+// a * b, both WADs
+pub fn wad_mul(a: u128, b: u128) -> Option<u128> {
+    a.checked_mul(b).map(|product| product / WAD)
+}
+
+// the rate earned over `elapsed` seconds at `apr_percent`, as a WAD. 5% for a
+// year is 0.05 * WAD
+pub fn period_rate(apr_percent: u64, elapsed: u64) -> Option<u128> {
+    (apr_percent as u128)
+        .checked_mul(WAD)?
+        .checked_mul(elapsed as u128)
+        .map(|rate| rate / 100 / SECOND_PER_YEAR)
+}
+
+// 1 + r, the rate is paid once on the balance at the start of the period
+pub fn simple_growth(rate: u128) -> Option<u128> {
+    WAD.checked_add(rate)
+}
+
+// e^r, the rate compounded continuously. Summed from its series
+// 1 + r + r^2/2! + r^3/3! + ..., each term is the last one * r / n, until the
+// terms round to zero
+pub fn compound_growth(rate: u128) -> Option<u128> {
+    let mut growth = WAD;
+    let mut term = WAD;
+    let mut n = 1;
+    while term > 0 {
+        term = wad_mul(term, rate)? / n;
+        growth = growth.checked_add(term)?;
+        n += 1;
+    }
+    Some(growth)
+}
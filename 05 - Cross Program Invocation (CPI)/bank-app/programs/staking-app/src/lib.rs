@@ -2,6 +2,7 @@ use anchor_lang::{prelude::*, system_program};
 
 declare_id!("EYdKY4wWuwNr7uVRQNBUEXeJyLCAatSELPck3quW7JvA");
 
+pub mod fixed_point;
 pub mod transfer_helper;
 
 #[program]
@@ -10,22 +11,11 @@ pub mod staking_app {
 
     use super::*;
 
-    const STAKING_APR: u64 = 5; //5%
-    const SECOND_PER_YEAR: u64 = 31_536_000;
-
     pub fn stake(ctx: Context<Stake>, amount: u64, is_stake: bool) -> Result<()> {
         let user_info = &mut ctx.accounts.user_info;
 
         let current_time: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
-        let pass_time = if user_info.last_update_time == 0 {
-            //just initialized
-            0
-        } else {
-            current_time - user_info.last_update_time
-        };
-
-        user_info.amount += user_info.amount * STAKING_APR * pass_time / 100 / SECOND_PER_YEAR;
-        user_info.last_update_time = current_time;
+        user_info.accrue(current_time)?;
 
         if amount != 0 {
             if is_stake {
@@ -53,8 +43,20 @@ pub mod staking_app {
         }
         Ok(())
     }
+
+    pub fn set_accrual_mode(ctx: Context<SetAccrualMode>, accrual_mode: AccrualMode) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+
+        // interest up to now is paid under the old mode
+        let current_time: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        user_info.accrue(current_time)?;
+        user_info.accrual_mode = accrual_mode;
+        Ok(())
+    }
 }
 
+pub const STAKING_APR: u64 = 5; //5%
+
 #[derive(Accounts)]
 pub struct Stake<'info> {
     /// CHECK:
@@ -84,9 +86,59 @@ pub struct Stake<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetAccrualMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"USER_INFO", user.key().as_ref()],
+        bump,
+    )]
+    pub user_info: Box<Account<'info, UserInfo>>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccrualMode {
+    // the APR on the balance at the last update, for the whole time since
+    #[default]
+    Simple,
+    // the APR compounded continuously, e^(rate * time)
+    Compound,
+}
+
 #[account]
 #[derive(Default)]
 pub struct UserInfo {
     pub amount: u64,
     pub last_update_time: u64,
+    pub accrual_mode: AccrualMode,
+}
+
+impl UserInfo {
+    pub fn accrue(&mut self, current_time: u64) -> Result<()> {
+        let pass_time = if self.last_update_time == 0 {
+            //just initialized
+            0
+        } else {
+            current_time - self.last_update_time
+        };
+
+        self.amount = accrued_amount(self.amount, self.accrual_mode, pass_time)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.last_update_time = current_time;
+        Ok(())
+    }
+}
+
+// `amount` after `pass_time` seconds at STAKING_APR
+pub fn accrued_amount(amount: u64, accrual_mode: AccrualMode, pass_time: u64) -> Option<u64> {
+    let rate = fixed_point::period_rate(STAKING_APR, pass_time)?;
+    let growth = match accrual_mode {
+        AccrualMode::Simple => fixed_point::simple_growth(rate)?,
+        AccrualMode::Compound => fixed_point::compound_growth(rate)?,
+    };
+    fixed_point::wad_mul(amount as u128, growth)?
+        .try_into()
+        .ok()
 }
@@ -0,0 +1,78 @@
+use staking_app::{accrued_amount, AccrualMode};
+
+const SOL: u64 = 1_000_000_000;
+const DAY: u64 = 86_400;
+const YEAR: u64 = 365 * DAY;
+
+fn simple(amount: u64, pass_time: u64) -> u64 {
+    accrued_amount(amount, AccrualMode::Simple, pass_time).unwrap()
+}
+
+fn compound(amount: u64, pass_time: u64) -> u64 {
+    accrued_amount(amount, AccrualMode::Compound, pass_time).unwrap()
+}
+
+#[test]
+fn simple_pays_the_apr_once_per_period() {
+    assert_eq!(simple(100 * SOL, YEAR), 105 * SOL);
+    assert_eq!(simple(100 * SOL, 10 * YEAR), 150 * SOL);
+    assert_eq!(simple(100 * SOL, 0), 100 * SOL);
+}
+
+#[test]
+fn compound_follows_e_to_the_rate() {
+    for (years, expected) in [
+        (1, 105.127_109_637),
+        (10, 164.872_127_070),
+        (50, 1_218.249_396_070),
+    ] {
+        let amount = compound(100 * SOL, years * YEAR) as f64 / SOL as f64;
+        println!(
+            "{} years: simple {} compound {}",
+            years,
+            simple(100 * SOL, years * YEAR) as f64 / SOL as f64,
+            amount
+        );
+        assert!((amount - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn compound_pulls_ahead_over_long_periods() {
+    // the gap grows with time: a lamport after an hour, 0.127 SOL after a year
+    assert!(compound(100 * SOL, 3_600) - simple(100 * SOL, 3_600) <= 1);
+    assert_eq!(
+        compound(100 * SOL, YEAR) - simple(100 * SOL, YEAR),
+        127_109_637
+    );
+
+    let mut gap = 0;
+    for years in 1..=100 {
+        let next = compound(100 * SOL, years * YEAR) - simple(100 * SOL, years * YEAR);
+        assert!(next > gap);
+        gap = next;
+    }
+}
+
+#[test]
+fn compound_matches_simple_accrued_every_day() {
+    // a staker under Simple who calls stake every day compounds daily, which
+    // for 10 years lands within 0.01% of the continuous Compound result
+    let mut daily = 100 * SOL;
+    for _ in 0..3_650 {
+        daily = simple(daily, DAY);
+    }
+    let continuous = compound(100 * SOL, 3_650 * DAY);
+
+    assert!(daily < continuous);
+    assert!((continuous - daily) * 10_000 < continuous);
+}
+
+#[test]
+fn overflow_is_none_not_a_panic() {
+    assert_eq!(accrued_amount(u64::MAX, AccrualMode::Simple, YEAR), None);
+    assert_eq!(
+        accrued_amount(SOL, AccrualMode::Compound, 10_000 * YEAR),
+        None
+    );
+}
@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+# dumps the mainnet accounts used by crates/bank-app-testing into tests/fixtures
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+mkdir -p tests/fixtures
+
+dump() {
+  solana account "$2" --url mainnet-beta --output json --output-file "tests/fixtures/$1.json"
+}
+
+dump usdc-mint EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v
+dump pyth-sol-usd H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG
+dump jito-stake-pool Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb
@@ -0,0 +1,9 @@
+#!/usr/bin/env bash
+# copies the parts of the built IDL that bank-app-types generates code from into
+# crates/bank-app-types/idl, so the crate builds before the next `anchor build`.
+# Run it after every change to a #[constant] or to BankAppError
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+jq '{address, metadata, constants, errors}' target/idl/bank_app.json \
+  > crates/bank-app-types/idl/bank_app.json
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+# builds the bank with the unboxed DepositAnyToken copy and prints what the SBF
+# linker says about stack frames that are over the 4KB limit
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+cargo test -p bank-app --features stack-bench --test stack_usage -- --nocapture
+
+cargo build-sbf --manifest-path programs/bank-app/Cargo.toml --features stack-bench 2>&1 \
+  | grep -E "Stack offset of|exceeded max offset" \
+  | grep -E "DepositAnyToken|try_accounts" \
+  || echo "no frame is over the limit"
@@ -1,10 +1,19 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { BankApp } from "../target/types/bank_app";
-import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { BankCaller } from "../target/types/bank_caller";
+import { AddressLookupTableProgram, ComputeBudgetProgram, Ed25519Program, Keypair, PublicKey, StakeProgram, SystemProgram, SYSVAR_CLOCK_PUBKEY, SYSVAR_INSTRUCTIONS_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY, SYSVAR_STAKE_HISTORY_PUBKEY, Transaction, TransactionInstruction } from "@solana/web3.js";
 import { BN } from "bn.js";
-import { createAssociatedTokenAccountInstruction, getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { amountToUiAmount, createAssociatedTokenAccountIdempotentInstruction, createAssociatedTokenAccountInstruction, createInitializeMint2Instruction, createInitializePermanentDelegateInstruction, createMintToInstruction, createTransferCheckedWithTransferHookInstruction, ExtensionType, getAccount, getAssociatedTokenAddressSync, getInterestBearingMintConfigState, getMint, getMintLen, getTokenMetadata, MINT_SIZE, NATIVE_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { ReceiptHook } from "../target/types/receipt_hook";
 import { StakingApp } from "../target/types/staking_app";
+import { createHash } from "crypto";
+import { assert } from "chai";
+import * as sb from "@switchboard-xyz/on-demand";
+import { fetchTaggedDeposits } from "../app/deposit-records";
+import { testIds } from "../app/test-ids";
+import { cpiPrograms, translateCpiError } from "../app/cpi-errors";
+import { getConcurrentMerkleTreeAccountSize, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID } from "@solana/spl-account-compression";
 
 describe("bank-app", () => {
   // Configure the client to use the local cluster.
@@ -12,20 +21,22 @@ describe("bank-app", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.BankApp as Program<BankApp>;
+  const bankCallerProgram = anchor.workspace.BankCaller as Program<BankCaller>;
   const stakingProgram = anchor.workspace.StakingApp as Program<StakingApp>;
+  const receiptHookProgram = anchor.workspace.ReceiptHook as Program<ReceiptHook>;
 
-  const BANK_APP_ACCOUNTS = {
-    bankInfo: PublicKey.findProgramAddressSync(
-      [Buffer.from("BANK_INFO_SEED")],
-      program.programId
-    )[0],
-    bankVault: PublicKey.findProgramAddressSync(
-      [Buffer.from("BANK_VAULT_SEED")],
-      program.programId
-    )[0],
+  // seeds and limits come from the #[constant] entries of the IDL, bytes are
+  // written as "[66, 65, ...]" and numbers as their literal
+  const idlConstant = (name: string) => program.idl.constants.find((constant) => constant.name == name).value
+  const idlSeed = (name: string) => Buffer.from(JSON.parse(idlConstant(name)))
+  const idlNumber = (name: string) => Number(idlConstant(name).replace(/_/g, ""))
+
+  const BANK_APP_SEEDS = {
+    bankInfo: () => [idlSeed("BANK_INFO_SEED")],
+    bankVault: () => [idlSeed("BANK_VAULT_SEED")],
     userReserve: (pubkey: PublicKey, tokenMint?: PublicKey) => {
       let SEEDS = [
-        Buffer.from("USER_RESERVE_SEED"),
+        idlSeed("USER_RESERVE_SEED"),
         pubkey.toBuffer(),
       ]
 
@@ -33,13 +44,185 @@ describe("bank-app", () => {
         SEEDS.push(tokenMint.toBuffer())
       }
 
-      return PublicKey.findProgramAddressSync(
-        SEEDS,
-        program.programId
-      )[0]
+      return SEEDS
+    },
+    snapshot: (epoch: number) => [
+      idlSeed("SNAPSHOT_SEED"),
+      new BN(epoch).toArrayLike(Buffer, "le", 8)
+    ],
+    claimBitmap: (snapshot: PublicKey) => [
+      idlSeed("CLAIM_BITMAP_SEED"),
+      snapshot.toBuffer()
+    ],
+    luckyTicket: (pubkey: PublicKey) => [
+      idlSeed("LUCKY_TICKET_SEED"),
+      pubkey.toBuffer()
+    ],
+    slothashTicket: (pubkey: PublicKey) => [
+      idlSeed("SLOTHASH_TICKET_SEED"),
+      pubkey.toBuffer()
+    ],
+    wormholeEmitter: () => [idlSeed("EMITTER_SEED")],
+    wormholeMessage: (sequence: BN) => [
+      idlSeed("WORMHOLE_MESSAGE_SEED"),
+      sequence.toArrayLike(Buffer, "le", 8)
+    ],
+    foreignEmitter: (chain: number) => [
+      idlSeed("FOREIGN_EMITTER_SEED"),
+      new BN(chain).toArrayLike(Buffer, "le", 2)
+    ],
+    receivedVaa: (vaaHash: Buffer) => [
+      idlSeed("RECEIVED_VAA_SEED"),
+      vaaHash
+    ],
+    depositCounter: (pubkey: PublicKey) => [
+      idlSeed("DEPOSIT_COUNTER_SEED"),
+      pubkey.toBuffer()
+    ],
+    depositRecord: (pubkey: PublicKey, index: number) => [
+      idlSeed("DEPOSIT_RECORD_SEED"),
+      pubkey.toBuffer(),
+      new BN(index).toArrayLike(Buffer, "le", 8)
+    ],
+    badgeConfig: () => [idlSeed("BADGE_CONFIG_SEED")],
+    badgeTreeAuthority: () => [idlSeed("BADGE_TREE_AUTHORITY_SEED")],
+    milestoneBadges: (pubkey: PublicKey) => [
+      idlSeed("MILESTONE_BADGES_SEED"),
+      pubkey.toBuffer()
+    ],
+    reconciliation: () => [idlSeed("RECONCILIATION_SEED")],
+    opRecord: (pubkey: PublicKey, opId: Buffer) => [
+      idlSeed("OP_RECORD_SEED"),
+      pubkey.toBuffer(),
+      opId
+    ],
+    opsQueue: () => [idlSeed("OPS_QUEUE_SEED")],
+    versionBeacon: () => [idlSeed("VERSION_BEACON_SEED")],
+    goal: (pubkey: PublicKey, index: number) => [
+      idlSeed("GOAL_SEED"),
+      pubkey.toBuffer(),
+      Buffer.from([index])
+    ],
+    recurringDeposit: (pubkey: PublicKey, tokenMint: PublicKey) => [
+      idlSeed("RECURRING_DEPOSIT_SEED"),
+      pubkey.toBuffer(),
+      tokenMint.toBuffer()
+    ],
+    insuranceFund: () => [idlSeed("INSURANCE_FUND_SEED")],
+    withdrawalWindow: (pubkey: PublicKey) => [
+      idlSeed("WITHDRAWAL_WINDOW_SEED"),
+      pubkey.toBuffer()
+    ],
+    approvalSet: () => [idlSeed("APPROVAL_SET_SEED")],
+    userHistory: (pubkey: PublicKey) => [
+      idlSeed("USER_HISTORY_SEED"),
+      pubkey.toBuffer()
+    ],
+  }
+
+  const findPda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0]
+
+  const BANK_APP_ACCOUNTS = {
+    bankInfo: findPda(BANK_APP_SEEDS.bankInfo()),
+    bankVault: findPda(BANK_APP_SEEDS.bankVault()),
+    userReserve: (pubkey: PublicKey, tokenMint?: PublicKey) => findPda(BANK_APP_SEEDS.userReserve(pubkey, tokenMint)),
+    snapshot: (epoch: number) => findPda(BANK_APP_SEEDS.snapshot(epoch)),
+    claimBitmap: (snapshot: PublicKey) => findPda(BANK_APP_SEEDS.claimBitmap(snapshot)),
+    luckyTicket: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.luckyTicket(pubkey)),
+    slothashTicket: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.slothashTicket(pubkey)),
+    wormholeEmitter: findPda(BANK_APP_SEEDS.wormholeEmitter()),
+    wormholeMessage: (sequence: BN) => findPda(BANK_APP_SEEDS.wormholeMessage(sequence)),
+    foreignEmitter: (chain: number) => findPda(BANK_APP_SEEDS.foreignEmitter(chain)),
+    depositCounter: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.depositCounter(pubkey)),
+    depositRecord: (pubkey: PublicKey, index: number) => findPda(BANK_APP_SEEDS.depositRecord(pubkey, index)),
+    badgeConfig: findPda(BANK_APP_SEEDS.badgeConfig()),
+    badgeTreeAuthority: findPda(BANK_APP_SEEDS.badgeTreeAuthority()),
+    milestoneBadges: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.milestoneBadges(pubkey)),
+    reconciliation: findPda(BANK_APP_SEEDS.reconciliation()),
+    opRecord: (pubkey: PublicKey, opId: Buffer) => findPda(BANK_APP_SEEDS.opRecord(pubkey, opId)),
+    opsQueue: findPda(BANK_APP_SEEDS.opsQueue()),
+    versionBeacon: findPda(BANK_APP_SEEDS.versionBeacon()),
+    goal: (pubkey: PublicKey, index: number) => findPda(BANK_APP_SEEDS.goal(pubkey, index)),
+    recurringDeposit: (pubkey: PublicKey, tokenMint: PublicKey) => findPda(BANK_APP_SEEDS.recurringDeposit(pubkey, tokenMint)),
+    insuranceFund: findPda(BANK_APP_SEEDS.insuranceFund()),
+    withdrawalWindow: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.withdrawalWindow(pubkey)),
+    approvalSet: findPda(BANK_APP_SEEDS.approvalSet()),
+    userHistory: (pubkey: PublicKey) => findPda(BANK_APP_SEEDS.userHistory(pubkey)),
+  }
+
+  // the bank only takes deposits of mints the authority added
+  const addSupportedToken = async (tokenMint: PublicKey) => {
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    if (bankInfo.tokenConfigs.some((config) => config.mint.equals(tokenMint))) {
+      return
     }
+
+    const tx = await program.methods.addSupportedToken()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Add supported token signature: ", tx);
   }
 
+  const WORMHOLE_PROGRAM_ID = new PublicKey("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5")
+  const findWormholePda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, WORMHOLE_PROGRAM_ID)[0]
+
+  const sha256 = (...chunks: Buffer[]) => createHash("sha256").update(Buffer.concat(chunks)).digest()
+  const rewardLeaf = (index: number, user: PublicKey, amount: BN) => sha256(
+    new BN(index).toArrayLike(Buffer, "le", 4),
+    user.toBuffer(),
+    amount.toArrayLike(Buffer, "le", 8)
+  )
+  const hashPair = (a: Buffer, b: Buffer) => Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a)
+
+  it("Has no PDA seed collisions!", async () => {
+    // seeds are hashed as one concatenated byte string, so two different seed
+    // lists that concatenate to the same bytes would derive the same address
+    const preimages = new Map<string, string>()
+    const addresses = new Set<string>()
+    const record = (kind: string, seeds: Buffer[]) => {
+      const preimage = Buffer.concat(seeds).toString("hex")
+      assert.isFalse(preimages.has(preimage), `${kind} collides with ${preimages.get(preimage)}`)
+      preimages.set(preimage, kind)
+      addresses.add(findPda(seeds).toBase58())
+    }
+
+    record("bankInfo", BANK_APP_SEEDS.bankInfo())
+    record("bankVault", BANK_APP_SEEDS.bankVault())
+    record("wormholeEmitter", BANK_APP_SEEDS.wormholeEmitter())
+    record("badgeConfig", BANK_APP_SEEDS.badgeConfig())
+    record("badgeTreeAuthority", BANK_APP_SEEDS.badgeTreeAuthority())
+    for (let i = 0; i < 20; i++) {
+      const user = Keypair.generate().publicKey
+      const mint = Keypair.generate().publicKey
+
+      record("userReserve", BANK_APP_SEEDS.userReserve(user))
+      record("userTokenReserve", BANK_APP_SEEDS.userReserve(user, mint))
+      record("snapshot", BANK_APP_SEEDS.snapshot(i))
+      record("claimBitmap", BANK_APP_SEEDS.claimBitmap(BANK_APP_ACCOUNTS.snapshot(i)))
+      record("luckyTicket", BANK_APP_SEEDS.luckyTicket(user))
+      record("slothashTicket", BANK_APP_SEEDS.slothashTicket(user))
+      record("wormholeMessage", BANK_APP_SEEDS.wormholeMessage(new BN(i)))
+      record("foreignEmitter", BANK_APP_SEEDS.foreignEmitter(i))
+      record("receivedVaa", BANK_APP_SEEDS.receivedVaa(sha256(user.toBuffer())))
+      record("depositCounter", BANK_APP_SEEDS.depositCounter(user))
+      record("depositRecord", BANK_APP_SEEDS.depositRecord(user, i))
+      record("milestoneBadges", BANK_APP_SEEDS.milestoneBadges(user))
+      record("userHistory", BANK_APP_SEEDS.userHistory(user))
+    }
+
+    assert.equal(addresses.size, preimages.size)
+  });
+
+  it("Has deterministic test identities!", async () => {
+    // crates/bank-app-testing derives the same key from the same seed
+    assert.equal(testIds().alice.publicKey.toBase58(), "8uTA6ta8AnahVS69ea5o3YjbcyuVYqc1GRarDutmoxs3")
+    assert.notEqual(testIds("other-seed").alice.publicKey.toBase58(), testIds().alice.publicKey.toBase58())
+  });
+
   it("Is initialized!", async () => {
     try {
       const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
@@ -49,6 +232,7 @@ describe("bank-app", () => {
         .accounts({
           bankInfo: BANK_APP_ACCOUNTS.bankInfo,
           bankVault: BANK_APP_ACCOUNTS.bankVault,
+          versionBeacon: BANK_APP_ACCOUNTS.versionBeacon,
           authority: provider.publicKey,
           systemProgram: SystemProgram.programId
         }).rpc();
@@ -56,8 +240,31 @@ describe("bank-app", () => {
     }
   });
 
+  it("Is reserve migrated!", async () => {
+    // reserves created by chapters 03/04 still hold the 16-byte v1 layout
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    // and v2 ones from before interest accrual, the version byte follows deposited_amount
+    const accountInfo = await provider.connection.getAccountInfo(userReserve)
+    const version = accountInfo == null || accountInfo.data.length == 16 ? 0 : accountInfo.data[16]
+    if (accountInfo == null || version >= idlNumber("USER_RESERVE_VERSION")) {
+      return
+    }
+
+    const tx = await program.methods.migrateReserve(null)
+      .accounts({
+        userReserve,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Migrate reserve signature: ", tx);
+
+    const migrated = await program.account.userReserve.fetch(userReserve)
+    assert.equal(migrated.version, idlNumber("USER_RESERVE_VERSION"))
+  });
+
   it("Is deposited!", async () => {
-    const tx = await program.methods.deposit(new BN(1_000_000))
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 60)
+    const tx = await program.methods.deposit({ v2: { amount: new BN(1_000_000), deadline } })
       .accounts({
         bankInfo: BANK_APP_ACCOUNTS.bankInfo,
         bankVault: BANK_APP_ACCOUNTS.bankVault,
@@ -71,36 +278,2316 @@ describe("bank-app", () => {
     console.log("User reserve: ", userReserve.depositedAmount.toString())
   });
 
-  it("Is deposited token!", async () => {
-    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
-    let userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
-    let bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+  it("Are deposit and withdraw events emitted!", async () => {
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+      user: provider.publicKey,
+      systemProgram: SystemProgram.programId
+    }
 
-    let preInstructions: TransactionInstruction[] = []
-    if (await provider.connection.getAccountInfo(bankAta) == null) {
-      preInstructions.push(createAssociatedTokenAccountInstruction(
-        provider.publicKey,
-        bankAta,
-        BANK_APP_ACCOUNTS.bankVault,
-        tokenMint
-      ))
+    // live: a websocket subscription to the program's logs, decoded by the IDL
+    const received = new Promise<any>((resolve) => {
+      const listener = program.addEventListener("depositEvent", (event) => {
+        program.removeEventListener(listener)
+        resolve(event)
+      })
+    })
+    await program.methods.deposit({ v1: { amount: new BN(1_000_000) } }).accounts(accounts).rpc()
+    const depositEvent = await received
+    console.log("Deposit event: ", depositEvent)
+    assert.isTrue(depositEvent.user.equals(provider.publicKey))
+    assert.isTrue(depositEvent.mint.equals(PublicKey.default))
+    assert.equal(depositEvent.amount.toNumber(), 1_000_000)
+
+    // after the fact: parse the logs of a confirmed transaction
+    const tx = await program.methods.withdraw(new BN(1_000_000), { dryRun: false }).accounts(accounts).rpc({ commitment: "confirmed" })
+    const { meta } = await provider.connection.getTransaction(tx, { commitment: "confirmed", maxSupportedTransactionVersion: 0 })
+    const events = [...new anchor.EventParser(program.programId, program.coder).parseLogs(meta.logMessages)]
+    const withdrawEvent = events.find((event) => event.name == "withdrawEvent")
+    console.log("Withdraw event: ", withdrawEvent)
+    assert.equal(withdrawEvent.data.amount.toNumber(), 1_000_000)
+  });
+
+  it("Is a permit used once and in order!", async () => {
+    // the user only signs messages, the provider relays and pays the fees
+    const user = Keypair.generate()
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: user.publicKey,
+      lamports: 100_000_000,
+    })))
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(user.publicKey)
+    await program.methods.deposit({ v1: { amount: new BN(50_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId
+      }).signers([user]).rpc()
+
+    const permitNonce = findPda([idlSeed("PERMIT_NONCE_SEED"), user.publicKey.toBuffer()])
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 600)
+    const permitMessage = (amount: number, nonce: number) => Buffer.concat([
+      idlSeed("PERMIT_DOMAIN"),
+      program.programId.toBuffer(),
+      user.publicKey.toBuffer(),
+      new BN(amount).toArrayLike(Buffer, "le", 8),
+      new BN(nonce).toArrayLike(Buffer, "le", 8),
+      deadline.toArrayLike(Buffer, "le", 8),
+    ])
+    const withdrawWithPermit = (amount: number, nonce: number, signer = user, preInstructions: TransactionInstruction[] = []) =>
+      program.methods.withdrawWithPermit(new BN(amount), new BN(nonce), deadline)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve,
+          withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(user.publicKey),
+          permitNonce,
+          user: user.publicKey,
+          relayer: provider.publicKey,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId
+        })
+        // the signature is checked by the precompile right before the withdraw
+        .preInstructions([
+          ...preInstructions,
+          Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message: permitMessage(amount, nonce) }),
+        ])
+        .rpc()
+    const expectError = async (call: Promise<string>, code: string) => {
+      try {
+        await call
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+      }
+    }
+
+    await expectError(withdrawWithPermit(1_000_000, 0, Keypair.generate()), "InvalidPermit")
+    // out of order: nonce 1 before nonce 0
+    await expectError(withdrawWithPermit(1_000_000, 1), "InvalidPermitNonce")
+
+    const tx = await withdrawWithPermit(1_000_000, 0)
+    console.log("Withdraw with permit signature: ", tx);
+
+    // the same permit again. A compute budget instruction makes it a new transaction,
+    // otherwise the runtime would drop it as a duplicate before the nonce check
+    await expectError(
+      withdrawWithPermit(1_000_000, 0, user, [ComputeBudgetProgram.setComputeUnitLimit({ units: 300_000 })]),
+      "InvalidPermitNonce"
+    )
+
+    await withdrawWithPermit(2_000_000, 1)
+    assert.equal((await program.account.permitNonce.fetch(permitNonce)).nextNonce.toNumber(), 2)
+    assert.equal((await program.account.userReserve.fetch(userReserve)).depositedAmount.toNumber(), 47_000_000)
+  });
+
+  it("Is a withdrawal paid only after its cooldown!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const setCooldown = (withdrawCooldown: number) => program.methods.setWithdrawCooldown(new BN(withdrawCooldown))
+      .accounts({ bankInfo: BANK_APP_ACCOUNTS.bankInfo, authority: provider.publicKey }).rpc()
+    const executeWithdraw = () => program.methods.executeWithdraw()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const expectError = async (call: Promise<string>, code: string) => {
+      try {
+        await call
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+      }
     }
+    const chainTime = async () => provider.connection.getBlockTime(await provider.connection.getSlot())
 
-    const tx = await program.methods.depositToken(new BN(1_000_000_000))
+    await setCooldown(3)
+    await program.methods.deposit({ v1: { amount: new BN(10_000_000) } })
       .accounts({
         bankInfo: BANK_APP_ACCOUNTS.bankInfo,
         bankVault: BANK_APP_ACCOUNTS.bankVault,
-        tokenMint,
-        userAta,
-        bankAta,
-        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+        userReserve,
         user: provider.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId
-      }).preInstructions(preInstructions).rpc();
-    console.log("Deposit token signature: ", tx);
+      }).rpc()
 
-    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint))
-    console.log("User reserve: ", userReserve.depositedAmount.toString())
+    // the direct path is closed while the cooldown is set
+    await expectError(program.methods.withdraw(new BN(1_000_000), { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc(), "WithdrawCooldownActive")
+
+    await expectError(executeWithdraw(), "NoPendingWithdrawal")
+    const tx = await program.methods.requestWithdraw(new BN(10_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        userReserve,
+        user: provider.publicKey,
+      }).rpc();
+    console.log("Request withdraw signature: ", tx);
+    const requested = await program.account.userReserve.fetch(userReserve)
+    assert.equal(requested.pendingWithdrawal.toNumber(), 10_000_000)
+    await expectError(executeWithdraw(), "WithdrawalLocked")
+
+    // a localnet can't warp, so wait for the cooldown to pass
+    while ((await chainTime()) < requested.unlockAt.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    const executeTx = await executeWithdraw();
+    console.log("Execute withdraw signature: ", executeTx);
+    const executed = await program.account.userReserve.fetch(userReserve)
+    assert.equal(executed.pendingWithdrawal.toNumber(), 0)
+    assert.equal(requested.depositedAmount.sub(executed.depositedAmount).toNumber(), 10_000_000)
+
+    // direct withdrawals for the tests after this one
+    await setCooldown(0)
+  });
+
+  it("Is the bank lookup table created and extended!", async () => {
+    // one table per bank, the first run creates it
+    const bankLookupTable = findPda([idlSeed("LOOKUP_TABLE_SEED")])
+    if (await provider.connection.getAccountInfo(bankLookupTable) == null) {
+      const recentSlot = await provider.connection.getSlot("finalized")
+      const [, lookupTable] = AddressLookupTableProgram.createLookupTable({
+        authority: bankLookupTable,
+        payer: provider.publicKey,
+        recentSlot,
+      })
+      const tx = await program.methods.createLookupTable(new BN(recentSlot))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          bankLookupTable,
+          lookupTable,
+          authority: provider.publicKey,
+          addressLookupTableProgram: AddressLookupTableProgram.programId,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      console.log("Create lookup table signature: ", tx);
+    }
+
+    const lookupTable = (await program.account.bankLookupTable.fetch(bankLookupTable)).address
+    const tableAddresses = async () => (await provider.connection.getAddressLookupTable(lookupTable)).value.state.addresses.map((address) => address.toBase58())
+
+    // bank_info is already in the table and is skipped
+    const newAddress = Keypair.generate().publicKey
+    const tx = await program.methods.extendLookupTable([BANK_APP_ACCOUNTS.bankInfo, newAddress])
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankLookupTable,
+        lookupTable,
+        authority: provider.publicKey,
+        addressLookupTableProgram: AddressLookupTableProgram.programId,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Extend lookup table signature: ", tx);
+
+    const addresses = await tableAddresses()
+    assert.include(addresses, newAddress.toBase58())
+    assert.include(addresses, BANK_APP_ACCOUNTS.bankVault.toBase58())
+    assert.equal(addresses.filter((address) => address == BANK_APP_ACCOUNTS.bankInfo.toBase58()).length, 1)
+  });
+
+  it("Is deposit matched by a campaign!", async () => {
+    // campaigns stay around until closed, a new id per run
+    const campaignId = new BN(Date.now())
+    const campaign = findPda([idlSeed("CAMPAIGN_SEED"), campaignId.toArrayLike(Buffer, "le", 8)])
+    const chainTime = async () => provider.connection.getBlockTime(await provider.connection.getSlot())
+    const endAt = (await chainTime()) + 5
+
+    // 50% match, at most 300_000 per user
+    await program.methods.createCampaign(campaignId, 5_000, new BN(1_000_000), new BN(300_000), new BN(0), new BN(endAt))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        campaign,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const depositMatched = async (amount: number) => {
+      const before = (await program.account.userReserve.fetch(userReserve)).depositedAmount
+      await program.methods.deposit({ v1: { amount: new BN(amount) } })
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId,
+          opRecord: null,
+          campaign,
+          campaignMatch: findPda([idlSeed("CAMPAIGN_MATCH_SEED"), campaign.toBuffer(), provider.publicKey.toBuffer()]),
+        }).rpc()
+      return (await program.account.userReserve.fetch(userReserve)).depositedAmount.sub(before).toNumber()
+    }
+
+    assert.equal(await depositMatched(400_000), 600_000)
+    // only 100_000 of the user's cap is left
+    assert.equal(await depositMatched(400_000), 500_000)
+
+    const closeCampaign = () => program.methods.closeCampaign(campaignId)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        campaign,
+        authority: provider.publicKey,
+      }).rpc()
+    try {
+      await closeCampaign()
+      assert.fail("expected CampaignNotEnded")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "CampaignNotEnded")
+    }
+
+    while ((await chainTime()) < endAt) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    const tx = await closeCampaign();
+    console.log("Close campaign signature: ", tx);
+    assert.isNull(await program.account.campaign.fetchNullable(campaign))
+  });
+
+  it("Is withdrawn!", async () => {
+    const withdraw = (amount: BN) => program.methods.withdraw(amount, { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+
+    const tx = await withdraw(new BN(500_000)).rpc();
+    console.log("Withdraw signature: ", tx);
+
+    // both checks are account constraints, so the error names the account that
+    // failed instead of a line in the handler
+    const expectConstraintError = async (amount: BN, code: string) => {
+      try {
+        await withdraw(amount).rpc()
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+        assert.equal(e.error?.origin, "user_reserve")
+      }
+    }
+
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    await expectConstraintError(new BN(0), "InvalidAmount")
+    await expectConstraintError(userReserve.depositedAmount.addn(1), "InsufficientDeposit")
+  });
+
+  it("Is a disabled instruction rejected!", async () => {
+    const setWithdrawEnabled = (enabled: boolean) => program.methods.setInstructionEnabled({ withdraw: {} }, enabled)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+    const withdraw = () => program.methods.withdraw(new BN(100_000), { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    await setWithdrawEnabled(false)
+    try {
+      await withdraw()
+      assert.fail("expected InstructionDisabled")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InstructionDisabled")
+    }
+
+    // only withdraw is off, deposits still go through
+    const depositTx = await program.methods.deposit({ v1: { amount: new BN(100_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Deposit while withdraw is disabled signature: ", depositTx);
+
+    await setWithdrawEnabled(true)
+    const tx = await withdraw();
+    console.log("Withdraw after re-enabling signature: ", tx);
+  });
+
+  it("Is young reserve withdrawal limited!", async () => {
+    const DAY = 86_400
+    const setTiers = (tiers: { maxAge: BN, dailyLimit: BN }[]) => program.methods.setWithdrawalTiers(tiers)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+    const withdraw = (amount: number) => program.methods.withdraw(new BN(amount), { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    try {
+      await setTiers([
+        { maxAge: new BN(30 * DAY), dailyLimit: new BN(1_000_000) },
+        { maxAge: new BN(DAY), dailyLimit: new BN(2_000_000) },
+        { maxAge: new BN(0), dailyLimit: new BN(0) },
+      ])
+      assert.fail("expected InvalidWithdrawalTiers")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidWithdrawalTiers")
+    }
+
+    // the reserve was created by this run, so it's in the first tier. Leave
+    // room for exactly one more 100_000 withdrawal today
+    const window = await program.account.withdrawalWindow.fetch(BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey))
+    const dailyLimit = window.withdrawn.addn(150_000)
+    await setTiers([
+      { maxAge: new BN(DAY), dailyLimit },
+      { maxAge: new BN(30 * DAY), dailyLimit: dailyLimit.muln(10) },
+      { maxAge: new BN(365 * DAY), dailyLimit: dailyLimit.muln(100) },
+    ])
+
+    const tx = await withdraw(100_000);
+    console.log("Withdraw under the daily limit signature: ", tx);
+    try {
+      await withdraw(100_000)
+      assert.fail("expected DailyWithdrawalLimitExceeded")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "DailyWithdrawalLimitExceeded")
+    }
+
+    // no tiers, no limits for the tests after this one
+    const noTier = { maxAge: new BN(0), dailyLimit: new BN(0) }
+    await setTiers([noTier, noTier, noTier])
+  });
+
+  it("Is interest accrued on deposit and withdraw!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const chainTime = async () => provider.connection.getBlockTime(await provider.connection.getSlot())
+    const setRate = (interestRateBps: number) => program.methods.setInterestRate(interestRateBps)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+    const deposit = (amount: number) => program.methods.deposit({ v2: { amount: new BN(amount), deadline: new BN((await chainTime()) + 60) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    try {
+      await setRate(idlNumber("MAX_INTEREST_RATE_BPS") + 1)
+      assert.fail("expected InvalidInterestRate")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidInterestRate")
+    }
+
+    // at 100% a year, 0.1 SOL earns about 3 lamports a second
+    await setRate(idlNumber("MAX_INTEREST_RATE_BPS"))
+    await deposit(100_000_000)
+    const before = await program.account.userReserve.fetch(userReserve)
+
+    while ((await chainTime()) < before.depositTimestamp.toNumber() + 3) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    const tx = await program.methods.withdraw(new BN(100_000_000), { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Withdraw with interest signature: ", tx);
+
+    const after = await program.account.userReserve.fetch(userReserve)
+    assert.isTrue(after.accruedInterest.gt(before.accruedInterest))
+    assert.isTrue(after.depositTimestamp.gt(before.depositTimestamp))
+
+    // no interest for the tests after this one
+    await setRate(0)
+  });
+
+  it("Are interest cranks run once per slot!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const authorityAccounts = { bankInfo: BANK_APP_ACCOUNTS.bankInfo, authority: provider.publicKey }
+    const setRate = (interestRateBps: number) => program.methods.setInterestRate(interestRateBps).accounts(authorityAccounts).rpc()
+    const setClock = (interestClock: object) => program.methods.setInterestClock(interestClock as any).accounts(authorityAccounts).rpc()
+    const crank = () => program.methods.accrueInterest()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        userReserve,
+        user: provider.publicKey,
+      })
+    const expectCrankAlreadyRan = async (call: Promise<string>) => {
+      try {
+        await call
+        assert.fail("expected CrankAlreadyRan")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "CrankAlreadyRan")
+      }
+    }
+    const chainTime = async () => provider.connection.getBlockTime(await provider.connection.getSlot())
+
+    await setRate(idlNumber("MAX_INTEREST_RATE_BPS"))
+    await program.methods.deposit({ v1: { amount: new BN(100_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    // both instructions of one transaction run in the same slot
+    await expectCrankAlreadyRan(crank().postInstructions([await crank().instruction()]).rpc())
+
+    // by slot: a localnet can't warp, so wait for the slots to pass
+    await setClock({ slot: {} })
+    let before = await program.account.userReserve.fetch(userReserve)
+    while ((await provider.connection.getSlot()) < before.accrualSlot.toNumber() + 20) {
+      await new Promise((resolve) => setTimeout(resolve, 200))
+    }
+    await crank().rpc()
+    let after = await program.account.userReserve.fetch(userReserve)
+    assert.isTrue(after.accruedInterest.gt(before.accruedInterest))
+    assert.isTrue(after.lastCrankSlot.eq(after.accrualSlot))
+
+    // by timestamp, the same wait in seconds
+    await setClock({ timestamp: {} })
+    before = after
+    while ((await chainTime()) < before.depositTimestamp.toNumber() + 2) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    await crank().rpc()
+    after = await program.account.userReserve.fetch(userReserve)
+    assert.isTrue(after.accruedInterest.gt(before.accruedInterest))
+
+    // the staking app's interest, settled by anyone
+    const [stakingVault] = PublicKey.findProgramAddressSync([Buffer.from("STAKING_VAULT")], stakingProgram.programId)
+    const [stakingInfo] = PublicKey.findProgramAddressSync([Buffer.from("USER_INFO"), BANK_APP_ACCOUNTS.bankVault.toBuffer()], stakingProgram.programId)
+    const harvest = () => program.methods.harvest()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        stakingVault,
+        stakingInfo,
+        stakingProgram: stakingProgram.programId,
+        payer: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+    await expectCrankAlreadyRan(harvest().postInstructions([await harvest().instruction()]).rpc())
+    const tx = await harvest().rpc()
+    console.log("Harvest signature: ", tx);
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    assert.isTrue(bankInfo.lastHarvestSlot.gtn(0))
+
+    await setRate(0)
+  });
+
+  it("Is deposited with the legacy args layout!", async () => {
+    // a client built before DepositArgs existed sends the discriminator and a bare u64
+    const ix = await program.methods.deposit({ v1: { amount: new BN(1_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).instruction();
+    ix.data = Buffer.concat([ix.data.subarray(0, 8), new BN(1_000_000).toArrayLike(Buffer, "le", 8)])
+
+    const before = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    const tx = await provider.sendAndConfirm(new Transaction().add(ix))
+    console.log("Legacy deposit signature: ", tx);
+
+    const after = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    assert.equal(after.depositedAmount.sub(before.depositedAmount).toNumber(), 1_000_000)
+  });
+
+  it("Is expired deposit rejected!", async () => {
+    try {
+      await program.methods.deposit({ v2: { amount: new BN(1_000_000), deadline: new BN(1) } })
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      assert.fail("a deposit past its deadline should be rejected")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "DepositExpired")
+    }
+  });
+
+  it("Is retried deposit credited once!", async () => {
+    // the off-chain system picks the op_id once and reuses it for every retry
+    const opId = Keypair.generate().publicKey.toBuffer().subarray(0, 16)
+    const opRecord = BANK_APP_ACCOUNTS.opRecord(provider.publicKey, opId)
+    const deposit = () => program.methods.deposit({ v3: { amount: new BN(1_000_000), deadline: null, opId: [...opId] } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+        opRecord,
+      })
+
+    const before = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    const tx = await deposit().rpc();
+    console.log("Deposit with op_id signature: ", tx);
+
+    try {
+      await deposit().rpc()
+      assert.fail("a deposit with a used op_id should be rejected")
+    } catch (e) {
+      assert.isTrue(e.logs?.some((log: string) => log.includes("already in use")))
+    }
+
+    const after = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    assert.equal(after.depositedAmount.sub(before.depositedAmount).toNumber(), 1_000_000)
+
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    const rentDestination = bankInfo.rentDestination.equals(PublicKey.default) ? provider.publicKey : bankInfo.rentDestination
+    try {
+      await program.methods.closeOpRecord([...opId])
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          opRecord,
+          rentDestination,
+          user: provider.publicKey,
+        }).rpc()
+      assert.fail("a fresh op record can't be closed")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "OpRecordTooRecent")
+    }
+  });
+
+  it("Is queued withdrawal settled!", async () => {
+    if (await provider.connection.getAccountInfo(BANK_APP_ACCOUNTS.opsQueue) == null) {
+      await program.methods.initOpsQueue()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          opsQueue: BANK_APP_ACCOUNTS.opsQueue,
+          authority: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+    }
+
+    const tx = await program.methods.queueWithdrawal(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        opsQueue: BANK_APP_ACCOUNTS.opsQueue,
+        user: provider.publicKey,
+      }).rpc();
+    console.log("Queue withdrawal signature: ", tx);
+
+    // FIFO: everything queued before ours is settled first
+    const ourOp = (await program.account.opsQueue.fetch(BANK_APP_ACCOUNTS.opsQueue)).tail.subn(1)
+    const [stakingVault] = PublicKey.findProgramAddressSync([Buffer.from("STAKING_VAULT")], stakingProgram.programId)
+    const [stakingInfo] = PublicKey.findProgramAddressSync([Buffer.from("USER_INFO"), BANK_APP_ACCOUNTS.bankVault.toBuffer()], stakingProgram.programId)
+
+    let queue = await program.account.opsQueue.fetch(BANK_APP_ACCOUNTS.opsQueue)
+    while (queue.head.lte(ourOp)) {
+      const front = queue.ops[queue.head.modn(queue.ops.length)]
+      const tx = await program.methods.processNextOp()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          opsQueue: BANK_APP_ACCOUNTS.opsQueue,
+          recipient: front.user,
+          stakingVault,
+          stakingInfo,
+          stakingProgram: stakingProgram.programId,
+          payer: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      console.log("Process next op signature: ", tx);
+      queue = await program.account.opsQueue.fetch(BANK_APP_ACCOUNTS.opsQueue)
+    }
+    assert.isTrue(queue.head.gt(ourOp))
+  });
+
+  it("Is accrual mode switched!", async () => {
+    const [stakingVault] = PublicKey.findProgramAddressSync([Buffer.from("STAKING_VAULT")], stakingProgram.programId)
+    const [stakingInfo] = PublicKey.findProgramAddressSync([Buffer.from("USER_INFO"), BANK_APP_ACCOUNTS.bankVault.toBuffer()], stakingProgram.programId)
+    // a zero invest creates the bank's staking position if it has none yet
+    await program.methods.invest(new BN(0), true)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        stakingVault,
+        stakingInfo,
+        stakingProgram: stakingProgram.programId,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const setAccrualMode = (compound: boolean) => program.methods.setAccrualMode(compound)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        stakingInfo,
+        stakingProgram: stakingProgram.programId,
+        authority: provider.publicKey,
+      }).rpc()
+
+    await setAccrualMode(true)
+    assert.deepEqual((await stakingProgram.account.userInfo.fetch(stakingInfo)).accrualMode, { compound: {} })
+
+    // the long-period comparison is in programs/staking-app/tests/accrual.rs,
+    // a localnet clock can't be moved forward years
+    await setAccrualMode(false)
+    assert.deepEqual((await stakingProgram.account.userInfo.fetch(stakingInfo)).accrualMode, { simple: {} })
+  });
+
+  it("Is goal unlocked at its deadline!", async () => {
+    // goals stay around between runs, take the first free index
+    let goalIndex = 0
+    while (await provider.connection.getAccountInfo(BANK_APP_ACCOUNTS.goal(provider.publicKey, goalIndex)) != null) {
+      goalIndex++
+    }
+    const goal = BANK_APP_ACCOUNTS.goal(provider.publicKey, goalIndex)
+    const chainTime = async () => provider.connection.getBlockTime(await provider.connection.getSlot())
+    const lockUntil = (await chainTime()) + 3
+
+    await program.methods.createGoal(goalIndex, "holiday", new BN(5_000_000), new BN(lockUntil))
+      .accounts({
+        goal,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+      goal,
+      user: provider.publicKey,
+    }
+    const reserveBefore = (await program.account.userReserve.fetch(accounts.userReserve)).depositedAmount
+    await program.methods.moveToGoal(goalIndex, new BN(1_000_000)).accounts(accounts).rpc()
+    assert.equal((await program.account.goal.fetch(goal)).amount.toNumber(), 1_000_000)
+
+    try {
+      await program.methods.moveFromGoal(goalIndex, new BN(1_000_000)).accounts(accounts).rpc()
+      assert.fail("expected GoalLocked")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "GoalLocked")
+    }
+
+    // nobody unlocks it, the deadline passing is enough
+    while ((await chainTime()) < lockUntil) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    await program.methods.moveFromGoal(goalIndex, new BN(1_000_000)).accounts(accounts).rpc()
+    assert.equal((await program.account.goal.fetch(goal)).amount.toNumber(), 0)
+    assert.isTrue((await program.account.userReserve.fetch(accounts.userReserve)).depositedAmount.eq(reserveBefore))
+  });
+
+  it("Is tagged deposit listed!", async () => {
+    const depositCounter = BANK_APP_ACCOUNTS.depositCounter(provider.publicKey)
+    const counter = await program.account.depositCounter.fetchNullable(depositCounter)
+    const index = counter == null ? 0 : counter.count.toNumber()
+    const tag = `rent-${index}`
+
+    const tx = await program.methods.depositTagged(new BN(1_000_000), tag)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        depositCounter,
+        depositRecord: BANK_APP_ACCOUNTS.depositRecord(provider.publicKey, index),
+        user: provider.publicKey,
+      }).rpc({ commitment: "confirmed" });
+    console.log("Deposit tagged signature: ", tx);
+
+    const deposits = await fetchTaggedDeposits(program, provider.publicKey)
+    console.log("Tagged deposits: ", deposits.map((d) => `${d.index}: ${d.amount.toString()} ${d.tag}`))
+    assert.equal(deposits[deposits.length - 1].tag, tag)
+  });
+
+  it("Is deposit record closed!", async () => {
+    const counter = await program.account.depositCounter.fetch(BANK_APP_ACCOUNTS.depositCounter(provider.publicKey))
+    const index = counter.count.toNumber() - 1
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    // Pubkey::default() means rent goes back to the user
+    const rentDestination = bankInfo.rentDestination.equals(PublicKey.default) ? provider.publicKey : bankInfo.rentDestination
+
+    const close = (rentDestination: PublicKey) => program.methods.closeDepositRecord(new BN(index))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        depositRecord: BANK_APP_ACCOUNTS.depositRecord(provider.publicKey, index),
+        rentDestination,
+        user: provider.publicKey,
+      })
+
+    try {
+      await close(Keypair.generate().publicKey).rpc()
+      assert.fail("rent must go to the configured destination")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidRentDestination")
+    }
+
+    const tx = await close(rentDestination).rpc();
+    console.log("Close deposit record signature: ", tx);
+  });
+
+  it("Is deposited token!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    let userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    let bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true)
+    await addSupportedToken(tokenMint)
+
+    let preInstructions: TransactionInstruction[] = []
+    if (await provider.connection.getAccountInfo(bankAta) == null) {
+      preInstructions.push(createAssociatedTokenAccountInstruction(
+        provider.publicKey,
+        bankAta,
+        BANK_APP_ACCOUNTS.bankVault,
+        tokenMint
+      ))
+    }
+
+    const depositToken = (amount: BN) => program.methods.depositToken(amount)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        userAta,
+        bankAta,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      })
+    const tx = await depositToken(new BN(1_000_000_000)).preInstructions(preInstructions).rpc();
+    console.log("Deposit token signature: ", tx);
+
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint))
+    console.log("User reserve: ", userReserve.depositedAmount.toString())
+
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    const tokenConfig = bankInfo.tokenConfigs.find((config) => config.mint.equals(tokenMint))
+    console.log("Bank total for the token: ", tokenConfig.totalDeposited.toString())
+    assert.isTrue(tokenConfig.totalDeposited.gten(1_000_000_000))
+
+    // the per-mint limits, set back to the defaults at the end
+    const setTokenConfig = (depositCap: BN, isPaused: boolean) => program.methods.setTokenConfig(0, depositCap, isPaused)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        authority: provider.publicKey,
+      }).rpc()
+    const expectError = async (code: string) => {
+      try {
+        await depositToken(new BN(1)).rpc()
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+      }
+    }
+
+    await setTokenConfig(tokenConfig.totalDeposited, false)
+    await expectError("DepositCapExceeded")
+    await setTokenConfig(new BN(0), true)
+    await expectError("TokenPaused")
+    await setTokenConfig(new BN(0), false)
+  });
+
+  it("Is TVL priced per mint!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    const priceFeed = Keypair.generate().publicKey
+
+    const setTokenOracle = (priceFeed: PublicKey, depositCapUsd: number, borrowLtvBps: number) =>
+      program.methods.setTokenOracle(priceFeed, new BN(0), new BN(depositCapUsd), borrowLtvBps)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          tokenMint,
+          authority: provider.publicKey,
+        }).rpc()
+    const expectError = async (call: Promise<string>, code: string) => {
+      try {
+        await call
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+      }
+    }
+
+    await expectError(setTokenOracle(priceFeed, 0, 20_000), "InvalidBorrowLtv")
+    await expectError(setTokenOracle(PublicKey.default, 1, 0), "InvalidPriceFeed")
+
+    // priced, but the feed isn't passed
+    await setTokenOracle(priceFeed, 0, 5_000)
+    let tokenConfig = (await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo))
+      .tokenConfigs.find((config) => config.mint.equals(tokenMint))
+    assert.isTrue(tokenConfig.priceFeed.equals(priceFeed))
+    assert.equal(tokenConfig.borrowLtvBps, 5_000)
+    await expectError(
+      program.methods.totalValueLocked().accounts({ bankInfo: BANK_APP_ACCOUNTS.bankInfo }).rpc(),
+      "InvalidPriceFeed"
+    )
+
+    // a USD cap needs the feed on every deposit
+    await setTokenOracle(priceFeed, 1, 5_000)
+    await expectError(
+      program.methods.depositToken(new BN(1))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          tokenMint,
+          userAta: getAssociatedTokenAddressSync(tokenMint, provider.publicKey),
+          bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true),
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+          user: provider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        }).rpc(),
+      "InvalidPriceFeed"
+    )
+
+    // unpriced again, then no mint counts toward the TVL
+    await setTokenOracle(PublicKey.default, 0, 0)
+    const tvl = await program.methods.totalValueLocked().accounts({ bankInfo: BANK_APP_ACCOUNTS.bankInfo }).view()
+    console.log("TVL in micro-USD: ", tvl.toString())
+    assert.isTrue(tvl.eqn(0))
+  });
+
+  it("Is receipt interest shown by Token-2022!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    const receiptMint = findPda([idlSeed("RECEIPT_MINT_SEED"), tokenMint.toBuffer()])
+    const userReceiptAta = getAssociatedTokenAddressSync(receiptMint, provider.publicKey, false, TOKEN_2022_PROGRAM_ID)
+    const receiptAccounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      tokenMint,
+      receiptMint,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+    }
+
+    // the rate comes from the token's interest_rate_bps, a new receipt mint starts
+    // at it and sync_receipt_rate copies over later changes
+    const setTokenRate = (interestRateBps: number) => program.methods.setTokenConfig(interestRateBps, new BN(0), false)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        authority: provider.publicKey,
+      }).rpc()
+    const syncReceiptRate = () => program.methods.syncReceiptRate().accounts(receiptAccounts).rpc()
+
+    await setTokenRate(500)
+    if (await provider.connection.getAccountInfo(receiptMint) == null) {
+      const tx = await program.methods.createReceiptMint("Bank Receipt", "rBANK", "")
+        .accounts({
+          ...receiptAccounts,
+          authority: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      console.log("Create receipt mint signature: ", tx);
+
+      // the metadata is on the mint, behind a metadata pointer to itself
+      const metadata = await getTokenMetadata(provider.connection, receiptMint)
+      assert.equal(metadata.name, "Bank Receipt")
+      assert.equal(metadata.symbol, "rBANK")
+      assert.isTrue(metadata.updateAuthority.equals(BANK_APP_ACCOUNTS.bankVault))
+    } else {
+      await syncReceiptRate()
+    }
+
+    const receiptBalance = async () => (await getAccount(provider.connection, userReceiptAta, undefined, TOKEN_2022_PROGRAM_ID)).amount
+    const receiptsBefore = await provider.connection.getAccountInfo(userReceiptAta) == null ? BigInt(0) : await receiptBalance()
+
+    const tx = await program.methods.depositToken(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint,
+        userAta: getAssociatedTokenAddressSync(tokenMint, provider.publicKey),
+        bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true),
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        receiptMint,
+        userReceiptAta,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions([createAssociatedTokenAccountIdempotentInstruction(
+        provider.publicKey,
+        userReceiptAta,
+        provider.publicKey,
+        receiptMint,
+        TOKEN_2022_PROGRAM_ID
+      )]).rpc();
+    console.log("Deposit token with receipt signature: ", tx);
+
+    // receipts are minted 1:1, wallets add the interest when they display them
+    const receipts = await receiptBalance()
+    assert.equal((receipts - receiptsBefore).toString(), "1000000")
+    const mint = await getMint(provider.connection, receiptMint, undefined, TOKEN_2022_PROGRAM_ID)
+    assert.equal(getInterestBearingMintConfigState(mint).currentRate, 500)
+    const uiAmount = await amountToUiAmount(provider.connection, (provider.wallet as anchor.Wallet).payer, receiptMint, receipts, TOKEN_2022_PROGRAM_ID)
+    console.log(`Receipts: ${receipts} raw, ${uiAmount} with interest`)
+
+    await setTokenRate(0)
+    await syncReceiptRate()
+  });
+
+  it("Is denied wallet's receipt transfer refused by the hook!", async () => {
+    // receipt mints can't change their extensions, so a new token gets a hooked
+    // one. Each run adds a mint to BankInfo, like the test below
+    const tokenMint = Keypair.generate()
+    const userAta = getAssociatedTokenAddressSync(tokenMint.publicKey, provider.publicKey)
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.publicKey,
+        newAccountPubkey: tokenMint.publicKey,
+        space: MINT_SIZE,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(tokenMint.publicKey, 6, provider.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, tokenMint.publicKey),
+      createMintToInstruction(tokenMint.publicKey, userAta, provider.publicKey, 1_000_000),
+    ), [tokenMint])
+    await addSupportedToken(tokenMint.publicKey)
+
+    const receiptMint = findPda([idlSeed("RECEIPT_MINT_SEED"), tokenMint.publicKey.toBuffer()])
+    const userReceiptAta = getAssociatedTokenAddressSync(receiptMint, provider.publicKey, false, TOKEN_2022_PROGRAM_ID)
+    let tx = await program.methods.createReceiptMint("Bank Receipt", "rBANK", "")
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint: tokenMint.publicKey,
+        receiptMint,
+        authority: provider.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        transferHookProgram: receiptHookProgram.programId,
+      }).rpc();
+    console.log("Create hooked receipt mint signature: ", tx);
+
+    // the hook's side of the setup, Token-2022 reads this list on every transfer
+    tx = await receiptHookProgram.methods.initializeExtraAccountMetaList()
+      .accounts({
+        mint: receiptMint,
+        payer: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Initialize extra account metas signature: ", tx);
+
+    const bankAta = getAssociatedTokenAddressSync(tokenMint.publicKey, BANK_APP_ACCOUNTS.bankVault, true)
+    await program.methods.depositToken(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        tokenMint: tokenMint.publicKey,
+        userAta,
+        bankAta,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint.publicKey),
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        receiptMint,
+        userReceiptAta,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, bankAta, BANK_APP_ACCOUNTS.bankVault, tokenMint.publicKey),
+        createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userReceiptAta, provider.publicKey, receiptMint, TOKEN_2022_PROGRAM_ID),
+      ]).rpc()
+
+    // spl-token resolves the hook's extra accounts from the list
+    const friend = Keypair.generate().publicKey
+    const friendReceiptAta = getAssociatedTokenAddressSync(receiptMint, friend, false, TOKEN_2022_PROGRAM_ID)
+    const transferReceipts = async (amount: number) => provider.sendAndConfirm(new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, friendReceiptAta, friend, receiptMint, TOKEN_2022_PROGRAM_ID),
+      await createTransferCheckedWithTransferHookInstruction(
+        provider.connection,
+        userReceiptAta,
+        receiptMint,
+        friendReceiptAta,
+        provider.publicKey,
+        BigInt(amount),
+        6,
+        [],
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ))
+    const setDenied = (wallet: PublicKey, denied: boolean) => (denied ? program.methods.denyWallet(wallet) : program.methods.allowWallet(wallet))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        denyEntry: findPda([idlSeed("DENYLIST_SEED"), wallet.toBuffer()]),
+        authority: provider.publicKey,
+      }).rpc()
+
+    tx = await transferReceipts(400_000)
+    console.log("Receipt transfer signature: ", tx);
+
+    await setDenied(friend, true)
+    try {
+      await transferReceipts(400_000)
+      assert.fail("expected WalletDenied")
+    } catch (e) {
+      assert.isTrue(e.logs?.some((log: string) => log.includes("WalletDenied")), String(e))
+    }
+
+    await setDenied(friend, false)
+    await transferReceipts(400_000)
+    const friendReceipts = await getAccount(provider.connection, friendReceiptAta, undefined, TOKEN_2022_PROGRAM_ID)
+    assert.equal(friendReceipts.amount.toString(), "800000")
+  });
+
+  it("Is public balance of a confidential mint deposited!", async () => {
+    // a new Token-2022 mint with the given extensions and 1 token for the provider.
+    // Each run adds two mints to BankInfo, restart localnet with `--reset` once
+    // MAX_SUPPORTED_TOKENS is reached
+    const createToken2022Mint = async (extensions: ExtensionType[], initExtensions: (mint: PublicKey) => TransactionInstruction[]) => {
+      const mint = Keypair.generate()
+      const space = getMintLen(extensions)
+      const userAta = getAssociatedTokenAddressSync(mint.publicKey, provider.publicKey, false, TOKEN_2022_PROGRAM_ID)
+      await provider.sendAndConfirm(new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        ...initExtensions(mint.publicKey),
+        createInitializeMint2Instruction(mint.publicKey, 6, provider.publicKey, null, TOKEN_2022_PROGRAM_ID),
+        createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, mint.publicKey, TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(mint.publicKey, userAta, provider.publicKey, 1_000_000, [], TOKEN_2022_PROGRAM_ID),
+      ), [mint])
+      await addSupportedToken(mint.publicKey)
+      return mint.publicKey
+    }
+
+    const depositToken2022 = (tokenMint: PublicKey, amount: number) => {
+      const bankAta = getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true, TOKEN_2022_PROGRAM_ID)
+      return program.methods.depositToken2022(new BN(amount))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          tokenMint,
+          userAta: getAssociatedTokenAddressSync(tokenMint, provider.publicKey, false, TOKEN_2022_PROGRAM_ID),
+          bankAta,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+          user: provider.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        })
+        .preInstructions([createAssociatedTokenAccountIdempotentInstruction(
+          provider.publicKey,
+          bankAta,
+          BANK_APP_ACCOUNTS.bankVault,
+          tokenMint,
+          TOKEN_2022_PROGRAM_ID
+        )])
+    }
+
+    // spl-token has no builder for the confidential transfer extension: instruction
+    // 27 (ConfidentialTransferExtension), 0 (InitializeMint), then the authority,
+    // auto-approve and an empty auditor ElGamal key
+    const confidentialMint = await createToken2022Mint([ExtensionType.ConfidentialTransferMint], (mint) => [
+      new TransactionInstruction({
+        programId: TOKEN_2022_PROGRAM_ID,
+        keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+        data: Buffer.concat([Buffer.from([27, 0]), provider.publicKey.toBuffer(), Buffer.from([1]), Buffer.alloc(32)]),
+      })
+    ])
+
+    const tx = await depositToken2022(confidentialMint, 400_000).rpc();
+    console.log("Deposit token 2022 signature: ", tx);
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, confidentialMint))
+    assert.equal(userReserve.depositedAmount.toNumber(), 400_000)
+    const bankAta = await getAccount(provider.connection, getAssociatedTokenAddressSync(confidentialMint, BANK_APP_ACCOUNTS.bankVault, true, TOKEN_2022_PROGRAM_ID), undefined, TOKEN_2022_PROGRAM_ID)
+    assert.equal(bankAta.amount.toString(), "400000")
+
+    // a permanent delegate could pull the deposit back out of the bank ATA
+    const delegatedMint = await createToken2022Mint([ExtensionType.PermanentDelegate], (mint) => [
+      createInitializePermanentDelegateInstruction(mint, provider.publicKey, TOKEN_2022_PROGRAM_ID)
+    ])
+    try {
+      await depositToken2022(delegatedMint, 400_000).rpc()
+      assert.fail("expected UnsupportedMintExtension")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "UnsupportedMintExtension")
+    }
+  });
+
+  it("Is recurring deposit pulled when due!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //the same mint as above
+    let userAta = getAssociatedTokenAddressSync(tokenMint, provider.publicKey)
+    const recurringDeposit = BANK_APP_ACCOUNTS.recurringDeposit(provider.publicKey, tokenMint)
+    const amount = new BN(1_000_000)
+
+    // due right away, then once an hour
+    await program.methods.setupRecurring(amount, new BN(3600))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        recurringDeposit,
+        tokenMint,
+        userAta,
+        user: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const approved = await getAccount(provider.connection, userAta)
+    assert.isTrue(approved.delegate.equals(recurringDeposit))
+    assert.equal(approved.delegatedAmount, BigInt(amount.toNumber() * idlNumber("RECURRING_APPROVAL_PERIODS")))
+
+    // permissionless: `user` isn't a signer, the payer could be anyone
+    const execute = () => program.methods.executeRecurring()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        recurringDeposit,
+        user: provider.publicKey,
+        tokenMint,
+        userAta,
+        bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true),
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+        payer: provider.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId
+      })
+    const reserveBefore = (await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint))).depositedAmount
+    await execute().rpc()
+    const reserveAfter = (await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint))).depositedAmount
+    assert.isTrue(reserveAfter.sub(reserveBefore).eq(amount))
+    assert.equal((await getAccount(provider.connection, userAta)).delegatedAmount, approved.delegatedAmount - BigInt(amount.toNumber()))
+
+    try {
+      await execute().rpc()
+      assert.fail("expected RecurringNotDue")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "RecurringNotDue")
+    }
+  });
+
+  it("Is base asset rejected as swap input!", async () => {
+    // the base asset is credited with deposit_token, swapping it into itself is not a route
+    const bankAta = getAssociatedTokenAddressSync(NATIVE_MINT, BANK_APP_ACCOUNTS.bankVault, true)
+    const userAta = getAssociatedTokenAddressSync(NATIVE_MINT, provider.publicKey)
+    const WHIRLPOOL_PROGRAM_ID = new PublicKey("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc")
+
+    try {
+      await program.methods.depositAnyToken(new BN(1), Buffer.from([]))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          inputMint: NATIVE_MINT,
+          baseMint: NATIVE_MINT,
+          userAta,
+          bankAta,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, NATIVE_MINT),
+          inputPriceFeed: SystemProgram.programId,
+          basePriceFeed: SystemProgram.programId,
+          swapProgram: WHIRLPOOL_PROGRAM_ID,
+          user: provider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        })
+        .preInstructions([
+          createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, userAta, provider.publicKey, NATIVE_MINT),
+          createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, bankAta, BANK_APP_ACCOUNTS.bankVault, NATIVE_MINT),
+        ])
+        .rpc();
+      assert.fail("swapping the base asset should be rejected")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidSwapRoute")
+    }
+  });
+
+  it("Is a swap priced only by the configured feeds!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+    const priceFeed = Keypair.generate().publicKey
+    const bankAta = getAssociatedTokenAddressSync(NATIVE_MINT, BANK_APP_ACCOUNTS.bankVault, true)
+    const WHIRLPOOL_PROGRAM_ID = new PublicKey("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc")
+
+    await program.methods.setTokenOracle(priceFeed, new BN(0), new BN(0), 0)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        authority: provider.publicKey,
+      }).rpc()
+
+    // a feed the caller picked could agree with a skewed pool, only the mint's own is read
+    try {
+      await program.methods.depositAnyToken(new BN(1), Buffer.from([]))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          inputMint: tokenMint,
+          baseMint: NATIVE_MINT,
+          userAta: getAssociatedTokenAddressSync(tokenMint, provider.publicKey),
+          bankAta,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, NATIVE_MINT),
+          inputPriceFeed: Keypair.generate().publicKey,
+          basePriceFeed: Keypair.generate().publicKey,
+          swapProgram: WHIRLPOOL_PROGRAM_ID,
+          user: provider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        })
+        .preInstructions([
+          createAssociatedTokenAccountIdempotentInstruction(provider.publicKey, bankAta, BANK_APP_ACCOUNTS.bankVault, NATIVE_MINT),
+        ])
+        .rpc();
+      assert.fail("a feed that isn't the mint's should be rejected")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "InvalidPriceFeed")
+    }
+
+    await program.methods.setTokenOracle(PublicKey.default, new BN(0), new BN(0), 0)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        tokenMint,
+        authority: provider.publicKey,
+      }).rpc()
+  });
+
+  it("Is token CPI error translated!", async () => {
+    let tokenMint = new PublicKey("FBUoe8bLbPBh4VcF4jwg1L53XZBdSJoERry16u26UnNL") //you should put your token mint here
+
+    // the token program fails with InsufficientFunds (0x1), which anchor alone would
+    // surface as the bank's error code 1
+    try {
+      await program.methods.depositToken(new BN("18446744073709551615"))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          tokenMint,
+          userAta: getAssociatedTokenAddressSync(tokenMint, provider.publicKey),
+          bankAta: getAssociatedTokenAddressSync(tokenMint, BANK_APP_ACCOUNTS.bankVault, true),
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey, tokenMint),
+          user: provider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      assert.fail("depositing more than the balance should fail")
+    } catch (e) {
+      const cpiError = translateCpiError(e, program.programId, cpiPrograms(stakingProgram.programId))
+      console.log("Translated CPI error: ", cpiError)
+      assert.equal(cpiError?.kind, "TokenCpiFailed")
+      assert.equal(cpiError?.name, "InsufficientFunds")
+    }
+  });
+
+  it("Is snapshot taken!", async () => {
+    const { epoch } = await provider.connection.getEpochInfo()
+    const snapshot = BANK_APP_ACCOUNTS.snapshot(epoch)
+
+    if (await provider.connection.getAccountInfo(snapshot) == null) {
+      const tx = await program.methods.snapshot(new BN(epoch))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          snapshot,
+          payer: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      console.log("Snapshot signature: ", tx);
+    }
+
+    const snapshotInfo = await program.account.snapshot.fetch(snapshot)
+    console.log("Snapshot: ", {
+      epoch: snapshotInfo.epoch.toString(),
+      totalDeposited: snapshotInfo.totalDeposited.toString(),
+      vaultBalance: snapshotInfo.vaultBalance.toString(),
+    })
+  });
+
+  it("Is snapshot reward claimed!", async () => {
+    const { epoch } = await provider.connection.getEpochInfo()
+    const snapshot = BANK_APP_ACCOUNTS.snapshot(epoch)
+    const claimBitmap = BANK_APP_ACCOUNTS.claimBitmap(snapshot)
+
+    // two-leaf tree: the provider at index 0 and a random user at index 1
+    const amount = new BN(1_000)
+    const leaf = rewardLeaf(0, provider.publicKey, amount)
+    const sibling = rewardLeaf(1, Keypair.generate().publicKey, amount)
+    const root = hashPair(leaf, sibling)
+
+    if (await provider.connection.getAccountInfo(claimBitmap) != null) {
+      console.log("Rewards root already posted for epoch ", epoch)
+      return
+    }
+
+    const vaultBefore = await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault)
+    const postTx = await program.methods.postSnapshotRoot(new BN(epoch), Array.from(root), amount.muln(2), 2)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        snapshot,
+        claimBitmap,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Post snapshot root signature: ", postTx);
+    // the authority funds the rewards, claims don't touch deposits
+    assert.equal(await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault), vaultBefore + amount.muln(2).toNumber())
+
+    const claimTx = await program.methods.claimSnapshotReward(new BN(epoch), 0, amount, [Array.from(sibling)])
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        snapshot,
+        claimBitmap,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Claim snapshot reward signature: ", claimTx);
+
+    try {
+      await program.methods.claimSnapshotReward(new BN(epoch), 0, amount, [Array.from(sibling)])
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          snapshot,
+          claimBitmap,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc();
+      assert.fail("double claim should be rejected")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "RewardAlreadyClaimed")
+    }
+  });
+
+  it("Is reconciled in batches!", async () => {
+    // migrated SOL reserves only: v1 reserves are too short to decode, token
+    // reserves share the discriminator but not the address
+    const reserveAccounts = await provider.connection.getProgramAccounts(program.programId, {
+      filters: [{ memcmp: program.coder.accounts.memcmp("userReserve") }]
+    })
+    const reserves = reserveAccounts
+      .filter(({ account }) => account.data.length > 16)
+      .filter(({ pubkey, account }) => {
+        const reserve = program.coder.accounts.decode("userReserve", account.data)
+        return reserve.version > 0 && BANK_APP_ACCOUNTS.userReserve(reserve.owner).equals(pubkey)
+      })
+      .map(({ pubkey }) => pubkey)
+      .sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()))
+
+    await program.methods.startReconciliation(new BN(reserves.length))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        reconciliation: BANK_APP_ACCOUNTS.reconciliation,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+
+    // one transaction per batch, the cursor lives in the PDA in between
+    const batchSize = idlNumber("MAX_RECONCILE_BATCH")
+    for (let i = 0; i < reserves.length; i += batchSize) {
+      const tx = await program.methods.reconcileBatch()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          reconciliation: BANK_APP_ACCOUNTS.reconciliation,
+        })
+        .remainingAccounts(reserves.slice(i, i + batchSize).map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc();
+      console.log("Reconcile batch signature: ", tx);
+    }
+
+    const reconciliation = await program.account.reconciliation.fetch(BANK_APP_ACCOUNTS.reconciliation)
+    console.log("Reconciliation: ", {
+      processedReserves: reconciliation.processedReserves.toString(),
+      tally: reconciliation.tally.toString(),
+      isMatched: reconciliation.isMatched,
+    })
+    assert.isTrue(reconciliation.isDone)
+    assert.equal(reconciliation.processedReserves.toNumber(), reserves.length)
+  });
+
+  it("Is foreign emitter registered!", async () => {
+    // chain 10002 is Ethereum Sepolia, the emitter is the 32-byte left-padded contract address
+    const chain = 10002
+    const address = Buffer.alloc(32)
+    Buffer.from("00000000000000000000000000000000deadbeef", "hex").copy(address, 12)
+
+    const tx = await program.methods.registerForeignEmitter(chain, Array.from(address))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        foreignEmitter: BANK_APP_ACCOUNTS.foreignEmitter(chain),
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Register foreign emitter signature: ", tx);
+
+    const foreignEmitter = await program.account.foreignEmitter.fetch(BANK_APP_ACCOUNTS.foreignEmitter(chain))
+    assert.deepEqual(Buffer.from(foreignEmitter.address), address)
+  });
+
+  it("Is small deposit not broadcast!", async () => {
+    const wormholeSequence = findWormholePda([Buffer.from("Sequence"), BANK_APP_ACCOUNTS.wormholeEmitter.toBuffer()])
+    const sequenceInfo = await provider.connection.getAccountInfo(wormholeSequence)
+    const sequence = sequenceInfo == null ? new BN(0) : new BN(sequenceInfo.data.subarray(0, 8), "le")
+
+    try {
+      await program.methods.depositAndNotify(new BN(1_000_000))
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          wormholeBridge: findWormholePda([Buffer.from("Bridge")]),
+          wormholeMessage: BANK_APP_ACCOUNTS.wormholeMessage(sequence),
+          wormholeEmitter: BANK_APP_ACCOUNTS.wormholeEmitter,
+          wormholeSequence,
+          wormholeFeeCollector: findWormholePda([Buffer.from("fee_collector")]),
+          wormholeProgram: WORMHOLE_PROGRAM_ID,
+          user: provider.publicKey,
+        }).rpc();
+      assert.fail("deposits below the threshold should be rejected")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "DepositBelowThreshold")
+    }
+  });
+
+  it("Is milestone badge claimed!", async () => {
+    const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY")
+    const treeConfig = (merkleTree: PublicKey) => PublicKey.findProgramAddressSync([merkleTree.toBuffer()], BUBBLEGUM_PROGRAM_ID)[0]
+
+    let badgeConfig = await program.account.badgeConfig.fetchNullable(BANK_APP_ACCOUNTS.badgeConfig)
+    if (badgeConfig == null) {
+      // depth 14 holds 16k badges, the tree account is allocated by the client
+      const maxDepth = 14
+      const maxBufferSize = 64
+      const merkleTree = Keypair.generate()
+      const space = getConcurrentMerkleTreeAccountSize(maxDepth, maxBufferSize)
+
+      const tx = await program.methods.initializeBadgeTree(maxDepth, maxBufferSize)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          badgeConfig: BANK_APP_ACCOUNTS.badgeConfig,
+          treeAuthority: BANK_APP_ACCOUNTS.badgeTreeAuthority,
+          treeConfig: treeConfig(merkleTree.publicKey),
+          merkleTree: merkleTree.publicKey,
+          authority: provider.publicKey,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+        })
+        .preInstructions([SystemProgram.createAccount({
+          fromPubkey: provider.publicKey,
+          newAccountPubkey: merkleTree.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          space,
+          programId: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        })])
+        .signers([merkleTree])
+        .rpc();
+      console.log("Initialize badge tree signature: ", tx);
+
+      badgeConfig = await program.account.badgeConfig.fetch(BANK_APP_ACCOUNTS.badgeConfig)
+    }
+
+    const userReserve = await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(provider.publicKey))
+    const claim = () => program.methods.claimMilestoneBadge(0)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        badgeConfig: BANK_APP_ACCOUNTS.badgeConfig,
+        milestoneBadges: BANK_APP_ACCOUNTS.milestoneBadges(provider.publicKey),
+        treeAuthority: BANK_APP_ACCOUNTS.badgeTreeAuthority,
+        treeConfig: treeConfig(badgeConfig.merkleTree),
+        merkleTree: badgeConfig.merkleTree,
+        user: provider.publicKey,
+        logWrapper: SPL_NOOP_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      })
+
+    if (userReserve.depositedAmount.lt(new BN(1_000_000_000))) {
+      try {
+        await claim().rpc()
+        assert.fail("the 1 SOL badge needs 1 SOL deposited")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "MilestoneNotReached")
+      }
+      return
+    }
+
+    const badges = await program.account.milestoneBadges.fetchNullable(BANK_APP_ACCOUNTS.milestoneBadges(provider.publicKey))
+    if (badges == null || (badges.minted & 1) == 0) {
+      const tx = await claim().rpc();
+      console.log("Claim milestone badge signature: ", tx);
+    }
+  });
+
+  it("Is vault SOL invested in the stake pool and divested!", async () => {
+    // Jito's pool only exists on localnet when cloned from mainnet, see the README
+    const STAKE_POOL_PROGRAM_ID = new PublicKey("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy")
+    const stakePool = new PublicKey("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb")
+    const pool = await provider.connection.getAccountInfo(stakePool)
+    if (pool == null) {
+      console.log("Stake pool not found, skipped")
+      return
+    }
+
+    // the fixed head of StakePool, same offsets as stake_pool.rs
+    const poolKey = (offset: number) => new PublicKey(pool.data.subarray(offset, offset + 32))
+    const poolMint = poolKey(162)
+    const bankPoolAta = getAssociatedTokenAddressSync(poolMint, BANK_APP_ACCOUNTS.bankVault, true)
+    const poolTokens = async () => (await provider.connection.getAccountInfo(bankPoolAta)) == null ? BigInt(0) : (await getAccount(provider.connection, bankPoolAta)).amount
+    const stakePoolAccounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      stakePool,
+      stakePoolWithdrawAuthority: PublicKey.createProgramAddressSync([stakePool.toBuffer(), Buffer.from("withdraw"), Buffer.from([pool.data[97]])], STAKE_POOL_PROGRAM_ID),
+      reserveStake: poolKey(130),
+      managerFeeAccount: poolKey(194),
+      poolMint,
+      bankPoolAta,
+      stakePoolProgram: STAKE_POOL_PROGRAM_ID,
+      authority: provider.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+
+    const before = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    const poolTokensBefore = await poolTokens()
+    let tx = await program.methods.investStakePool(new BN(1_000_000))
+      .accounts({ ...stakePoolAccounts, systemProgram: SystemProgram.programId }).rpc();
+    console.log("Invest in stake pool signature: ", tx);
+
+    const invested = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    assert.equal(invested.stakePoolPrincipal.sub(before.stakePoolPrincipal).toNumber(), 1_000_000)
+    const minted = (await poolTokens()) - poolTokensBefore
+    assert.isTrue(minted > BigInt(0))
+
+    // the tokens just minted can't be worth twice what was put in
+    const divest = (minAmountOut: number) => program.methods.divestStakePool(new BN(minted.toString()), new BN(minAmountOut))
+      .accounts({
+        ...stakePoolAccounts,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        stakeProgram: StakeProgram.programId,
+      }).rpc()
+    try {
+      await divest(2_000_000)
+      assert.fail("expected SlippageExceeded")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "SlippageExceeded")
+    }
+
+    const vaultBefore = await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault)
+    tx = await divest(0);
+    console.log("Divest from stake pool signature: ", tx);
+    assert.equal(await poolTokens(), poolTokensBefore)
+    assert.isTrue((await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault)) > vaultBefore)
+    const divested = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    assert.isTrue(divested.stakePoolPrincipal.lt(invested.stakePoolPrincipal))
+  });
+
+  it("Is vault checked!", async () => {
+    const expectError = async (vault: PublicKey, code: string) => {
+      try {
+        await program.methods.checkVault().accounts({ vault }).rpc();
+        assert.fail(`expected ${code}`)
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, code)
+      }
+    }
+
+    await expectError(Keypair.generate().publicKey, "VaultOnCurve")
+    await expectError(BANK_APP_ACCOUNTS.bankInfo, "InvalidVault")
+
+    const tx = await program.methods.checkVault()
+      .accounts({ vault: BANK_APP_ACCOUNTS.bankVault })
+      .rpc();
+    console.log("Check vault signature: ", tx);
+  });
+
+  it("Is a destructive action gated by M-of-N approvals!", async () => {
+    // deterministic approvers, so a rerun on the same validator finds the same set
+    const { alice, bob, attacker } = testIds()
+    const approvers = [provider.publicKey, alice.publicKey, bob.publicKey]
+    const setApprovers = () => program.methods.setApprovers(approvers, 2, new BN(150))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        approvalSet: BANK_APP_ACCOUNTS.approvalSet,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const approve = (approver: Keypair | null, action: object) => program.methods.approveAction(action as any)
+      .accounts({
+        approvalSet: BANK_APP_ACCOUNTS.approvalSet,
+        approver: approver?.publicKey ?? provider.publicKey,
+      }).signers(approver == null ? [] : [approver]).rpc()
+
+    // the first set needs only the authority
+    if (await provider.connection.getAccountInfo(BANK_APP_ACCOUNTS.approvalSet) == null) {
+      const tx = await setApprovers()
+      console.log("Set approvers signature: ", tx);
+    }
+
+    try {
+      await approve(attacker, { coverShortfall: {} })
+      assert.fail("expected NotAnApprover")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "NotAnApprover")
+    }
+
+    // from then on a change needs 2 of the 3 approvers
+    await approve(null, { setApprovers: {} })
+    try {
+      await setApprovers()
+      assert.fail("expected ApprovalThresholdNotMet")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "ApprovalThresholdNotMet")
+    }
+
+    await approve(alice, { setApprovers: {} })
+    const tx = await setApprovers()
+    console.log("Set approvers with approvals signature: ", tx);
+
+    const approvalSet = await program.account.approvalSet.fetch(BANK_APP_ACCOUNTS.approvalSet)
+    assert.equal(approvalSet.threshold, 2)
+    assert.equal(approvalSet.approverCount, 3)
+    // the approvals were used up
+    assert.isTrue(approvalSet.approvedAt.flat().every((slot) => slot.isZero()))
+  });
+
+  it("Is insurance fund only paid out on a shortfall!", async () => {
+    const [stakingInfo] = PublicKey.findProgramAddressSync([Buffer.from("USER_INFO"), BANK_APP_ACCOUNTS.bankVault.toBuffer()], stakingProgram.programId)
+    const invariantAccounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      stakingInfo,
+    }
+
+    const fundTx = await program.methods.fundInsurance(new BN(1_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        insuranceFund: BANK_APP_ACCOUNTS.insuranceFund,
+        funder: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc();
+    console.log("Fund insurance signature: ", fundTx);
+
+    // simulated, the report comes back as return data
+    const report = await program.methods.verifyInvariants().accounts(invariantAccounts).view()
+    console.log("Invariant report: ", report)
+    assert.equal(report.shortfall.toNumber(), 0)
+
+    try {
+      await program.methods.coverShortfall()
+        .accounts({
+          ...invariantAccounts,
+          insuranceFund: BANK_APP_ACCOUNTS.insuranceFund,
+          approvalSet: BANK_APP_ACCOUNTS.approvalSet,
+          authority: provider.publicKey,
+        }).rpc();
+      assert.fail("expected NoShortfall")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "NoShortfall")
+    }
+  });
+
+  it("Does user vault execute only allowed programs!", async () => {
+    await program.methods.setCpiAllowlist([SystemProgram.programId])
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        cpiAllowlist: findPda([idlSeed("CPI_ALLOWLIST_SEED")]),
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    // a user vault is a plain system account, funding it is a transfer
+    const userVault = findPda([idlSeed("USER_VAULT_SEED"), provider.publicKey.toBuffer()])
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: userVault,
+      lamports: 10_000_000,
+    })))
+
+    // any instruction of an allowed program, the vault's signature is added onchain
+    const execute = (ix: TransactionInstruction) => program.methods.execute(ix.data)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        cpiAllowlist: findPda([idlSeed("CPI_ALLOWLIST_SEED")]),
+        userVault,
+        targetProgram: ix.programId,
+        user: provider.publicKey,
+      })
+      .remainingAccounts(ix.keys.map((key) => ({ ...key, isSigner: false })))
+      .rpc()
+
+    const vaultBefore = await provider.connection.getBalance(userVault)
+    const tx = await execute(SystemProgram.transfer({
+      fromPubkey: userVault,
+      toPubkey: provider.publicKey,
+      lamports: 1_000_000,
+    }))
+    console.log("Execute from user vault signature: ", tx);
+    assert.equal(vaultBefore - await provider.connection.getBalance(userVault), 1_000_000)
+
+    // staking from the vault works the same way once the staking app is allowed
+    try {
+      await execute(new TransactionInstruction({ programId: stakingProgram.programId, keys: [], data: Buffer.alloc(8) }))
+      assert.fail("expected ProgramNotAllowed")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "ProgramNotAllowed")
+    }
+  });
+
+  it("Is dry run rolled back!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const accounts = {
+      bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+      bankVault: BANK_APP_ACCOUNTS.bankVault,
+      userReserve,
+      user: provider.publicKey,
+      systemProgram: SystemProgram.programId
+    }
+    const eventParser = new anchor.EventParser(program.programId, program.coder)
+
+    // a dry run always fails with DryRunOk, the would-be outcome is the event in its logs
+    const dryRun = async (simulate: () => Promise<unknown>) => {
+      let logs: string[] = []
+      try {
+        await simulate()
+      } catch (e) {
+        logs = e.simulationResponse?.logs ?? e.logs ?? []
+      }
+      assert.isTrue(logs.some((log) => log.includes("DryRunOk")), "expected DryRunOk")
+      return [...eventParser.parseLogs(logs)][0]
+    }
+
+    const before = await program.account.userReserve.fetch(userReserve)
+
+    const deposited = await dryRun(() => program.methods.deposit({ v4: { amount: new BN(1_000_000), deadline: null, opId: null, dryRun: true } })
+      .accounts({ ...accounts, opRecord: null, campaign: null, campaignMatch: null })
+      .simulate())
+    console.log("Deposit dry run: ", deposited)
+    assert.equal(deposited.name, "depositDryRun")
+    assert.equal(deposited.data.depositedAmount.toString(), before.depositedAmount.add(new BN(1_000_000)).toString())
+
+    const withdrawn = await dryRun(() => program.methods.withdraw(new BN(100_000), { dryRun: true })
+      .accounts(accounts)
+      .simulate())
+    console.log("Withdraw dry run: ", withdrawn)
+    assert.equal(withdrawn.name, "withdrawDryRun")
+    assert.equal(withdrawn.data.depositedAmount.toString(), before.depositedAmount.sub(new BN(100_000)).toString())
+
+    // neither was applied
+    const after = await program.account.userReserve.fetch(userReserve)
+    assert.equal(after.depositedAmount.toString(), before.depositedAmount.toString())
+  });
+
+  it("Has program info!", async () => {
+    // simulated, the version comes back as return data
+    const info = await program.methods.programInfo().view()
+    console.log("Program info: ", info)
+    assert.equal(info.version, "0.1.0")
+    assert.match(info.gitCommit, /^([0-9a-f]{40}|unknown)$/)
+  });
+
+  it("Can the guardian only pause!", async () => {
+    const guardian = Keypair.generate()
+    await program.methods.setGuardian(guardian.publicKey)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: provider.publicKey,
+      }).rpc()
+
+    const guardianPause = (signer: PublicKey) => program.methods.guardianSetPaused()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        guardian: signer,
+      })
+    const unpause = (signer: PublicKey) => program.methods.unpause()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: signer,
+      })
+    const expectConstraintAddress = async (call: Promise<string>) => {
+      try {
+        await call
+        assert.fail("expected ConstraintAddress")
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "ConstraintAddress")
+      }
+    }
+
+    // the authority can't use the guardian's instruction
+    await expectConstraintAddress(guardianPause(provider.publicKey).rpc())
+
+    await guardianPause(guardian.publicKey).signers([guardian]).rpc()
+    assert.isTrue((await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)).isPaused)
+
+    // and the guardian can't use the authority's
+    await expectConstraintAddress(program.methods.setRentDestination(guardian.publicKey)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: guardian.publicKey,
+      }).signers([guardian]).rpc())
+    await expectConstraintAddress(program.methods.setGuardian(guardian.publicKey)
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        authority: guardian.publicKey,
+      }).signers([guardian]).rpc())
+
+    // only the authority unpauses
+    await expectConstraintAddress(unpause(guardian.publicKey).signers([guardian]).rpc())
+    await unpause(provider.publicKey).rpc()
+    assert.isFalse((await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)).isPaused)
+  });
+
+  it("Does joint withdrawal need both owners!", async () => {
+    // the owners are sorted so a pair has one address
+    const coOwner = Keypair.generate()
+    const [ownerA, ownerB] = [provider.publicKey, coOwner.publicKey]
+      .sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()))
+    const jointReserve = findPda([idlSeed("JOINT_RESERVE_SEED"), ownerA.toBuffer(), ownerB.toBuffer()])
+
+    await program.methods.createJointReserve(new BN(1_000_000))
+      .accounts({
+        jointReserve,
+        ownerA,
+        ownerB,
+        payer: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).signers([coOwner]).rpc()
+    await program.methods.depositJoint(new BN(3_000_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        jointReserve,
+        owner: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    const withdraw = (amount: number, coOwnerKey: PublicKey | null) => program.methods.withdrawJoint(new BN(amount))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        jointReserve,
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        owner: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+        coOwner: coOwnerKey,
+      })
+
+    // up to the limit one owner is enough
+    await withdraw(1_000_000, null).rpc()
+    try {
+      await withdraw(2_000_000, null).rpc()
+      assert.fail("expected CoSignerRequired")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "CoSignerRequired")
+    }
+    await withdraw(2_000_000, coOwner.publicKey).signers([coOwner]).rpc()
+
+    assert.equal((await program.account.jointReserve.fetch(jointReserve)).depositedAmount.toNumber(), 0)
+  });
+
+  it("Is reserve inherited after inactivity!", async () => {
+    // a throwaway owner, the beneficiary takes its whole reserve
+    const owner = Keypair.generate()
+    await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({
+      fromPubkey: provider.publicKey,
+      toPubkey: owner.publicKey,
+      lamports: 50_000_000,
+    })))
+    await program.methods.deposit({ v1: { amount: new BN(10_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(owner.publicKey),
+        user: owner.publicKey,
+        systemProgram: SystemProgram.programId
+      }).signers([owner]).rpc()
+
+    const inheritance = findPda([idlSeed("INHERITANCE_SEED"), owner.publicKey.toBuffer()])
+    await program.methods.setBeneficiary(provider.publicKey, new BN(2))
+      .accounts({
+        inheritance,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId
+      }).signers([owner]).rpc()
+
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    const claim = (amount: number) => program.methods.claimInheritance(new BN(amount))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        inheritance,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(owner.publicKey),
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(owner.publicKey),
+        owner: owner.publicKey,
+        rentDestination: bankInfo.rentDestination.equals(PublicKey.default) ? owner.publicKey : bankInfo.rentDestination,
+        beneficiary: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    // a check-in restarts the timer
+    await program.methods.checkIn().accounts({ inheritance, owner: owner.publicKey }).signers([owner]).rpc()
+    try {
+      await claim(10_000_000)
+      assert.fail("expected OwnerStillActive")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "OwnerStillActive")
+    }
+
+    const { lastActivityTs, inactivityPeriod } = await program.account.inheritance.fetch(inheritance)
+    const claimableAt = lastActivityTs.add(inactivityPeriod).toNumber()
+    while ((await provider.connection.getBlockTime(await provider.connection.getSlot())) < claimableAt) {
+      await new Promise((resolve) => setTimeout(resolve, 500))
+    }
+    // the switch stays until the reserve is empty
+    await claim(4_000_000)
+    assert.isNotNull(await program.account.inheritance.fetchNullable(inheritance))
+    await claim(6_000_000)
+
+    assert.equal((await program.account.userReserve.fetch(BANK_APP_ACCOUNTS.userReserve(owner.publicKey))).depositedAmount.toNumber(), 0)
+    assert.isNull(await program.account.inheritance.fetchNullable(inheritance))
+  });
+
+  it("Is version asserted!", async () => {
+    // banks initialized before the beacon existed get one here
+    await program.methods.syncVersionBeacon()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        versionBeacon: BANK_APP_ACCOUNTS.versionBeacon,
+        authority: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    const beacon = await program.account.versionBeacon.fetch(BANK_APP_ACCOUNTS.versionBeacon)
+    assert.equal(beacon.version, (0 << 16) | (1 << 8) | 0)
+    // BankInfo carries the same copy
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    assert.equal(bankInfo.version, beacon.version)
+    assert.isTrue(bankInfo.features.eq(beacon.features))
+
+    // the guard goes first, the deposit only lands if it passes
+    const required = new BN(idlNumber("FEATURE_DEPOSIT_OP_ID") | idlNumber("FEATURE_OPS_QUEUE"))
+    const deposit = () => program.methods.deposit({ v1: { amount: new BN(1_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+    await deposit()
+      .preInstructions([await program.methods.assertVersion(beacon.version, required).instruction()])
+      .rpc()
+
+    // a client built for 1.0.0 is refused before the deposit runs
+    try {
+      await deposit()
+        .preInstructions([await program.methods.assertVersion(1 << 16, required).instruction()])
+        .rpc()
+      assert.fail("expected VersionMismatch")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "VersionMismatch")
+    }
+
+    // or as the deposit's own trailing argument, no guard instruction
+    try {
+      await program.methods.deposit({ v5: { amount: new BN(1_000_000), deadline: null, opId: null, dryRun: false, expectedVersion: 1 << 16 } })
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+      assert.fail("expected VersionMismatch")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "VersionMismatch")
+    }
+  });
+
+  it("Is slothash lucky draw grindable!", async () => {
+    const luckyTicket = BANK_APP_ACCOUNTS.slothashTicket(provider.publicKey)
+    const { epoch } = await provider.connection.getEpochInfo()
+    const ticket = await program.account.luckyTicket.fetchNullable(luckyTicket)
+    if (ticket != null && ticket.nextDrawEpoch.toNumber() > epoch) {
+      console.log("Slothash draw already entered this epoch")
+      return
+    }
+
+    const draw = () => program.methods.luckyDrawSlothash()
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        luckyTicket,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+
+    // the attacker never pays for a losing draw: simulate against the current
+    // slot hash and only send once the simulated outcome wins
+    // the odds are 1 in 10 per slot hash, so 200 tries missing every time is
+    // far less likely than a flaky network
+    const MAX_SIMULATIONS = 200
+    let simulations = 0
+    let won = false
+    while (!won && simulations < MAX_SIMULATIONS) {
+      const { raw } = await draw().simulate()
+      won = raw.some((log) => log.includes("lucky depositor!"))
+      simulations++
+    }
+    console.log(`Simulations until a winning slot hash: ${simulations} (odds are 1 in 10)`)
+    assert.isTrue(won, `no winning slot hash in ${MAX_SIMULATIONS} simulations`)
+
+    const tx = await draw().rpc();
+    console.log("Ground lucky draw signature: ", tx);
+  });
+
+  it("Is VRF lucky draw settled!", async function () {
+    // Switchboard's on-demand queue only exists on devnet
+    if (!provider.connection.rpcEndpoint.includes("devnet")) {
+      console.log("VRF lucky draw needs devnet, skipped")
+      this.skip()
+    }
+
+    const luckyTicket = BANK_APP_ACCOUNTS.luckyTicket(provider.publicKey)
+    const { epoch } = await provider.connection.getEpochInfo()
+    const ticket = await program.account.luckyTicket.fetchNullable(luckyTicket)
+    if (ticket != null && ticket.nextDrawEpoch.toNumber() > epoch) {
+      console.log("Lucky draw already entered this epoch")
+      return
+    }
+
+    const sbProgramId = await sb.getProgramId(provider.connection)
+    const sbProgram = new anchor.Program(await anchor.Program.fetchIdl(sbProgramId, provider), provider)
+    const queue = sb.ON_DEMAND_DEVNET_QUEUE
+
+    const randomnessKeypair = Keypair.generate()
+    const [randomness, createIx] = await sb.Randomness.create(sbProgram, randomnessKeypair, queue)
+    await provider.sendAndConfirm(new Transaction().add(createIx), [randomnessKeypair])
+
+    // commit: the value is fixed now but nobody can read it yet
+    const commitTx = await provider.sendAndConfirm(new Transaction().add(
+      await randomness.commitIx(queue),
+      await program.methods.commitLuckyDraw()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+          luckyTicket,
+          randomnessAccountData: randomness.pubkey,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).instruction()
+    ))
+    console.log("Commit lucky draw signature: ", commitTx);
+
+    // reveal + settle: simulating first no longer helps, the outcome was
+    // decided at commit time
+    const settleTx = await provider.sendAndConfirm(new Transaction().add(
+      await randomness.revealIx(),
+      await program.methods.settleLuckyDraw()
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          luckyTicket,
+          randomnessAccountData: randomness.pubkey,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).instruction()
+    ))
+    console.log("Settle lucky draw signature: ", settleTx);
+  });
+
+  it("Is rent topped up only where it's short!", async () => {
+    const topUpRent = (target: PublicKey) => program.methods.topUpRent()
+      .accounts({
+        target,
+        payer: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    // every realloc so far was paid for, so BankInfo has nothing to top up
+    try {
+      await topUpRent(BANK_APP_ACCOUNTS.bankInfo)
+      assert.fail("expected NoRentShortfall")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "NoRentShortfall")
+    }
+
+    // the vault is owned by the system program, not the bank
+    try {
+      await topUpRent(BANK_APP_ACCOUNTS.bankVault)
+      assert.fail("expected NotProgramOwned")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "NotProgramOwned")
+    }
+  });
+
+  it("Is time-weighted balance accumulated!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const deposit = () => program.methods.deposit({ v1: { amount: new BN(1_000_000) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+
+    await deposit()
+    const reserveBefore = await program.account.userReserve.fetch(userReserve)
+    const bankBefore = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    await new Promise((resolve) => setTimeout(resolve, 2_000))
+    await deposit()
+    const reserveAfter = await program.account.userReserve.fetch(userReserve)
+    const bankAfter = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+
+    // the second deposit first added the balance held since the first one
+    const seconds = reserveAfter.balanceSeconds.updatedAt.sub(reserveBefore.balanceSeconds.updatedAt)
+    assert.isTrue(seconds.gtn(0))
+    assert.equal(
+      reserveAfter.balanceSeconds.balanceSeconds.sub(reserveBefore.balanceSeconds.balanceSeconds).toString(),
+      reserveBefore.depositedAmount.mul(seconds).toString()
+    )
+    const totalSeconds = bankAfter.totalBalanceSeconds.updatedAt.sub(bankBefore.totalBalanceSeconds.updatedAt)
+    assert.equal(
+      bankAfter.totalBalanceSeconds.balanceSeconds.sub(bankBefore.totalBalanceSeconds.balanceSeconds).toString(),
+      bankBefore.totalDeposited.mul(totalSeconds).toString()
+    )
+  });
+  it("Is the vault kept above its rent floor!", async () => {
+    const bankInfo = await program.account.bankInfo.fetch(BANK_APP_ACCOUNTS.bankInfo)
+    const rentFloor = await provider.connection.getMinimumBalanceForRentExemption(0)
+    assert.equal(bankInfo.rentFloor.toNumber(), rentFloor)
+
+    // staking one lamport more than the vault holds above the floor would eat into it
+    const vaultLamports = await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault)
+    const [stakingVault] = PublicKey.findProgramAddressSync([Buffer.from("STAKING_VAULT")], stakingProgram.programId)
+    const [stakingInfo] = PublicKey.findProgramAddressSync([Buffer.from("USER_INFO"), BANK_APP_ACCOUNTS.bankVault.toBuffer()], stakingProgram.programId)
+    try {
+      await program.methods.invest(new BN(vaultLamports - rentFloor + 1), true)
+        .accounts({
+          bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+          bankVault: BANK_APP_ACCOUNTS.bankVault,
+          stakingVault,
+          stakingInfo,
+          stakingProgram: stakingProgram.programId,
+          authority: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+      assert.fail("expected VaultBelowRentFloor")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "VaultBelowRentFloor")
+    }
+    assert.equal(await provider.connection.getBalance(BANK_APP_ACCOUNTS.bankVault), vaultLamports)
+  });
+  it("Is history kept in a ring buffer!", async () => {
+    const userHistory = BANK_APP_ACCOUNTS.userHistory(provider.publicKey)
+    if (await provider.connection.getAccountInfo(userHistory) == null) {
+      await program.methods.initHistory()
+        .accounts({
+          userHistory,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId
+        }).rpc()
+    }
+    await program.methods.clearHistory()
+      .accounts({ userHistory, user: provider.publicKey }).rpc()
+
+    const capacity = (await program.account.userHistory.fetch(userHistory)).entries.length
+    assert.equal((await provider.connection.getAccountInfo(userHistory)).data.length, program.account.userHistory.size)
+
+    const deposit = (amount: number) => program.methods.deposit({ v1: { amount: new BN(amount) } })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+        userHistory
+      }).instruction()
+    // one more than fits, so the first one is overwritten
+    for (let i = 0; i <= capacity; i += 8) {
+      const ixs = await Promise.all([...Array(Math.min(8, capacity + 1 - i)).keys()].map((j) => deposit(1_000 + i + j)))
+      await provider.sendAndConfirm(new Transaction().add(...ixs))
+    }
+    await program.methods.withdraw(new BN(500), { dryRun: false })
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve: BANK_APP_ACCOUNTS.userReserve(provider.publicKey),
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        systemProgram: SystemProgram.programId,
+        userHistory
+      }).rpc()
+
+    const history = await program.account.userHistory.fetch(userHistory)
+    assert.isTrue(history.user.equals(provider.publicKey))
+    assert.equal(history.count.toNumber(), capacity + 2)
+    // the oldest two deposits are gone, the withdrawal took the slot after the newest deposit
+    const newest = history.entries[(capacity + 1) % capacity]
+    assert.equal(newest.kind, idlNumber("HISTORY_KIND_WITHDRAW"))
+    assert.equal(newest.amount.toNumber(), 500)
+    const oldest = history.entries[(capacity + 2) % capacity]
+    assert.equal(oldest.kind, idlNumber("HISTORY_KIND_DEPOSIT"))
+    assert.equal(oldest.amount.toNumber(), 1_002)
+
+    await program.methods.clearHistory()
+      .accounts({ userHistory, user: provider.publicKey }).rpc()
+    const cleared = await program.account.userHistory.fetch(userHistory)
+    assert.equal(cleared.count.toNumber(), 0)
+    assert.isTrue(cleared.entries.every((entry) => entry.amount.isZero()))
+  });
+  it("Is the bank callable through CPI!", async () => {
+    const userReserve = BANK_APP_ACCOUNTS.userReserve(provider.publicKey)
+    const before = (await program.account.userReserve.fetch(userReserve)).depositedAmount
+
+    await bankCallerProgram.methods.deposit(new BN(3_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        user: provider.publicKey,
+        bankProgram: program.programId,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    assert.equal((await program.account.userReserve.fetch(userReserve)).depositedAmount.toNumber(), before.toNumber() + 3_000)
+
+    await bankCallerProgram.methods.withdraw(new BN(1_000))
+      .accounts({
+        bankInfo: BANK_APP_ACCOUNTS.bankInfo,
+        bankVault: BANK_APP_ACCOUNTS.bankVault,
+        userReserve,
+        withdrawalWindow: BANK_APP_ACCOUNTS.withdrawalWindow(provider.publicKey),
+        user: provider.publicKey,
+        bankProgram: program.programId,
+        systemProgram: SystemProgram.programId
+      }).rpc()
+    assert.equal((await program.account.userReserve.fetch(userReserve)).depositedAmount.toNumber(), before.toNumber() + 2_000)
   });
 });
@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { join } from "path";
+import { CuReporter } from "../app/cu-report";
+
+// CU_REPORT=1 anchor test writes reports/cu-report.json. These are root hooks,
+// so they wrap every test file, whichever order mocha loads them in
+if (process.env.CU_REPORT) {
+  let reporter: CuReporter
+
+  before(() => {
+    const programs: Program[] = [
+      anchor.workspace.BankApp,
+      anchor.workspace.FaucetApp,
+      anchor.workspace.MemoryLesson,
+      anchor.workspace.ReceiptHook,
+      anchor.workspace.StakingApp,
+    ]
+    reporter = new CuReporter(programs)
+    reporter.install()
+  })
+
+  after(() => {
+    const path = join(__dirname, "../reports/cu-report.json")
+    reporter.write(path)
+    console.log("CU report written to ", path)
+  })
+}
@@ -0,0 +1,64 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { FaucetApp } from "../target/types/faucet_app";
+import { PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("faucet-app", () => {
+  const provider = anchor.AnchorProvider.env()
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.FaucetApp as Program<FaucetApp>;
+
+  const faucetConfig = PublicKey.findProgramAddressSync([Buffer.from("FAUCET_CONFIG")], program.programId)[0]
+  const faucetMint = PublicKey.findProgramAddressSync([Buffer.from("FAUCET_MINT")], program.programId)[0]
+  const dripAmount = new BN(1_000_000_000_000) // 1000 tokens
+
+  it("Is faucet initialized!", async () => {
+    if (await provider.connection.getAccountInfo(faucetConfig) != null) {
+      return
+    }
+
+    const tx = await program.methods.initialize(dripAmount, new BN(3600))
+      .accounts({
+        authority: provider.publicKey,
+      }).rpc();
+    console.log("Initialize faucet signature: ", tx);
+  });
+
+  it("Is dripped once per cooldown!", async () => {
+    const userAta = getAssociatedTokenAddressSync(faucetMint, provider.publicKey)
+    const before = await provider.connection.getTokenAccountBalance(userAta)
+      .then((balance) => new BN(balance.value.amount))
+      .catch(() => new BN(0))
+
+    try {
+      const tx = await program.methods.drip()
+        .accounts({
+          mint: faucetMint,
+          user: provider.publicKey,
+        }).rpc();
+      console.log("Drip signature: ", tx);
+    } catch (e) {
+      // a rerun on the same validator is still inside the cooldown of the last run
+      assert.equal(e.error?.errorCode?.code, "CooldownActive")
+      return
+    }
+
+    const after = await provider.connection.getTokenAccountBalance(userAta)
+    assert.equal(new BN(after.value.amount).sub(before).toString(), dripAmount.toString())
+
+    try {
+      await program.methods.drip()
+        .accounts({
+          mint: faucetMint,
+          user: provider.publicKey,
+        }).rpc();
+      assert.fail("a second drip within the cooldown must fail")
+    } catch (e) {
+      assert.equal(e.error?.errorCode?.code, "CooldownActive")
+    }
+  });
+});
@@ -0,0 +1,107 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MemoryLesson } from "../target/types/memory_lesson";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+describe("memory-lesson", () => {
+  const provider = anchor.AnchorProvider.env()
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MemoryLesson as Program<MemoryLesson>;
+
+  const findPda = (seed: string, index: number) =>
+    PublicKey.findProgramAddressSync([Buffer.from(seed), Buffer.from([index])], program.programId)[0]
+  const INDEXES = [0, 1, 2, 3, 4, 5]
+  const bigStates = INDEXES.map((index) => findPda("BIG_STATE", index))
+  const bigZeroCopies = INDEXES.map((index) => findPda("BIG_ZERO_COPY", index))
+
+  const bigAccounts = (addresses: PublicKey[]) => Object.fromEntries(
+    addresses.map((address, index) => [`big${index}`, address])
+  )
+
+  before(async () => {
+    for (const index of INDEXES) {
+      if (await provider.connection.getAccountInfo(bigStates[index]) == null) {
+        await program.methods.initBigState(index).accounts({ payer: provider.publicKey }).rpc()
+      }
+      if (await provider.connection.getAccountInfo(bigZeroCopies[index]) == null) {
+        await program.methods.initBigZeroCopy(index).accounts({ payer: provider.publicKey }).rpc()
+      }
+    }
+  });
+
+  it("Is stack overflowed by six unboxed accounts!", async () => {
+    try {
+      await program.methods.overflow().accounts(bigAccounts(bigStates)).rpc()
+      assert.fail("six unboxed BigState accounts must not fit in a 4KB frame")
+    } catch (e) {
+      const logs: string[] = e.logs ?? e.transactionLogs ?? []
+      assert.isTrue(
+        logs.some((log) => log.includes("Access violation in stack frame")),
+        logs.join("\n")
+      )
+    }
+  });
+
+  it("Is fixed by boxing!", async () => {
+    const tx = await program.methods.boxed().accounts(bigAccounts(bigStates)).rpc()
+    console.log("Boxed signature: ", tx);
+  });
+
+  it("Is fixed by zero-copy!", async () => {
+    const tx = await program.methods.zeroCopy().accounts(bigAccounts(bigZeroCopies)).rpc()
+    console.log("Zero-copy signature: ", tx);
+  });
+
+  it("Is fixed by splitting the instruction!", async () => {
+    const first = await program.methods.splitFirst().accounts(bigAccounts(bigStates.slice(0, 3))).instruction()
+    const second = await program.methods.splitSecond().accounts(bigAccounts(bigStates.slice(3))).instruction()
+
+    const tx = await provider.sendAndConfirm(new anchor.web3.Transaction().add(first, second))
+    console.log("Split signature: ", tx);
+  });
+
+  const labelBook = PublicKey.findProgramAddressSync(
+    [Buffer.from("LABEL_BOOK"), provider.publicKey.toBuffer()],
+    program.programId
+  )[0]
+
+  const simulate = async (method: any) => {
+    const tx = await method.accounts({ payer: provider.publicKey }).transaction()
+    tx.feePayer = provider.publicKey
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash
+    return (await provider.connection.simulateTransaction(tx)).value
+  }
+
+  it("Is heap exhausted by naive labels!", async () => {
+    // a handful of labels fits in the 32KB heap
+    const small = await simulate(program.methods.heapNaive(50))
+    assert.isNull(small.err)
+
+    const large = await simulate(program.methods.heapNaive(1_000))
+    assert.isNotNull(large.err)
+    assert.isTrue(
+      large.logs.some((log) => log.includes("memory allocation failed, out of memory")),
+      large.logs.join("\n")
+    )
+  });
+
+  it("Is heap spared by fixed-size labels!", async () => {
+    if (await provider.connection.getAccountInfo(labelBook) == null) {
+      await program.methods.initLabelBook().accounts({ payer: provider.publicKey }).rpc()
+    }
+
+    const full = await simulate(program.methods.heapFixed(512))
+    assert.isNull(full.err, full.logs?.join("\n"))
+
+    // same 50 labels: no allocator and no formatting machinery is cheaper too
+    const naive = await simulate(program.methods.heapNaive(50))
+    const fixed = await simulate(program.methods.heapFixed(50))
+    console.log("CU for 50 labels, naive vs fixed: ", naive.unitsConsumed, fixed.unitsConsumed)
+    assert.isBelow(fixed.unitsConsumed, naive.unitsConsumed)
+
+    const tooMany = await simulate(program.methods.heapFixed(513))
+    assert.isTrue(tooMany.logs.some((log) => log.includes("TooManyLabels")))
+  });
+});